@@ -1,5 +1,11 @@
-from healpix_geo import geometry, nested, ring, slices, zuniq
+from healpix_geo import geodesic, geometry, nested, ring, slices, zuniq
+from healpix_geo.healpix_geo import (
+    Ellipsoid,
+    InvalidGeometryError,
+    LengthMismatchError,
+)
 from healpix_geo.geometry import Bbox
+from healpix_geo.parallel import get_num_threads, set_num_threads
 from healpix_geo.slices import ConcreteSlice, MultiConcreteSlice, Slice
 
 __all__ = [
@@ -7,9 +13,15 @@ __all__ = [
     "ring",
     "zuniq",
     "slices",
+    "geodesic",
     "geometry",
     "Bbox",
+    "Ellipsoid",
+    "InvalidGeometryError",
+    "LengthMismatchError",
     "ConcreteSlice",
     "MultiConcreteSlice",
     "Slice",
+    "get_num_threads",
+    "set_num_threads",
 ]
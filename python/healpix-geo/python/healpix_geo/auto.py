@@ -5,7 +5,8 @@ from typing import Literal
 import numpy as np
 import numpy.typing as npt
 
-from healpix_geo.typing import EllipsoidLike
+from healpix_geo.typing import CrsLike, EllipsoidLike
+from healpix_geo.utils import _as_cell_ids
 
 
 def _dispatch_module(indexing_scheme: str) -> ModuleType:
@@ -47,7 +48,15 @@ class Grid:
 
 
 def healpix_to_lonlat(
-    ipix: npt.NDArray[np.uint64], grid: Grid, *, num_threads: int = 0
+    ipix: npt.NDArray[np.uint64],
+    grid: Grid,
+    *,
+    num_threads: int = 0,
+    out: tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]] | None = None,
+    on_invalid: Literal["raise", "sentinel", "mask"] = "raise",
+    latitude_kind: Literal[
+        "geographic", "geocentric", "parametric", "rectifying", "conformal", "authalic"
+    ] = "geographic",
 ) -> (npt.NDArray[np.float64], npt.NDArray[np.float64]):
     r"""Get the longitudes and latitudes of the center of some HEALPix cells.
 
@@ -61,11 +70,25 @@ def healpix_to_lonlat(
         Specifies the number of threads to use for the computation. Default to 0 means
         it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
         or the number of logical CPUs (otherwise)
+    out : tuple of `numpy.ndarray`, optional
+        `np.float64` arrays with the shape and memory order of ``ipix`` to write the
+        longitudes and latitudes into. New arrays are allocated if not given.
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid cell indexes. ``"raise"`` raises a `ValueError`,
+        ``"sentinel"`` returns NaN coordinates for them and ``"mask"`` additionally
+        returns a mask of the invalid cells.
+    latitude_kind : str, default: "geographic"
+        The kind of the returned latitudes: one of ``"geographic"``, ``"geocentric"``,
+        ``"parametric"``, ``"rectifying"``, ``"conformal"`` or ``"authalic"``. All
+        kinds are equal on a sphere.
 
     Returns
     -------
     lon, lat : array-like
         The coordinates of the center of the HEALPix cells given as a longitude, latitude tuple.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid cells. Only returned if
+        ``on_invalid="mask"``.
 
     Examples
     --------
@@ -81,7 +104,14 @@ def healpix_to_lonlat(
     """
     module = _dispatch_module(grid.indexing_scheme)
     params = grid._as_params()
-    return module.healpix_to_lonlat(ipix, num_threads=num_threads, **params)
+    return module.healpix_to_lonlat(
+        ipix,
+        num_threads=num_threads,
+        out=out,
+        on_invalid=on_invalid,
+        latitude_kind=latitude_kind,
+        **params,
+    )
 
 
 def lonlat_to_healpix(
@@ -90,6 +120,11 @@ def lonlat_to_healpix(
     grid: Grid,
     *,
     num_threads: int = 0,
+    out: npt.NDArray[np.uint64] | None = None,
+    on_invalid: Literal["raise", "sentinel", "mask"] = "raise",
+    latitude_kind: Literal[
+        "geographic", "geocentric", "parametric", "rectifying", "conformal", "authalic"
+    ] = "geographic",
 ) -> npt.NDArray[np.uint64]:
     r"""Get the HEALPix indexes that contains specific points.
 
@@ -105,11 +140,26 @@ def lonlat_to_healpix(
         Specifies the number of threads to use for the computation. Default to 0 means
         it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
         or the number of logical CPUs (otherwise)
+    out : `numpy.ndarray`, optional
+        `np.uint64` array with the shape and memory order of ``lon`` to write the
+        cell indexes into. A new array is allocated if not given.
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid points, i.e. non-finite longitudes or latitudes outside
+        of :math:`[-90, 90]`. ``"raise"`` raises a `ValueError`, ``"sentinel"``
+        returns the maximum `np.uint64` value for them and ``"mask"`` additionally
+        returns a mask of the invalid points.
+    latitude_kind : str, default: "geographic"
+        The kind of the given latitudes: one of ``"geographic"``, ``"geocentric"``,
+        ``"parametric"``, ``"rectifying"``, ``"conformal"`` or ``"authalic"``. All
+        kinds are equal on a sphere.
 
     Returns
     -------
     ipix : `numpy.ndarray`
         A numpy array containing all the HEALPix cell indexes stored as `np.uint64`.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid points. Only returned if
+        ``on_invalid="mask"``.
 
     Examples
     --------
@@ -125,7 +175,239 @@ def lonlat_to_healpix(
     module = _dispatch_module(grid.indexing_scheme)
     params = {"depth": grid.level, "ellipsoid": grid.ellipsoid}
 
-    return module.lonlat_to_healpix(lon, lat, num_threads=num_threads, **params)
+    return module.lonlat_to_healpix(
+        lon,
+        lat,
+        num_threads=num_threads,
+        out=out,
+        on_invalid=on_invalid,
+        latitude_kind=latitude_kind,
+        **params,
+    )
+
+
+def is_valid_cell(
+    ipix: npt.NDArray[np.uint64], grid: Grid, *, num_threads: int = 0
+) -> npt.NDArray[np.bool_]:
+    """Check which cell indexes are valid in the grid.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    grid : Grid
+        The definition of the HEALPix grid.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    valid : `numpy.ndarray`
+        Boolean array that is `True` for the valid cell indexes.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {} if grid.indexing_scheme == "zuniq" else {"depth": grid.level}
+
+    return module.is_valid_cell(ipix, num_threads=num_threads, **params)
+
+
+def interpolation_weights(
+    lon: npt.NDArray[np.float64],
+    lat: npt.NDArray[np.float64],
+    grid: Grid,
+    *,
+    num_threads: int = 0,
+) -> (npt.NDArray[np.uint64], npt.NDArray[np.float64]):
+    r"""Get the bilinear interpolation weights of some points.
+
+    Parameters
+    ----------
+    lon : array-like
+        The longitudes of the input points, in degrees.
+    lat : array-like
+        The latitudes of the input points, in degrees.
+    grid : Grid
+        The definition of the HEALPix grid.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        The cell indexes of the 4 cells surrounding each point, with the shape of
+        ``lon`` and an additional axis of size 4.
+    weights : `numpy.ndarray`
+        The weights of the cells, which sum to 1 for each point.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {"depth": grid.level, "ellipsoid": grid.ellipsoid}
+
+    return module.interpolation_weights(lon, lat, num_threads=num_threads, **params)
+
+
+def interpolate(
+    values: npt.NDArray[np.float64],
+    ipix: npt.NDArray[np.uint64],
+    lon: npt.NDArray[np.float64],
+    lat: npt.NDArray[np.float64],
+    grid: Grid,
+    *,
+    num_threads: int = 0,
+) -> npt.NDArray[np.float64]:
+    r"""Bilinear interpolation of a field at some points.
+
+    Parameters
+    ----------
+    values : array-like
+        The values of the field, one for each cell of ``ipix``.
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes the field is defined on. Cells missing from ``ipix``
+        are skipped and the weights of the other cells renormalized.
+    lon : array-like
+        The longitudes of the points to interpolate at, in degrees.
+    lat : array-like
+        The latitudes of the points to interpolate at, in degrees.
+    grid : Grid
+        The definition of the HEALPix grid.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    interpolated : `numpy.ndarray`
+        The interpolated values, with the shape of ``lon``. Points for which none of
+        the surrounding cells are in ``ipix`` are NaN.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {"depth": grid.level, "ellipsoid": grid.ellipsoid}
+
+    return module.interpolate(values, ipix, lon, lat, num_threads=num_threads, **params)
+
+
+Projection = Literal["equirectangular", "mollweide", "orthographic"]
+
+
+def rasterize(
+    values: npt.NDArray[np.float64],
+    ipix: npt.NDArray[np.uint64],
+    grid: Grid,
+    shape: tuple[int, int],
+    *,
+    projection: Projection = "equirectangular",
+    central_longitude: float = 0.0,
+    central_latitude: float = 0.0,
+    fill_value: float = np.nan,
+    num_threads: int = 0,
+) -> npt.NDArray[np.float64]:
+    r"""Render a HEALPix map onto an image.
+
+    Parameters
+    ----------
+    values : array-like
+        The values of the map, one for each cell of ``ipix``.
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes the map is defined on.
+    grid : Grid
+        The definition of the HEALPix grid.
+    shape : tuple of int
+        The shape of the image, as ``(height, width)``.
+    projection : {"equirectangular", "mollweide", "orthographic"}, default: "equirectangular"
+        The map projection of the image.
+    central_longitude : float, default: 0
+        The longitude at the center of the image, in degrees.
+    central_latitude : float, default: 0
+        The latitude at the center of the image, in degrees. Only used by the
+        orthographic projection.
+    fill_value : float, default: nan
+        The value of pixels outside of the sphere or whose cell is not in ``ipix``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    image : `numpy.ndarray`
+        The image, with north at the top and east to the right.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {"depth": grid.level, "ellipsoid": grid.ellipsoid}
+
+    return module.rasterize(
+        values,
+        ipix,
+        shape=shape,
+        projection=projection,
+        central_longitude=central_longitude,
+        central_latitude=central_latitude,
+        fill_value=fill_value,
+        num_threads=num_threads,
+        **params,
+    )
+
+
+def regridding_weights(
+    lon_bounds: npt.NDArray[np.float64],
+    lat_bounds: npt.NDArray[np.float64],
+    grid: Grid,
+    *,
+    direction: Literal["to_healpix", "from_healpix"] = "to_healpix",
+    refinement: int = 3,
+    num_threads: int = 0,
+) -> tuple[npt.NDArray[np.uint64], tuple[npt.NDArray, npt.NDArray, npt.NDArray]]:
+    r"""Get area-weighted regridding weights between a lon/lat grid and HEALPix.
+
+    The overlaps between the source cells and the HEALPix cells are computed by
+    clipping the source cells to the HEALPix cells in the HEALPix projection, which
+    preserves areas.
+
+    Parameters
+    ----------
+    lon_bounds : array-like
+        The longitudes of the vertices of the source cells, in degrees. The last axis
+        contains the vertices of each cell, and the source cells are the other axes,
+        flattened in C order.
+    lat_bounds : array-like
+        The latitudes of the vertices of the source cells, in degrees.
+    grid : Grid
+        The definition of the HEALPix grid.
+    direction : {"to_healpix", "from_healpix"}, default: "to_healpix"
+        Whether the weights regrid from the source cells to the HEALPix cells or the
+        other way around.
+    refinement : int, default: 3
+        The edges of the source cells that are neither meridians nor parallels are
+        followed in steps the size of the cells ``refinement`` levels below the grid
+        level.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        The sorted HEALPix cell indexes overlapping any source cell.
+    weights : tuple of `numpy.ndarray`
+        The ``(data, indices, indptr)`` arrays of the weights in compressed sparse row
+        format.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {"depth": grid.level, "ellipsoid": grid.ellipsoid}
+
+    return module.regridding_weights(
+        lon_bounds,
+        lat_bounds,
+        direction=direction,
+        refinement=refinement,
+        num_threads=num_threads,
+        **params,
+    )
 
 
 def vertices(
@@ -251,141 +533,807 @@ def vertices(
     return module.vertices(ipix, num_threads=num_threads, step=step, **params)
 
 
-def kth_neighbourhood(
-    ipix: npt.NDArray[np.uint64], grid: Grid, *, ring: int, num_threads: int = 0
-) -> npt.NDArray[np.int64]:
-    """Get the kth ring neighbouring cells of some HEALPix cells.
-
-    This method returns a :math:`N` x :math:`(2 k + 1)^2` `np.uint64` numpy array containing the neighbours of each cell of the :math:`N` sized `ipix` array.
-    This method is wrapped around the `kth_neighbourhood <https://docs.rs/cdshealpix/0.1.5/cdshealpix/nested/struct.Layer.html#method.kth_neighbourhood>`__
-    method from the `cdshealpix Rust crate <https://crates.io/crates/cdshealpix>`__.
+def projected_to_healpix(
+    x: npt.NDArray[np.float64],
+    y: npt.NDArray[np.float64],
+    grid: Grid,
+    crs: CrsLike,
+    *,
+    z: npt.NDArray[np.float64] | None = None,
+    num_threads: int = 0,
+    on_invalid: Literal["raise", "sentinel", "mask"] = "raise",
+) -> npt.NDArray[np.uint64]:
+    r"""Get the HEALPix indexes that contain points given in a coordinate reference system.
 
     Parameters
     ----------
-    ipix : `numpy.ndarray`
-        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    x, y : array-like
+        The coordinates of the input points in the coordinate reference system.
     grid : Grid
         The definition of the HEALPix grid.
-    ring : int
-        The number of rings. `ring=0` returns just the input cell ids, `ring=1` returns the 8 (or 7) immediate
-        neighbours, `ring=2` returns the 8 (or 7) immediate neighbours plus their immediate neighbours (a total of 24 cells), and so on.
-    num_threads : int, default: 0
+    crs : crs-like
+        The coordinate reference system of the points. See :term:`crs-like`.
+    z : array-like, optional
+        The third coordinate of the input points. Required for three-dimensional
+        coordinate reference systems, and the height otherwise.
+    num_threads : int, optional
         Specifies the number of threads to use for the computation. Default to 0 means
         it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
         or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle points that can't be converted to longitudes and latitudes.
+        ``"raise"`` raises a `ValueError`, ``"sentinel"`` returns the maximum
+        `np.uint64` value for them and ``"mask"`` additionally returns a mask of the
+        invalid points.
 
     Returns
     -------
-    neighbours : `numpy.ndarray`
-        A :math:`N` x :math:`(2 k + 1)^2` `np.int64` numpy array containing the kth ring neighbours of each cell.
-        The :math:`5^{th}` element corresponds to the index of HEALPix cell from which the neighbours are evaluated.
-        All its 8 neighbours occup the remaining elements of the line.
+    ipix : `numpy.ndarray`
+        A numpy array containing all the HEALPix cell indexes stored as `np.uint64`.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid points. Only returned if
+        ``on_invalid="mask"``.
 
     Examples
     --------
     >>> import healpix_geo.auto as hg
     >>> import numpy as np
-    >>> ipix = np.array([42, 6, 10])
-    >>> grid = hg.Grid(level=12, indexing_scheme="nested", ellipsoid="sphere")
-    >>> grid
-    Grid(level=12, indexing_scheme='nested', ellipsoid='sphere')
-    >>> ring = 3
-    >>> neighbours = hg.kth_neighbourhood(ipix, grid, ring=ring)
-    >>> neighbours
-    array([[       42,  72701309,  72701311,  72701397,       128,       129,
-                   43,        41,        40,  72701302,  72701308,  72701310,
-             72701396,  72701398,  72701399,  72701303,       130,       131,
-                  134,       132,        46,        44,        38,        35,
-                   34,  72701297,  72701299,  72701305,  72701307,  72701393,
-             72701395,  72701401,  72701404,  72701405,  72701301,  72701300,
-                  136,       137,       140,       141,       135,       133,
-                   47,        45,        39,        37,        36,        33,
-                   32],
-           [        6,         1,         3,         9,        12,        13,
-                    7,         5,         4,  95070890,  95070906,  95070895,
-             95070894,  95070891,         0,         2,         8,        10,
-                   11,        14,        15,        26,        24,        18,
-                   16, 150994941, 150994943,  72701269,  72701271,  72701277,
-             72701279,  72701301,  95070907,  95070905,  95070904,  95070893,
-             95070892,  95070889,  95070888,        32,        33,        36,
-                   37,        48,        49,        27,        25,        19,
-                   17],
-           [       10,  72701277,  72701279,  72701301,        32,        33,
-                   11,         9,         8,  72701270,  72701276,  72701278,
-             72701300,  72701302,  72701303,  72701271,        34,        35,
-                   38,        36,        14,        12,         6,         3,
-                    2,  72701265,  72701267,  72701273,  72701275,  72701297,
-             72701299,  72701305,  72701308,  72701309,  72701269,  72701268,
-                   40,        41,        44,        45,        39,        37,
-                   15,        13,         7,         5,         4,         1,
-                    0]])
+    >>> x = np.array([0.0])
+    >>> y = np.array([-1e6])
+    >>> grid = hg.Grid(level=0, indexing_scheme="nested", ellipsoid="WGS84")
+    >>> hg.projected_to_healpix(x, y, grid, "EPSG:3413")
+    array([3], dtype=uint64)
     """
     module = _dispatch_module(grid.indexing_scheme)
-    params = {}
-    if grid.indexing_scheme != "zuniq":
-        params["depth"] = grid.level
+    params = {"depth": grid.level, "ellipsoid": grid.ellipsoid}
 
-    return module.kth_neighbourhood(ipix, ring=ring, num_threads=num_threads, **params)
+    return module.projected_to_healpix(
+        x,
+        y,
+        crs=crs,
+        z=z,
+        num_threads=num_threads,
+        on_invalid=on_invalid,
+        **params,
+    )
 
 
-def zone_coverage(
-    bbox: tuple[float, float, float, float], grid: Grid, *, flat: bool = True
-) -> (
-    tuple[npt.NDArray[np.uint64], npt.NDArray[np.uint8], npt.NDArray[np.bool]]
-    | tuple[npt.NDArray[np.uint64], npt.NDArray[np.bool]]
-):
-    """Search the cells covering the given bounding box
+def healpix_to_projected(
+    ipix: npt.NDArray[np.uint64],
+    grid: Grid,
+    crs: CrsLike,
+    *,
+    num_threads: int = 0,
+    on_invalid: Literal["raise", "sentinel", "mask"] = "raise",
+) -> tuple[npt.NDArray[np.float64], ...]:
+    r"""Get the coordinates of the center of some HEALPix cells in a coordinate reference system.
 
     Parameters
     ----------
-    bbox : tuple of float
-        The 2D bounding box to rasterize.
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
     grid : Grid
         The definition of the HEALPix grid.
-    flat : bool, default: True
-        If ``True``, the cells returned will all be at the passed depth.
+    crs : crs-like
+        The coordinate reference system of the points. See :term:`crs-like`.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid cell indexes. ``"raise"`` raises a `ValueError`,
+        ``"sentinel"`` returns NaN coordinates for them and ``"mask"`` additionally
+        returns a mask of the invalid cells.
 
     Returns
     -------
-    cell_ids : numpy.ndarray
-        The rasterized cell ids.
-    levels : numpy.ndarray
-        The refinement levels of the cell ids. If ``flat is True``, these will all have the
-        same value. Only returned for ``nested`` and ``ring``.
-    fully_covered : numpy.ndarray
-        Boolean array marking whether the cells are fully covered by the bounding box.
+    x, y : `numpy.ndarray`
+        The coordinates of the center of the HEALPix cells.
+    z : `numpy.ndarray`
+        The third coordinate of the center of the HEALPix cells. Only returned for
+        three-dimensional coordinate reference systems.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid cells and the cells that can't
+        be converted. Only returned if ``on_invalid="mask"``.
+
+    Examples
+    --------
+    >>> import healpix_geo.auto as hg
+    >>> import numpy as np
+    >>> ipix = np.array([4])
+    >>> grid = hg.Grid(level=0, indexing_scheme="nested", ellipsoid="WGS84")
+    >>> x, y, z = hg.healpix_to_projected(ipix, grid, "EPSG:4978")
+    >>> x
+    array([6378137.])
     """
     module = _dispatch_module(grid.indexing_scheme)
-    return module.zone_coverage(
-        bbox, depth=grid.level, ellipsoid=grid.ellipsoid, flat=flat
+    params = grid._as_params()
+
+    return module.healpix_to_projected(
+        ipix, crs=crs, num_threads=num_threads, on_invalid=on_invalid, **params
     )
 
 
-def box_coverage(
-    center: tuple[float, float] | npt.NDArray[np.float64],
-    size: tuple[float, float] | npt.NDArray[np.float64],
-    angle: float,
+def vertices_projected(
+    ipix: npt.NDArray[np.uint64],
     grid: Grid,
+    crs: CrsLike,
     *,
-    flat: bool = True,
-) -> (
-    tuple[npt.NDArray[np.uint64], npt.NDArray[np.uint8], npt.NDArray[np.bool]]
-    | tuple[npt.NDArray[np.uint64], npt.NDArray[np.bool]]
-):
-    """Search the cells covering the given box.
+    step: int = 1,
+    num_threads: int = 0,
+) -> tuple[npt.NDArray[np.float64], ...]:
+    """Get the coordinates of the vertices of some HEALPix cells in a coordinate reference system.
 
     Parameters
     ----------
-    center : numpy.ndarray or tuple of float
-        The center of the box, either as a 2-sized array or as a 2-tuple of float.
-    size : numpy.ndarray or tuple of float
-        The size of the box, in degree.
-    angle : float
-        The angle by which the box is rotated, in degree.
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
     grid : Grid
         The definition of the HEALPix grid.
-    flat : bool, default: True
-        If ``True``, the cells returned will all be at the passed depth.
+    crs : crs-like
+        The coordinate reference system of the points. See :term:`crs-like`.
+    step : int, default: 1
+        The number of vertices returned per HEALPix side. The number of vertices
+        returned is ``4 * step``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    x, y : `numpy.ndarray`
+        The coordinates of the vertices, with an additional trailing dimension of
+        size ``4 * step``.
+    z : `numpy.ndarray`
+        The third coordinate of the vertices. Only returned for three-dimensional
+        coordinate reference systems.
+
+    Examples
+    --------
+    >>> import healpix_geo.auto as hg
+    >>> import numpy as np
+    >>> ipix = np.array([4])
+    >>> grid = hg.Grid(level=0, indexing_scheme="nested", ellipsoid="WGS84")
+    >>> x, y = hg.vertices_projected(ipix, grid, 32631)
+    >>> x.shape
+    (1, 4)
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = grid._as_params()
+
+    return module.vertices_projected(
+        ipix, crs=crs, num_threads=num_threads, step=step, **params
+    )
+
+
+def xyz_to_healpix(
+    x: npt.NDArray[np.float64],
+    y: npt.NDArray[np.float64],
+    z: npt.NDArray[np.float64],
+    grid: Grid,
+    *,
+    num_threads: int = 0,
+    on_invalid: Literal["raise", "sentinel", "mask"] = "raise",
+) -> npt.NDArray[np.uint64]:
+    r"""Get the HEALPix indexes that contain points given in body-centered cartesian coordinates.
+
+    Parameters
+    ----------
+    x, y, z : array-like
+        The body-centered cartesian coordinates of the input points, in the unit of
+        the ellipsoid axes (usually meters).
+    grid : Grid
+        The definition of the HEALPix grid.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid points, like the center of the ellipsoid.
+        ``"raise"`` raises a `ValueError`, ``"sentinel"`` returns the maximum
+        `np.uint64` value for them and ``"mask"`` additionally returns a mask of the
+        invalid points.
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        A numpy array containing all the HEALPix cell indexes stored as `np.uint64`.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid points. Only returned if
+        ``on_invalid="mask"``.
+
+    Examples
+    --------
+    >>> import healpix_geo.auto as hg
+    >>> import numpy as np
+    >>> x = np.array([0.0])
+    >>> y = np.array([7e6])
+    >>> z = np.array([0.0])
+    >>> grid = hg.Grid(level=0, indexing_scheme="nested", ellipsoid="WGS84")
+    >>> hg.xyz_to_healpix(x, y, z, grid)
+    array([5], dtype=uint64)
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {"depth": grid.level, "ellipsoid": grid.ellipsoid}
+
+    return module.xyz_to_healpix(
+        x, y, z, num_threads=num_threads, on_invalid=on_invalid, **params
+    )
+
+
+def healpix_to_xyz(
+    ipix: npt.NDArray[np.uint64],
+    grid: Grid,
+    *,
+    height: float = 0.0,
+    num_threads: int = 0,
+    on_invalid: Literal["raise", "sentinel", "mask"] = "raise",
+) -> tuple[npt.NDArray[np.float64], ...]:
+    r"""Get the body-centered cartesian coordinates of the center of some HEALPix cells.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    grid : Grid
+        The definition of the HEALPix grid.
+    height : float, default: 0.0
+        The height above the ellipsoid, in the unit of the ellipsoid axes (usually
+        meters).
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid cell indexes. ``"raise"`` raises a `ValueError`,
+        ``"sentinel"`` returns NaN coordinates for them and ``"mask"`` additionally
+        returns a mask of the invalid cells.
+
+    Returns
+    -------
+    x, y, z : `numpy.ndarray`
+        The body-centered cartesian coordinates of the center of the HEALPix cells,
+        in the unit of the ellipsoid axes.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid cells. Only returned if
+        ``on_invalid="mask"``.
+
+    Examples
+    --------
+    >>> import healpix_geo.auto as hg
+    >>> import numpy as np
+    >>> ipix = np.array([4])
+    >>> grid = hg.Grid(level=0, indexing_scheme="nested", ellipsoid="WGS84")
+    >>> x, y, z = hg.healpix_to_xyz(ipix, grid)
+    >>> x
+    array([6378137.])
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = grid._as_params()
+
+    return module.healpix_to_xyz(
+        ipix, height=height, num_threads=num_threads, on_invalid=on_invalid, **params
+    )
+
+
+def vertices_xyz(
+    ipix: npt.NDArray[np.uint64],
+    grid: Grid,
+    *,
+    height: float = 0.0,
+    step: int = 1,
+    num_threads: int = 0,
+) -> tuple[npt.NDArray[np.float64], ...]:
+    """Get the body-centered cartesian coordinates of the vertices of some HEALPix cells.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    grid : Grid
+        The definition of the HEALPix grid.
+    height : float, default: 0.0
+        The height above the ellipsoid, in the unit of the ellipsoid axes (usually
+        meters).
+    step : int, default: 1
+        The number of vertices returned per HEALPix side. The number of vertices
+        returned is ``4 * step``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    x, y, z : `numpy.ndarray`
+        The body-centered cartesian coordinates of the vertices, with an additional
+        trailing dimension of size ``4 * step``.
+
+    Examples
+    --------
+    >>> import healpix_geo.auto as hg
+    >>> import numpy as np
+    >>> ipix = np.array([4])
+    >>> grid = hg.Grid(level=0, indexing_scheme="nested", ellipsoid="WGS84")
+    >>> x, y, z = hg.vertices_xyz(ipix, grid)
+    >>> x.shape
+    (1, 4)
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = grid._as_params()
+
+    return module.vertices_xyz(
+        ipix, height=height, num_threads=num_threads, step=step, **params
+    )
+
+
+def cell_area(
+    ipix: npt.NDArray[np.uint64], grid: Grid, *, num_threads: int = 0
+) -> npt.NDArray[np.float64]:
+    """Compute the area of HEALPix cells on the reference ellipsoid.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    grid : Grid
+        The definition of the HEALPix grid.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    area : numpy.ndarray
+        The area of each cell, in squared units of the semimajor axis of the
+        ellipsoid.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = grid._as_params()
+
+    return module.cell_area(ipix, num_threads=num_threads, **params)
+
+
+def cell_perimeter(
+    ipix: npt.NDArray[np.uint64], grid: Grid, *, step: int = 1, num_threads: int = 0
+) -> npt.NDArray[np.float64]:
+    """Compute the geodesic perimeter of HEALPix cells on the reference ellipsoid.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    grid : Grid
+        The definition of the HEALPix grid.
+    step : int, default: 1
+        The number of vertices per cell edge.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    perimeter : numpy.ndarray
+        The perimeter of each cell, in units of the semimajor axis of the
+        ellipsoid.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = grid._as_params()
+
+    return module.cell_perimeter(ipix, num_threads=num_threads, step=step, **params)
+
+
+def kth_neighbourhood(
+    ipix: npt.NDArray[np.uint64], grid: Grid, *, ring: int, num_threads: int = 0
+) -> npt.NDArray[np.int64]:
+    """Get the kth ring neighbouring cells of some HEALPix cells.
+
+    This method returns a :math:`N` x :math:`(2 k + 1)^2` `np.uint64` numpy array containing the neighbours of each cell of the :math:`N` sized `ipix` array.
+    This method is wrapped around the `kth_neighbourhood <https://docs.rs/cdshealpix/0.1.5/cdshealpix/nested/struct.Layer.html#method.kth_neighbourhood>`__
+    method from the `cdshealpix Rust crate <https://crates.io/crates/cdshealpix>`__.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    grid : Grid
+        The definition of the HEALPix grid.
+    ring : int
+        The number of rings. `ring=0` returns just the input cell ids, `ring=1` returns the 8 (or 7) immediate
+        neighbours, `ring=2` returns the 8 (or 7) immediate neighbours plus their immediate neighbours (a total of 24 cells), and so on.
+    num_threads : int, default: 0
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    neighbours : `numpy.ndarray`
+        A :math:`N` x :math:`(2 k + 1)^2` `np.int64` numpy array containing the kth ring neighbours of each cell.
+        The :math:`5^{th}` element corresponds to the index of HEALPix cell from which the neighbours are evaluated.
+        All its 8 neighbours occup the remaining elements of the line.
+
+    Examples
+    --------
+    >>> import healpix_geo.auto as hg
+    >>> import numpy as np
+    >>> ipix = np.array([42, 6, 10])
+    >>> grid = hg.Grid(level=12, indexing_scheme="nested", ellipsoid="sphere")
+    >>> grid
+    Grid(level=12, indexing_scheme='nested', ellipsoid='sphere')
+    >>> ring = 3
+    >>> neighbours = hg.kth_neighbourhood(ipix, grid, ring=ring)
+    >>> neighbours
+    array([[       42,  72701309,  72701311,  72701397,       128,       129,
+                   43,        41,        40,  72701302,  72701308,  72701310,
+             72701396,  72701398,  72701399,  72701303,       130,       131,
+                  134,       132,        46,        44,        38,        35,
+                   34,  72701297,  72701299,  72701305,  72701307,  72701393,
+             72701395,  72701401,  72701404,  72701405,  72701301,  72701300,
+                  136,       137,       140,       141,       135,       133,
+                   47,        45,        39,        37,        36,        33,
+                   32],
+           [        6,         1,         3,         9,        12,        13,
+                    7,         5,         4,  95070890,  95070906,  95070895,
+             95070894,  95070891,         0,         2,         8,        10,
+                   11,        14,        15,        26,        24,        18,
+                   16, 150994941, 150994943,  72701269,  72701271,  72701277,
+             72701279,  72701301,  95070907,  95070905,  95070904,  95070893,
+             95070892,  95070889,  95070888,        32,        33,        36,
+                   37,        48,        49,        27,        25,        19,
+                   17],
+           [       10,  72701277,  72701279,  72701301,        32,        33,
+                   11,         9,         8,  72701270,  72701276,  72701278,
+             72701300,  72701302,  72701303,  72701271,        34,        35,
+                   38,        36,        14,        12,         6,         3,
+                    2,  72701265,  72701267,  72701273,  72701275,  72701297,
+             72701299,  72701305,  72701308,  72701309,  72701269,  72701268,
+                   40,        41,        44,        45,        39,        37,
+                   15,        13,         7,         5,         4,         1,
+                    0]])
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {}
+    if grid.indexing_scheme != "zuniq":
+        params["depth"] = grid.level
+
+    return module.kth_neighbourhood(ipix, ring=ring, num_threads=num_threads, **params)
+
+
+def neighbours(
+    ipix: npt.NDArray[np.uint64], grid: Grid, *, num_threads: int = 0
+) -> npt.NDArray[np.int64]:
+    """Find the 8 neighbours of every cell, labelled by direction
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    grid : Grid
+        The definition of the HEALPix grid.
+    num_threads : int, default: 0
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    neighbours : `numpy.ndarray`
+        A `np.int64` numpy array with the shape of ``ipix`` and an additional axis of
+        size 8, containing the neighbours in the N, NE, E, SE, S, SW, W and NW
+        directions, in that order. Missing neighbours are set to -1.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {}
+    if grid.indexing_scheme != "zuniq":
+        params["depth"] = grid.level
+
+    return module.neighbours(ipix, num_threads=num_threads, **params)
+
+
+def neighbours_within_distance(
+    ipix: npt.NDArray[np.uint64], grid: Grid, *, radius: float, num_threads: int = 0
+) -> tuple[npt.NDArray[np.uint64], npt.NDArray[np.float64], npt.NDArray[np.int64]]:
+    """Find the cells whose center is within a distance of the center of every cell
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    grid : Grid
+        The definition of the HEALPix grid.
+    radius : float
+        The maximum geodesic distance between cell centers, in the unit of the
+        ellipsoid axes (usually meters).
+    num_threads : int, default: 0
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    neighbours : `numpy.ndarray`
+        The neighbours of all cells, flattened, as a `np.uint64` numpy array. The
+        neighbours of each cell include the cell itself and are sorted by distance.
+    distances : `numpy.ndarray`
+        The geodesic distances between the center of each cell and the centers of its
+        neighbours, in the unit of the ellipsoid axes.
+    offsets : `numpy.ndarray`
+        A `np.int64` numpy array of size ``ipix.size + 1``. The neighbours of the
+        ``i``-th cell of the flattened ``ipix`` are
+        ``neighbours[offsets[i]:offsets[i + 1]]``.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = grid._as_params()
+
+    return module.neighbours_within_distance(
+        ipix, radius=radius, num_threads=num_threads, **params
+    )
+
+
+def zoom_to(
+    ipix: npt.NDArray[np.uint64], grid: Grid, *, new_level: int, num_threads: int = 0
+) -> npt.NDArray[np.uint64]:
+    r"""Change the resolutions the given cell ids
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    grid : Grid
+        The definition of the HEALPix grid.
+    new_level : int
+        The new refinement level of the cells.
+    num_threads : int, default: 0
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    cells : numpy.ndarray
+        A :math:`N` (coarser level) or :math:`N` x :math:`4^{\delta d}` (finer level)
+        `np.uint64` numpy array containing the parents or children of the given cells.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {}
+    if grid.indexing_scheme != "zuniq":
+        params["depth"] = grid.level
+
+    return module.zoom_to(ipix, new_depth=new_level, num_threads=num_threads, **params)
+
+
+def siblings(
+    ipix: npt.NDArray[np.uint64], grid: Grid, *, num_threads: int = 0
+) -> npt.NDArray[np.uint64]:
+    """Find the siblings for every cell
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    grid : Grid
+        The definition of the HEALPix grid.
+    num_threads : int, default: 0
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    cells : numpy.ndarray
+        A :math:`N` x :math:`4` or :math:`N` x :math:`12` `np.uint64` numpy array
+        containing the siblings of the given cells. The siblings of base cells are
+        the base cells.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {}
+    if grid.indexing_scheme != "zuniq":
+        params["depth"] = grid.level
+
+    return module.siblings(ipix, num_threads=num_threads, **params)
+
+
+def aggregate_to_parent(
+    ipix: npt.NDArray[np.uint64],
+    values: npt.NDArray[np.float64],
+    grid: Grid,
+    *,
+    new_level: int,
+    op: Literal["sum", "mean", "min", "max", "count", "area_weighted_mean"] = "mean",
+    num_threads: int = 0,
+) -> tuple[npt.NDArray[np.uint64], npt.NDArray[np.float64]]:
+    """Aggregate the values of cells into their parents.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    values : array-like
+        The values of the cells, one for each cell of ``ipix``.
+    grid : Grid
+        The definition of the HEALPix grid.
+    new_level : int
+        The level of the parents.
+    op : {"sum", "mean", "min", "max", "count", "area_weighted_mean"}, default: "mean"
+        The reduction applied to the values of the cells sharing a parent.
+    num_threads : int, default: 0
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    parents : numpy.ndarray
+        The sorted parents of the cells.
+    reduced : numpy.ndarray
+        The reduced values of each parent.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {}
+    if grid.indexing_scheme != "zuniq":
+        params["depth"] = grid.level
+
+    return module.aggregate_to_parent(
+        ipix, values, new_depth=new_level, op=op, num_threads=num_threads, **params
+    )
+
+
+def broadcast_to_children(
+    ipix: npt.NDArray[np.uint64],
+    values: npt.NDArray[np.float64],
+    grid: Grid,
+    *,
+    new_level: int,
+    num_threads: int = 0,
+) -> tuple[npt.NDArray[np.uint64], npt.NDArray[np.float64]]:
+    """Broadcast the values of cells to their children.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    values : array-like
+        The values of the cells, one for each cell of ``ipix``.
+    grid : Grid
+        The definition of the HEALPix grid.
+    new_level : int
+        The level of the children.
+    num_threads : int, default: 0
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    children : numpy.ndarray
+        The children of the cells.
+    broadcasted : numpy.ndarray
+        The value of the parent of each child.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    params = {}
+    if grid.indexing_scheme != "zuniq":
+        params["depth"] = grid.level
+
+    return module.broadcast_to_children(
+        ipix, values, new_depth=new_level, num_threads=num_threads, **params
+    )
+
+
+def internal_boundary(
+    ipix: npt.NDArray[np.uint64], grid: Grid
+) -> npt.NDArray[np.uint64]:
+    """Extract the internal boundary from a set of cells
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes describing a connected area.
+    grid : Grid
+        The definition of the HEALPix grid.
+
+    Returns
+    -------
+    internal_boundary : numpy.ndarray
+        The cells of the area that touch its boundary.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    ipix = _as_cell_ids(ipix)
+
+    if grid.indexing_scheme == "zuniq":
+        return module.internal_boundary(ipix)
+    else:
+        return module.internal_boundary(grid.level, ipix)
+
+
+def external_boundary(
+    ipix: npt.NDArray[np.uint64], grid: Grid
+) -> npt.NDArray[np.uint64]:
+    """Extract the external boundary from a set of cells
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes describing a connected area.
+    grid : Grid
+        The definition of the HEALPix grid.
+
+    Returns
+    -------
+    external_boundary : numpy.ndarray
+        The cells outside the area that touch its boundary.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    ipix = _as_cell_ids(ipix)
+
+    if grid.indexing_scheme == "zuniq":
+        return module.external_boundary(ipix)
+    else:
+        return module.external_boundary(grid.level, ipix)
+
+
+def zone_coverage(
+    bbox: tuple[float, float, float, float], grid: Grid, *, flat: bool = True
+) -> (
+    tuple[npt.NDArray[np.uint64], npt.NDArray[np.uint8], npt.NDArray[np.bool]]
+    | tuple[npt.NDArray[np.uint64], npt.NDArray[np.bool]]
+):
+    """Search the cells covering the given bounding box
+
+    Parameters
+    ----------
+    bbox : tuple of float
+        The 2D bounding box to rasterize.
+    grid : Grid
+        The definition of the HEALPix grid.
+    flat : bool, default: True
+        If ``True``, the cells returned will all be at the passed depth.
+
+    Returns
+    -------
+    cell_ids : numpy.ndarray
+        The rasterized cell ids.
+    levels : numpy.ndarray
+        The refinement levels of the cell ids. If ``flat is True``, these will all have the
+        same value. Only returned for ``nested`` and ``ring``.
+    fully_covered : numpy.ndarray
+        Boolean array marking whether the cells are fully covered by the bounding box.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    return module.zone_coverage(
+        bbox, depth=grid.level, ellipsoid=grid.ellipsoid, flat=flat
+    )
+
+
+def box_coverage(
+    center: tuple[float, float] | npt.NDArray[np.float64],
+    size: tuple[float, float] | npt.NDArray[np.float64],
+    angle: float,
+    grid: Grid,
+    *,
+    flat: bool = True,
+) -> (
+    tuple[npt.NDArray[np.uint64], npt.NDArray[np.uint8], npt.NDArray[np.bool]]
+    | tuple[npt.NDArray[np.uint64], npt.NDArray[np.bool]]
+):
+    """Search the cells covering the given box.
+
+    Parameters
+    ----------
+    center : numpy.ndarray or tuple of float
+        The center of the box, either as a 2-sized array or as a 2-tuple of float.
+    size : numpy.ndarray or tuple of float
+        The size of the box, in degree.
+    angle : float
+        The angle by which the box is rotated, in degree.
+    grid : Grid
+        The definition of the HEALPix grid.
+    flat : bool, default: True
+        If ``True``, the cells returned will all be at the passed depth.
 
     Returns
     -------
@@ -441,6 +1389,32 @@ def polygon_coverage(
     )
 
 
+def linestring_coverage(
+    vertices: npt.NDArray[np.float64], grid: Grid, *, rhumb: bool = False
+) -> npt.NDArray[np.uint64]:
+    """Search the cells crossed by the given line.
+
+    Parameters
+    ----------
+    vertices : numpy.ndarray
+        The vertices of the line. Must be an array of shape ``(n, 2)`` with ``n >= 2``.
+    grid : Grid
+        The definition of the HEALPix grid.
+    rhumb : bool, default: False
+        If ``True``, connect the vertices with rhumb lines (lines of constant
+        bearing) instead of great circles.
+
+    Returns
+    -------
+    cell_ids : numpy.ndarray
+        The sorted ids of all cells crossed by the line.
+    """
+    module = _dispatch_module(grid.indexing_scheme)
+    return module.linestring_coverage(
+        vertices=vertices, depth=grid.level, ellipsoid=grid.ellipsoid, rhumb=rhumb
+    )
+
+
 def cone_coverage(
     center: tuple[float, float] | npt.NDArray[np.float64],
     radius: float,
@@ -0,0 +1,77 @@
+import numpy as np
+
+from healpix_geo import healpix_geo
+from healpix_geo.utils import _as_coordinates
+
+
+def geodesic_distances(
+    from_longitude,
+    from_latitude,
+    to_longitude,
+    to_latitude,
+    ellipsoid="sphere",
+    num_threads=0,
+):
+    """Compute the geodesic distances between points
+
+    The distances are computed on the reference ellipsoid using Karney's algorithm.
+
+    Parameters
+    ----------
+    from_longitude, from_latitude : numpy.ndarray
+        The coordinates of the source points in degrees. Should be 1D.
+    to_longitude, to_latitude : numpy.ndarray
+        The coordinates of the destination points in degrees. Should be 2D, with one
+        row per source point.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to compute the geodesics on.
+    num_threads : int, default: 0
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    distances : numpy.ndarray
+        The geodesic distances in the unit of the ellipsoid axes (usually meters).
+    forward_azimuths : numpy.ndarray
+        The azimuths at the source points, in degrees clockwise from north.
+    back_azimuths : numpy.ndarray
+        The azimuths at the destination points pointing back to the source points,
+        in degrees clockwise from north.
+    """
+    from_longitude, from_latitude = np.broadcast_arrays(
+        _as_coordinates(from_longitude),
+        _as_coordinates(from_latitude),
+    )
+    to_longitude, to_latitude = np.broadcast_arrays(
+        _as_coordinates(to_longitude),
+        _as_coordinates(to_latitude),
+    )
+
+    if (
+        from_longitude.shape != to_longitude.shape
+        and from_longitude.shape != to_longitude.shape[:-1]
+    ):
+        raise ValueError(
+            "The shape of the source points must be compatible with the shape of the destination points:\n"
+            f"{to_longitude.shape} or {to_longitude.shape[:-1]} must be equal to {from_longitude.shape}."
+        )
+
+    if from_longitude.shape == to_longitude.shape:
+        intermediate_shape = to_longitude.shape + (1,)
+    else:
+        intermediate_shape = to_longitude.shape
+
+    num_threads = np.uint16(num_threads)
+
+    results = healpix_geo.geodesic.geodesic_distances(
+        from_longitude,
+        from_latitude,
+        np.reshape(to_longitude, intermediate_shape),
+        np.reshape(to_latitude, intermediate_shape),
+        ellipsoid,
+        num_threads,
+    )
+
+    return tuple(np.reshape(result, to_longitude.shape) for result in results)
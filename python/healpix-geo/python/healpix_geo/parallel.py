@@ -0,0 +1,64 @@
+from healpix_geo import healpix_geo
+
+
+def get_num_threads():
+    """Get the number of threads used by default
+
+    Returns
+    -------
+    num_threads : int
+        The number of threads used by functions called with ``num_threads=0``. ``0``
+        means the number of threads is chosen based on the RAYON_NUM_THREADS
+        environment variable (if set), or the number of logical CPUs (otherwise).
+    """
+    return healpix_geo.get_num_threads()
+
+
+class set_num_threads:
+    """Set the number of threads used by default
+
+    Applies to every function called with ``num_threads=0``. The thread pools are
+    reused by subsequent calls.
+
+    Can be used as a context manager, in which case the previous value is restored
+    on exit.
+
+    Parameters
+    ----------
+    num_threads : int
+        The number of threads. ``0`` restores the default: the number of threads is
+        chosen based on the RAYON_NUM_THREADS environment variable (if set), or the
+        number of logical CPUs (otherwise).
+
+    Notes
+    -----
+    The setting is global to the process and takes effect when ``set_num_threads``
+    is called, not when entering the ``with`` block. Restoring the previous value on
+    exit also affects functions running in other threads at that time. To control
+    the number of threads of a call made from multiple threads, pass ``num_threads``
+    to the function instead.
+
+    Examples
+    --------
+    >>> import healpix_geo
+    >>> _ = healpix_geo.set_num_threads(4)
+    >>> healpix_geo.get_num_threads()
+    4
+    >>> with healpix_geo.set_num_threads(1):
+    ...     healpix_geo.get_num_threads()
+    1
+    >>> healpix_geo.get_num_threads()
+    4
+    >>> _ = healpix_geo.set_num_threads(0)
+    """
+
+    def __init__(self, num_threads):
+        self._previous = healpix_geo.get_num_threads()
+
+        healpix_geo.set_num_threads(num_threads)
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *exc_info):
+        healpix_geo.set_num_threads(self._previous)
@@ -1,10 +1,32 @@
 import numpy as np
 
 from healpix_geo import healpix_geo
-from healpix_geo.utils import _check_depth, _check_ipixels, _check_ring
+from healpix_geo.utils import (
+    _as_cell_ids,
+    _as_coordinates,
+    _check_depth,
+    _check_ipixels,
+    _check_ring,
+)
 
+RangeMOCIndex = healpix_geo.ring.RangeMOCIndex
+internal_boundary = healpix_geo.ring.internal_boundary
+external_boundary = healpix_geo.ring.external_boundary
 
-def healpix_to_lonlat(ipix, depth, ellipsoid="sphere", num_threads=0):
+
+def create_empty(depth, ellipsoid="sphere"):
+    return RangeMOCIndex.create_empty(depth, ellipsoid=ellipsoid)
+
+
+def healpix_to_lonlat(
+    ipix,
+    depth,
+    ellipsoid="sphere",
+    num_threads=0,
+    out=None,
+    on_invalid="raise",
+    latitude_kind="geographic",
+):
     r"""Get the longitudes and latitudes of the center of some HEALPix cells.
 
     Parameters
@@ -20,55 +42,1346 @@ def healpix_to_lonlat(ipix, depth, ellipsoid="sphere", num_threads=0):
         Specifies the number of threads to use for the computation. Default to 0 means
         it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
         or the number of logical CPUs (otherwise)
+    out : tuple of `numpy.ndarray`, optional
+        `np.float64` arrays with the shape and memory order of ``ipix`` to write the
+        longitudes and latitudes into. New arrays are allocated if not given.
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid cell indexes. ``"raise"`` raises a `ValueError`,
+        ``"sentinel"`` returns NaN coordinates for them and ``"mask"`` additionally
+        returns a mask of the invalid cells.
+    latitude_kind : str, default: "geographic"
+        The kind of the returned latitudes: one of ``"geographic"``, ``"geocentric"``,
+        ``"parametric"``, ``"rectifying"``, ``"conformal"`` or ``"authalic"``. All
+        kinds are equal on a sphere.
+
+    Returns
+    -------
+    lon, lat : array-like
+        The coordinates of the center of the HEALPix cells given as a longitude, latitude tuple.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid cells. Only returned if
+        ``on_invalid="mask"``.
+
+    Raises
+    ------
+    ValueError
+        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`
+        and ``on_invalid="raise"``.
+    ValueError
+        When the name of the ellipsoid is unknown.
+    ValueError
+        When the latitude kind is unknown.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import healpix_to_lonlat
+    >>> import numpy as np
+    >>> ipix = np.array([42, 6, 10])
+    >>> depth = 3
+    >>> lon, lat = healpix_to_lonlat(ipix, depth, ellipsoid="WGS84")
+    >>> lon
+    array([ 45. , 112.5, 292.5])
+    >>> lat
+    array([60.54441647, 78.33504545, 78.33504545])
+    """
+    _check_depth(depth)
+    ipix = np.atleast_1d(ipix)
+    if on_invalid == "raise":
+        _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    lon, lat, mask = healpix_geo.ring.healpix_to_lonlat(
+        depth, ipix, ellipsoid, num_threads, out, on_invalid, latitude_kind
+    )
+    if on_invalid == "mask":
+        return lon, lat, mask
+
+    return lon, lat
+
+
+def lonlat_to_healpix(
+    longitude,
+    latitude,
+    depth,
+    ellipsoid="sphere",
+    num_threads=0,
+    out=None,
+    on_invalid="raise",
+    latitude_kind="geographic",
+):
+    r"""Get the HEALPix indexes that contains specific points.
+
+    Parameters
+    ----------
+    lon : array-like
+        The longitudes of the input points, in degrees.
+    lat : array-like
+        The latitudes of the input points, in degrees.
+    depth : int or array-like of int
+        The HEALPix cell depth given as a `np.uint8` numpy array.
+    ellipsoid : str, default: "sphere"
+        Reference ellipsoid to evaluate healpix on. If ``"sphere"``, this will return
+        the same result as :py:func:`cdshealpix.ring.lonlat_to_healpix`.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+    out : `numpy.ndarray`, optional
+        `np.uint64` array with the shape and memory order of ``lon`` to write the
+        cell indexes into. A new array is allocated if not given.
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid points, i.e. non-finite longitudes or latitudes outside
+        of :math:`[-90, 90]`. ``"raise"`` raises a `ValueError`, ``"sentinel"``
+        returns the maximum `np.uint64` value for them and ``"mask"`` additionally
+        returns a mask of the invalid points.
+    latitude_kind : str, default: "geographic"
+        The kind of the given latitudes: one of ``"geographic"``, ``"geocentric"``,
+        ``"parametric"``, ``"rectifying"``, ``"conformal"`` or ``"authalic"``. All
+        kinds are equal on a sphere.
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        A numpy array containing all the HEALPix cell indexes stored as `np.uint64`.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid points. Only returned if
+        ``on_invalid="mask"``.
+
+    Raises
+    ------
+    ValueError
+        When the number of longitudes and latitudes given do not match.
+    ValueError
+        When the coordinates are invalid and ``on_invalid="raise"``.
+    ValueError
+        When the name of the ellipsoid is unknown.
+    ValueError
+        When the latitude kind is unknown.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import lonlat_to_healpix
+    >>> import numpy as np
+    >>> lon = np.array([0, 50, 25], dtype="float64")
+    >>> lat = np.array([6, -12, 45], dtype="float64")
+    >>> depth = 3
+    >>> ipix = lonlat_to_healpix(lon, lat, depth, ellipsoid="WGS84")
+    >>> ipix
+    array([336, 436, 114], dtype=uint64)
+    """
+    _check_depth(depth)
+    longitude = _as_coordinates(longitude)
+    latitude = _as_coordinates(latitude)
+
+    num_threads = np.uint16(num_threads)
+
+    ipix, mask = healpix_geo.ring.lonlat_to_healpix(
+        depth,
+        longitude,
+        latitude,
+        ellipsoid,
+        num_threads,
+        out,
+        on_invalid,
+        latitude_kind,
+    )
+    if on_invalid == "mask":
+        return ipix, mask
+
+    return ipix
+
+
+def is_valid_cell(ipix, depth, num_threads=0):
+    r"""Check which cell indexes are valid at a given depth.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The depth of the HEALPix cells.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    valid : `numpy.ndarray`
+        Boolean array that is `True` for the cell indexes in :math:`[0, 12 \cdot 4^{depth}[`.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import is_valid_cell
+    >>> import numpy as np
+    >>> ipix = np.array([-1, 0, 767, 768])
+    >>> is_valid_cell(ipix, depth=3)
+    array([False,  True,  True, False])
+    """
+    _check_depth(depth)
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.ring.is_valid_cell(depth, ipix, num_threads)
+
+
+def interpolation_weights(
+    longitude, latitude, depth, ellipsoid="sphere", num_threads=0
+):
+    r"""Get the bilinear interpolation weights of some points.
+
+    The weights are computed on the authalic sphere, after converting the geographic
+    latitudes of the points. This is the equivalent of
+    :py:func:`healpy.get_interp_weights`.
+
+    Parameters
+    ----------
+    longitude : array-like
+        The longitudes of the input points, in degrees.
+    latitude : array-like
+        The latitudes of the input points, in degrees.
+    depth : int
+        The depth of the HEALPix cells to interpolate from.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes of the 4 cells surrounding each point, with the shape of
+        ``longitude`` and an additional axis of size 4.
+    weights : `numpy.ndarray`
+        The weights of the cells, which sum to 1 for each point. Where a cell only has
+        7 neighbours, the missing cell is replaced by the cell containing the point
+        with a weight of 0.
+
+    Raises
+    ------
+    ValueError
+        When the number of longitudes and latitudes given do not match.
+    ValueError
+        When a longitude is not finite or a latitude is outside of :math:`[-90, 90]`.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import interpolation_weights
+    >>> import numpy as np
+    >>> lon = np.array([0, 50], dtype="float64")
+    >>> lat = np.array([6, -12], dtype="float64")
+    >>> ipix, weights = interpolation_weights(lon, lat, depth=3, ellipsoid="WGS84")
+    >>> ipix.shape
+    (2, 4)
+    >>> weights.sum(axis=-1)
+    array([1., 1.])
+    """
+    _check_depth(depth)
+    longitude = _as_coordinates(longitude)
+    latitude = _as_coordinates(latitude)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.ring.interpolation_weights(
+        depth, longitude, latitude, ellipsoid, num_threads
+    )
+
+
+def interpolate(
+    values, ipix, longitude, latitude, depth, ellipsoid="sphere", num_threads=0
+):
+    r"""Bilinear interpolation of a field at some points.
+
+    Parameters
+    ----------
+    values : array-like
+        The values of the field, one for each cell of ``ipix``.
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes the field is defined on. They don't have to cover the
+        whole sphere: cells missing from ``ipix`` are skipped and the weights of the
+        other cells renormalized.
+    longitude : array-like
+        The longitudes of the points to interpolate at, in degrees.
+    latitude : array-like
+        The latitudes of the points to interpolate at, in degrees.
+    depth : int
+        The depth of the HEALPix cells.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    interpolated : `numpy.ndarray`
+        The interpolated values, with the shape of ``longitude``. Points for which
+        none of the surrounding cells are in ``ipix`` are NaN.
+
+    Raises
+    ------
+    ValueError
+        When the number of values and cells or of longitudes and latitudes do not
+        match.
+    ValueError
+        When a longitude is not finite or a latitude is outside of :math:`[-90, 90]`.
+
+    See Also
+    --------
+    interpolation_weights
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import interpolate
+    >>> import numpy as np
+    >>> depth = 3
+    >>> ipix = np.arange(768)
+    >>> values = np.full_like(ipix, 2.0, dtype="float64")
+    >>> lon = np.array([0, 50], dtype="float64")
+    >>> lat = np.array([6, -12], dtype="float64")
+    >>> interpolate(values, ipix, lon, lat, depth, ellipsoid="WGS84")
+    array([2., 2.])
+    """
+    _check_depth(depth)
+    values = np.asarray(values, dtype="float64")
+    ipix = _as_cell_ids(ipix)
+    longitude = _as_coordinates(longitude)
+    latitude = _as_coordinates(latitude)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.ring.interpolate(
+        depth, values, ipix, longitude, latitude, ellipsoid, num_threads
+    )
+
+
+def rasterize(
+    values,
+    ipix,
+    depth,
+    shape,
+    ellipsoid="sphere",
+    *,
+    projection="equirectangular",
+    central_longitude=0.0,
+    central_latitude=0.0,
+    fill_value=np.nan,
+    num_threads=0,
+):
+    r"""Render a HEALPix map onto an image.
+
+    Each pixel of the image is projected back to longitude and latitude and takes the
+    value of the cell containing it.
+
+    Parameters
+    ----------
+    values : array-like
+        The values of the map, one for each cell of ``ipix``.
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes the map is defined on. They don't have to cover the
+        whole sphere.
+    depth : int
+        The depth of the HEALPix cells.
+    shape : tuple of int
+        The shape of the image, as ``(height, width)``.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    projection : {"equirectangular", "mollweide", "orthographic"}, default: "equirectangular"
+        The map projection of the image. The image covers the whole extent of the
+        projection: the sphere for the equirectangular and Mollweide projections, and
+        the hemisphere centered on ``(central_longitude, central_latitude)`` for the
+        orthographic projection.
+    central_longitude : float, default: 0
+        The longitude at the center of the image, in degrees.
+    central_latitude : float, default: 0
+        The latitude at the center of the image, in degrees. Only used by the
+        orthographic projection.
+    fill_value : float, default: nan
+        The value of pixels outside of the sphere or whose cell is not in ``ipix``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    image : `numpy.ndarray`
+        The image, with north at the top and east to the right.
+
+    Raises
+    ------
+    ValueError
+        When the number of values and cells do not match.
+    ValueError
+        When ``projection`` is not one of the supported projections.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import rasterize
+    >>> import numpy as np
+    >>> depth = 1
+    >>> ipix = np.arange(48)
+    >>> values = np.ones(ipix.size)
+    >>> image = rasterize(values, ipix, depth, (3, 6), projection="mollweide")
+    >>> np.isnan(image)
+    array([[ True, False, False, False, False,  True],
+           [False, False, False, False, False, False],
+           [ True, False, False, False, False,  True]])
+    """
+    _check_depth(depth)
+    values = np.asarray(values, dtype="float64")
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.ring.rasterize(
+        depth,
+        values,
+        ipix,
+        ellipsoid,
+        tuple(shape),
+        projection,
+        central_longitude,
+        central_latitude,
+        fill_value,
+        num_threads,
+    )
+
+
+def regridding_weights(
+    lon_bounds,
+    lat_bounds,
+    depth,
+    ellipsoid="sphere",
+    *,
+    direction="to_healpix",
+    refinement=3,
+    num_threads=0,
+):
+    r"""Get area-weighted regridding weights between a lon/lat grid and HEALPix.
+
+    The weights are the areas of the overlaps between the source cells and the
+    HEALPix cells, normalized to sum to 1 for each target cell. The overlaps are
+    computed by clipping the source cells to the HEALPix cells in the HEALPix
+    projection, which preserves areas.
+
+    Parameters
+    ----------
+    lon_bounds : array-like
+        The longitudes of the vertices of the source cells, in degrees. The last axis
+        contains the vertices of each cell, and the source cells are the other axes,
+        flattened in C order.
+    lat_bounds : array-like
+        The latitudes of the vertices of the source cells, in degrees. Must have the
+        same shape as ``lon_bounds``.
+    depth : int
+        The depth of the HEALPix cells.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    direction : {"to_healpix", "from_healpix"}, default: "to_healpix"
+        Whether the weights regrid from the source cells to the HEALPix cells or the
+        other way around.
+    refinement : int, default: 3
+        The edges of the source cells that are neither meridians nor parallels are
+        followed in steps the size of the cells ``refinement`` levels below ``depth``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        The sorted HEALPix cell indexes overlapping any source cell.
+    weights : tuple of `numpy.ndarray`
+        The ``(data, indices, indptr)`` arrays of the weights in compressed sparse row
+        format. For ``"to_healpix"``, the rows are the cells of ``ipix`` and the
+        columns the source cells, and the other way around for ``"from_healpix"``.
+
+    Raises
+    ------
+    ValueError
+        When the shapes of ``lon_bounds`` and ``lat_bounds`` do not match.
+    ValueError
+        When a cell has less than 3 vertices, or a vertex is not a valid coordinate.
+    ValueError
+        When ``direction`` is not one of the supported directions.
+
+    Notes
+    -----
+    The edges of the source cells are straight lines in longitude and latitude, as
+    for rectilinear and most curvilinear grids. Source cells must not contain a pole,
+    unless they are bounded by it.
+
+    The weights can be used with :py:class:`scipy.sparse.csr_array`:
+
+    .. code:: python
+
+        matrix = scipy.sparse.csr_array(weights, shape=(ipix.size, n_source_cells))
+        regridded = matrix @ values
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import regridding_weights
+    >>> import numpy as np
+    >>> lon_edges = np.arange(0, 361, 90, dtype="float64")
+    >>> lat_edges = np.array([-90, 0, 90], dtype="float64")
+    >>> lon0, lat0 = np.meshgrid(lon_edges[:-1], lat_edges[:-1])
+    >>> lon1, lat1 = np.meshgrid(lon_edges[1:], lat_edges[1:])
+    >>> lon_bounds = np.stack([lon0, lon1, lon1, lon0], axis=-1)
+    >>> lat_bounds = np.stack([lat0, lat0, lat1, lat1], axis=-1)
+    >>> ipix, (data, indices, indptr) = regridding_weights(
+    ...     lon_bounds, lat_bounds, depth=1, ellipsoid="WGS84"
+    ... )
+    >>> ipix.size
+    48
+    >>> np.allclose(np.add.reduceat(data, indptr[:-1]), 1)
+    True
+    """
+    _check_depth(depth)
+    lon_bounds = _as_coordinates(lon_bounds)
+    lat_bounds = _as_coordinates(lat_bounds)
+
+    if lon_bounds.shape != lat_bounds.shape:
+        raise ValueError(
+            "The longitude and latitude bounds must have the same shape, got"
+            f" {lon_bounds.shape} and {lat_bounds.shape}"
+        )
+    n_vertices = lon_bounds.shape[-1]
+
+    num_threads = np.uint16(num_threads)
+
+    ipix, data, indices, indptr = healpix_geo.ring.regridding_weights(
+        depth,
+        lon_bounds.reshape(-1, n_vertices),
+        lat_bounds.reshape(-1, n_vertices),
+        ellipsoid,
+        refinement,
+        direction,
+        num_threads,
+    )
+
+    return ipix, (data, indices, indptr)
+
+
+def vertices(ipix, depth, ellipsoid, step=1, num_threads=0):
+    """Get the longitudes and latitudes of the vertices of some HEALPix cells at a given depth.
+
+    This method returns the 4 vertices of each cell in `ipix`.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int, or `numpy.ndarray`
+        The depth of the HEALPix cells. If given as an array, should have the same shape than ipix
+    ellipsoid : str, default: "sphere"
+        Reference ellipsoid to evaluate healpix on. If ``"sphere"``, this will return
+        the same result as :py:func:`cdshealpix.ring.vertices`.
+    step : int, default: 1
+        The number of vertices returned per HEALPix side. By default it is set to 1 meaning that
+        it will only return the vertices of the cell. 2 means that it will return the vertices of
+        the cell plus one more vertex per edge (the center of the edge). More generally, the number
+        of vertices returned is ``4 * step``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    longitude, latitude : array-like
+        The sky coordinates of the 4 vertices of the HEALPix cells.
+        `lon` and `lat` are of shape :math:`N` x :math:`4` numpy arrays where N is the number of HEALPix cell given in `ipix`.
+
+    Raises
+    ------
+    ValueError
+        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import vertices
+    >>> import numpy as np
+    >>> ipix = np.array([42, 6, 10])
+    >>> depth = 12
+    >>> lon, lat = vertices(ipix, depth, ellipsoid="sphere")
+    >>> np.stack([lon, lat], axis=-1)
+    array([[[ 45.        ,  89.93147196],
+            [ 54.        ,  89.9428933 ],
+            [ 45.        ,  89.95431464],
+            [ 36.        ,  89.9428933 ]],
+    <BLANKLINE>
+           [[120.        ,  89.96573598],
+            [135.        ,  89.97715732],
+            [ 90.        ,  89.98857866],
+            [ 90.        ,  89.97715732]],
+    <BLANKLINE>
+           [[300.        ,  89.96573598],
+            [315.        ,  89.97715732],
+            [270.        ,  89.98857866],
+            [270.        ,  89.97715732]]])
+    """
+    _check_depth(depth)
+    ipix = np.atleast_1d(ipix)
+    _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.ring.vertices(depth, ipix, ellipsoid, step, num_threads)
+
+
+def projected_to_healpix(
+    x,
+    y,
+    depth,
+    crs,
+    ellipsoid="sphere",
+    *,
+    z=None,
+    num_threads=0,
+    on_invalid="raise",
+):
+    r"""Get the HEALPix indexes that contain points given in a coordinate reference system.
+
+    The points are converted to longitudes and latitudes on the ellipsoid before
+    looking up their cells.
+
+    Parameters
+    ----------
+    x, y : array-like
+        The coordinates of the input points in the coordinate reference system.
+    depth : int
+        The HEALPix cell depth.
+    crs : crs-like
+        The coordinate reference system of the points. See :term:`crs-like`.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    z : array-like, optional
+        The third coordinate of the input points. Required for three-dimensional
+        coordinate reference systems, and the height otherwise.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle points that can't be converted to longitudes and latitudes.
+        ``"raise"`` raises a `ValueError`, ``"sentinel"`` returns the maximum
+        `np.uint64` value for them and ``"mask"`` additionally returns a mask of the
+        invalid points.
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        A numpy array containing all the HEALPix cell indexes stored as `np.uint64`.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid points. Only returned if
+        ``on_invalid="mask"``.
+
+    Raises
+    ------
+    ValueError
+        When the coordinate reference system is unknown or invalid.
+    ValueError
+        When the points are invalid and ``on_invalid="raise"``.
+    ValueError
+        When the name of the ellipsoid is unknown.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import projected_to_healpix
+    >>> import numpy as np
+    >>> x = np.array([0.0])
+    >>> y = np.array([-1e6])
+    >>> projected_to_healpix(x, y, 0, "EPSG:3413", ellipsoid="WGS84")
+    array([3], dtype=uint64)
+    """
+    _check_depth(depth)
+    x = _as_coordinates(x)
+    y = _as_coordinates(y)
+    if z is not None:
+        z = _as_coordinates(z)
+
+    num_threads = np.uint16(num_threads)
+
+    ipix, mask = healpix_geo.ring.projected_to_healpix(
+        depth, x, y, z, crs, ellipsoid, num_threads, on_invalid
+    )
+    if on_invalid == "mask":
+        return ipix, mask
+
+    return ipix
+
+
+def healpix_to_projected(
+    ipix, depth, crs, ellipsoid="sphere", num_threads=0, on_invalid="raise"
+):
+    r"""Get the coordinates of the center of some HEALPix cells in a coordinate reference system.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The HEALPix cell depth.
+    crs : crs-like
+        The coordinate reference system of the points. See :term:`crs-like`.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid cell indexes. ``"raise"`` raises a `ValueError`,
+        ``"sentinel"`` returns NaN coordinates for them and ``"mask"`` additionally
+        returns a mask of the invalid cells.
+
+    Returns
+    -------
+    x, y : `numpy.ndarray`
+        The coordinates of the center of the HEALPix cells.
+    z : `numpy.ndarray`
+        The third coordinate of the center of the HEALPix cells. Only returned for
+        three-dimensional coordinate reference systems.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid cells and the cells that can't
+        be converted. Only returned if ``on_invalid="mask"``.
+
+    Raises
+    ------
+    ValueError
+        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`
+        and ``on_invalid="raise"``.
+    ValueError
+        When the coordinate reference system is unknown or invalid.
+    ValueError
+        When the name of the ellipsoid is unknown.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import healpix_to_projected
+    >>> import numpy as np
+    >>> ipix = np.array([4])
+    >>> x, y, z = healpix_to_projected(ipix, 0, "EPSG:4978", ellipsoid="WGS84")
+    >>> x
+    array([6378137.])
+    """
+    _check_depth(depth)
+    ipix = np.atleast_1d(ipix)
+    if on_invalid == "raise":
+        _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    x, y, z, mask = healpix_geo.ring.healpix_to_projected(
+        depth, ipix, crs, ellipsoid, num_threads, on_invalid
+    )
+    coordinates = (x, y) if z is None else (x, y, z)
+    if on_invalid == "mask":
+        return *coordinates, mask
+
+    return coordinates
+
+
+def vertices_projected(ipix, depth, crs, ellipsoid="sphere", step=1, num_threads=0):
+    """Get the coordinates of the vertices of some HEALPix cells in a coordinate reference system.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The HEALPix cell depth.
+    crs : crs-like
+        The coordinate reference system of the points. See :term:`crs-like`.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    step : int, default: 1
+        The number of vertices returned per HEALPix side. The number of vertices
+        returned is ``4 * step``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    x, y : `numpy.ndarray`
+        The coordinates of the vertices, with an additional trailing dimension of
+        size ``4 * step``.
+    z : `numpy.ndarray`
+        The third coordinate of the vertices. Only returned for three-dimensional
+        coordinate reference systems.
+
+    Raises
+    ------
+    ValueError
+        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`.
+    ValueError
+        When the coordinate reference system is unknown or invalid.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import vertices_projected
+    >>> import numpy as np
+    >>> ipix = np.array([4])
+    >>> x, y = vertices_projected(ipix, 0, 32631, ellipsoid="WGS84")
+    >>> x.shape
+    (1, 4)
+    """
+    _check_depth(depth)
+    ipix = np.atleast_1d(ipix)
+    _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    x, y, z = healpix_geo.ring.vertices_projected(
+        depth, ipix, crs, ellipsoid, step, num_threads
+    )
+    if z is None:
+        return x, y
+
+    return x, y, z
+
+
+def xyz_to_healpix(
+    x, y, z, depth, ellipsoid="sphere", num_threads=0, on_invalid="raise"
+):
+    r"""Get the HEALPix indexes that contain points given in body-centered cartesian coordinates.
+
+    The points are projected onto the ellipsoid along its normal, so their height
+    above the ellipsoid does not change the cell they belong to.
+
+    Parameters
+    ----------
+    x, y, z : array-like
+        The body-centered cartesian coordinates of the input points, in the unit of
+        the ellipsoid axes (usually meters).
+    depth : int
+        The HEALPix cell depth.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid points, like the center of the ellipsoid.
+        ``"raise"`` raises a `ValueError`, ``"sentinel"`` returns the maximum
+        `np.uint64` value for them and ``"mask"`` additionally returns a mask of the
+        invalid points.
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        A numpy array containing all the HEALPix cell indexes stored as `np.uint64`.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid points. Only returned if
+        ``on_invalid="mask"``.
+
+    Raises
+    ------
+    ValueError
+        When the points are invalid and ``on_invalid="raise"``.
+    ValueError
+        When the name of the ellipsoid is unknown.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import xyz_to_healpix
+    >>> import numpy as np
+    >>> x = np.array([0.0])
+    >>> y = np.array([7e6])
+    >>> z = np.array([0.0])
+    >>> xyz_to_healpix(x, y, z, 0, ellipsoid="WGS84")
+    array([5], dtype=uint64)
+    """
+    _check_depth(depth)
+    x = _as_coordinates(x)
+    y = _as_coordinates(y)
+    z = _as_coordinates(z)
+
+    num_threads = np.uint16(num_threads)
+
+    ipix, mask = healpix_geo.ring.xyz_to_healpix(
+        depth, x, y, z, ellipsoid, num_threads, on_invalid
+    )
+    if on_invalid == "mask":
+        return ipix, mask
+
+    return ipix
+
+
+def healpix_to_xyz(
+    ipix, depth, ellipsoid="sphere", height=0.0, num_threads=0, on_invalid="raise"
+):
+    r"""Get the body-centered cartesian coordinates of the center of some HEALPix cells.
+
+    The coordinates are on the surface of the ellipsoid, or at ``height`` above it,
+    and not on the unit sphere.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The HEALPix cell depth.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    height : float, default: 0.0
+        The height above the ellipsoid, in the unit of the ellipsoid axes (usually
+        meters).
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid cell indexes. ``"raise"`` raises a `ValueError`,
+        ``"sentinel"`` returns NaN coordinates for them and ``"mask"`` additionally
+        returns a mask of the invalid cells.
+
+    Returns
+    -------
+    x, y, z : `numpy.ndarray`
+        The body-centered cartesian coordinates of the center of the HEALPix cells,
+        in the unit of the ellipsoid axes.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid cells. Only returned if
+        ``on_invalid="mask"``.
+
+    Raises
+    ------
+    ValueError
+        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`
+        and ``on_invalid="raise"``.
+    ValueError
+        When the name of the ellipsoid is unknown.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import healpix_to_xyz
+    >>> import numpy as np
+    >>> ipix = np.array([4])
+    >>> x, y, z = healpix_to_xyz(ipix, 0, ellipsoid="WGS84")
+    >>> x
+    array([6378137.])
+    """
+    _check_depth(depth)
+    ipix = np.atleast_1d(ipix)
+    if on_invalid == "raise":
+        _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    x, y, z, mask = healpix_geo.ring.healpix_to_xyz(
+        depth, ipix, ellipsoid, height, num_threads, on_invalid
+    )
+    if on_invalid == "mask":
+        return x, y, z, mask
+
+    return x, y, z
+
+
+def vertices_xyz(ipix, depth, ellipsoid="sphere", height=0.0, step=1, num_threads=0):
+    """Get the body-centered cartesian coordinates of the vertices of some HEALPix cells.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The HEALPix cell depth.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    height : float, default: 0.0
+        The height above the ellipsoid, in the unit of the ellipsoid axes (usually
+        meters).
+    step : int, default: 1
+        The number of vertices returned per HEALPix side. The number of vertices
+        returned is ``4 * step``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    x, y, z : `numpy.ndarray`
+        The body-centered cartesian coordinates of the vertices, with an additional
+        trailing dimension of size ``4 * step``.
+
+    Raises
+    ------
+    ValueError
+        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`.
+    ValueError
+        When the name of the ellipsoid is unknown.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import vertices_xyz
+    >>> import numpy as np
+    >>> ipix = np.array([4])
+    >>> x, y, z = vertices_xyz(ipix, 0, ellipsoid="WGS84")
+    >>> x.shape
+    (1, 4)
+    """
+    _check_depth(depth)
+    ipix = np.atleast_1d(ipix)
+    _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.ring.vertices_xyz(
+        depth, ipix, ellipsoid, height, step, num_threads
+    )
+
+
+def cell_area(ipix, depth, ellipsoid="sphere", num_threads=0):
+    """Compute the area of HEALPix cells on the reference ellipsoid.
+
+    HEALPix cells have equal areas on the authalic sphere, so the area only depends
+    on the depth of the cell and on the authalic radius of the ellipsoid.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The depth of the HEALPix cells.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    area : numpy.ndarray
+        The area of each cell, in squared units of the semimajor axis of the
+        ellipsoid (m² for named ellipsoids like ``"WGS84"``).
+    """
+    _check_depth(depth)
+    ipix = np.atleast_1d(ipix)
+    _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.ring.cell_area(depth, ipix, ellipsoid, num_threads)
+
+
+def cell_perimeter(ipix, depth, ellipsoid="sphere", step=1, num_threads=0):
+    """Compute the perimeter of HEALPix cells on the reference ellipsoid.
+
+    The perimeter is the length of the closed geodesic polygon through the
+    vertices returned by :py:func:`vertices`. Since the cell edges are not
+    geodesics, increasing ``step`` makes the result converge towards the length
+    of the actual cell boundary.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The depth of the HEALPix cells.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    step : int, default: 1
+        The number of vertices per cell edge.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    perimeter : numpy.ndarray
+        The perimeter of each cell, in units of the semimajor axis of the
+        ellipsoid (m for named ellipsoids like ``"WGS84"``).
+    """
+    _check_depth(depth)
+    ipix = np.atleast_1d(ipix)
+    _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.ring.cell_perimeter(depth, ipix, ellipsoid, step, num_threads)
+
+
+def kth_neighbourhood(ipix, depth, ring, num_threads=0):
+    """Get the kth ring neighbouring cells of some HEALPix cells at a given depth.
+
+    This method returns a :math:`N` x :math:`(2 k + 1)^2` `np.uint64` numpy array containing the neighbours of each cell of the :math:`N` sized `ipix` array.
+    This method is wrapped around the `kth_neighbourhood <https://docs.rs/cdshealpix/0.1.5/cdshealpix/nested/struct.Layer.html#method.neighbours_in_kth_ring>`__
+    method from the `cdshealpix Rust crate <https://crates.io/crates/cdshealpix>`__.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The depth of the HEALPix cells.
+    ring : int
+        The number of rings. `ring=0` returns just the input cell ids, `ring=1` returns the 8 (or 7) immediate
+        neighbours, `ring=2` returns the 8 (or 7) immediate neighbours plus their immediate neighbours (a total of 24 cells), and so on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    neighbours : `numpy.ndarray`
+        A :math:`N` x :math:`(2 k + 1)^2` `np.int64` numpy array containing the kth ring neighbours of each cell.
+        The :math:`5^{th}` element corresponds to the index of HEALPix cell from which the neighbours are evaluated.
+        All its 8 neighbours occup the remaining elements of the line.
+
+    Raises
+    ------
+    ValueError
+        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import kth_neighbourhood
+    >>> import numpy as np
+    >>> ipix = np.array([42, 6, 10])
+    >>> depth = 12
+    >>> ring = 3
+    >>> neighbours = kth_neighbourhood(ipix, depth, ring)
+    >>> neighbours
+    array([[ 42,  87,  62,  41,  25,  13,  26,  43,  63, 148, 115,  86,  61,
+             40,  24,  12,   4,   0,   5,  14,  27,  44,  64,  88, 116,  83,
+             59,  39,  23,  11,   3,   2,  66,   1,   6,  15,  28,  45, 225,
+            184, 147, 114,  85,  60,  65,  89, 117, 149, 185],
+           [  6,  14,   5,   0,  29,  15,   1,   7,  16,  43,  26,  13,   4,
+             27,  11,   3,  18,   2,   8,  68,  46,  28,  17,  30,  47,  88,
+             63,  42,  25,  12,  44,  64,  38,  22,  10,  23,  51,  32,   9,
+             19,  33, 123,  93,  67,  45,  31,  48,  69,  94],
+           [ 10,  20,   9,   2,  37,  21,   3,  11,  22,  53,  34,  19,   8,
+             35,   7,   1,  12,   0,   4,  80,  56,  36,  23,  38,  57, 102,
+             75,  52,  33,  18,  54,  76,  30,  16,   6,  17,  41,  24,   5,
+             13,  25, 139, 107,  79,  55,  39,  58,  81, 108]])
+    """
+    _check_depth(depth)
+    ipix = np.atleast_1d(ipix)
+    _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
+    _check_ring(depth, ring)
+
+    num_threads = np.uint16(num_threads)
+    return healpix_geo.ring.kth_neighbourhood(depth, ipix, ring, num_threads)
+
+
+def neighbours(ipix, depth, num_threads=0):
+    r"""Find the 8 neighbours of every cell, labelled by direction
+
+    Parameters
+    ----------
+    ipix : numpy.ndarray
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The depth of the HEALPix cells.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    neighbours : numpy.ndarray
+        A `np.int64` numpy array with the shape of ``ipix`` and an additional axis of
+        size 8, containing the neighbours in the N, NE, E, SE, S, SW, W and NW
+        directions, in that order. Missing neighbours are set to -1.
+
+    Notes
+    -----
+    The directions are relative to the base cell containing each cell: the N, E, S
+    and W neighbours only share a vertex with the cell, while the NE, SE, SW and NW
+    neighbours share an edge. Around the 8 vertices of base cells touching only 3 base
+    cells, one of the neighbours is missing.
+
+    Examples
+    --------
+    >>> import healpix_geo
+    >>> from healpix_geo.ring import neighbours
+    >>> import numpy as np
+    >>> ipix = healpix_geo.ring.zoom_to(np.array([0]), 0, 1)[0]
+    >>> neighbours(ipix, depth=1).shape
+    (4, 8)
+    >>> int((neighbours(ipix, depth=1) == -1).sum())
+    2
+    """
+    _check_depth(depth)
+    ipix = np.atleast_1d(ipix)
+    _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.ring.neighbours(depth, ipix, num_threads)
+
+
+def neighbours_within_distance(ipix, depth, radius, ellipsoid="sphere", num_threads=0):
+    r"""Find the cells whose center is within a distance of the center of every cell
+
+    Parameters
+    ----------
+    ipix : numpy.ndarray
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The depth of the HEALPix cells.
+    radius : float
+        The maximum geodesic distance between cell centers, in the unit of the
+        ellipsoid axes (usually meters).
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    neighbours : numpy.ndarray
+        The neighbours of all cells, flattened, as a `np.uint64` numpy array. The
+        neighbours of each cell include the cell itself and are sorted by distance.
+    distances : numpy.ndarray
+        The geodesic distances between the center of each cell and the centers of its
+        neighbours, in the unit of the ellipsoid axes.
+    offsets : numpy.ndarray
+        A `np.int64` numpy array of size ``ipix.size + 1``. The neighbours of the
+        ``i``-th cell of the flattened ``ipix`` are
+        ``neighbours[offsets[i]:offsets[i + 1]]``.
+
+    Raises
+    ------
+    ValueError
+        When ``radius`` is not a positive number.
+
+    Notes
+    -----
+    Unlike :py:func:`kth_neighbourhood`, the neighbourhoods cover the same physical
+    extent everywhere on the ellipsoid, which makes them suitable for kernel-based
+    smoothing.
+
+    Examples
+    --------
+    >>> from healpix_geo.ring import neighbours_within_distance
+    >>> import numpy as np
+    >>> ipix = np.array([0, 100])
+    >>> neighbours, distances, offsets = neighbours_within_distance(
+    ...     ipix, depth=3, radius=1_000_000, ellipsoid="WGS84"
+    ... )
+    >>> offsets.size
+    3
+    >>> int(neighbours[offsets[1]]), float(distances[offsets[1]])
+    (100, 0.0)
+    >>> bool((distances <= 1_000_000).all())
+    True
+    """
+    _check_depth(depth)
+    ipix = np.ravel(ipix)
+    _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.ring.neighbours_within_distance(
+        depth, ipix, radius, ellipsoid, num_threads
+    )
+
+
+def zoom_to(ipix, depth, new_depth, num_threads=0):
+    r"""Change the resolutions the given cell ids
+
+    Parameters
+    ----------
+    ipix : numpy.ndarray
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The depth of the HEALPix cells.
+    new_depth : int
+        The new depth of the HEALPix cells.
+
+    Returns
+    -------
+    cells : numpy.ndarray
+        A :math:`N` (`depth >= new_depth`) or :math:`N` x :math:`4^{\delta d}` `np.uint64` numpy array containing the parents or children of the given cells.
+        If `depth == new_depth`, returns the input pixels
+    """
+    _check_depth(depth)
+    _check_depth(new_depth)
+
+    if depth == new_depth:
+        return ipix
+
+    ipix = np.atleast_1d(ipix)
+    _check_ipixels(data=ipix, depth=depth)
+    ipix = _as_cell_ids(ipix)
 
-    Returns
-    -------
-    lon, lat : array-like
-        The coordinates of the center of the HEALPix cells given as a longitude, latitude tuple.
+    num_threads = np.uint16(num_threads)
+    return healpix_geo.ring.zoom_to(depth, ipix, new_depth, num_threads)
 
-    Raises
-    ------
-    ValueError
-        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`.
-    ValueError
-        When the name of the ellipsoid is unknown.
 
-    Examples
-    --------
-    >>> from healpix_geo.ring import healpix_to_lonlat
-    >>> import numpy as np
-    >>> ipix = np.array([42, 6, 10])
-    >>> depth = 3
-    >>> lon, lat = healpix_to_lonlat(ipix, depth, ellipsoid="WGS84")
-    >>> lon
-    array([ 45. , 112.5, 292.5])
-    >>> lat
-    array([60.54441647, 78.33504545, 78.33504545])
+def siblings(ipix, depth, num_threads=0):
+    r"""Find the siblings for every cell
+
+    Parameters
+    ----------
+    ipix : numpy.ndarray
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    depth : int
+        The depth of the HEALPix cells.
+
+    Returns
+    -------
+    cells : numpy.ndarray
+        A :math:`N` x :math:`4` or :math:`N` x :math:`12` `np.uint64` numpy array containing the siblings of the given cells.
+        If `depth == 0`, the siblings are the base cells.
     """
     _check_depth(depth)
     ipix = np.atleast_1d(ipix)
     _check_ipixels(data=ipix, depth=depth)
-    ipix = ipix.astype(np.uint64)
+    ipix = _as_cell_ids(ipix)
 
     num_threads = np.uint16(num_threads)
 
-    return healpix_geo.ring.healpix_to_lonlat(depth, ipix, ellipsoid, num_threads)
+    return healpix_geo.ring.siblings(depth, ipix, num_threads)
 
 
-def lonlat_to_healpix(longitude, latitude, depth, ellipsoid="sphere", num_threads=0):
-    r"""Get the HEALPix indexes that contains specific points.
+def aggregate_to_parent(ipix, values, depth, new_depth, op="mean", num_threads=0):
+    r"""Aggregate the values of cells into their parents.
 
     Parameters
     ----------
-    lon : array-like
-        The longitudes of the input points, in degrees.
-    lat : array-like
-        The latitudes of the input points, in degrees.
-    depth : int or array-like of int
-        The HEALPix cell depth given as a `np.uint8` numpy array.
-    ellipsoid : str, default: "sphere"
-        Reference ellipsoid to evaluate healpix on. If ``"sphere"``, this will return
-        the same result as :py:func:`cdshealpix.ring.lonlat_to_healpix`.
+    ipix : numpy.ndarray
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    values : array-like
+        The values of the cells, one for each cell of ``ipix``.
+    depth : int
+        The depth of the HEALPix cells.
+    new_depth : int
+        The depth of the parents. Must not be finer than ``depth``.
+    op : {"sum", "mean", "min", "max", "count", "area_weighted_mean"}, default: "mean"
+        The reduction applied to the values of the cells sharing a parent. All cells
+        of a depth have the same area, so ``"area_weighted_mean"`` is the same as
+        ``"mean"``.
     num_threads : int, optional
         Specifies the number of threads to use for the computation. Default to 0 means
         it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
@@ -76,57 +1389,62 @@ def lonlat_to_healpix(longitude, latitude, depth, ellipsoid="sphere", num_thread
 
     Returns
     -------
-    ipix : `numpy.ndarray`
-        A numpy array containing all the HEALPix cell indexes stored as `np.uint64`.
+    parents : numpy.ndarray
+        The sorted parents of the cells, as a `np.uint64` numpy array.
+    reduced : numpy.ndarray
+        The reduced values of each parent.
 
     Raises
     ------
     ValueError
-        When the number of longitudes and latitudes given do not match.
+        When the number of values and cells do not match.
     ValueError
-        When the name of the ellipsoid is unknown.
+        When ``new_depth`` is finer than ``depth`` or ``op`` is not a supported
+        reduction.
+
+    See Also
+    --------
+    broadcast_to_children
 
     Examples
     --------
-    >>> from healpix_geo.ring import lonlat_to_healpix
+    >>> from healpix_geo.ring import aggregate_to_parent
     >>> import numpy as np
-    >>> lon = np.array([0, 50, 25], dtype="float64")
-    >>> lat = np.array([6, -12, 45], dtype="float64")
-    >>> depth = 3
-    >>> ipix = lonlat_to_healpix(lon, lat, depth, ellipsoid="WGS84")
-    >>> ipix
-    array([336, 436, 114], dtype=uint64)
+    >>> ipix = np.arange(48)
+    >>> values = np.ones(48)
+    >>> parents, counts = aggregate_to_parent(
+    ...     ipix, values, depth=1, new_depth=0, op="count"
+    ... )
+    >>> parents
+    array([ 0,  1,  2,  3,  4,  5,  6,  7,  8,  9, 10, 11], dtype=uint64)
+    >>> counts
+    array([4., 4., 4., 4., 4., 4., 4., 4., 4., 4., 4., 4.])
     """
     _check_depth(depth)
-    longitude = np.atleast_1d(longitude).astype("float64")
-    latitude = np.atleast_1d(latitude).astype("float64")
+    _check_depth(new_depth)
+    ipix = _as_cell_ids(np.ravel(ipix))
+    values = np.ravel(np.asarray(values, dtype="float64"))
 
     num_threads = np.uint16(num_threads)
 
-    return healpix_geo.ring.lonlat_to_healpix(
-        depth, longitude, latitude, ellipsoid, num_threads
+    return healpix_geo.ring.aggregate_to_parent(
+        depth, ipix, values, new_depth, op, num_threads
     )
 
 
-def vertices(ipix, depth, ellipsoid, step=1, num_threads=0):
-    """Get the longitudes and latitudes of the vertices of some HEALPix cells at a given depth.
-
-    This method returns the 4 vertices of each cell in `ipix`.
+def broadcast_to_children(ipix, values, depth, new_depth, num_threads=0):
+    r"""Broadcast the values of cells to their children.
 
     Parameters
     ----------
-    ipix : `numpy.ndarray`
+    ipix : numpy.ndarray
         The HEALPix cell indexes given as a `np.uint64` numpy array.
-    depth : int, or `numpy.ndarray`
-        The depth of the HEALPix cells. If given as an array, should have the same shape than ipix
-    ellipsoid : str, default: "sphere"
-        Reference ellipsoid to evaluate healpix on. If ``"sphere"``, this will return
-        the same result as :py:func:`cdshealpix.ring.vertices`.
-    step : int, default: 1
-        The number of vertices returned per HEALPix side. By default it is set to 1 meaning that
-        it will only return the vertices of the cell. 2 means that it will return the vertices of
-        the cell plus one more vertex per edge (the center of the edge). More generally, the number
-        of vertices returned is ``4 * step``.
+    values : array-like
+        The values of the cells, one for each cell of ``ipix``.
+    depth : int
+        The depth of the HEALPix cells.
+    new_depth : int
+        The depth of the children. Must not be coarser than ``depth``.
     num_threads : int, optional
         Specifies the number of threads to use for the computation. Default to 0 means
         it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
@@ -134,115 +1452,110 @@ def vertices(ipix, depth, ellipsoid, step=1, num_threads=0):
 
     Returns
     -------
-    longitude, latitude : array-like
-        The sky coordinates of the 4 vertices of the HEALPix cells.
-        `lon` and `lat` are of shape :math:`N` x :math:`4` numpy arrays where N is the number of HEALPix cell given in `ipix`.
+    children : numpy.ndarray
+        The children of the cells, as a `np.uint64` numpy array.
+    broadcasted : numpy.ndarray
+        The value of the parent of each child.
 
     Raises
     ------
     ValueError
-        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`.
+        When the number of values and cells do not match.
+    ValueError
+        When ``new_depth`` is coarser than ``depth``.
+
+    See Also
+    --------
+    aggregate_to_parent
 
     Examples
     --------
-    >>> from healpix_geo.ring import vertices
+    >>> from healpix_geo.ring import broadcast_to_children
     >>> import numpy as np
-    >>> ipix = np.array([42, 6, 10])
-    >>> depth = 12
-    >>> lon, lat = vertices(ipix, depth, ellipsoid="sphere")
-    >>> np.stack([lon, lat], axis=-1)
-    array([[[ 45.        ,  89.93147196],
-            [ 54.        ,  89.9428933 ],
-            [ 45.        ,  89.95431464],
-            [ 36.        ,  89.9428933 ]],
-    <BLANKLINE>
-           [[120.        ,  89.96573598],
-            [135.        ,  89.97715732],
-            [ 90.        ,  89.98857866],
-            [ 90.        ,  89.97715732]],
-    <BLANKLINE>
-           [[300.        ,  89.96573598],
-            [315.        ,  89.97715732],
-            [270.        ,  89.98857866],
-            [270.        ,  89.97715732]]])
+    >>> children, broadcasted = broadcast_to_children(
+    ...     np.array([3]), np.array([1.5]), depth=0, new_depth=1
+    ... )
+    >>> children.size
+    4
+    >>> broadcasted
+    array([1.5, 1.5, 1.5, 1.5])
     """
     _check_depth(depth)
-    ipix = np.atleast_1d(ipix)
-    _check_ipixels(data=ipix, depth=depth)
-    ipix = ipix.astype(np.uint64)
+    _check_depth(new_depth)
+    ipix = _as_cell_ids(np.ravel(ipix))
+    values = np.ravel(np.asarray(values, dtype="float64"))
 
     num_threads = np.uint16(num_threads)
 
-    return healpix_geo.ring.vertices(depth, ipix, ellipsoid, step, num_threads)
-
+    return healpix_geo.ring.broadcast_to_children(
+        depth, ipix, values, new_depth, num_threads
+    )
 
-def kth_neighbourhood(ipix, depth, ring, num_threads=0):
-    """Get the kth ring neighbouring cells of some HEALPix cells at a given depth.
 
-    This method returns a :math:`N` x :math:`(2 k + 1)^2` `np.uint64` numpy array containing the neighbours of each cell of the :math:`N` sized `ipix` array.
-    This method is wrapped around the `kth_neighbourhood <https://docs.rs/cdshealpix/0.1.5/cdshealpix/nested/struct.Layer.html#method.neighbours_in_kth_ring>`__
-    method from the `cdshealpix Rust crate <https://crates.io/crates/cdshealpix>`__.
+def angular_distances(from_, to_, depth, num_threads=0):
+    """Compute the angular distances
 
     Parameters
     ----------
-    ipix : `numpy.ndarray`
-        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    from_ : numpy.ndarray
+        The source Healpix cell indexes given as a ``np.uint64`` numpy array. Should be 1D.
+    to_ : numpy.ndarray
+        The destination Healpix cell indexes given as a ``np.uint64`` numpy array.
+        Should be 2D.
     depth : int
-        The depth of the HEALPix cells.
-    ring : int
-        The number of rings. `ring=0` returns just the input cell ids, `ring=1` returns the 8 (or 7) immediate
-        neighbours, `ring=2` returns the 8 (or 7) immediate neighbours plus their immediate neighbours (a total of 24 cells), and so on.
-    num_threads : int, optional
+        The depth of the Healpix cells.
+    num_threads : int, default: 0
         Specifies the number of threads to use for the computation. Default to 0 means
         it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
         or the number of logical CPUs (otherwise)
 
     Returns
     -------
-    neighbours : `numpy.ndarray`
-        A :math:`N` x :math:`(2 k + 1)^2` `np.int64` numpy array containing the kth ring neighbours of each cell.
-        The :math:`5^{th}` element corresponds to the index of HEALPix cell from which the neighbours are evaluated.
-        All its 8 neighbours occup the remaining elements of the line.
+    distances : numpy.ndarray
+        The angular distances in radians.
 
     Raises
     ------
     ValueError
-        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`.
-
-    Examples
-    --------
-    >>> from healpix_geo.ring import kth_neighbourhood
-    >>> import numpy as np
-    >>> ipix = np.array([42, 6, 10])
-    >>> depth = 12
-    >>> ring = 3
-    >>> neighbours = kth_neighbourhood(ipix, depth, ring)
-    >>> neighbours
-    array([[ 42,  87,  62,  41,  25,  13,  26,  43,  63, 148, 115,  86,  61,
-             40,  24,  12,   4,   0,   5,  14,  27,  44,  64,  88, 116,  83,
-             59,  39,  23,  11,   3,   2,  66,   1,   6,  15,  28,  45, 225,
-            184, 147, 114,  85,  60,  65,  89, 117, 149, 185],
-           [  6,  14,   5,   0,  29,  15,   1,   7,  16,  43,  26,  13,   4,
-             27,  11,   3,  18,   2,   8,  68,  46,  28,  17,  30,  47,  88,
-             63,  42,  25,  12,  44,  64,  38,  22,  10,  23,  51,  32,   9,
-             19,  33, 123,  93,  67,  45,  31,  48,  69,  94],
-           [ 10,  20,   9,   2,  37,  21,   3,  11,  22,  53,  34,  19,   8,
-             35,   7,   1,  12,   0,   4,  80,  56,  36,  23,  38,  57, 102,
-             75,  52,  33,  18,  54,  76,  30,  16,   6,  17,  41,  24,   5,
-             13,  25, 139, 107,  79,  55,  39,  58,  81, 108]])
+        When the Healpix cell indexes given have values out of :math:`[0, 4^{depth}[`.
     """
     _check_depth(depth)
-    ipix = np.atleast_1d(ipix)
-    _check_ipixels(data=ipix, depth=depth)
-    ipix = ipix.astype(np.uint64)
-    _check_ring(depth, ring)
+
+    from_ = np.atleast_1d(from_)
+    _check_ipixels(data=from_, depth=depth)
+    from_ = _as_cell_ids(from_)
+
+    mask = to_ != -1
+    masked_to = np.where(mask, to_, 0)
+
+    to_ = np.atleast_1d(masked_to)
+    _check_ipixels(data=to_, depth=depth)
+    to_ = _as_cell_ids(to_)
+
+    if from_.shape != to_.shape and from_.shape != to_.shape[:-1]:
+        raise ValueError(
+            "The shape of `from_` must be compatible with the shape of `to_`:\n"
+            f"{to_.shape} or {to_.shape[:-1]} must be equal to {from_.shape}."
+        )
+
+    if from_.shape == to_.shape:
+        intermediate_shape = to_.shape + (1,)
+    else:
+        intermediate_shape = to_.shape
 
     num_threads = np.uint16(num_threads)
-    return healpix_geo.ring.kth_neighbourhood(depth, ipix, ring, num_threads)
 
+    distances = healpix_geo.ring.angular_distances(
+        depth, from_, np.reshape(to_, intermediate_shape), num_threads
+    )
+
+    return np.where(mask, np.reshape(distances, to_.shape), np.nan)
 
-def angular_distances(from_, to_, depth, num_threads=0):
-    """Compute the angular distances
+
+def geodesic_distances(from_, to_, depth, ellipsoid="sphere", num_threads=0):
+    """Compute the geodesic distances between cell centers
+
+    The distances are computed on the reference ellipsoid using Karney's algorithm.
 
     Parameters
     ----------
@@ -250,9 +1563,11 @@ def angular_distances(from_, to_, depth, num_threads=0):
         The source Healpix cell indexes given as a ``np.uint64`` numpy array. Should be 1D.
     to_ : numpy.ndarray
         The destination Healpix cell indexes given as a ``np.uint64`` numpy array.
-        Should be 2D.
+        Should be 2D. Entries set to ``-1`` are ignored.
     depth : int
         The depth of the Healpix cells.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
     num_threads : int, default: 0
         Specifies the number of threads to use for the computation. Default to 0 means
         it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
@@ -261,7 +1576,12 @@ def angular_distances(from_, to_, depth, num_threads=0):
     Returns
     -------
     distances : numpy.ndarray
-        The angular distances in radians.
+        The geodesic distances in the unit of the ellipsoid axes (usually meters).
+    forward_azimuths : numpy.ndarray
+        The azimuths at the source cell centers, in degrees clockwise from north.
+    back_azimuths : numpy.ndarray
+        The azimuths at the destination cell centers pointing back to the source,
+        in degrees clockwise from north.
 
     Raises
     ------
@@ -272,14 +1592,14 @@ def angular_distances(from_, to_, depth, num_threads=0):
 
     from_ = np.atleast_1d(from_)
     _check_ipixels(data=from_, depth=depth)
-    from_ = from_.astype("uint64")
+    from_ = _as_cell_ids(from_)
 
     mask = to_ != -1
     masked_to = np.where(mask, to_, 0)
 
     to_ = np.atleast_1d(masked_to)
     _check_ipixels(data=to_, depth=depth)
-    to_ = to_.astype("uint64")
+    to_ = _as_cell_ids(to_)
 
     if from_.shape != to_.shape and from_.shape != to_.shape[:-1]:
         raise ValueError(
@@ -294,11 +1614,87 @@ def angular_distances(from_, to_, depth, num_threads=0):
 
     num_threads = np.uint16(num_threads)
 
-    distances = healpix_geo.ring.angular_distances(
-        depth, from_, np.reshape(to_, intermediate_shape), num_threads
+    results = healpix_geo.ring.geodesic_distances(
+        depth, from_, np.reshape(to_, intermediate_shape), ellipsoid, num_threads
     )
 
-    return np.where(mask, np.reshape(distances, to_.shape), np.nan)
+    return tuple(
+        np.where(mask, np.reshape(result, to_.shape), np.nan) for result in results
+    )
+
+
+def geodesic_distances_to_points(
+    from_, longitude, latitude, depth, ellipsoid="sphere", num_threads=0
+):
+    """Compute the geodesic distances between cell centers and points
+
+    The distances are computed on the reference ellipsoid using Karney's algorithm.
+
+    Parameters
+    ----------
+    from_ : numpy.ndarray
+        The source Healpix cell indexes given as a ``np.uint64`` numpy array. Should be 1D.
+    longitude, latitude : numpy.ndarray
+        The coordinates of the destination points in degrees. Should be 2D, with one row
+        per source cell.
+    depth : int
+        The depth of the Healpix cells.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, default: 0
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    distances : numpy.ndarray
+        The geodesic distances in the unit of the ellipsoid axes (usually meters).
+    forward_azimuths : numpy.ndarray
+        The azimuths at the cell centers, in degrees clockwise from north.
+    back_azimuths : numpy.ndarray
+        The azimuths at the points pointing back to the cell centers, in degrees
+        clockwise from north.
+
+    Raises
+    ------
+    ValueError
+        When the Healpix cell indexes given have values out of :math:`[0, 4^{depth}[`.
+    """
+    _check_depth(depth)
+
+    from_ = np.atleast_1d(from_)
+    _check_ipixels(data=from_, depth=depth)
+    from_ = _as_cell_ids(from_)
+
+    longitude, latitude = np.broadcast_arrays(
+        _as_coordinates(longitude),
+        _as_coordinates(latitude),
+    )
+
+    if from_.shape != longitude.shape and from_.shape != longitude.shape[:-1]:
+        raise ValueError(
+            "The shape of `from_` must be compatible with the shape of the points:\n"
+            f"{longitude.shape} or {longitude.shape[:-1]} must be equal to {from_.shape}."
+        )
+
+    if from_.shape == longitude.shape:
+        intermediate_shape = longitude.shape + (1,)
+    else:
+        intermediate_shape = longitude.shape
+
+    num_threads = np.uint16(num_threads)
+
+    results = healpix_geo.ring.geodesic_distances_to_points(
+        depth,
+        from_,
+        np.reshape(longitude, intermediate_shape),
+        np.reshape(latitude, intermediate_shape),
+        ellipsoid,
+        num_threads,
+    )
+
+    return tuple(np.reshape(result, longitude.shape) for result in results)
 
 
 def zone_coverage(bbox, depth, *, ellipsoid="sphere", flat=True):
@@ -410,6 +1806,33 @@ def polygon_coverage(vertices, depth, *, ellipsoid="sphere", flat=True):
     )
 
 
+def linestring_coverage(vertices, depth, *, ellipsoid="sphere", rhumb=False):
+    """Search the cells crossed by the given line.
+
+    Parameters
+    ----------
+    vertices : numpy.ndarray
+        The vertices of the line. Must be an array of shape ``(n, 2)`` with ``n >= 2``.
+    depth : int
+        The depth of the cells to be returned.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    rhumb : bool, default: False
+        If ``True``, connect the vertices with rhumb lines (lines of constant
+        bearing) instead of great circles.
+
+    Returns
+    -------
+    cell_ids : numpy.ndarray
+        The sorted ids of all cells crossed by the line.
+    """
+    _check_depth(depth)
+
+    return healpix_geo.ring.linestring_coverage(
+        depth, vertices, ellipsoid=ellipsoid, rhumb=rhumb
+    )
+
+
 def cone_coverage(
     center, radius, depth, *, delta_depth=0, ellipsoid="sphere", flat=True
 ):
@@ -0,0 +1,112 @@
+import numpy as np
+import pytest
+
+import healpix_geo
+import healpix_geo.auto as hg
+
+
+def geodetic_to_cartesian(lon, lat, height, ellipsoid):
+    a = ellipsoid.semimajor_axis
+    e2 = ellipsoid.flattening * (2 - ellipsoid.flattening)
+
+    lon = np.deg2rad(lon)
+    lat = np.deg2rad(lat)
+    normal = a / np.sqrt(1 - e2 * np.sin(lat) ** 2)
+
+    return (
+        (normal + height) * np.cos(lat) * np.cos(lon),
+        (normal + height) * np.cos(lat) * np.sin(lon),
+        (normal * (1 - e2) + height) * np.sin(lat),
+    )
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+@pytest.mark.parametrize("height", [0.0, 1e5])
+def test_roundtrip(indexing_scheme, height):
+    module = getattr(healpix_geo, indexing_scheme)
+    ellipsoid = healpix_geo.Ellipsoid("WGS84")
+    depth = 4
+    cell_ids = np.arange(12 * 4**depth, dtype="uint64")
+    if indexing_scheme == "zuniq":
+        cell_ids = healpix_geo.zuniq.from_nested(cell_ids, depth)
+    args = () if indexing_scheme == "zuniq" else (depth,)
+
+    x, y, z = module.healpix_to_xyz(cell_ids, *args, ellipsoid, height=height)
+    lon, lat = module.healpix_to_lonlat(cell_ids, *args, ellipsoid)
+
+    expected = geodetic_to_cartesian(lon, lat, height, ellipsoid)
+    np.testing.assert_allclose((x, y, z), expected, atol=1e-6)
+
+    actual = module.xyz_to_healpix(x, y, z, depth, ellipsoid)
+    np.testing.assert_equal(actual, cell_ids)
+
+
+def test_ellipsoid_surface():
+    ellipsoid = healpix_geo.Ellipsoid("WGS84")
+    cell_ids = np.arange(48)
+
+    x, y, z = healpix_geo.nested.healpix_to_xyz(cell_ids, 1, ellipsoid)
+
+    # the centers are on the ellipsoid, not on the authalic sphere
+    actual = (x**2 + y**2) / ellipsoid.semimajor_axis**2
+    actual += z**2 / ellipsoid.semiminor_axis**2
+    np.testing.assert_allclose(actual, 1)
+    assert not np.allclose(np.sqrt(x**2 + y**2 + z**2), ellipsoid.authalic_radius)
+
+
+def test_vertices():
+    ellipsoid = healpix_geo.Ellipsoid("GRS80")
+    cell_ids = np.array([[0, 20], [31, 47]])
+
+    x, y, z = healpix_geo.ring.vertices_xyz(cell_ids, 1, ellipsoid, height=10, step=2)
+    lon, lat = healpix_geo.ring.vertices(cell_ids, 1, ellipsoid, step=2)
+
+    assert x.shape == y.shape == z.shape == (2, 2, 8)
+    np.testing.assert_allclose(
+        (x, y, z), geodetic_to_cartesian(lon, lat, 10, ellipsoid), atol=1e-6
+    )
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+def test_vertices_empty(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    args = () if indexing_scheme == "zuniq" else (3,)
+
+    x, y, z = module.vertices_xyz(np.array([], dtype="uint64"), *args, "WGS84", step=2)
+
+    assert x.shape == y.shape == z.shape == (0, 8)
+
+
+def test_invalid():
+    x = np.array([0.0, 7e6])
+    y = np.zeros(2)
+    z = np.zeros(2)
+
+    with pytest.raises(ValueError):
+        healpix_geo.nested.xyz_to_healpix(x, y, z, 0, "WGS84")
+
+    ipix, mask = healpix_geo.nested.xyz_to_healpix(
+        x, y, z, 0, "WGS84", on_invalid="mask"
+    )
+    np.testing.assert_equal(mask, [True, False])
+    assert ipix[1] == 4
+
+    cell_ids = np.array([42, 768, 6], dtype="int64")
+    x, y, z, mask = healpix_geo.nested.healpix_to_xyz(
+        cell_ids, 3, "WGS84", on_invalid="mask"
+    )
+    assert np.isnan(x[1]) and np.isnan(y[1]) and np.isnan(z[1])
+    np.testing.assert_equal(mask, [False, True, False])
+
+
+def test_auto():
+    grid = hg.Grid(level=10, indexing_scheme="ring", ellipsoid="WGS84")
+    cell_ids = np.array([42, 6, 10])
+
+    x, y, z = hg.healpix_to_xyz(cell_ids, grid, height=500.0)
+    actual = hg.xyz_to_healpix(x, y, z, grid)
+
+    np.testing.assert_equal(actual, cell_ids)
+
+    x, y, z = hg.vertices_xyz(cell_ids, grid)
+    assert x.shape == (3, 4)
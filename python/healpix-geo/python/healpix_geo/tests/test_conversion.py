@@ -97,3 +97,245 @@ class TestZuniq:
 
         np.testing.assert_equal(actual_cell_ids, expected_cell_ids)
         np.testing.assert_equal(actual_depths, expected_depths)
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring"])
+def test_healpix_to_lonlat_out(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    cell_ids = np.array([[42, 6], [10, 500]], dtype="uint64")
+    depth = 3
+
+    expected_lon, expected_lat = module.healpix_to_lonlat(cell_ids, depth, "WGS84")
+
+    out = (np.empty((2, 2), dtype="float64"), np.empty((2, 2), dtype="float64"))
+    lon, lat = module.healpix_to_lonlat(cell_ids, depth, "WGS84", out=out)
+
+    assert lon is out[0]
+    assert lat is out[1]
+    np.testing.assert_equal(lon, expected_lon)
+    np.testing.assert_equal(lat, expected_lat)
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+def test_lonlat_to_healpix_out(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    lon = np.array([0, 50, 25], dtype="float64")
+    lat = np.array([6, -12, 45], dtype="float64")
+    depth = 3
+
+    expected = module.lonlat_to_healpix(lon, lat, depth, "WGS84")
+
+    out = np.empty(3, dtype="uint64")
+    actual = module.lonlat_to_healpix(lon, lat, depth, "WGS84", out=out)
+
+    assert actual is out
+    np.testing.assert_equal(actual, expected)
+
+
+def test_lonlat_to_healpix_out_wrong_shape():
+    lon = np.array([0, 50, 25], dtype="float64")
+    lat = np.array([6, -12, 45], dtype="float64")
+    out = np.empty(4, dtype="uint64")
+
+    with pytest.raises(ValueError, match="wrong shape"):
+        healpix_geo.nested.lonlat_to_healpix(lon, lat, 3, out=out)
+
+
+def test_zuniq_lonlat_to_healpix_depth_array():
+    lon = np.array([0, 50, 25], dtype="float64")
+    lat = np.array([6, -12, 45], dtype="float64")
+    depth = np.array([1, 3, 7], dtype="uint8")
+
+    actual = healpix_geo.zuniq.lonlat_to_healpix(lon, lat, depth, "WGS84")
+    expected = np.concatenate(
+        [
+            healpix_geo.zuniq.lonlat_to_healpix(lon_, lat_, int(depth_), "WGS84")
+            for lon_, lat_, depth_ in zip(lon, lat, depth)
+        ]
+    )
+
+    np.testing.assert_equal(actual, expected)
+
+
+@pytest.mark.parametrize("dtype", ["uint64", "int64", "uint32", "int32"])
+def test_healpix_to_lonlat_cell_id_dtypes(dtype):
+    cell_ids = np.array([42, 6, 10, 500])
+
+    expected_lon, expected_lat = healpix_geo.nested.healpix_to_lonlat(cell_ids, 3)
+    actual_lon, actual_lat = healpix_geo.nested.healpix_to_lonlat(
+        cell_ids.astype(dtype), 3
+    )
+
+    np.testing.assert_equal(actual_lon, expected_lon)
+    np.testing.assert_equal(actual_lat, expected_lat)
+
+
+def test_lonlat_to_healpix_float32():
+    lon = np.array([0, 50, 25], dtype="float32")
+    lat = np.array([6, -12, 45], dtype="float32")
+
+    expected = healpix_geo.ring.lonlat_to_healpix(
+        lon.astype("float64"), lat.astype("float64"), 3
+    )
+    actual = healpix_geo.ring.lonlat_to_healpix(lon, lat, 3)
+
+    np.testing.assert_equal(actual, expected)
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring"])
+def test_healpix_to_lonlat_strided(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    cell_ids = np.arange(48, dtype="uint64").reshape(6, 8)
+
+    view = cell_ids[::2, 1::3]
+    lon, lat = module.healpix_to_lonlat(view, 2)
+    expected_lon, expected_lat = module.healpix_to_lonlat(view.copy(), 2)
+
+    np.testing.assert_equal(lon, expected_lon)
+    np.testing.assert_equal(lat, expected_lat)
+
+
+def test_healpix_to_lonlat_memory_order():
+    cell_ids = np.asfortranarray(np.arange(12, dtype="uint64").reshape(3, 4))
+
+    lon, lat = healpix_geo.nested.healpix_to_lonlat(cell_ids, 1)
+    expected_lon, expected_lat = healpix_geo.nested.healpix_to_lonlat(
+        np.ascontiguousarray(cell_ids), 1
+    )
+
+    assert lon.flags.f_contiguous and lat.flags.f_contiguous
+    np.testing.assert_equal(lon, expected_lon)
+    np.testing.assert_equal(lat, expected_lat)
+
+    transposed = healpix_geo.nested.lonlat_to_healpix(lon.T, lat.T, 1)
+
+    assert transposed.flags.c_contiguous
+    np.testing.assert_equal(transposed, cell_ids.T)
+
+
+def test_negative_cell_ids():
+    with pytest.raises(ValueError, match="non-negative"):
+        healpix_geo.zuniq.to_nested(np.array([-1], dtype="int64"))
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+def test_lonlat_to_healpix_invalid_coordinates(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    lon = np.array([0, np.nan, 25, 10], dtype="float64")
+    lat = np.array([6, -12, 95, 45], dtype="float64")
+    expected = module.lonlat_to_healpix(lon[[0, 3]], lat[[0, 3]], 3)
+
+    with pytest.raises(ValueError, match="invalid coordinates"):
+        module.lonlat_to_healpix(lon, lat, 3)
+
+    actual = module.lonlat_to_healpix(lon, lat, 3, on_invalid="sentinel")
+    np.testing.assert_equal(actual[[0, 3]], expected)
+    np.testing.assert_equal(actual[[1, 2]], np.iinfo("uint64").max)
+
+    actual, mask = module.lonlat_to_healpix(lon, lat, 3, on_invalid="mask")
+    np.testing.assert_equal(actual[[0, 3]], expected)
+    np.testing.assert_equal(mask, [False, True, True, False])
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+@pytest.mark.parametrize("kind", ["geocentric", "parametric"])
+def test_lonlat_to_healpix_invalid_latitude_kind(indexing_scheme, kind):
+    module = getattr(healpix_geo, indexing_scheme)
+    lon = np.array([0.0, 25.0])
+    lat = np.array([6.0, 95.0])
+
+    with pytest.raises(ValueError, match="invalid coordinates"):
+        module.lonlat_to_healpix(lon, lat, 3, "WGS84", latitude_kind=kind)
+
+    _, mask = module.lonlat_to_healpix(
+        lon, lat, 3, "WGS84", latitude_kind=kind, on_invalid="mask"
+    )
+    np.testing.assert_equal(mask, [False, True])
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring"])
+def test_healpix_to_lonlat_invalid_cell_ids(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    cell_ids = np.array([42, 768, -1, 6], dtype="int64")
+    expected_lon, expected_lat = module.healpix_to_lonlat(cell_ids[[0, 3]], 3)
+
+    with pytest.raises(ValueError):
+        module.healpix_to_lonlat(cell_ids, 3)
+
+    lon, lat = module.healpix_to_lonlat(cell_ids, 3, on_invalid="sentinel")
+    np.testing.assert_equal(lon[[0, 3]], expected_lon)
+    np.testing.assert_equal(lat[[0, 3]], expected_lat)
+    assert np.isnan(lon[[1, 2]]).all() and np.isnan(lat[[1, 2]]).all()
+
+    lon, lat, mask = module.healpix_to_lonlat(cell_ids, 3, on_invalid="mask")
+    np.testing.assert_equal(lon[[0, 3]], expected_lon)
+    np.testing.assert_equal(mask, [False, True, True, False])
+
+
+def test_healpix_to_lonlat_invalid_policy():
+    with pytest.raises(ValueError, match="on_invalid must be one of"):
+        healpix_geo.nested.healpix_to_lonlat(np.array([42]), 3, on_invalid="ignore")
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+@pytest.mark.parametrize(
+    "kind", ["geocentric", "parametric", "rectifying", "conformal", "authalic"]
+)
+def test_latitude_kind(indexing_scheme, kind):
+    module = getattr(healpix_geo, indexing_scheme)
+    ellipsoid = healpix_geo.Ellipsoid("WGS84")
+    lon = np.array([0.0, 50.0, 25.0, 300.0])
+    lat = np.array([6.0, -12.0, 45.0, 80.0])
+    depth = 8
+    args = () if indexing_scheme == "zuniq" else (depth,)
+
+    converted = ellipsoid.convert_latitude(lat, "geographic", kind)
+    expected = module.lonlat_to_healpix(lon, lat, depth, ellipsoid)
+    actual = module.lonlat_to_healpix(
+        lon, converted, depth, ellipsoid, latitude_kind=kind
+    )
+    np.testing.assert_equal(actual, expected)
+
+    _, geographic = module.healpix_to_lonlat(expected, *args, ellipsoid)
+    _, actual = module.healpix_to_lonlat(expected, *args, ellipsoid, latitude_kind=kind)
+    np.testing.assert_allclose(
+        actual, ellipsoid.convert_latitude(geographic, "geographic", kind)
+    )
+
+
+def test_latitude_kind_sphere():
+    cell_ids = np.array([42, 6, 10])
+
+    expected = healpix_geo.nested.healpix_to_lonlat(cell_ids, 3)
+    actual = healpix_geo.nested.healpix_to_lonlat(
+        cell_ids, 3, latitude_kind="geocentric"
+    )
+
+    np.testing.assert_equal(actual, expected)
+
+
+def test_latitude_kind_invalid():
+    with pytest.raises(ValueError, match="Latitude kind must be one of"):
+        healpix_geo.nested.healpix_to_lonlat(np.array([42]), 3, latitude_kind="polar")
+
+
+@pytest.mark.parametrize(
+    ["indexing_scheme", "cell_ids", "expected"],
+    (
+        ("nested", [-1, 0, 767, 768], [False, True, True, False]),
+        ("ring", [-1, 0, 767, 768], [False, True, True, False]),
+        (
+            "zuniq",
+            [-1, 0, 4683743612465315840, 206039682952200192],
+            [False, False, True, True],
+        ),
+    ),
+)
+def test_is_valid_cell(indexing_scheme, cell_ids, expected):
+    module = getattr(healpix_geo, indexing_scheme)
+    cell_ids = np.array(cell_ids, dtype="int64")
+    args = () if indexing_scheme == "zuniq" else (3,)
+
+    actual = module.is_valid_cell(cell_ids, *args)
+
+    np.testing.assert_equal(actual, expected)
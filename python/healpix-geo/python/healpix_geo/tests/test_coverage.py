@@ -862,3 +862,88 @@ def test_elliptical_cone_coverage(
 
     np.testing.assert_equal(cell_ids, expected_cell_ids)
     np.testing.assert_equal(fully_covered, expected_coverage)
+
+
+@pytest.mark.parametrize("scheme", ["nested", "ring", "zuniq"])
+def test_cone_coverage_invalid(scheme):
+    ns = getattr(healpix_geo, scheme)
+
+    with pytest.raises(ValueError, match="depth must be between 0 and 29"):
+        ns.cone_coverage((45.0, 45.0), 5.0, 20, delta_depth=10, ellipsoid="WGS84")
+
+    with pytest.raises(
+        healpix_geo.InvalidGeometryError, match="the radius must be a positive number"
+    ):
+        ns.cone_coverage((45.0, 45.0), -5.0, 1, ellipsoid="WGS84")
+
+
+@pytest.mark.parametrize("scheme", ["nested", "ring", "zuniq"])
+def test_polygon_coverage_invalid(scheme):
+    ns = getattr(healpix_geo, scheme)
+    vertices = np.array([[0.0, 0.0], [10.0, 10.0]])
+
+    with pytest.raises(
+        healpix_geo.InvalidGeometryError, match="a polygon needs at least 3 vertices"
+    ):
+        ns.polygon_coverage(vertices, 1, ellipsoid="WGS84")
+
+
+def test_error_types():
+    assert issubclass(healpix_geo.InvalidGeometryError, ValueError)
+    assert issubclass(healpix_geo.LengthMismatchError, ValueError)
+    assert healpix_geo.InvalidGeometryError is not healpix_geo.LengthMismatchError
+
+
+@pytest.mark.parametrize("ellipsoid", ["sphere", "WGS84"])
+@pytest.mark.parametrize(
+    ["vertices", "rhumb", "samples"],
+    (
+        pytest.param(
+            np.array([[10.0, -40.0], [10.0, 40.0]]),
+            False,
+            np.stack(
+                [np.full(1000, 10.0), np.linspace(-40.0, 40.0, 1000)], axis=-1
+            ),
+            id="meridian",
+        ),
+        pytest.param(
+            np.array([[0.0, 45.0], [90.0, 45.0]]),
+            True,
+            np.stack([np.linspace(0.0, 90.0, 1000), np.full(1000, 45.0)], axis=-1),
+            id="parallel-rhumb",
+        ),
+    ),
+)
+def test_linestring_coverage(vertices, rhumb, samples, ellipsoid):
+    depth = 6
+
+    actual = healpix_geo.nested.linestring_coverage(
+        vertices, depth, ellipsoid=ellipsoid, rhumb=rhumb
+    )
+    expected = np.unique(
+        healpix_geo.nested.lonlat_to_healpix(
+            samples[:, 0], samples[:, 1], depth, ellipsoid=ellipsoid
+        )
+    )
+
+    np.testing.assert_equal(np.diff(actual.astype("int64")) > 0, True)
+    assert np.isin(expected, actual).all()
+
+    zuniq = healpix_geo.zuniq.linestring_coverage(
+        vertices, depth, ellipsoid=ellipsoid, rhumb=rhumb
+    )
+    np.testing.assert_equal(zuniq, healpix_geo.zuniq.from_nested(actual, depth))
+
+    ring = healpix_geo.ring.linestring_coverage(
+        vertices, depth, ellipsoid=ellipsoid, rhumb=rhumb
+    )
+    assert ring.size == actual.size
+
+
+@pytest.mark.parametrize("scheme", ["nested", "ring", "zuniq"])
+def test_linestring_coverage_invalid(scheme):
+    ns = getattr(healpix_geo, scheme)
+    vertices = np.array([[0.0, 0.0]])
+
+    with pytest.raises(ValueError, match="a linestring needs at least 2 vertices"):
+        ns.linestring_coverage(vertices, 1)
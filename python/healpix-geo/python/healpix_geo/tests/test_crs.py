@@ -0,0 +1,142 @@
+import numpy as np
+import pytest
+
+import healpix_geo
+import healpix_geo.auto as hg
+
+points = {
+    3413: ([-45.0, 0.0, 100.0], [70.0, 80.0, 85.0]),
+    3031: ([0.0, 120.0, -60.0], [-70.0, -75.0, -85.0]),
+    "EPSG:32632": ([9.0, 10.0, 11.0], [45.0, 50.0, 55.0]),
+    "EPSG:32755": ([144.0, 146.0, 148.0], [-40.0, -35.0, -30.0]),
+    "epsg:3035": ([0.0, 10.0, 20.0], [45.0, 52.0, 60.0]),
+    "EPSG:3857": ([-100.0, 0.0, 100.0], [-60.0, 0.0, 60.0]),
+}
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+@pytest.mark.parametrize("crs", list(points))
+def test_roundtrip(indexing_scheme, crs):
+    module = getattr(healpix_geo, indexing_scheme)
+    lon, lat = (np.array(values) for values in points[crs])
+    depth = 10
+    args = () if indexing_scheme == "zuniq" else (depth,)
+
+    cell_ids = module.lonlat_to_healpix(lon, lat, depth, "WGS84")
+
+    x, y = module.healpix_to_projected(cell_ids, *args, crs, "WGS84")
+    actual = module.projected_to_healpix(x, y, depth, crs, "WGS84")
+
+    np.testing.assert_equal(actual, cell_ids)
+
+
+def test_polar_stereographic():
+    # the northern vertex of the polar cells is the origin of the projection
+    x, y = healpix_geo.nested.vertices_projected(
+        np.array([0, 1, 2, 3]), 0, 3413, "WGS84"
+    )
+
+    np.testing.assert_allclose(x[:, 2], 0, atol=1e-6)
+    np.testing.assert_allclose(y[:, 2], 0, atol=1e-6)
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+def test_vertices_projected_empty(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    args = () if indexing_scheme == "zuniq" else (3,)
+
+    x, y = module.vertices_projected(
+        np.array([], dtype="uint64"), *args, 3857, "WGS84", step=2
+    )
+
+    assert x.shape == y.shape == (0, 8)
+
+
+def test_cartesian():
+    ellipsoid = healpix_geo.Ellipsoid("WGS84")
+    cell_ids = np.array([4, 5, 6, 7])
+
+    x, y, z = healpix_geo.nested.healpix_to_projected(
+        cell_ids, 0, "EPSG:4978", ellipsoid
+    )
+
+    np.testing.assert_allclose(np.hypot(x, y), ellipsoid.semimajor_axis)
+    np.testing.assert_allclose(z, 0, atol=1e-6)
+
+    actual = healpix_geo.nested.projected_to_healpix(
+        x, y, 0, "EPSG:4978", ellipsoid, z=z
+    )
+    np.testing.assert_equal(actual, cell_ids)
+
+    with pytest.raises(ValueError, match="z coordinate"):
+        healpix_geo.nested.projected_to_healpix(x, y, 0, "EPSG:4978", ellipsoid)
+
+    x, y, z = healpix_geo.nested.vertices_projected(
+        cell_ids, 0, "EPSG:4978", ellipsoid, step=2
+    )
+    assert x.shape == y.shape == z.shape == (4, 8)
+
+
+def test_other_ellipsoid():
+    ellipsoid = healpix_geo.Ellipsoid("sphere")
+    cell_ids = np.array([4, 5, 6, 7])
+
+    x, y, z = healpix_geo.nested.healpix_to_projected(
+        cell_ids, 0, "EPSG:4978", ellipsoid
+    )
+
+    np.testing.assert_allclose(np.hypot(x, y), ellipsoid.semimajor_axis)
+    np.testing.assert_allclose(z, 0, atol=1e-6)
+
+
+@pytest.mark.parametrize(
+    ["crs", "expected", "ellipsoid"],
+    (
+        ("utm zone=32 ellps=GRS80", "EPSG:25832", "GRS80"),
+        ({"operator": "cart ellps=WGS84", "dimension": 3}, 4978, "WGS84"),
+    ),
+)
+def test_operator(crs, expected, ellipsoid):
+    cell_ids = np.array([42, 6, 10])
+
+    actual = healpix_geo.nested.healpix_to_projected(cell_ids, 3, crs, ellipsoid)
+    expected = healpix_geo.nested.healpix_to_projected(
+        cell_ids, 3, expected, ellipsoid
+    )
+
+    np.testing.assert_equal(actual, expected)
+
+
+@pytest.mark.parametrize(
+    ["crs", "message"],
+    (
+        (4326, "unsupported EPSG code"),
+        ("EPSG:north", "invalid EPSG code"),
+        ("not_an_operator", "invalid CRS"),
+        ({"operator": "cart", "dimension": 4}, "dimension must be 2 or 3"),
+    ),
+)
+def test_invalid_crs(crs, message):
+    with pytest.raises(ValueError, match=message):
+        healpix_geo.nested.healpix_to_projected(np.array([42]), 3, crs)
+
+
+def test_invalid_cell_ids():
+    cell_ids = np.array([42, 768, 6], dtype="int64")
+
+    x, y, mask = healpix_geo.nested.healpix_to_projected(
+        cell_ids, 3, 3857, "WGS84", on_invalid="mask"
+    )
+
+    assert np.isnan(x[1]) and np.isnan(y[1])
+    np.testing.assert_equal(mask, [False, True, False])
+
+
+def test_auto():
+    grid = hg.Grid(level=10, indexing_scheme="ring", ellipsoid="WGS84")
+    cell_ids = np.array([42, 6, 10])
+
+    x, y = hg.healpix_to_projected(cell_ids, grid, 3413)
+    actual = hg.projected_to_healpix(x, y, grid, 3413)
+
+    np.testing.assert_equal(actual, cell_ids)
@@ -79,3 +79,94 @@ def test_distance_error(indexing_scheme):
 
     with pytest.raises(ValueError, match="The shape of `from_` must be compatible"):
         angular_distances(from_, to_, depth)
+
+
+def test_geodesic_distances_points():
+    from_lon = np.array([0.0, 0.0])
+    from_lat = np.array([0.0, 0.0])
+    to_lon = np.array([[1.0, 0.0], [0.0, 0.0]])
+    to_lat = np.array([[0.0, 90.0], [0.0, -1.0]])
+
+    distances, forward, back = healpix_geo.geodesic.geodesic_distances(
+        from_lon, from_lat, to_lon, to_lat, ellipsoid="WGS84"
+    )
+
+    np.testing.assert_allclose(
+        distances,
+        [[111319.49079327357, 10001965.729312724], [0.0, 110574.38855779878]],
+    )
+    np.testing.assert_allclose(forward, [[90.0, 0.0], [180.0, 180.0]])
+    np.testing.assert_allclose(back, [[-90.0, 180.0], [0.0, 0.0]])
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring"])
+def test_geodesic_distances_sphere(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+
+    from_ = np.array([0, 16, 25, 32, 46])
+    to_ = np.array([[2, 4], [15, 7], [27, 26], [40, -1], [-1, 41]], dtype="int64")
+    depth = 2
+
+    radius = 6370997.0
+    expected = module.angular_distances(from_, to_, depth) * radius
+    distances, _, _ = module.geodesic_distances(
+        from_, to_, depth, ellipsoid={"radius": radius}
+    )
+
+    np.testing.assert_allclose(distances, expected)
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring"])
+def test_geodesic_distances_to_points(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+
+    from_ = np.array([0, 16, 25])
+    to_ = np.array([[2, 4], [15, 7], [27, 26]])
+    depth = 2
+
+    expected = module.geodesic_distances(from_, to_, depth, ellipsoid="WGS84")
+
+    lon, lat = module.healpix_to_lonlat(to_, depth, ellipsoid="WGS84")
+    actual = module.geodesic_distances_to_points(
+        from_, lon, lat, depth, ellipsoid="WGS84"
+    )
+
+    np.testing.assert_allclose(actual, expected)
+
+
+def test_distance_zuniq():
+    from_ = np.array([0, 16, 25, 32, 46])
+    to_ = np.array([[2, 4], [15, 7], [27, 26], [40, -1], [-1, 41]], dtype="int64")
+    depth = 2
+
+    expected = healpix_geo.nested.angular_distances(from_, to_, depth)
+
+    from_zuniq = healpix_geo.zuniq.from_nested(from_, depth)
+    to_zuniq = np.where(
+        to_ != -1,
+        healpix_geo.zuniq.from_nested(np.where(to_ != -1, to_, 0), depth).astype(
+            "int64"
+        ),
+        -1,
+    )
+    actual = healpix_geo.zuniq.angular_distances(from_zuniq, to_zuniq)
+
+    np.testing.assert_allclose(actual, expected)
+
+
+def test_distance_zuniq_mixed_depths():
+    from_ = healpix_geo.zuniq.from_nested(np.array([3, 45]), np.array([0, 2]))
+    to_ = healpix_geo.zuniq.from_nested(
+        np.array([[5, 7], [1000, 2]]), np.array([[1, 3], [4, 1]])
+    )
+
+    lon1, lat1 = np.deg2rad(healpix_geo.zuniq.healpix_to_lonlat(from_, "sphere"))
+    lon2, lat2 = np.deg2rad(healpix_geo.zuniq.healpix_to_lonlat(to_, "sphere"))
+    expected = np.arccos(
+        np.sin(lat1[:, None]) * np.sin(lat2)
+        + np.cos(lat1[:, None]) * np.cos(lat2) * np.cos(lon2 - lon1[:, None])
+    )
+
+    actual = healpix_geo.zuniq.angular_distances(from_, to_)
+
+    np.testing.assert_allclose(actual, expected)
@@ -0,0 +1,147 @@
+import pickle
+
+import numpy as np
+import pytest
+
+import healpix_geo
+from healpix_geo import Ellipsoid
+
+
+def test_parameters():
+    wgs84 = Ellipsoid("WGS84")
+
+    assert wgs84.semimajor_axis == 6378137.0
+    assert wgs84.inverse_flattening == pytest.approx(298.257223563)
+    assert wgs84.semiminor_axis == pytest.approx(6356752.314245)
+    assert wgs84.eccentricity == pytest.approx(0.0818191908426)
+    assert wgs84.authalic_radius == pytest.approx(6371007.1809)
+    assert not wgs84.is_sphere
+
+    sphere = Ellipsoid({"radius": 1000.0})
+
+    assert sphere.is_sphere
+    assert sphere.semiminor_axis == 1000.0
+    assert sphere.authalic_radius == 1000.0
+    assert sphere.flattening == 0.0
+    assert sphere.inverse_flattening == np.inf
+
+
+def test_planetary_bodies():
+    mars = Ellipsoid("Mars")
+
+    assert not mars.is_sphere
+    assert mars.semimajor_axis == 3396190.0
+    assert mars.semiminor_axis == pytest.approx(3376200.0)
+
+    mars_sphere = Ellipsoid("mars_sphere")
+
+    assert mars_sphere.is_sphere
+    assert mars_sphere.semimajor_axis == 3389500.0
+
+    moon = Ellipsoid("moon")
+
+    assert moon.is_sphere
+    assert moon.semimajor_axis == 1737400.0
+
+
+def test_comparison():
+    ellipsoid = Ellipsoid("WGS84")
+    flattening = {"semimajor_axis": 6378137.0, "flattening": ellipsoid.flattening}
+
+    assert ellipsoid == Ellipsoid("WGS84")
+    assert ellipsoid == Ellipsoid(flattening)
+    assert ellipsoid != Ellipsoid("sphere")
+    assert ellipsoid != "WGS84"
+
+    assert hash(ellipsoid) == hash(Ellipsoid(flattening))
+    assert len({ellipsoid, Ellipsoid("WGS84"), Ellipsoid("sphere")}) == 2
+
+
+@pytest.mark.parametrize(
+    "ellipsoid_like",
+    [
+        "WGS84",
+        "sphere",
+        {"radius": 1.0},
+        {"semimajor_axis": 1, "inverse_flattening": 3},
+    ],
+)
+def test_pickle_roundtrip(ellipsoid_like):
+    ellipsoid = Ellipsoid(ellipsoid_like)
+
+    roundtripped = pickle.loads(pickle.dumps(ellipsoid))
+
+    assert roundtripped == ellipsoid
+    assert roundtripped.is_sphere == ellipsoid.is_sphere
+
+
+def test_repr():
+    assert repr(Ellipsoid({"radius": 2.0})) == "Ellipsoid(radius=2.0)"
+    assert (
+        repr(Ellipsoid({"semimajor_axis": 1.0, "inverse_flattening": 4.0}))
+        == "Ellipsoid(semimajor_axis=1.0, inverse_flattening=4.0)"
+    )
+
+
+def test_latitude_conversions():
+    ellipsoid = Ellipsoid("WGS84")
+    latitude = np.array([[-90.0, -45.0, 0.0], [30.0, 60.0, 89.5]])
+
+    authalic = ellipsoid.geographic_to_authalic(latitude)
+
+    assert authalic.shape == latitude.shape
+    assert authalic[0, 2] == 0.0
+    assert (np.abs(authalic[:, 1:]) < np.abs(latitude[:, 1:])).all()
+    np.testing.assert_allclose(
+        ellipsoid.authalic_to_geographic(authalic), latitude, atol=1e-9
+    )
+
+    sphere = Ellipsoid("sphere")
+    np.testing.assert_equal(sphere.geographic_to_authalic(latitude), latitude)
+
+
+def test_convert_latitude():
+    ellipsoid = Ellipsoid("WGS84")
+    latitude = np.array([-60.0, 0.0, 45.0])
+
+    geocentric = ellipsoid.convert_latitude(latitude, "geographic", "geocentric")
+    np.testing.assert_allclose(
+        np.tan(np.deg2rad(geocentric)),
+        (1 - ellipsoid.flattening) ** 2 * np.tan(np.deg2rad(latitude)),
+    )
+
+    authalic = ellipsoid.convert_latitude(geocentric, "geocentric", "authalic")
+    np.testing.assert_allclose(authalic, ellipsoid.geographic_to_authalic(latitude))
+
+    with pytest.raises(ValueError, match="Latitude kind must be one of"):
+        ellipsoid.convert_latitude(latitude, "geographic", "polar")
+
+
+def test_latitude_conversions_match_cell_centers():
+    ellipsoid = Ellipsoid("WGS84")
+    cell_ids = np.arange(12 * 4**2)
+
+    _, lat_sphere = healpix_geo.nested.healpix_to_lonlat(cell_ids, 2)
+    _, lat = healpix_geo.nested.healpix_to_lonlat(cell_ids, 2, ellipsoid=ellipsoid)
+
+    np.testing.assert_allclose(ellipsoid.authalic_to_geographic(lat_sphere), lat)
+
+
+def test_accepted_as_ellipsoid_like():
+    lon = np.array([0.0, 45.0, -120.0])
+    lat = np.array([10.0, 60.0, -75.0])
+    ellipsoid = Ellipsoid("WGS84")
+
+    actual = healpix_geo.nested.lonlat_to_healpix(lon, lat, 8, ellipsoid=ellipsoid)
+    expected = healpix_geo.nested.lonlat_to_healpix(lon, lat, 8, ellipsoid="WGS84")
+    np.testing.assert_equal(actual, expected)
+
+    RangeMOCIndex = healpix_geo.nested.RangeMOCIndex
+    actual = RangeMOCIndex.full_domain(2, ellipsoid=ellipsoid)
+    expected = RangeMOCIndex.full_domain(2, ellipsoid="WGS84")
+    assert actual.ellipsoid == expected.ellipsoid
+
+
+def test_invalid():
+    with pytest.raises(ValueError, match="The radius must be greater than 0"):
+        Ellipsoid({"radius": -1.0})
@@ -7,6 +7,64 @@ import shapely
 import healpix_geo
 
 
+def sample_great_circle_arcs(coords, samples=1000):
+    lon, lat = np.deg2rad(coords).T
+    points = np.stack(
+        [np.cos(lat) * np.cos(lon), np.cos(lat) * np.sin(lon), np.sin(lat)], axis=-1
+    )
+
+    t = np.linspace(0, 1, samples)[:, None]
+    arcs = []
+    for start, end in zip(points[:-1], points[1:]):
+        omega = np.arccos(np.clip(np.dot(start, end), -1, 1))
+        arcs.append(
+            (np.sin((1 - t) * omega) * start + np.sin(t * omega) * end) / np.sin(omega)
+        )
+    x, y, z = np.concatenate(arcs).T
+
+    return np.rad2deg(np.arctan2(y, x)), np.rad2deg(np.arcsin(np.clip(z, -1, 1)))
+
+
+def fits_moc_u32(depth, cell_ids):
+    # spatial MOC following version 1.0 of the IVOA standard, with 32-bit indices
+    def card(key, value):
+        if isinstance(value, bool):
+            value = f"{'T' if value else 'F':>20}"
+        elif isinstance(value, str):
+            value = f"'{value:<8}'"
+        else:
+            value = f"{value:>20}"
+
+        return f"{key:<8}= {value}"
+
+    def header(**cards):
+        lines = [card(key, value) for key, value in cards.items()] + ["END"]
+        data = "".join(f"{line:<80}" for line in lines).encode("ascii")
+
+        return data + b" " * (-len(data) % 2880)
+
+    primary = header(SIMPLE=True, BITPIX=8, NAXIS=0, EXTEND=True)
+    extension = header(
+        XTENSION="BINTABLE",
+        BITPIX=8,
+        NAXIS=2,
+        NAXIS1=4,
+        NAXIS2=cell_ids.size,
+        PCOUNT=0,
+        GCOUNT=1,
+        TFIELDS=1,
+        TTYPE1="UNIQ",
+        TFORM1="1J",
+        PIXTYPE="HEALPIX",
+        ORDERING="NUNIQ",
+        COORDSYS="C",
+        MOCORDER=depth,
+    )
+    data = (4 * 4**depth + cell_ids).astype(">i4").tobytes()
+
+    return primary + extension + data + b"\0" * (-len(data) % 2880)
+
+
 class TestRangeMOCIndex:
     @pytest.mark.parametrize("level", [0, 3, 6])
     def test_full_domain(self, level):
@@ -286,6 +344,110 @@ class TestRangeMOCIndex:
         assert index.depth == unpickled.depth
         np.testing.assert_equal(unpickled.cell_ids(), index.cell_ids())
 
+    def test_pickle_roundtrip_ellipsoid(self):
+        cell_ids = np.arange(12 * 4**2, dtype="uint64")
+        index = healpix_geo.nested.RangeMOCIndex.from_cell_ids(
+            2, cell_ids, ellipsoid="WGS84"
+        )
+
+        unpickled = pickle.loads(pickle.dumps(index))
+
+        assert unpickled.ellipsoid == index.ellipsoid
+        # would raise if the ellipsoids differed
+        unpickled.union(index)
+
+    def test_pickle_compact(self):
+        index = healpix_geo.nested.RangeMOCIndex.full_domain(12)
+
+        # a single range, independent of the number of cells
+        assert len(pickle.dumps(index)) < 200
+
+    def test_setstate_invalid(self):
+        index = healpix_geo.nested.RangeMOCIndex.create_empty(2)
+        state = index.__getstate__()
+
+        with pytest.raises(ValueError, match="Truncated"):
+            index.__setstate__(state[:-4])
+        with pytest.raises(ValueError, match="version"):
+            index.__setstate__(state[:5] + bytes([255]) + state[6:])
+
+    def test_fits_roundtrip(self):
+        cell_ids = np.array([0, 1, 2, 3, 17, 50, 51], dtype="uint64")
+        index = healpix_geo.nested.RangeMOCIndex.from_cell_ids(3, cell_ids)
+
+        data = index.to_fits()
+        assert isinstance(data, bytes)
+        assert data.startswith(b"SIMPLE")
+
+        roundtripped = healpix_geo.nested.RangeMOCIndex.from_fits(
+            data, ellipsoid="WGS84"
+        )
+        assert roundtripped.depth == index.depth
+        assert roundtripped.ellipsoid == {
+            "semimajor_axis": 6378137.0,
+            "inverse_flattening": pytest.approx(298.257223563),
+        }
+        np.testing.assert_equal(roundtripped.cell_ids(), index.cell_ids())
+
+    def test_from_fits_u32(self):
+        cell_ids = np.array([17, 50, 51], dtype="uint64")
+
+        index = healpix_geo.nested.RangeMOCIndex.from_fits(fits_moc_u32(3, cell_ids))
+
+        assert index.depth == 3
+        np.testing.assert_equal(index.cell_ids(), cell_ids)
+
+    def test_ascii_roundtrip(self):
+        cell_ids = np.array([0, 1, 2, 3, 17, 50, 51], dtype="uint64")
+        index = healpix_geo.nested.RangeMOCIndex.from_cell_ids(3, cell_ids)
+
+        data = index.to_ascii()
+        assert isinstance(data, str)
+
+        roundtripped = healpix_geo.nested.RangeMOCIndex.from_ascii(data)
+        assert roundtripped.depth == index.depth
+        np.testing.assert_equal(roundtripped.cell_ids(), index.cell_ids())
+
+    def test_from_ascii(self):
+        index = healpix_geo.nested.RangeMOCIndex.from_ascii("1/1 2/20-21")
+
+        assert index.depth == 2
+        np.testing.assert_equal(
+            index.cell_ids(), np.array([4, 5, 6, 7, 20, 21], dtype="uint64")
+        )
+
+    def test_from_ascii_invalid(self):
+        with pytest.raises(ValueError):
+            healpix_geo.nested.RangeMOCIndex.from_ascii("not a moc")
+
+    def test_set_ops_ellipsoid_mismatch(self):
+        cell_ids = np.arange(12 * 4**2, dtype="uint64")
+        index1 = healpix_geo.nested.RangeMOCIndex.from_cell_ids(2, cell_ids)
+        index2 = healpix_geo.nested.RangeMOCIndex.from_cell_ids(
+            2, cell_ids, ellipsoid="WGS84"
+        )
+
+        with pytest.raises(ValueError, match="different ellipsoids"):
+            index1.union(index2)
+        with pytest.raises(ValueError, match="different ellipsoids"):
+            index1.intersection(index2)
+
+    @pytest.mark.parametrize("ellipsoid", ["WGS84", "sphere"])
+    def test_query_ellipsoid(self, ellipsoid):
+        depth = 10
+        index = healpix_geo.nested.RangeMOCIndex.full_domain(
+            depth, ellipsoid=ellipsoid
+        )
+        lon, lat = 30.0, 45.0
+
+        expected = healpix_geo.nested.lonlat_to_healpix(
+            np.array([lon]), np.array([lat]), depth, ellipsoid=ellipsoid
+        )
+        _, moc = index.query(shapely.Point(lon, lat))
+
+        np.testing.assert_equal(moc.cell_ids(), expected)
+        assert moc.ellipsoid == index.ellipsoid
+
     @pytest.mark.parametrize("depth", (0, 2, 10))
     @pytest.mark.parametrize(
         "geom",
@@ -316,14 +478,14 @@ class TestRangeMOCIndex:
             lat = Latitude([coords[0]], unit="deg")
             expected = cdshealpix.nested.lonlat_to_healpix(lon, lat, depth=depth)
         elif isinstance(geom, shapely.LineString):
-            coords = np.asarray(geom.coords[:])
-            lon = Longitude(coords[:, 0], unit="deg")
-            lat = Latitude(coords[:, 1], unit="deg")
-
-            expected_ = np.unique(
-                cdshealpix.nested.lonlat_to_healpix(lon, lat, depth=depth)
+            # the cells of points densely sampled along the great circle arcs must all
+            # be part of the exact coverage
+            lon, lat = sample_great_circle_arcs(np.asarray(geom.coords[:]))
+            samples = cdshealpix.nested.lonlat_to_healpix(
+                Longitude(lon, unit="deg"), Latitude(lat, unit="deg"), depth=depth
             )
-            expected = expected_[np.isin(expected_, cell_ids)]
+            samples = np.unique(samples[np.isin(samples, cell_ids)])
+            expected = None
         elif isinstance(geom, shapely.Polygon):
             coords = np.asarray(geom.exterior.coords[:])
             lon = Longitude(coords[:, 0], unit="deg")
@@ -344,4 +506,294 @@ class TestRangeMOCIndex:
 
         if expected is not None:
             np.testing.assert_equal(reconstructed, expected)
+        if isinstance(geom, shapely.LineString):
+            assert samples.size > 0
+            assert np.isin(samples, reconstructed).all()
         np.testing.assert_equal(actual, reconstructed)
+
+    def test_query_polygon_with_hole(self):
+        depth = 6
+        index = healpix_geo.nested.RangeMOCIndex.full_domain(depth)
+        polygon = shapely.Polygon(
+            shapely.box(-30, -30, 30, 30).exterior.coords,
+            holes=[shapely.box(-10, -10, 10, 10).exterior.coords],
+        )
+
+        _, moc = index.query(polygon)
+        _, exterior = index.query(shapely.box(-30, -30, 30, 30))
+
+        inside_hole, outside_hole = healpix_geo.nested.lonlat_to_healpix(
+            np.array([2.0, 20.0]), np.array([3.0, 20.0]), depth
+        )
+        actual = moc.cell_ids()
+
+        assert inside_hole not in actual
+        assert outside_hole in actual
+        assert actual.size < exterior.size
+
+    @pytest.mark.parametrize(
+        "geoms",
+        (
+            pytest.param(
+                [shapely.box(-25, 15, -5, 35), shapely.box(5, 15, 25, 35)],
+                id="polygons",
+            ),
+            pytest.param([shapely.Point(30, 30), shapely.Point(-30, -30)], id="points"),
+            pytest.param(
+                [shapely.box(5, 15, 25, 35), shapely.Point(-30, -30)],
+                id="mixed",
+            ),
+        ),
+    )
+    def test_query_multipart(self, geoms):
+        depth = 5
+        index = healpix_geo.nested.RangeMOCIndex.full_domain(depth)
+        geometry = shapely.GeometryCollection(geoms)
+        if all(isinstance(geom, shapely.Polygon) for geom in geoms):
+            geometry = shapely.MultiPolygon(geoms)
+        elif all(isinstance(geom, shapely.Point) for geom in geoms):
+            geometry = shapely.MultiPoint(geoms)
+
+        _, moc = index.query(geometry)
+
+        expected = np.unique(
+            np.concatenate([index.query(geom)[1].cell_ids() for geom in geoms])
+        )
+        np.testing.assert_equal(moc.cell_ids(), expected)
+
+
+class TestRingRangeMOCIndex:
+    @pytest.mark.parametrize("depth", (0, 2, 10))
+    def test_full_domain(self, depth):
+        index = healpix_geo.ring.RangeMOCIndex.full_domain(depth)
+
+        np.testing.assert_equal(
+            index.cell_ids(), np.arange(12 * 4**depth, dtype="uint64")
+        )
+        assert index.depth == depth
+
+    def test_from_cell_ids(self):
+        cell_ids = np.array([3, 7, 8, 9, 20], dtype="uint64")
+        index = healpix_geo.ring.RangeMOCIndex.from_cell_ids(1, cell_ids)
+
+        assert index.size == cell_ids.size
+        np.testing.assert_equal(index.cell_ids(), cell_ids)
+
+    @pytest.mark.parametrize(
+        "indexer",
+        (
+            slice(None),
+            slice(1, 4),
+            slice(None, None, 2),
+            np.array([0, 2, 4], dtype="uint64"),
+        ),
+    )
+    def test_isel(self, indexer):
+        cell_ids = np.array([3, 7, 8, 9, 20, 40, 41], dtype="uint64")
+        index = healpix_geo.ring.RangeMOCIndex.from_cell_ids(2, cell_ids)
+
+        actual = index.isel(indexer)
+
+        np.testing.assert_equal(actual.cell_ids(), cell_ids[indexer])
+
+    @pytest.mark.parametrize(
+        ["indexer", "expected"],
+        (
+            pytest.param(slice(7, 20), np.array([7, 8, 9, 20]), id="slice"),
+            pytest.param(slice(None, 8), np.array([3, 7, 8]), id="slice-open"),
+            pytest.param(
+                np.array([9, 40], dtype="uint64"), np.array([9, 40]), id="array"
+            ),
+        ),
+    )
+    def test_sel(self, indexer, expected):
+        cell_ids = np.array([3, 7, 8, 9, 20, 40, 41], dtype="uint64")
+        index = healpix_geo.ring.RangeMOCIndex.from_cell_ids(2, cell_ids)
+
+        positions, actual = index.sel(indexer)
+
+        np.testing.assert_equal(cell_ids[positions], expected)
+        np.testing.assert_equal(actual.cell_ids(), expected)
+
+    def test_sel_missing(self):
+        cell_ids = np.array([3, 7, 8], dtype="uint64")
+        index = healpix_geo.ring.RangeMOCIndex.from_cell_ids(2, cell_ids)
+
+        with pytest.raises(KeyError):
+            index.sel(np.array([4], dtype="uint64"))
+
+    @pytest.mark.parametrize(
+        "geom",
+        (
+            pytest.param(shapely.box(-25, 15, 25, 35), id="polygon"),
+            pytest.param(healpix_geo.geometry.Bbox(-25, 15, 25, 35), id="bbox"),
+        ),
+    )
+    def test_query(self, geom):
+        depth = 4
+        index = healpix_geo.ring.RangeMOCIndex.full_domain(depth)
+        cell_ids = index.cell_ids()
+
+        multi_slice, moc = index.query(geom)
+
+        nested_index = healpix_geo.nested.RangeMOCIndex.full_domain(depth)
+        _, nested_moc = nested_index.query(geom)
+        lon, lat = healpix_geo.nested.healpix_to_lonlat(nested_moc.cell_ids(), depth)
+        expected = np.sort(healpix_geo.ring.lonlat_to_healpix(lon, lat, depth))
+
+        reconstructed = np.concatenate(
+            [cell_ids[s.as_pyslice()] for s in multi_slice], axis=0
+        )
+        np.testing.assert_equal(reconstructed, moc.cell_ids())
+        assert moc.size == nested_moc.size
+        np.testing.assert_equal(moc.cell_ids(), expected)
+
+    def test_pickle_roundtrip(self):
+        cell_ids = np.array([3, 7, 8, 9, 20, 40, 41], dtype="uint64")
+        index = healpix_geo.ring.RangeMOCIndex.from_cell_ids(
+            2, cell_ids, ellipsoid="WGS84"
+        )
+
+        unpickled = pickle.loads(pickle.dumps(index))
+
+        assert isinstance(unpickled, healpix_geo.ring.RangeMOCIndex)
+        assert unpickled.depth == index.depth
+        assert unpickled.ellipsoid == index.ellipsoid
+        np.testing.assert_equal(unpickled.cell_ids(), index.cell_ids())
+
+    def test_ascii_roundtrip(self):
+        cell_ids = np.array([3, 7, 8, 9, 20, 40, 41], dtype="uint64")
+        index = healpix_geo.ring.RangeMOCIndex.from_cell_ids(2, cell_ids)
+
+        roundtripped = healpix_geo.ring.RangeMOCIndex.from_ascii(index.to_ascii())
+
+        np.testing.assert_equal(roundtripped.cell_ids(), index.cell_ids())
+
+
+class TestZuniqRangeMOCIndex:
+    @staticmethod
+    def mixed_cells():
+        # two coarse cells and the children of a third one
+        coarse = healpix_geo.zuniq.from_nested(np.array([0, 5], dtype="uint64"), 1)
+        fine = healpix_geo.zuniq.from_nested(np.arange(4, 8, dtype="uint64"), 2)
+
+        return np.sort(np.concatenate([coarse, fine]))
+
+    def test_from_cell_ids(self):
+        cell_ids = self.mixed_cells()
+        index = healpix_geo.zuniq.RangeMOCIndex.from_cell_ids(cell_ids[::-1].copy())
+
+        assert index.size == cell_ids.size
+        assert index.depths == [1, 2]
+        np.testing.assert_equal(index.cell_ids(), cell_ids)
+
+    def test_from_cell_ids_overlap(self):
+        parent = healpix_geo.zuniq.from_nested(np.array([1], dtype="uint64"), 1)
+        child = healpix_geo.zuniq.from_nested(np.array([4], dtype="uint64"), 2)
+
+        with pytest.raises(ValueError, match="must not overlap"):
+            healpix_geo.zuniq.RangeMOCIndex.from_cell_ids(np.concatenate([parent, child]))
+
+    @pytest.mark.parametrize(
+        "indexer",
+        (slice(None), slice(1, 4), np.array([0, 2, 5], dtype="uint64")),
+    )
+    def test_isel(self, indexer):
+        cell_ids = self.mixed_cells()
+        index = healpix_geo.zuniq.RangeMOCIndex.from_cell_ids(cell_ids)
+
+        actual = index.isel(indexer)
+
+        np.testing.assert_equal(actual.cell_ids(), cell_ids[indexer])
+
+    def test_sel(self):
+        cell_ids = self.mixed_cells()
+        index = healpix_geo.zuniq.RangeMOCIndex.from_cell_ids(cell_ids)
+
+        positions, actual = index.sel(cell_ids[[1, 4]])
+        np.testing.assert_equal(positions, [1, 4])
+        np.testing.assert_equal(actual.cell_ids(), cell_ids[[1, 4]])
+
+        positions, actual = index.sel(slice(int(cell_ids[1]), int(cell_ids[3])))
+        np.testing.assert_equal(cell_ids[positions], cell_ids[1:4])
+        np.testing.assert_equal(actual.cell_ids(), cell_ids[1:4])
+
+        with pytest.raises(KeyError):
+            # the parent of cells in the index is not part of it
+            index.sel(healpix_geo.zuniq.from_nested(np.array([1], dtype="uint64"), 1))
+
+    def test_query(self):
+        cell_ids = self.mixed_cells()
+        index = healpix_geo.zuniq.RangeMOCIndex.from_cell_ids(cell_ids)
+        lon, lat = healpix_geo.nested.healpix_to_lonlat(
+            np.array([5], dtype="uint64"), 2
+        )
+        geom = shapely.Point(lon[0], lat[0])
+
+        multi_slice, moc = index.query(geom)
+
+        reconstructed = np.concatenate(
+            [cell_ids[s.as_pyslice()] for s in multi_slice], axis=0
+        )
+        np.testing.assert_equal(reconstructed, moc.cell_ids())
+
+        expected = healpix_geo.zuniq.from_nested(np.array([5], dtype="uint64"), 2)
+        np.testing.assert_equal(moc.cell_ids(), expected)
+
+    def test_set_operations(self):
+        cell_ids = self.mixed_cells()
+        index1 = healpix_geo.zuniq.RangeMOCIndex.from_cell_ids(cell_ids[:3])
+        index2 = healpix_geo.zuniq.RangeMOCIndex.from_cell_ids(cell_ids[2:])
+
+        np.testing.assert_equal(index1.union(index2).cell_ids(), cell_ids)
+        np.testing.assert_equal(
+            index1.intersection(index2).cell_ids(), cell_ids[2:3]
+        )
+        np.testing.assert_equal(index1.difference(index2).cell_ids(), cell_ids[:2])
+
+    def test_pickle_roundtrip(self):
+        cell_ids = self.mixed_cells()
+        index = healpix_geo.zuniq.RangeMOCIndex.from_cell_ids(
+            cell_ids, ellipsoid="WGS84"
+        )
+
+        unpickled = pickle.loads(pickle.dumps(index))
+
+        assert isinstance(unpickled, healpix_geo.zuniq.RangeMOCIndex)
+        assert unpickled.ellipsoid == index.ellipsoid
+        np.testing.assert_equal(unpickled.cell_ids(), index.cell_ids())
+
+    @staticmethod
+    def moc_cells():
+        # cells that can not be merged into their parents
+        coarse = healpix_geo.zuniq.from_nested(np.array([0, 5], dtype="uint64"), 1)
+        fine = healpix_geo.zuniq.from_nested(np.array([4, 5], dtype="uint64"), 2)
+
+        return np.sort(np.concatenate([coarse, fine]))
+
+    def test_fits_roundtrip(self):
+        cell_ids = self.moc_cells()
+        index = healpix_geo.zuniq.RangeMOCIndex.from_cell_ids(cell_ids)
+
+        data = index.to_fits()
+        assert data.startswith(b"SIMPLE")
+
+        roundtripped = healpix_geo.zuniq.RangeMOCIndex.from_fits(data)
+        assert roundtripped.depths == [1, 2]
+        np.testing.assert_equal(roundtripped.cell_ids(), cell_ids)
+
+    def test_ascii_roundtrip(self):
+        cell_ids = self.moc_cells()
+        index = healpix_geo.zuniq.RangeMOCIndex.from_cell_ids(cell_ids)
+
+        roundtripped = healpix_geo.zuniq.RangeMOCIndex.from_ascii(index.to_ascii())
+
+        np.testing.assert_equal(roundtripped.cell_ids(), cell_ids)
+
+    def test_to_ascii_merges_cells(self):
+        index = healpix_geo.zuniq.RangeMOCIndex.from_cell_ids(self.mixed_cells())
+
+        roundtripped = healpix_geo.zuniq.RangeMOCIndex.from_ascii(index.to_ascii())
+
+        expected = healpix_geo.zuniq.from_nested(np.array([0, 1, 5], dtype="uint64"), 1)
+        np.testing.assert_equal(roundtripped.cell_ids(), expected)
@@ -51,6 +51,14 @@ class Ellipsoid:
             nullcontext(),
             id="dict-ellipsoid",
         ),
+        pytest.param(
+            {"semimajor_axis": 1, "flattening": 0.1},
+            nullcontext(),
+            id="dict-ellipsoid-flattening",
+        ),
+        pytest.param(
+            healpix_geo.Ellipsoid("WGS84"), nullcontext(), id="instance-ellipsoid"
+        ),
         pytest.param(
             {"abc": 2},
             pytest.raises(TypeError, match="failed to extract enum"),
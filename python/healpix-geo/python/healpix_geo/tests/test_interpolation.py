@@ -0,0 +1,69 @@
+import numpy as np
+import pytest
+
+import healpix_geo
+
+lon = np.array([[0.0, 50.0, 25.0], [-170.0, 300.0, 45.0]])
+lat = np.array([[6.0, -12.0, 45.0], [-89.5, 70.0, 41.8]])
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+def test_interpolation_weights(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+
+    ipix, weights = module.interpolation_weights(lon, lat, 4, ellipsoid="WGS84")
+
+    assert ipix.shape == (2, 3, 4)
+    assert weights.shape == (2, 3, 4)
+    np.testing.assert_allclose(weights.sum(axis=-1), 1.0)
+
+    containing = module.lonlat_to_healpix(lon, lat, 4, ellipsoid="WGS84")
+    assert (ipix == containing[..., None]).any(axis=-1).all()
+
+
+def test_interpolation_weights_schemes():
+    nested, weights = healpix_geo.nested.interpolation_weights(lon, lat, 4)
+
+    zuniq, zuniq_weights = healpix_geo.zuniq.interpolation_weights(lon, lat, 4)
+    np.testing.assert_equal(zuniq, healpix_geo.zuniq.from_nested(nested, 4))
+    np.testing.assert_equal(zuniq_weights, weights)
+
+    ring, ring_weights = healpix_geo.ring.interpolation_weights(lon, lat, 4)
+    centers = healpix_geo.nested.healpix_to_lonlat(nested, 4)
+    np.testing.assert_equal(healpix_geo.ring.lonlat_to_healpix(*centers, 4), ring)
+    np.testing.assert_equal(ring_weights, weights)
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring"])
+def test_interpolate_at_cell_centers(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    depth = 3
+    cell_ids = np.arange(12 * 4**depth, dtype="uint64")
+    values = np.random.default_rng(0).normal(size=cell_ids.size)
+
+    lon_, lat_ = module.healpix_to_lonlat(cell_ids[::7], depth, ellipsoid="WGS84")
+    actual = module.interpolate(values, cell_ids, lon_, lat_, depth, ellipsoid="WGS84")
+
+    np.testing.assert_allclose(actual, values[::7], atol=1e-9)
+
+
+def test_interpolate_missing_cells():
+    depth = 4
+    ipix, weights = healpix_geo.nested.interpolation_weights(lon[0], lat[0], depth)
+
+    # only keep the first and second cell of the first point
+    cell_ids = ipix[0, :2]
+    values = np.array([1.0, 3.0])
+
+    actual = healpix_geo.nested.interpolate(values, cell_ids, lon[0], lat[0], depth)
+
+    expected = (weights[0, 0] + 3 * weights[0, 1]) / weights[0, :2].sum()
+    np.testing.assert_allclose(actual[0], expected)
+    assert np.isnan(actual[1:]).all()
+
+
+def test_interpolate_length_mismatch():
+    with pytest.raises(ValueError):
+        healpix_geo.nested.interpolate(
+            np.array([1.0]), np.array([0, 1]), lon[0], lat[0], 3
+        )
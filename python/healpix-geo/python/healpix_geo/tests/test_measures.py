@@ -0,0 +1,73 @@
+import numpy as np
+import pytest
+
+import healpix_geo
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring"])
+@pytest.mark.parametrize(
+    ["ellipsoid", "radius"],
+    (
+        ("sphere", 6370997.0),
+        ("WGS84", 6371007.1809),
+        ({"radius": 1.0}, 1.0),
+    ),
+)
+def test_cell_area(indexing_scheme, ellipsoid, radius):
+    module = getattr(healpix_geo, indexing_scheme)
+
+    depth = 2
+    cell_ids = np.arange(12 * 4**depth, dtype="uint64")
+
+    actual = module.cell_area(cell_ids, depth, ellipsoid=ellipsoid)
+
+    assert actual.shape == cell_ids.shape
+    np.testing.assert_allclose(actual, actual[0])
+    np.testing.assert_allclose(actual.sum(), 4 * np.pi * radius**2, rtol=1e-9)
+
+
+def test_cell_area_zuniq():
+    depths = np.array([0, 3, 7], dtype="uint8")
+    nested = np.array([5, 100, 3000], dtype="uint64")
+    cell_ids = healpix_geo.zuniq.from_nested(nested, depths)
+
+    actual = healpix_geo.zuniq.cell_area(cell_ids, ellipsoid="WGS84")
+    expected = np.stack(
+        [
+            healpix_geo.nested.cell_area(cell_id, depth, ellipsoid="WGS84")[0]
+            for cell_id, depth in zip(nested, depths)
+        ]
+    )
+
+    np.testing.assert_allclose(actual, expected)
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring"])
+def test_cell_perimeter(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+
+    depth = 4
+    cell_ids = np.array([[0, 17], [600, 3071]], dtype="uint64")
+
+    coarse = module.cell_perimeter(cell_ids, depth, ellipsoid="WGS84")
+    fine = module.cell_perimeter(cell_ids, depth, ellipsoid="WGS84", step=16)
+
+    assert coarse.shape == cell_ids.shape
+    # a cell is roughly a square of side sqrt(area)
+    side = np.sqrt(healpix_geo.nested.cell_area(cell_ids, depth, ellipsoid="WGS84"))
+    np.testing.assert_allclose(fine, 4 * side, rtol=0.2)
+    # the edges are not geodesics
+    assert np.all(fine >= coarse)
+
+
+def test_cell_perimeter_zuniq():
+    cell_ids = healpix_geo.zuniq.from_nested(
+        np.array([17], dtype="uint64"), np.array([4], dtype="uint8")
+    )
+
+    actual = healpix_geo.zuniq.cell_perimeter(cell_ids, ellipsoid="WGS84", step=4)
+    expected = healpix_geo.nested.cell_perimeter(
+        np.array([17], dtype="uint64"), 4, ellipsoid="WGS84", step=4
+    )
+
+    np.testing.assert_allclose(actual, expected)
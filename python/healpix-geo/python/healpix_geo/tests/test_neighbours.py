@@ -201,3 +201,106 @@ def test_kth_neighbourhood(depth, cell_ids, ring, indexing_scheme, expected):
     func = funcs[indexing_scheme]
     actual = func(cell_ids)
     np.testing.assert_equal(actual, expected)
+
+
+@pytest.mark.parametrize("depth", [2, 8])
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring"])
+def test_neighbours_against_cdshealpix(depth, indexing_scheme):
+    ipixels = np.array([0, 1, 2, 50, 100, 12 * 4**depth - 1], dtype="int64")
+
+    if indexing_scheme == "nested":
+        expected = cdshealpix.nested.neighbours(ipixels, depth)
+    elif indexing_scheme == "ring":
+        nested = cdshealpix.from_ring(ipixels, depth)
+        expected = cdshealpix.nested.neighbours(nested, depth)
+        expected = np.where(
+            expected == -1, -1, cdshealpix.to_ring(np.maximum(expected, 0), depth)
+        )
+
+    # cdshealpix orders the neighbours as S, SE, E, SW, C, NE, W, NW, N
+    expected = expected[:, [8, 5, 2, 1, 0, 3, 6, 7]]
+
+    module = getattr(healpix_geo, indexing_scheme)
+    actual = module.neighbours(ipixels, depth)
+
+    np.testing.assert_equal(actual, expected)
+
+
+@pytest.mark.parametrize("depth", [0, 1, 3])
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+def test_neighbours_missing(depth, indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    ipix = np.arange(12 * 4**depth, dtype="uint64")
+
+    if indexing_scheme == "zuniq":
+        actual = module.neighbours(module.from_nested(ipix, depth))
+    else:
+        actual = module.neighbours(ipix, depth)
+
+    assert actual.shape == (ipix.size, 8)
+    # 3 cells touch each of the 8 vertices shared by only 3 base cells, and each base
+    # cell touches 2 of them
+    assert (actual == -1).sum() == 24
+    if depth > 0:
+        assert ((actual == -1).sum(axis=-1) <= 1).all()
+
+
+def test_neighbours_zuniq():
+    depth = 4
+    nested = np.array([[3, 200], [1000, 3071]], dtype="uint64")
+
+    actual = healpix_geo.zuniq.neighbours(healpix_geo.zuniq.from_nested(nested, depth))
+    expected = healpix_geo.nested.neighbours(nested, depth)
+
+    np.testing.assert_equal(actual == -1, expected == -1)
+    np.testing.assert_equal(
+        actual[expected != -1],
+        healpix_geo.zuniq.from_nested(expected[expected != -1], depth),
+    )
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring"])
+def test_neighbours_within_distance(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    depth = 3
+    radius = 1_500_000.0
+    ipix = np.array([0, 100, 767], dtype="int64")
+    all_cells = np.arange(12 * 4**depth, dtype="int64")
+
+    neighbours, distances, offsets = module.neighbours_within_distance(
+        ipix, depth, radius, ellipsoid="WGS84"
+    )
+    expected, _, _ = module.geodesic_distances(
+        ipix, np.tile(all_cells, (ipix.size, 1)), depth, ellipsoid="WGS84"
+    )
+
+    assert offsets.shape == (ipix.size + 1,)
+    for index, cell in enumerate(ipix):
+        actual = neighbours[offsets[index] : offsets[index + 1]]
+        actual_distances = distances[offsets[index] : offsets[index + 1]]
+
+        assert actual[0] == cell
+        assert (np.diff(actual_distances) >= 0).all()
+        np.testing.assert_equal(np.sort(actual), all_cells[expected[index] <= radius])
+
+
+def test_neighbours_within_distance_zuniq():
+    depth = 4
+    nested = np.array([3, 200, 3071], dtype="uint64")
+
+    actual = healpix_geo.zuniq.neighbours_within_distance(
+        healpix_geo.zuniq.from_nested(nested, depth), 800_000.0
+    )
+    expected = healpix_geo.nested.neighbours_within_distance(nested, depth, 800_000.0)
+
+    np.testing.assert_equal(
+        actual[0], healpix_geo.zuniq.from_nested(expected[0], depth)
+    )
+    np.testing.assert_equal(actual[1], expected[1])
+    np.testing.assert_equal(actual[2], expected[2])
+
+
+@pytest.mark.parametrize("radius", [0.0, -1.0, np.nan])
+def test_neighbours_within_distance_invalid_radius(radius):
+    with pytest.raises(ValueError, match="radius must be a positive number"):
+        healpix_geo.nested.neighbours_within_distance(np.array([0]), 3, radius)
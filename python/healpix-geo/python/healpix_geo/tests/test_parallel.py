@@ -0,0 +1,44 @@
+import numpy as np
+import pytest
+
+import healpix_geo
+
+
+def test_set_num_threads():
+    assert healpix_geo.get_num_threads() == 0
+
+    healpix_geo.set_num_threads(2)
+    try:
+        assert healpix_geo.get_num_threads() == 2
+    finally:
+        healpix_geo.set_num_threads(0)
+
+    assert healpix_geo.get_num_threads() == 0
+
+
+def test_set_num_threads_context_manager():
+    with healpix_geo.set_num_threads(3):
+        assert healpix_geo.get_num_threads() == 3
+
+        with healpix_geo.set_num_threads(1):
+            assert healpix_geo.get_num_threads() == 1
+
+        assert healpix_geo.get_num_threads() == 3
+
+    assert healpix_geo.get_num_threads() == 0
+
+
+def test_set_num_threads_invalid():
+    with pytest.raises(ValueError, match="must be non-negative"):
+        healpix_geo.set_num_threads(-1)
+
+
+@pytest.mark.parametrize("num_threads", [0, 1, 4])
+def test_results_independent_of_threads(num_threads):
+    cell_ids = np.arange(12 * 4**3, dtype="uint64")
+    expected = healpix_geo.nested.healpix_to_lonlat(cell_ids, 3)
+
+    with healpix_geo.set_num_threads(num_threads):
+        actual = healpix_geo.nested.healpix_to_lonlat(cell_ids, 3)
+
+    np.testing.assert_equal(actual, expected)
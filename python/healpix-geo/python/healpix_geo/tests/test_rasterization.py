@@ -0,0 +1,71 @@
+import numpy as np
+import pytest
+
+import healpix_geo
+
+
+def full_map(module, depth):
+    ipix = np.arange(12 * 4**depth, dtype="uint64")
+    if module is healpix_geo.zuniq:
+        ipix = healpix_geo.zuniq.from_nested(ipix, depth)
+
+    return ipix, np.arange(ipix.size, dtype="float64")
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+def test_rasterize_equirectangular(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    depth = 2
+    ipix, values = full_map(module, depth)
+
+    image = module.rasterize(values, ipix, depth, (9, 18), ellipsoid="WGS84")
+
+    lon = np.linspace(-180, 180, 19)[:-1] + 10
+    lat = np.linspace(90, -90, 10)[:-1] - 10
+    expected = module.lonlat_to_healpix(
+        *np.meshgrid(lon, lat), depth, ellipsoid="WGS84"
+    )
+    np.testing.assert_equal(ipix[image.astype("int64")], expected)
+
+
+def test_rasterize_fill_value():
+    depth = 1
+    ipix = np.arange(4, dtype="uint64")
+    values = np.ones(4)
+
+    image = healpix_geo.nested.rasterize(
+        values, ipix, depth, (90, 180), projection="mollweide", fill_value=-1
+    )
+
+    assert image.shape == (90, 180)
+    assert (image == 1).any()
+    assert (image[:, :10] == -1).all()
+    assert (image[-20:] == -1).all()
+
+
+def test_rasterize_orthographic():
+    depth = 3
+    ipix, values = full_map(healpix_geo.nested, depth)
+
+    image = healpix_geo.nested.rasterize(
+        values,
+        ipix,
+        depth,
+        (5, 5),
+        projection="orthographic",
+        central_longitude=100,
+        central_latitude=-30,
+    )
+
+    assert np.isnan(image[0, 0])
+    assert image[2, 2] == healpix_geo.nested.lonlat_to_healpix(100.0, -30.0, depth)
+
+
+def test_rasterize_invalid():
+    ipix = np.arange(48, dtype="uint64")
+
+    with pytest.raises(ValueError, match="Projection"):
+        healpix_geo.nested.rasterize(np.ones(48), ipix, 1, (2, 4), projection="unknown")
+
+    with pytest.raises(ValueError):
+        healpix_geo.nested.rasterize(np.ones(3), ipix, 1, (2, 4))
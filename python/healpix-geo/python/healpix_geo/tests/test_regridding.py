@@ -0,0 +1,87 @@
+import numpy as np
+import pytest
+
+import healpix_geo
+
+
+def rectilinear_bounds(lon_edges, lat_edges):
+    lon0, lat0 = np.meshgrid(lon_edges[:-1], lat_edges[:-1])
+    lon1, lat1 = np.meshgrid(lon_edges[1:], lat_edges[1:])
+
+    lon_bounds = np.stack([lon0, lon1, lon1, lon0], axis=-1)
+    lat_bounds = np.stack([lat0, lat0, lat1, lat1], axis=-1)
+
+    return lon_bounds, lat_bounds
+
+
+global_bounds = rectilinear_bounds(np.linspace(-180, 180, 25), np.linspace(-90, 90, 13))
+
+
+def row_sums(data, indptr):
+    return np.array([data[start:stop].sum() for start, stop in zip(indptr, indptr[1:])])
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring", "zuniq"])
+def test_regridding_weights_to_healpix(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    depth = 2
+
+    ipix, (data, indices, indptr) = module.regridding_weights(
+        *global_bounds, depth, ellipsoid="WGS84"
+    )
+
+    expected = module.lonlat_to_healpix(
+        *module.healpix_to_lonlat(ipix, depth, ellipsoid="WGS84"),
+        depth,
+        ellipsoid="WGS84",
+    )
+    np.testing.assert_equal(ipix, expected)
+    assert ipix.size == 12 * 4**depth
+    assert indptr.shape == (ipix.size + 1,)
+    assert ((indices >= 0) & (indices < 12 * 24)).all()
+    np.testing.assert_allclose(row_sums(data, indptr), 1.0)
+
+
+def test_regridding_weights_from_healpix():
+    depth = 2
+
+    ipix, (data, indices, indptr) = healpix_geo.nested.regridding_weights(
+        *global_bounds, depth, direction="from_healpix"
+    )
+
+    assert indptr.shape == (12 * 24 + 1,)
+    assert ((indices >= 0) & (indices < ipix.size)).all()
+    np.testing.assert_allclose(row_sums(data, indptr), 1.0)
+
+
+def test_regridding_weights_regional():
+    lon_bounds, lat_bounds = rectilinear_bounds(
+        np.linspace(170, 190, 5), np.linspace(40, 50, 3)
+    )
+    # longitudes crossing the antimeridian
+    lon_bounds = (lon_bounds + 180) % 360 - 180
+
+    ipix, (data, indices, indptr) = healpix_geo.nested.regridding_weights(
+        lon_bounds, lat_bounds, 4
+    )
+
+    lon, lat = healpix_geo.nested.healpix_to_lonlat(ipix, 4)
+    assert ((np.abs(lon - 180) < 15) & (lat > 35) & (lat < 55)).all()
+    assert np.unique(indices).size == 8
+
+
+def test_regridding_weights_invalid():
+    lon_bounds, lat_bounds = global_bounds
+
+    with pytest.raises(ValueError, match="direction"):
+        healpix_geo.nested.regridding_weights(
+            lon_bounds, lat_bounds, 2, direction="sideways"
+        )
+
+    with pytest.raises(ValueError, match="same shape"):
+        healpix_geo.nested.regridding_weights(lon_bounds, lat_bounds[..., :3], 2)
+
+    with pytest.raises(ValueError):
+        healpix_geo.nested.regridding_weights(
+            lon_bounds[..., :2], lat_bounds[..., :2], 2
+        )
@@ -24,3 +24,66 @@ def test_internal_boundary(depth, center, size):
     actual = healpix_geo.nested.internal_boundary(depth, expanded)
 
     np.testing.assert_equal(np.sort(actual), expected)
+
+
+@pytest.mark.parametrize("depth", (3, 5))
+@pytest.mark.parametrize("center", (3, 41))
+def test_external_boundary(depth, center):
+    center = np.array([center], dtype="uint64")
+    domain = healpix_geo.nested.kth_neighbourhood(ipix=center, depth=depth, ring=2)
+    cells = np.unique(domain, sorted=True)
+    cells = cells[cells != -1].astype("uint64")
+
+    expanded_domain = healpix_geo.nested.kth_neighbourhood(
+        ipix=cells, depth=depth, ring=1
+    )
+    expanded = np.unique(expanded_domain, sorted=True)
+    expanded = expanded[expanded != -1].astype("uint64")
+
+    expected = np.setdiff1d(expanded, cells)
+
+    actual = healpix_geo.nested.external_boundary(depth, cells)
+
+    np.testing.assert_equal(np.sort(actual), expected)
+
+
+@pytest.mark.parametrize("kind", ["internal_boundary", "external_boundary"])
+def test_boundary_ring(kind):
+    import cdshealpix.nested
+    import cdshealpix.ring
+
+    depth = 4
+    cells = np.arange(100, 140, dtype="uint64")
+    nested = cdshealpix.ring.to_nested(cells, depth=depth)
+
+    expected = np.sort(
+        cdshealpix.nested.to_ring(
+            getattr(healpix_geo.nested, kind)(depth, nested), depth=depth
+        )
+    )
+    actual = getattr(healpix_geo.ring, kind)(depth, cells)
+
+    np.testing.assert_equal(actual, expected)
+
+
+@pytest.mark.parametrize("kind", ["internal_boundary", "external_boundary"])
+def test_boundary_zuniq(kind):
+    depth = 4
+    nested = np.arange(256, 512, dtype="uint64")
+    expected = healpix_geo.zuniq.from_nested(
+        getattr(healpix_geo.nested, kind)(depth, nested), depth
+    )
+
+    # the same area with a coarser cell in the middle
+    children = healpix_geo.nested.zoom_to(np.array([80], dtype="uint64"), 3, depth)
+    remaining = np.setdiff1d(nested, np.ravel(children))
+    cell_ids = np.concatenate(
+        [
+            healpix_geo.zuniq.from_nested(remaining, depth),
+            healpix_geo.zuniq.from_nested(np.array([80], dtype="uint64"), 3),
+        ]
+    )
+
+    actual = getattr(healpix_geo.zuniq, kind)(cell_ids)
+
+    np.testing.assert_equal(np.sort(actual), np.sort(expected))
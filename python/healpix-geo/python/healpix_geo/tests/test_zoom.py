@@ -74,3 +74,202 @@ def test_siblings(cell_ids, depth, indexing_scheme):
 
     actual = siblings(cell_ids, depth)
     np.testing.assert_equal(actual, expected)
+
+
+@pytest.mark.parametrize(
+    ["depth", "new_depth"],
+    (
+        pytest.param(1, 1, id="identity"),
+        pytest.param(2, 0, id="parents"),
+        pytest.param(0, 2, id="children"),
+    ),
+)
+def test_zoom_to_ring(depth, new_depth):
+    import cdshealpix.nested
+    import cdshealpix.ring
+
+    cell_ids = np.arange(12 * 4**depth, dtype="uint64")
+    nested = cdshealpix.ring.to_nested(cell_ids, depth=depth)
+
+    expected_nested = healpix_geo.nested.zoom_to(nested, depth, new_depth)
+    expected = cdshealpix.nested.to_ring(
+        np.ravel(expected_nested), depth=new_depth
+    ).reshape(expected_nested.shape)
+
+    actual = healpix_geo.ring.zoom_to(cell_ids, depth, new_depth)
+
+    np.testing.assert_equal(actual, expected)
+
+
+def test_siblings_ring():
+    import cdshealpix.nested
+    import cdshealpix.ring
+
+    depth = 2
+    cell_ids = np.array([1, 15, 53, 67, 150], dtype="uint64")
+    nested = cdshealpix.ring.to_nested(cell_ids, depth=depth)
+
+    expected_nested = healpix_geo.nested.siblings(nested, depth)
+    expected = cdshealpix.nested.to_ring(
+        np.ravel(expected_nested), depth=depth
+    ).reshape(expected_nested.shape)
+
+    actual = healpix_geo.ring.siblings(cell_ids, depth)
+
+    np.testing.assert_equal(actual, expected)
+
+
+@pytest.mark.parametrize(
+    ["depths", "new_depth"],
+    (
+        pytest.param([2, 2, 2], 2, id="identity"),
+        pytest.param([2, 3, 5], 1, id="parents-mixed depths"),
+        pytest.param([1, 1, 1], 3, id="children"),
+    ),
+)
+def test_zoom_to_zuniq(depths, new_depth):
+    depths = np.array(depths, dtype="uint8")
+    nested = np.array([5, 17, 30], dtype="uint64")
+    cell_ids = healpix_geo.zuniq.from_nested(nested, depths)
+
+    actual = healpix_geo.zuniq.zoom_to(cell_ids, new_depth)
+
+    if (depths >= new_depth).all():
+        expected_nested = nested // 4 ** (depths - new_depth).astype("uint64")
+        expected = healpix_geo.zuniq.from_nested(expected_nested, new_depth)
+    else:
+        expected_nested = healpix_geo.nested.zoom_to(nested, 1, new_depth)
+        expected = np.reshape(
+            healpix_geo.zuniq.from_nested(np.ravel(expected_nested), new_depth),
+            expected_nested.shape,
+        )
+
+    np.testing.assert_equal(actual, expected)
+
+
+def test_zoom_to_zuniq_mixed_depths():
+    cell_ids = healpix_geo.zuniq.from_nested(
+        np.array([5, 17], dtype="uint64"), np.array([1, 2], dtype="uint8")
+    )
+
+    with pytest.raises(ValueError, match="cannot zoom to a finer depth"):
+        healpix_geo.zuniq.zoom_to(cell_ids, 3)
+
+
+def test_siblings_zuniq():
+    depths = np.array([1, 3, 2], dtype="uint8")
+    nested = np.array([5, 17, 150], dtype="uint64")
+    cell_ids = healpix_geo.zuniq.from_nested(nested, depths)
+
+    first = nested // 4 * 4
+    expected = healpix_geo.zuniq.from_nested(
+        first[:, None] + np.arange(4, dtype="uint64"),
+        np.repeat(depths[:, None], 4, axis=1),
+    )
+
+    actual = healpix_geo.zuniq.siblings(cell_ids)
+
+    np.testing.assert_equal(actual, expected)
+
+
+@pytest.mark.parametrize(
+    ["op", "expected"],
+    (
+        pytest.param("sum", [6, 22, 38], id="sum"),
+        pytest.param("mean", [1.5, 5.5, 9.5], id="mean"),
+        pytest.param("min", [0, 4, 8], id="min"),
+        pytest.param("max", [3, 7, 11], id="max"),
+        pytest.param("count", [4, 4, 4], id="count"),
+        pytest.param("area_weighted_mean", [1.5, 5.5, 9.5], id="area_weighted_mean"),
+    ),
+)
+def test_aggregate_to_parent(op, expected):
+    cell_ids = np.arange(12, dtype="uint64")[::-1]
+    values = np.arange(12, dtype="float64")[::-1]
+
+    parents, actual = healpix_geo.nested.aggregate_to_parent(
+        cell_ids, values, 2, 1, op=op
+    )
+
+    np.testing.assert_equal(parents, [0, 1, 2])
+    np.testing.assert_equal(actual, expected)
+
+
+def test_aggregate_to_parent_ring():
+    depth = 2
+    cell_ids = np.arange(12 * 4**depth, dtype="uint64")
+    values = cell_ids.astype("float64")
+
+    parents, actual = healpix_geo.ring.aggregate_to_parent(cell_ids, values, depth, 1)
+
+    ring_parents = healpix_geo.ring.zoom_to(cell_ids, depth, 1).astype("int64")
+    expected = np.bincount(ring_parents, weights=values) / 4
+    np.testing.assert_equal(parents, np.arange(12 * 4))
+    np.testing.assert_allclose(actual, expected)
+
+
+def test_aggregate_to_parent_zuniq_mixed_depths():
+    ipix = np.concatenate(
+        [
+            healpix_geo.zuniq.from_nested(np.arange(1, 4), 1),
+            healpix_geo.zuniq.from_nested(np.arange(4), 2),
+        ]
+    )
+    values = np.array([1, 1, 1, 3, 3, 3, 3], dtype="float64")
+
+    parents, mean = healpix_geo.zuniq.aggregate_to_parent(ipix, values, 0, op="mean")
+    _, weighted = healpix_geo.zuniq.aggregate_to_parent(
+        ipix, values, 0, op="area_weighted_mean"
+    )
+
+    np.testing.assert_equal(parents, healpix_geo.zuniq.from_nested(np.array([0]), 0))
+    np.testing.assert_allclose(mean, [15 / 7])
+    np.testing.assert_allclose(weighted, [1.5])
+
+
+def test_aggregate_to_parent_invalid():
+    cell_ids = np.arange(4, dtype="uint64")
+
+    with pytest.raises(ValueError, match="Reduction"):
+        healpix_geo.nested.aggregate_to_parent(cell_ids, np.ones(4), 1, 0, op="median")
+
+    with pytest.raises(ValueError):
+        healpix_geo.nested.aggregate_to_parent(cell_ids, np.ones(4), 1, 2)
+
+    with pytest.raises(ValueError):
+        healpix_geo.nested.aggregate_to_parent(cell_ids, np.ones(3), 1, 0)
+
+
+@pytest.mark.parametrize("indexing_scheme", ["nested", "ring"])
+def test_broadcast_to_children(indexing_scheme):
+    module = getattr(healpix_geo, indexing_scheme)
+    cell_ids = np.array([3, 7], dtype="uint64")
+    values = np.array([1.0, 2.0])
+
+    children, actual = module.broadcast_to_children(cell_ids, values, 1, 2)
+
+    np.testing.assert_equal(children, module.zoom_to(cell_ids, 1, 2).ravel())
+    np.testing.assert_equal(actual, np.repeat(values, 4))
+
+    parents, roundtrip = module.aggregate_to_parent(children, actual, 2, 1)
+    np.testing.assert_equal(parents, cell_ids)
+    np.testing.assert_equal(roundtrip, values)
+
+
+def test_broadcast_to_children_zuniq():
+    ipix = np.concatenate(
+        [
+            healpix_geo.zuniq.from_nested(np.array([0]), 0),
+            healpix_geo.zuniq.from_nested(np.array([4]), 1),
+        ]
+    )
+
+    children, actual = healpix_geo.zuniq.broadcast_to_children(
+        ipix, np.array([1.0, 2.0]), 1
+    )
+
+    np.testing.assert_equal(children, healpix_geo.zuniq.from_nested(np.arange(5), 1))
+    np.testing.assert_equal(actual, [1, 1, 1, 1, 2])
+
+    with pytest.raises(ValueError):
+        healpix_geo.zuniq.broadcast_to_children(ipix, np.array([1.0, 2.0]), 0)
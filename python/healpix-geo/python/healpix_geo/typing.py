@@ -1,5 +1,7 @@
 from typing import Protocol, TypedDict
 
+from healpix_geo.healpix_geo import Ellipsoid
+
 
 class SphereDict(TypedDict):
     radius: float
@@ -14,12 +16,25 @@ class EllipsoidDict(TypedDict):
     inverse_flattening: float
 
 
+class EllipsoidFlatteningDict(TypedDict):
+    semimajor_axis: float
+    flattening: float
+
+
 class EllipsoidType(Protocol):
     semimajor_axis: float
     inverse_flattening: float
 
 
 _SphereLike = SphereDict | SphereType
-_EllipsoidLike = EllipsoidDict | EllipsoidType
+_EllipsoidLike = EllipsoidDict | EllipsoidFlatteningDict | EllipsoidType
+
+EllipsoidLike = str | Ellipsoid | _SphereLike | _EllipsoidLike
+
+
+class OperatorDict(TypedDict):
+    operator: str
+    dimension: int
+
 
-EllipsoidLike = str | _SphereLike | _EllipsoidLike
+CrsLike = int | str | OperatorDict
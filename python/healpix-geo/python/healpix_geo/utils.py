@@ -23,3 +23,25 @@ def _check_ring(depth, ring):
             "Crossing base cell boundaries more than once is not supported."
             f" Received ring={ring}, but expected an integer in the range of [0, {nside}]."
         )
+
+
+_CELL_ID_DTYPES = [np.dtype(dtype) for dtype in ["uint64", "int64", "uint32", "int32"]]
+_COORDINATE_DTYPES = [np.dtype(dtype) for dtype in ["float64", "float32"]]
+
+
+def _as_cell_ids(data):
+    """Convert cell ids to a supported dtype, copying only if needed."""
+    data = np.atleast_1d(data)
+    if data.dtype not in _CELL_ID_DTYPES:
+        data = data.astype("uint64")
+
+    return data
+
+
+def _as_coordinates(data):
+    """Convert coordinates to a supported dtype, copying only if needed."""
+    data = np.atleast_1d(data)
+    if data.dtype not in _COORDINATE_DTYPES:
+        data = data.astype("float64")
+
+    return data
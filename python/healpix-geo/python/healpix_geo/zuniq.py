@@ -1,7 +1,20 @@
 import numpy as np
 
 from healpix_geo import healpix_geo
-from healpix_geo.utils import _check_depth, _check_ipixels
+from healpix_geo.utils import (
+    _as_cell_ids,
+    _as_coordinates,
+    _check_depth,
+    _check_ipixels,
+)
+
+RangeMOCIndex = healpix_geo.zuniq.RangeMOCIndex
+internal_boundary = healpix_geo.zuniq.internal_boundary
+external_boundary = healpix_geo.zuniq.external_boundary
+
+
+def create_empty(ellipsoid="sphere"):
+    return RangeMOCIndex.create_empty(ellipsoid=ellipsoid)
 
 
 def from_nested(ipix, depth, num_threads=0):
@@ -38,9 +51,9 @@ def from_nested(ipix, depth, num_threads=0):
 
     ipix = np.atleast_1d(ipix)
     _check_ipixels(data=ipix, depth=depth)
-    ipix = ipix.astype(np.uint64)
+    ipix = _as_cell_ids(ipix)
 
-    depth = depth if isinstance(depth, int) else depth.astype("uint8")
+    depth = depth if isinstance(depth, int) else np.asarray(depth, "uint8")
     num_threads = np.uint16(num_threads)
 
     return healpix_geo.zuniq.from_nested(ipix, depth, num_threads)
@@ -84,14 +97,21 @@ def to_nested(ipix, num_threads=0):
     >>> depth
     array([1, 3, 2, 4], dtype=uint8)
     """
-    ipix = np.atleast_1d(ipix).astype(np.uint64)
+    ipix = _as_cell_ids(ipix)
 
     num_threads = np.uint16(num_threads)
 
     return healpix_geo.zuniq.to_nested(ipix, num_threads)
 
 
-def healpix_to_lonlat(ipix, ellipsoid, num_threads=0):
+def healpix_to_lonlat(
+    ipix,
+    ellipsoid,
+    num_threads=0,
+    out=None,
+    on_invalid="raise",
+    latitude_kind="geographic",
+):
     r"""Get the longitudes and latitudes of the center of some HEALPix cells.
 
     Parameters
@@ -106,18 +126,35 @@ def healpix_to_lonlat(ipix, ellipsoid, num_threads=0):
         Specifies the number of threads to use for the computation. Default to 0 means
         it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
         or the number of logical CPUs (otherwise)
+    out : tuple of `numpy.ndarray`, optional
+        `np.float64` arrays with the shape and memory order of ``ipix`` to write the
+        longitudes and latitudes into. New arrays are allocated if not given.
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid cell indexes. ``"raise"`` raises a `ValueError`,
+        ``"sentinel"`` returns NaN coordinates for them and ``"mask"`` additionally
+        returns a mask of the invalid cells.
+    latitude_kind : str, default: "geographic"
+        The kind of the returned latitudes: one of ``"geographic"``, ``"geocentric"``,
+        ``"parametric"``, ``"rectifying"``, ``"conformal"`` or ``"authalic"``. All
+        kinds are equal on a sphere.
 
     Returns
     -------
     lon, lat : array-like
         The coordinates of the center of the HEALPix cells given as a longitude, latitude tuple.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid cells. Only returned if
+        ``on_invalid="mask"``.
 
     Raises
     ------
     ValueError
-        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`.
+        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`
+        and ``on_invalid="raise"``.
     ValueError
         When the name of the ellipsoid is unknown.
+    ValueError
+        When the latitude kind is unknown.
 
     Examples
     --------
@@ -130,14 +167,29 @@ def healpix_to_lonlat(ipix, ellipsoid, num_threads=0):
     >>> lat
     array([2.85869025e-07, 1.42934512e-07, 1.42934512e-07])
     """
-    ipix = np.atleast_1d(ipix).astype(np.uint64)
+    ipix = _as_cell_ids(ipix)
 
     num_threads = np.uint16(num_threads)
 
-    return healpix_geo.zuniq.healpix_to_lonlat(ipix, ellipsoid, num_threads)
+    lon, lat, mask = healpix_geo.zuniq.healpix_to_lonlat(
+        ipix, ellipsoid, num_threads, out, on_invalid, latitude_kind
+    )
+    if on_invalid == "mask":
+        return lon, lat, mask
+
+    return lon, lat
 
 
-def lonlat_to_healpix(longitude, latitude, depth, ellipsoid="sphere", num_threads=0):
+def lonlat_to_healpix(
+    longitude,
+    latitude,
+    depth,
+    ellipsoid="sphere",
+    num_threads=0,
+    out=None,
+    on_invalid="raise",
+    latitude_kind="geographic",
+):
     r"""Get the HEALPix indexes that contains specific points.
 
     Parameters
@@ -156,18 +208,37 @@ def lonlat_to_healpix(longitude, latitude, depth, ellipsoid="sphere", num_thread
         Specifies the number of threads to use for the computation. Default to 0 means
         it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
         or the number of logical CPUs (otherwise)
+    out : `numpy.ndarray`, optional
+        `np.uint64` array with the shape and memory order of ``lon`` to write the
+        cell indexes into. A new array is allocated if not given.
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid points, i.e. non-finite longitudes or latitudes outside
+        of :math:`[-90, 90]`. ``"raise"`` raises a `ValueError`, ``"sentinel"``
+        returns the maximum `np.uint64` value for them and ``"mask"`` additionally
+        returns a mask of the invalid points.
+    latitude_kind : str, default: "geographic"
+        The kind of the given latitudes: one of ``"geographic"``, ``"geocentric"``,
+        ``"parametric"``, ``"rectifying"``, ``"conformal"`` or ``"authalic"``. All
+        kinds are equal on a sphere.
 
     Returns
     -------
     ipix : `numpy.ndarray`
         A numpy array containing all the HEALPix cell indexes stored as `np.uint64`.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid points. Only returned if
+        ``on_invalid="mask"``.
 
     Raises
     ------
     ValueError
         When the number of longitudes and latitudes given do not match.
+    ValueError
+        When the coordinates are invalid and ``on_invalid="raise"``.
     ValueError
         When the name of the ellipsoid is unknown.
+    ValueError
+        When the latitude kind is unknown.
 
     Examples
     --------
@@ -182,16 +253,405 @@ def lonlat_to_healpix(longitude, latitude, depth, ellipsoid="sphere", num_thread
           dtype=uint64)
     """
     _check_depth(depth)
-    longitude = np.atleast_1d(longitude).astype("float64")
-    latitude = np.atleast_1d(latitude).astype("float64")
+    longitude = _as_coordinates(longitude)
+    latitude = _as_coordinates(latitude)
+
+    depth = depth if isinstance(depth, int) else np.asarray(depth, "uint8")
+    num_threads = np.uint16(num_threads)
+
+    ipix, mask = healpix_geo.zuniq.lonlat_to_healpix(
+        depth,
+        longitude,
+        latitude,
+        ellipsoid,
+        num_threads,
+        out,
+        on_invalid,
+        latitude_kind,
+    )
+    if on_invalid == "mask":
+        return ipix, mask
+
+    return ipix
+
+
+def is_valid_cell(ipix, num_threads=0):
+    r"""Check which cell indexes are valid zuniq cell ids.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The zuniq cell ids given as a `np.uint64` numpy array.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    valid : `numpy.ndarray`
+        Boolean array that is `True` for the cell ids that encode a cell at a depth
+        in :math:`[0, 29]`.
+
+    Examples
+    --------
+    >>> from healpix_geo.zuniq import is_valid_cell
+    >>> import numpy as np
+    >>> ipix = np.array([4683743612465315840, 0], dtype="uint64")
+    >>> is_valid_cell(ipix)
+    array([ True, False])
+    """
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.zuniq.is_valid_cell(ipix, num_threads)
+
+
+def interpolation_weights(
+    longitude, latitude, depth, ellipsoid="sphere", num_threads=0
+):
+    r"""Get the bilinear interpolation weights of some points.
+
+    The weights are computed on the authalic sphere, after converting the geographic
+    latitudes of the points. This is the equivalent of
+    :py:func:`healpy.get_interp_weights`.
+
+    Parameters
+    ----------
+    longitude : array-like
+        The longitudes of the input points, in degrees.
+    latitude : array-like
+        The latitudes of the input points, in degrees.
+    depth : int
+        The depth of the HEALPix cells to interpolate from.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        The zuniq cell ids of the 4 cells surrounding each point, with the shape of
+        ``longitude`` and an additional axis of size 4.
+    weights : `numpy.ndarray`
+        The weights of the cells, which sum to 1 for each point. Where a cell only has
+        7 neighbours, the missing cell is replaced by the cell containing the point
+        with a weight of 0.
+
+    Raises
+    ------
+    ValueError
+        When the number of longitudes and latitudes given do not match.
+    ValueError
+        When a longitude is not finite or a latitude is outside of :math:`[-90, 90]`.
+
+    Examples
+    --------
+    >>> from healpix_geo.zuniq import interpolation_weights
+    >>> import numpy as np
+    >>> lon = np.array([0, 50], dtype="float64")
+    >>> lat = np.array([6, -12], dtype="float64")
+    >>> ipix, weights = interpolation_weights(lon, lat, depth=3, ellipsoid="WGS84")
+    >>> ipix.shape
+    (2, 4)
+    >>> weights.sum(axis=-1)
+    array([1., 1.])
+    """
+    _check_depth(depth)
+    longitude = _as_coordinates(longitude)
+    latitude = _as_coordinates(latitude)
 
     num_threads = np.uint16(num_threads)
 
-    return healpix_geo.zuniq.lonlat_to_healpix(
+    return healpix_geo.zuniq.interpolation_weights(
         depth, longitude, latitude, ellipsoid, num_threads
     )
 
 
+def interpolate(
+    values, ipix, longitude, latitude, depth, ellipsoid="sphere", num_threads=0
+):
+    r"""Bilinear interpolation of a field at some points.
+
+    Parameters
+    ----------
+    values : array-like
+        The values of the field, one for each cell of ``ipix``.
+    ipix : `numpy.ndarray`
+        The zuniq cell ids the field is defined on. They don't have to cover the
+        whole sphere: cells missing from ``ipix`` are skipped and the weights of the
+        other cells renormalized.
+    longitude : array-like
+        The longitudes of the points to interpolate at, in degrees.
+    latitude : array-like
+        The latitudes of the points to interpolate at, in degrees.
+    depth : int
+        The depth of the HEALPix cells.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    interpolated : `numpy.ndarray`
+        The interpolated values, with the shape of ``longitude``. Points for which
+        none of the surrounding cells are in ``ipix`` are NaN.
+
+    Raises
+    ------
+    ValueError
+        When the number of values and cells or of longitudes and latitudes do not
+        match.
+    ValueError
+        When a longitude is not finite or a latitude is outside of :math:`[-90, 90]`.
+
+    See Also
+    --------
+    interpolation_weights
+
+    Examples
+    --------
+    >>> import healpix_geo
+    >>> from healpix_geo.zuniq import interpolate
+    >>> import numpy as np
+    >>> depth = 3
+    >>> ipix = healpix_geo.zuniq.from_nested(np.arange(768), depth)
+    >>> values = np.full_like(ipix, 2.0, dtype="float64")
+    >>> lon = np.array([0, 50], dtype="float64")
+    >>> lat = np.array([6, -12], dtype="float64")
+    >>> interpolate(values, ipix, lon, lat, depth, ellipsoid="WGS84")
+    array([2., 2.])
+    """
+    _check_depth(depth)
+    values = np.asarray(values, dtype="float64")
+    ipix = _as_cell_ids(ipix)
+    longitude = _as_coordinates(longitude)
+    latitude = _as_coordinates(latitude)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.zuniq.interpolate(
+        depth, values, ipix, longitude, latitude, ellipsoid, num_threads
+    )
+
+
+def rasterize(
+    values,
+    ipix,
+    depth,
+    shape,
+    ellipsoid="sphere",
+    *,
+    projection="equirectangular",
+    central_longitude=0.0,
+    central_latitude=0.0,
+    fill_value=np.nan,
+    num_threads=0,
+):
+    r"""Render a HEALPix map onto an image.
+
+    Each pixel of the image is projected back to longitude and latitude and takes the
+    value of the cell containing it.
+
+    Parameters
+    ----------
+    values : array-like
+        The values of the map, one for each cell of ``ipix``.
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes the map is defined on. They don't have to cover the
+        whole sphere.
+    depth : int
+        The depth of the HEALPix cells.
+    shape : tuple of int
+        The shape of the image, as ``(height, width)``.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    projection : {"equirectangular", "mollweide", "orthographic"}, default: "equirectangular"
+        The map projection of the image. The image covers the whole extent of the
+        projection: the sphere for the equirectangular and Mollweide projections, and
+        the hemisphere centered on ``(central_longitude, central_latitude)`` for the
+        orthographic projection.
+    central_longitude : float, default: 0
+        The longitude at the center of the image, in degrees.
+    central_latitude : float, default: 0
+        The latitude at the center of the image, in degrees. Only used by the
+        orthographic projection.
+    fill_value : float, default: nan
+        The value of pixels outside of the sphere or whose cell is not in ``ipix``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    image : `numpy.ndarray`
+        The image, with north at the top and east to the right.
+
+    Raises
+    ------
+    ValueError
+        When the number of values and cells do not match.
+    ValueError
+        When ``projection`` is not one of the supported projections.
+
+    Examples
+    --------
+    >>> import healpix_geo
+    >>> from healpix_geo.zuniq import rasterize
+    >>> import numpy as np
+    >>> depth = 1
+    >>> ipix = healpix_geo.zuniq.from_nested(np.arange(48), depth)
+    >>> values = np.ones(ipix.size)
+    >>> image = rasterize(values, ipix, depth, (3, 6), projection="mollweide")
+    >>> np.isnan(image)
+    array([[ True, False, False, False, False,  True],
+           [False, False, False, False, False, False],
+           [ True, False, False, False, False,  True]])
+    """
+    _check_depth(depth)
+    values = np.asarray(values, dtype="float64")
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.zuniq.rasterize(
+        depth,
+        values,
+        ipix,
+        ellipsoid,
+        tuple(shape),
+        projection,
+        central_longitude,
+        central_latitude,
+        fill_value,
+        num_threads,
+    )
+
+
+def regridding_weights(
+    lon_bounds,
+    lat_bounds,
+    depth,
+    ellipsoid="sphere",
+    *,
+    direction="to_healpix",
+    refinement=3,
+    num_threads=0,
+):
+    r"""Get area-weighted regridding weights between a lon/lat grid and HEALPix.
+
+    The weights are the areas of the overlaps between the source cells and the
+    HEALPix cells, normalized to sum to 1 for each target cell. The overlaps are
+    computed by clipping the source cells to the HEALPix cells in the HEALPix
+    projection, which preserves areas.
+
+    Parameters
+    ----------
+    lon_bounds : array-like
+        The longitudes of the vertices of the source cells, in degrees. The last axis
+        contains the vertices of each cell, and the source cells are the other axes,
+        flattened in C order.
+    lat_bounds : array-like
+        The latitudes of the vertices of the source cells, in degrees. Must have the
+        same shape as ``lon_bounds``.
+    depth : int
+        The depth of the HEALPix cells.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    direction : {"to_healpix", "from_healpix"}, default: "to_healpix"
+        Whether the weights regrid from the source cells to the HEALPix cells or the
+        other way around.
+    refinement : int, default: 3
+        The edges of the source cells that are neither meridians nor parallels are
+        followed in steps the size of the cells ``refinement`` levels below ``depth``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        The sorted HEALPix cell indexes overlapping any source cell.
+    weights : tuple of `numpy.ndarray`
+        The ``(data, indices, indptr)`` arrays of the weights in compressed sparse row
+        format. For ``"to_healpix"``, the rows are the cells of ``ipix`` and the
+        columns the source cells, and the other way around for ``"from_healpix"``.
+
+    Raises
+    ------
+    ValueError
+        When the shapes of ``lon_bounds`` and ``lat_bounds`` do not match.
+    ValueError
+        When a cell has less than 3 vertices, or a vertex is not a valid coordinate.
+    ValueError
+        When ``direction`` is not one of the supported directions.
+
+    Notes
+    -----
+    The edges of the source cells are straight lines in longitude and latitude, as
+    for rectilinear and most curvilinear grids. Source cells must not contain a pole,
+    unless they are bounded by it.
+
+    The weights can be used with :py:class:`scipy.sparse.csr_array`:
+
+    .. code:: python
+
+        matrix = scipy.sparse.csr_array(weights, shape=(ipix.size, n_source_cells))
+        regridded = matrix @ values
+
+    Examples
+    --------
+    >>> from healpix_geo.zuniq import regridding_weights
+    >>> import numpy as np
+    >>> lon_edges = np.arange(0, 361, 90, dtype="float64")
+    >>> lat_edges = np.array([-90, 0, 90], dtype="float64")
+    >>> lon0, lat0 = np.meshgrid(lon_edges[:-1], lat_edges[:-1])
+    >>> lon1, lat1 = np.meshgrid(lon_edges[1:], lat_edges[1:])
+    >>> lon_bounds = np.stack([lon0, lon1, lon1, lon0], axis=-1)
+    >>> lat_bounds = np.stack([lat0, lat0, lat1, lat1], axis=-1)
+    >>> ipix, (data, indices, indptr) = regridding_weights(
+    ...     lon_bounds, lat_bounds, depth=1, ellipsoid="WGS84"
+    ... )
+    >>> ipix.size
+    48
+    >>> np.allclose(np.add.reduceat(data, indptr[:-1]), 1)
+    True
+    """
+    _check_depth(depth)
+    lon_bounds = _as_coordinates(lon_bounds)
+    lat_bounds = _as_coordinates(lat_bounds)
+
+    if lon_bounds.shape != lat_bounds.shape:
+        raise ValueError(
+            "The longitude and latitude bounds must have the same shape, got"
+            f" {lon_bounds.shape} and {lat_bounds.shape}"
+        )
+    n_vertices = lon_bounds.shape[-1]
+
+    num_threads = np.uint16(num_threads)
+
+    ipix, data, indices, indptr = healpix_geo.zuniq.regridding_weights(
+        depth,
+        lon_bounds.reshape(-1, n_vertices),
+        lat_bounds.reshape(-1, n_vertices),
+        ellipsoid,
+        refinement,
+        direction,
+        num_threads,
+    )
+
+    return ipix, (data, indices, indptr)
+
+
 def vertices(ipix, ellipsoid, step=1, num_threads=0):
     """Get the longitudes and latitudes of the vertices of some HEALPix cells in zuniq encoding.
 
@@ -249,61 +709,513 @@ def vertices(ipix, ellipsoid, step=1, num_threads=0):
             [4.49999999e+01, 2.13443412e-07],
             [4.49999998e+01, 1.42295608e-07]]])
     """
-    ipix = np.atleast_1d(ipix).astype(np.uint64)
+    ipix = _as_cell_ids(ipix)
 
     num_threads = np.uint16(num_threads)
 
     return healpix_geo.zuniq.vertices(ipix, ellipsoid, step, num_threads)
 
 
-def kth_neighbourhood(ipix, ring, num_threads=0):
-    """Get the kth ring neighbouring cells of some HEALPix cells.
+def projected_to_healpix(
+    x,
+    y,
+    depth,
+    crs,
+    ellipsoid="sphere",
+    *,
+    z=None,
+    num_threads=0,
+    on_invalid="raise",
+):
+    r"""Get the HEALPix indexes that contain points given in a coordinate reference system.
 
-    This method returns a :math:`N` x :math:`(2 k + 1)^2` `np.uint64` numpy array containing the neighbours of each cell of the :math:`N` sized `ipix` array.
-    This method is wrapped around the `kth_neighbourhood <https://docs.rs/cdshealpix/0.1.5/cdshealpix/nested/struct.Layer.html#method.kth_neighbourhood>`__
-    method from the `cdshealpix Rust crate <https://crates.io/crates/cdshealpix>`__.
+    The points are converted to longitudes and latitudes on the ellipsoid before
+    looking up their cells.
+
+    Parameters
+    ----------
+    x, y : array-like
+        The coordinates of the input points in the coordinate reference system.
+    depth : int or array-like of int
+        The HEALPix cell depth given as a `np.uint8` numpy array.
+    crs : crs-like
+        The coordinate reference system of the points. See :term:`crs-like`.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    z : array-like, optional
+        The third coordinate of the input points. Required for three-dimensional
+        coordinate reference systems, and the height otherwise.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle points that can't be converted to longitudes and latitudes.
+        ``"raise"`` raises a `ValueError`, ``"sentinel"`` returns the maximum
+        `np.uint64` value for them and ``"mask"`` additionally returns a mask of the
+        invalid points.
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        A numpy array containing all the HEALPix cell indexes stored as `np.uint64`.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid points. Only returned if
+        ``on_invalid="mask"``.
+
+    Raises
+    ------
+    ValueError
+        When the coordinate reference system is unknown or invalid.
+    ValueError
+        When the points are invalid and ``on_invalid="raise"``.
+    ValueError
+        When the name of the ellipsoid is unknown.
+
+    Examples
+    --------
+    >>> from healpix_geo.zuniq import projected_to_healpix
+    >>> import numpy as np
+    >>> x = np.array([0.0])
+    >>> y = np.array([-1e6])
+    >>> projected_to_healpix(x, y, 0, "EPSG:3413", ellipsoid="WGS84")
+    array([2017612633061982208], dtype=uint64)
+    """
+    _check_depth(depth)
+    x = _as_coordinates(x)
+    y = _as_coordinates(y)
+    if z is not None:
+        z = _as_coordinates(z)
+
+    depth = depth if isinstance(depth, int) else np.asarray(depth, "uint8")
+    num_threads = np.uint16(num_threads)
+
+    ipix, mask = healpix_geo.zuniq.projected_to_healpix(
+        depth, x, y, z, crs, ellipsoid, num_threads, on_invalid
+    )
+    if on_invalid == "mask":
+        return ipix, mask
+
+    return ipix
+
+
+def healpix_to_projected(
+    ipix, crs, ellipsoid="sphere", num_threads=0, on_invalid="raise"
+):
+    r"""Get the coordinates of the center of some HEALPix cells in a coordinate reference system.
 
     Parameters
     ----------
     ipix : `numpy.ndarray`
         The HEALPix cell indexes given as a `np.uint64` numpy array.
-    ring : int
-        The number of rings. `ring=0` returns just the input cell ids, `ring=1` returns the 8 (or 7) immediate
-        neighbours, `ring=2` returns the 8 (or 7) immediate neighbours plus their immediate neighbours (a total of 24 cells), and so on.
+    crs : crs-like
+        The coordinate reference system of the points. See :term:`crs-like`.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
     num_threads : int, optional
         Specifies the number of threads to use for the computation. Default to 0 means
         it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
         or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid cell indexes. ``"raise"`` raises a `ValueError`,
+        ``"sentinel"`` returns NaN coordinates for them and ``"mask"`` additionally
+        returns a mask of the invalid cells.
 
     Returns
     -------
-    neighbours : `numpy.ndarray`
-        A :math:`N` x :math:`(2 k + 1)^2` `np.int64` numpy array containing the kth ring neighbours of each cell.
-        The :math:`5^{th}` element corresponds to the index of HEALPix cell from which the neighbours are evaluated.
-        All its 8 neighbours occup the remaining elements of the line.
+    x, y : `numpy.ndarray`
+        The coordinates of the center of the HEALPix cells.
+    z : `numpy.ndarray`
+        The third coordinate of the center of the HEALPix cells. Only returned for
+        three-dimensional coordinate reference systems.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid cells and the cells that can't
+        be converted. Only returned if ``on_invalid="mask"``.
 
     Raises
     ------
     ValueError
-        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`.
+        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`
+        and ``on_invalid="raise"``.
+    ValueError
+        When the coordinate reference system is unknown or invalid.
+    ValueError
+        When the name of the ellipsoid is unknown.
 
     Examples
     --------
-    >>> from healpix_geo.zuniq import kth_neighbourhood
+    >>> import healpix_geo
+    >>> from healpix_geo.zuniq import healpix_to_projected
     >>> import numpy as np
-    >>> ipix = np.array([1460288880640, 223338299392, 360777252864], dtype="uint64")
-    >>> ring = 3
-    >>> neighbours = kth_neighbourhood(ipix, ring)
-    >>> neighbours
-    array([[      1460288880640, 2497997973430992896, 2497998042150469632,
-            2498000997087969280,       4415226380288,       4449586118656,
-                  1494648619008,       1425929142272,       1391569403904,
-            2497997732912824320, 2497997939071254528, 2497998007790731264,
-            2498000962728230912, 2498001031447707648, 2498001065807446016,
-            2497997767272562688,       4483945857024,       4518305595392,
-                  4621384810496,       4552665333760,       1597727834112,
-                  1529008357376,       1322849927168,       1219770712064,
-                  1185410973696, 2497997561114132480, 2497997629833609216,
+    >>> ipix = healpix_geo.zuniq.from_nested(np.array([4]), 0)
+    >>> x, y, z = healpix_to_projected(ipix, "EPSG:4978", ellipsoid="WGS84")
+    >>> x
+    array([6378137.])
+    """
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    x, y, z, mask = healpix_geo.zuniq.healpix_to_projected(
+        ipix, crs, ellipsoid, num_threads, on_invalid
+    )
+    coordinates = (x, y) if z is None else (x, y, z)
+    if on_invalid == "mask":
+        return *coordinates, mask
+
+    return coordinates
+
+
+def vertices_projected(ipix, crs, ellipsoid="sphere", step=1, num_threads=0):
+    """Get the coordinates of the vertices of some HEALPix cells in a coordinate reference system.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    crs : crs-like
+        The coordinate reference system of the points. See :term:`crs-like`.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    step : int, default: 1
+        The number of vertices returned per HEALPix side. The number of vertices
+        returned is ``4 * step``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    x, y : `numpy.ndarray`
+        The coordinates of the vertices, with an additional trailing dimension of
+        size ``4 * step``.
+    z : `numpy.ndarray`
+        The third coordinate of the vertices. Only returned for three-dimensional
+        coordinate reference systems.
+
+    Raises
+    ------
+    ValueError
+        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`.
+    ValueError
+        When the coordinate reference system is unknown or invalid.
+
+    Examples
+    --------
+    >>> import healpix_geo
+    >>> from healpix_geo.zuniq import vertices_projected
+    >>> import numpy as np
+    >>> ipix = healpix_geo.zuniq.from_nested(np.array([4]), 0)
+    >>> x, y = vertices_projected(ipix, 32631, ellipsoid="WGS84")
+    >>> x.shape
+    (1, 4)
+    """
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    x, y, z = healpix_geo.zuniq.vertices_projected(
+        ipix, crs, ellipsoid, step, num_threads
+    )
+    if z is None:
+        return x, y
+
+    return x, y, z
+
+
+def xyz_to_healpix(
+    x, y, z, depth, ellipsoid="sphere", num_threads=0, on_invalid="raise"
+):
+    r"""Get the HEALPix indexes that contain points given in body-centered cartesian coordinates.
+
+    The points are projected onto the ellipsoid along its normal, so their height
+    above the ellipsoid does not change the cell they belong to.
+
+    Parameters
+    ----------
+    x, y, z : array-like
+        The body-centered cartesian coordinates of the input points, in the unit of
+        the ellipsoid axes (usually meters).
+    depth : int or array-like of int
+        The HEALPix cell depth given as a `np.uint8` numpy array.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid points, like the center of the ellipsoid.
+        ``"raise"`` raises a `ValueError`, ``"sentinel"`` returns the maximum
+        `np.uint64` value for them and ``"mask"`` additionally returns a mask of the
+        invalid points.
+
+    Returns
+    -------
+    ipix : `numpy.ndarray`
+        A numpy array containing all the HEALPix cell indexes stored as `np.uint64`.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid points. Only returned if
+        ``on_invalid="mask"``.
+
+    Raises
+    ------
+    ValueError
+        When the points are invalid and ``on_invalid="raise"``.
+    ValueError
+        When the name of the ellipsoid is unknown.
+
+    Examples
+    --------
+    >>> from healpix_geo.zuniq import xyz_to_healpix
+    >>> import numpy as np
+    >>> x = np.array([0.0])
+    >>> y = np.array([7e6])
+    >>> z = np.array([0.0])
+    >>> xyz_to_healpix(x, y, z, 0, ellipsoid="WGS84")
+    array([3170534137668829184], dtype=uint64)
+    """
+    _check_depth(depth)
+    x = _as_coordinates(x)
+    y = _as_coordinates(y)
+    z = _as_coordinates(z)
+
+    depth = depth if isinstance(depth, int) else np.asarray(depth, "uint8")
+    num_threads = np.uint16(num_threads)
+
+    ipix, mask = healpix_geo.zuniq.xyz_to_healpix(
+        depth, x, y, z, ellipsoid, num_threads, on_invalid
+    )
+    if on_invalid == "mask":
+        return ipix, mask
+
+    return ipix
+
+
+def healpix_to_xyz(
+    ipix, ellipsoid="sphere", height=0.0, num_threads=0, on_invalid="raise"
+):
+    r"""Get the body-centered cartesian coordinates of the center of some HEALPix cells.
+
+    The coordinates are on the surface of the ellipsoid, or at ``height`` above it,
+    and not on the unit sphere.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    height : float, default: 0.0
+        The height above the ellipsoid, in the unit of the ellipsoid axes (usually
+        meters).
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+    on_invalid : {"raise", "sentinel", "mask"}, default: "raise"
+        How to handle invalid cell indexes. ``"raise"`` raises a `ValueError`,
+        ``"sentinel"`` returns NaN coordinates for them and ``"mask"`` additionally
+        returns a mask of the invalid cells.
+
+    Returns
+    -------
+    x, y, z : `numpy.ndarray`
+        The body-centered cartesian coordinates of the center of the HEALPix cells,
+        in the unit of the ellipsoid axes.
+    mask : `numpy.ndarray`
+        Boolean array that is `True` for the invalid cells. Only returned if
+        ``on_invalid="mask"``.
+
+    Raises
+    ------
+    ValueError
+        When the name of the ellipsoid is unknown.
+
+    Examples
+    --------
+    >>> import healpix_geo
+    >>> from healpix_geo.zuniq import healpix_to_xyz
+    >>> import numpy as np
+    >>> ipix = healpix_geo.zuniq.from_nested(np.array([4]), 0)
+    >>> x, y, z = healpix_to_xyz(ipix, ellipsoid="WGS84")
+    >>> x
+    array([6378137.])
+    """
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    x, y, z, mask = healpix_geo.zuniq.healpix_to_xyz(
+        ipix, ellipsoid, height, num_threads, on_invalid
+    )
+    if on_invalid == "mask":
+        return x, y, z, mask
+
+    return x, y, z
+
+
+def vertices_xyz(ipix, ellipsoid="sphere", height=0.0, step=1, num_threads=0):
+    """Get the body-centered cartesian coordinates of the vertices of some HEALPix cells.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    height : float, default: 0.0
+        The height above the ellipsoid, in the unit of the ellipsoid axes (usually
+        meters).
+    step : int, default: 1
+        The number of vertices returned per HEALPix side. The number of vertices
+        returned is ``4 * step``.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    x, y, z : `numpy.ndarray`
+        The body-centered cartesian coordinates of the vertices, with an additional
+        trailing dimension of size ``4 * step``.
+
+    Raises
+    ------
+    ValueError
+        When the name of the ellipsoid is unknown.
+
+    Examples
+    --------
+    >>> import healpix_geo
+    >>> from healpix_geo.zuniq import vertices_xyz
+    >>> import numpy as np
+    >>> ipix = healpix_geo.zuniq.from_nested(np.array([4]), 0)
+    >>> x, y, z = vertices_xyz(ipix, ellipsoid="WGS84")
+    >>> x.shape
+    (1, 4)
+    """
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.zuniq.vertices_xyz(ipix, ellipsoid, height, step, num_threads)
+
+
+def cell_area(ipix, ellipsoid="sphere", num_threads=0):
+    """Compute the area of HEALPix cells on the reference ellipsoid.
+
+    HEALPix cells have equal areas on the authalic sphere, so the area only depends
+    on the depth of the cell and on the authalic radius of the ellipsoid.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The zuniq cell ids given as a `np.uint64` numpy array.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    area : numpy.ndarray
+        The area of each cell, in squared units of the semimajor axis of the
+        ellipsoid (m² for named ellipsoids like ``"WGS84"``).
+    """
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.zuniq.cell_area(ipix, ellipsoid, num_threads)
+
+
+def cell_perimeter(ipix, ellipsoid="sphere", step=1, num_threads=0):
+    """Compute the perimeter of HEALPix cells on the reference ellipsoid.
+
+    The perimeter is the length of the closed geodesic polygon through the
+    vertices returned by :py:func:`vertices`. Since the cell edges are not
+    geodesics, increasing ``step`` makes the result converge towards the length
+    of the actual cell boundary.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The zuniq cell ids given as a `np.uint64` numpy array.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    step : int, default: 1
+        The number of vertices per cell edge.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    perimeter : numpy.ndarray
+        The perimeter of each cell, in units of the semimajor axis of the
+        ellipsoid (m for named ellipsoids like ``"WGS84"``).
+    """
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.zuniq.cell_perimeter(ipix, ellipsoid, step, num_threads)
+
+
+def kth_neighbourhood(ipix, ring, num_threads=0):
+    """Get the kth ring neighbouring cells of some HEALPix cells.
+
+    This method returns a :math:`N` x :math:`(2 k + 1)^2` `np.uint64` numpy array containing the neighbours of each cell of the :math:`N` sized `ipix` array.
+    This method is wrapped around the `kth_neighbourhood <https://docs.rs/cdshealpix/0.1.5/cdshealpix/nested/struct.Layer.html#method.kth_neighbourhood>`__
+    method from the `cdshealpix Rust crate <https://crates.io/crates/cdshealpix>`__.
+
+    Parameters
+    ----------
+    ipix : `numpy.ndarray`
+        The HEALPix cell indexes given as a `np.uint64` numpy array.
+    ring : int
+        The number of rings. `ring=0` returns just the input cell ids, `ring=1` returns the 8 (or 7) immediate
+        neighbours, `ring=2` returns the 8 (or 7) immediate neighbours plus their immediate neighbours (a total of 24 cells), and so on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    neighbours : `numpy.ndarray`
+        A :math:`N` x :math:`(2 k + 1)^2` `np.int64` numpy array containing the kth ring neighbours of each cell.
+        The :math:`5^{th}` element corresponds to the index of HEALPix cell from which the neighbours are evaluated.
+        All its 8 neighbours occup the remaining elements of the line.
+
+    Raises
+    ------
+    ValueError
+        When the HEALPix cell indexes given have values out of :math:`[0, 4^{29 - depth}[`.
+
+    Examples
+    --------
+    >>> from healpix_geo.zuniq import kth_neighbourhood
+    >>> import numpy as np
+    >>> ipix = np.array([1460288880640, 223338299392, 360777252864], dtype="uint64")
+    >>> ring = 3
+    >>> neighbours = kth_neighbourhood(ipix, ring)
+    >>> neighbours
+    array([[      1460288880640, 2497997973430992896, 2497998042150469632,
+            2498000997087969280,       4415226380288,       4449586118656,
+                  1494648619008,       1425929142272,       1391569403904,
+            2497997732912824320, 2497997939071254528, 2497998007790731264,
+            2498000962728230912, 2498001031447707648, 2498001065807446016,
+            2497997767272562688,       4483945857024,       4518305595392,
+                  4621384810496,       4552665333760,       1597727834112,
+                  1529008357376,       1322849927168,       1219770712064,
+                  1185410973696, 2497997561114132480, 2497997629833609216,
             2497997835992039424, 2497997904711516160, 2498000859649015808,
             2498000928368492544, 2498001134526922752, 2498001237606137856,
             2498001271965876224, 2497997698553085952, 2497997664193347584,
@@ -347,12 +1259,365 @@ def kth_neighbourhood(ipix, ring, num_threads=0):
                    188978561024,        154618822656,         51539607552,
                     17179869184]])
     """
-    ipix = np.astype(np.atleast_1d(ipix), np.uint64)
+    ipix = _as_cell_ids(ipix)
 
     num_threads = np.uint16(num_threads)
     return healpix_geo.zuniq.kth_neighbourhood(ipix, ring, num_threads)
 
 
+def neighbours(ipix, num_threads=0):
+    r"""Find the 8 neighbours of every cell, labelled by direction
+
+    Parameters
+    ----------
+    ipix : numpy.ndarray
+        The zuniq cell ids given as a `np.uint64` numpy array.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    neighbours : numpy.ndarray
+        A `np.int64` numpy array with the shape of ``ipix`` and an additional axis of
+        size 8, containing the neighbours in the N, NE, E, SE, S, SW, W and NW
+        directions, in that order. Missing neighbours are set to -1.
+
+    Notes
+    -----
+    The directions are relative to the base cell containing each cell: the N, E, S
+    and W neighbours only share a vertex with the cell, while the NE, SE, SW and NW
+    neighbours share an edge. Around the 8 vertices of base cells touching only 3 base
+    cells, one of the neighbours is missing.
+
+    Examples
+    --------
+    >>> from healpix_geo.zuniq import from_nested, neighbours
+    >>> import numpy as np
+    >>> ipix = from_nested(np.arange(4), 1)
+    >>> neighbours(ipix).shape
+    (4, 8)
+    >>> int((neighbours(ipix) == -1).sum())
+    2
+    """
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.zuniq.neighbours(ipix, num_threads)
+
+
+def neighbours_within_distance(ipix, radius, ellipsoid="sphere", num_threads=0):
+    r"""Find the cells whose center is within a distance of the center of every cell
+
+    Parameters
+    ----------
+    ipix : numpy.ndarray
+        The zuniq cell ids given as a `np.uint64` numpy array.
+    radius : float
+        The maximum geodesic distance between cell centers, in the unit of the
+        ellipsoid axes (usually meters).
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    neighbours : numpy.ndarray
+        The neighbours of all cells, flattened, as a `np.uint64` numpy array. The
+        neighbours of each cell include the cell itself and are sorted by distance.
+    distances : numpy.ndarray
+        The geodesic distances between the center of each cell and the centers of its
+        neighbours, in the unit of the ellipsoid axes.
+    offsets : numpy.ndarray
+        A `np.int64` numpy array of size ``ipix.size + 1``. The neighbours of the
+        ``i``-th cell of the flattened ``ipix`` are
+        ``neighbours[offsets[i]:offsets[i + 1]]``.
+
+    Raises
+    ------
+    ValueError
+        When ``radius`` is not a positive number.
+
+    Notes
+    -----
+    Unlike :py:func:`kth_neighbourhood`, the neighbourhoods cover the same physical
+    extent everywhere on the ellipsoid, which makes them suitable for kernel-based
+    smoothing.
+
+    Examples
+    --------
+    >>> from healpix_geo.zuniq import from_nested, neighbours_within_distance
+    >>> import numpy as np
+    >>> ipix = from_nested(np.array([0, 100]), 3)
+    >>> neighbours, distances, offsets = neighbours_within_distance(
+    ...     ipix, radius=1_000_000, ellipsoid="WGS84"
+    ... )
+    >>> offsets.size
+    3
+    >>> bool(neighbours[offsets[1]] == ipix[1]), float(distances[offsets[1]])
+    (True, 0.0)
+    >>> bool((distances <= 1_000_000).all())
+    True
+    """
+    ipix = _as_cell_ids(np.ravel(ipix))
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.zuniq.neighbours_within_distance(
+        ipix, radius, ellipsoid, num_threads
+    )
+
+
+def zoom_to(ipix, new_depth, num_threads=0):
+    r"""Change the resolutions the given cell ids
+
+    Since the depth is encoded in the cell ids, cells may have different depths. Cells
+    coarser than ``new_depth`` are replaced by their children, all others by their parent.
+
+    Parameters
+    ----------
+    ipix : numpy.ndarray
+        The zuniq cell ids given as a `np.uint64` numpy array.
+    new_depth : int
+        The new depth of the HEALPix cells.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    cells : numpy.ndarray
+        A :math:`N` (all cells at least as fine as `new_depth`) or :math:`N` x :math:`4^{\delta d}`
+        `np.uint64` numpy array containing the parents or children of the given cells.
+
+    Raises
+    ------
+    ValueError
+        When some cells need to be refined and the cells do not all have the same depth.
+    """
+    _check_depth(new_depth)
+
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+    return healpix_geo.zuniq.zoom_to(ipix, new_depth, num_threads)
+
+
+def siblings(ipix, num_threads=0):
+    r"""Find the siblings for every cell
+
+    Parameters
+    ----------
+    ipix : numpy.ndarray
+        The zuniq cell ids given as a `np.uint64` numpy array.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    cells : numpy.ndarray
+        A :math:`N` x :math:`4` or :math:`N` x :math:`12` `np.uint64` numpy array containing the siblings of the given cells.
+        The siblings of base cells are the base cells.
+
+    Raises
+    ------
+    ValueError
+        When base cells are mixed with cells of other depths.
+    """
+    ipix = _as_cell_ids(ipix)
+
+    num_threads = np.uint16(num_threads)
+    return healpix_geo.zuniq.siblings(ipix, num_threads)
+
+
+def aggregate_to_parent(ipix, values, new_depth, op="mean", num_threads=0):
+    r"""Aggregate the values of cells into their parents.
+
+    Since the depth is encoded in the cell ids, cells may have different depths.
+
+    Parameters
+    ----------
+    ipix : numpy.ndarray
+        The zuniq cell ids given as a `np.uint64` numpy array.
+    values : array-like
+        The values of the cells, one for each cell of ``ipix``.
+    new_depth : int
+        The depth of the parents. Must not be finer than the depth of any cell.
+    op : {"sum", "mean", "min", "max", "count", "area_weighted_mean"}, default: "mean"
+        The reduction applied to the values of the cells sharing a parent.
+        ``"area_weighted_mean"`` weights the values by the area of the cells, which
+        differs from ``"mean"`` for cells of different depths.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    parents : numpy.ndarray
+        The sorted parents of the cells, as a `np.uint64` numpy array.
+    reduced : numpy.ndarray
+        The reduced values of each parent.
+
+    Raises
+    ------
+    ValueError
+        When the number of values and cells do not match.
+    ValueError
+        When ``new_depth`` is finer than the depth of a cell or ``op`` is not a
+        supported reduction.
+
+    See Also
+    --------
+    broadcast_to_children
+
+    Examples
+    --------
+    >>> from healpix_geo.zuniq import aggregate_to_parent, from_nested
+    >>> import numpy as np
+    >>> ipix = np.concatenate(
+    ...     [from_nested(np.arange(1, 4), 1), from_nested(np.arange(4), 2)]
+    ... )
+    >>> values = np.array([1, 1, 1, 3, 3, 3, 3], dtype="float64")
+    >>> parents, means = aggregate_to_parent(
+    ...     ipix, values, new_depth=0, op="area_weighted_mean"
+    ... )
+    >>> means
+    array([1.5])
+    """
+    _check_depth(new_depth)
+    ipix = _as_cell_ids(np.ravel(ipix))
+    values = np.ravel(np.asarray(values, dtype="float64"))
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.zuniq.aggregate_to_parent(
+        ipix, values, new_depth, op, num_threads
+    )
+
+
+def broadcast_to_children(ipix, values, new_depth, num_threads=0):
+    r"""Broadcast the values of cells to their children.
+
+    Since the depth is encoded in the cell ids, cells may have different depths.
+
+    Parameters
+    ----------
+    ipix : numpy.ndarray
+        The zuniq cell ids given as a `np.uint64` numpy array.
+    values : array-like
+        The values of the cells, one for each cell of ``ipix``.
+    new_depth : int
+        The depth of the children. Must not be coarser than the depth of any cell.
+    num_threads : int, optional
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    children : numpy.ndarray
+        The children of the cells, as a `np.uint64` numpy array.
+    broadcasted : numpy.ndarray
+        The value of the parent of each child.
+
+    Raises
+    ------
+    ValueError
+        When the number of values and cells do not match.
+    ValueError
+        When ``new_depth`` is coarser than the depth of a cell.
+
+    See Also
+    --------
+    aggregate_to_parent
+
+    Examples
+    --------
+    >>> from healpix_geo.zuniq import broadcast_to_children, from_nested
+    >>> import numpy as np
+    >>> ipix = np.concatenate(
+    ...     [from_nested(np.array([0]), 0), from_nested(np.array([4]), 1)]
+    ... )
+    >>> children, broadcasted = broadcast_to_children(
+    ...     ipix, np.array([1.0, 2.0]), new_depth=1
+    ... )
+    >>> broadcasted
+    array([1., 1., 1., 1., 2.])
+    """
+    _check_depth(new_depth)
+    ipix = _as_cell_ids(np.ravel(ipix))
+    values = np.ravel(np.asarray(values, dtype="float64"))
+
+    num_threads = np.uint16(num_threads)
+
+    return healpix_geo.zuniq.broadcast_to_children(ipix, values, new_depth, num_threads)
+
+
+def angular_distances(from_, to_, num_threads=0):
+    """Compute the angular distances between cell centers
+
+    The cells may have different depths: the distances are computed from the center
+    of each cell at its own depth.
+
+    Parameters
+    ----------
+    from_ : numpy.ndarray
+        The source zuniq cell ids given as a ``np.uint64`` numpy array. Should be 1D.
+    to_ : numpy.ndarray
+        The destination zuniq cell ids given as a ``np.uint64`` numpy array.
+        Should be 2D. Entries set to ``-1`` are ignored.
+    num_threads : int, default: 0
+        Specifies the number of threads to use for the computation. Default to 0 means
+        it will choose the number of threads based on the RAYON_NUM_THREADS environment variable (if set),
+        or the number of logical CPUs (otherwise)
+
+    Returns
+    -------
+    distances : numpy.ndarray
+        The angular distances in radians.
+
+    Raises
+    ------
+    ValueError
+        When the zuniq cell ids given are invalid.
+    """
+    from_ = _as_cell_ids(from_)
+
+    # replace missing values by the id of the first base cell
+    mask = to_ != -1
+    masked_to = np.where(mask, to_, 2**58)
+
+    to_ = _as_cell_ids(masked_to)
+
+    if from_.shape != to_.shape and from_.shape != to_.shape[:-1]:
+        raise ValueError(
+            "The shape of `from_` must be compatible with the shape of `to_`:\n"
+            f"{to_.shape} or {to_.shape[:-1]} must be equal to {from_.shape}."
+        )
+
+    if from_.shape == to_.shape:
+        intermediate_shape = to_.shape + (1,)
+    else:
+        intermediate_shape = to_.shape
+
+    num_threads = np.uint16(num_threads)
+
+    distances = healpix_geo.zuniq.angular_distances(
+        from_, np.reshape(to_, intermediate_shape), num_threads
+    )
+
+    return np.where(mask, np.reshape(distances, to_.shape), np.nan)
+
+
 def zone_coverage(bbox, depth, *, ellipsoid="sphere", flat=True):
     """Search the cells covering the given bounding box
 
@@ -453,6 +1718,33 @@ def polygon_coverage(vertices, depth, *, ellipsoid="sphere", flat=True):
     )
 
 
+def linestring_coverage(vertices, depth, *, ellipsoid="sphere", rhumb=False):
+    """Search the cells crossed by the given line.
+
+    Parameters
+    ----------
+    vertices : numpy.ndarray
+        The vertices of the line. Must be an array of shape ``(n, 2)`` with ``n >= 2``.
+    depth : int
+        The depth of the cells to be returned.
+    ellipsoid : ellipsoid-like, default: "sphere"
+        Reference ellipsoid to evaluate healpix on.
+    rhumb : bool, default: False
+        If ``True``, connect the vertices with rhumb lines (lines of constant
+        bearing) instead of great circles.
+
+    Returns
+    -------
+    cell_ids : numpy.ndarray
+        The sorted ids of all cells crossed by the line.
+    """
+    _check_depth(depth)
+
+    return healpix_geo.zuniq.linestring_coverage(
+        depth, vertices, ellipsoid=ellipsoid, rhumb=rhumb
+    )
+
+
 def cone_coverage(
     center, radius, depth, *, delta_depth=0, ellipsoid="sphere", flat=True
 ):
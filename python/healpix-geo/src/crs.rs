@@ -0,0 +1,137 @@
+use healpix_geo_core::Error;
+use healpix_geo_core::crs::Crs;
+use numpy::PyArrayDyn;
+use pyo3::prelude::*;
+
+use crate::error::into_pyerr;
+use crate::indexing_schemes::arrays::{Coordinates, MemoryOrder};
+use crate::indexing_schemes::buffers::to_array;
+
+#[derive(FromPyObject)]
+pub(crate) enum CrsLike {
+    Epsg(u32),
+    Named(String),
+    OperatorParameters {
+        #[pyo3(item("operator"))]
+        operator: String,
+        #[pyo3(item("dimension"))]
+        dimension: usize,
+    },
+}
+
+impl CrsLike {
+    pub fn into_crs(self) -> PyResult<Crs> {
+        match self {
+            Self::Epsg(code) => Crs::epsg(code),
+            Self::Named(name) => match name.split_once(':') {
+                Some((authority, code)) if authority.eq_ignore_ascii_case("epsg") => code
+                    .parse()
+                    .map_err(|_| Error::InvalidCrs(format!("invalid EPSG code {code:?}")))
+                    .and_then(Crs::epsg),
+                _ => Crs::operator(&name, 2),
+            },
+            Self::OperatorParameters {
+                operator,
+                dimension,
+            } => Crs::operator(&operator, dimension),
+        }
+        .map_err(into_pyerr)
+    }
+}
+
+/// The memory order shared by the coordinates of a system.
+pub(crate) fn common_order(
+    x: &Coordinates<'_>,
+    y: &Coordinates<'_>,
+    z: Option<&Coordinates<'_>>,
+) -> MemoryOrder {
+    let orders: Vec<MemoryOrder> = [x, y].into_iter().chain(z).map(|c| c.order()).collect();
+
+    MemoryOrder::common(&orders)
+}
+
+/// Wrap cartesian coordinates into arrays.
+#[allow(clippy::type_complexity)]
+pub(crate) fn cartesian_arrays<'py>(
+    py: Python<'py>,
+    (x, y, z): (Vec<f64>, Vec<f64>, Vec<f64>),
+    shape: &[usize],
+    order: MemoryOrder,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+)> {
+    Ok((
+        to_array(py, x, shape, order)?,
+        to_array(py, y, shape, order)?,
+        to_array(py, z, shape, order)?,
+    ))
+}
+
+/// Wrap projected coordinates into arrays, leaving out `z` for two-dimensional
+/// systems.
+#[allow(clippy::type_complexity)]
+pub(crate) fn projected_arrays<'py>(
+    py: Python<'py>,
+    coordinates: (Vec<f64>, Vec<f64>, Vec<f64>),
+    crs: &Crs,
+    shape: &[usize],
+    order: MemoryOrder,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+    Option<Bound<'py, PyArrayDyn<f64>>>,
+)> {
+    let (x, y, z) = cartesian_arrays(py, coordinates, shape, order)?;
+
+    Ok((x, y, (crs.dimension() == 3).then_some(z)))
+}
+
+/// Split cartesian vertices into arrays with a trailing dimension for the vertices.
+///
+/// Each cell has `4 * step` vertices, which also gives the shape of empty inputs.
+#[allow(clippy::type_complexity)]
+pub(crate) fn vertices_cartesian_arrays<'py>(
+    py: Python<'py>,
+    vertices: Vec<Vec<(f64, f64, f64)>>,
+    shape: &[usize],
+    step: usize,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+)> {
+    let output_shape: Vec<usize> = shape.iter().copied().chain([4 * step]).collect();
+    let size: usize = output_shape.iter().product();
+
+    let mut x = Vec::with_capacity(size);
+    let mut y = Vec::with_capacity(size);
+    let mut z = Vec::with_capacity(size);
+    for vertex in vertices.into_iter().flatten() {
+        x.push(vertex.0);
+        y.push(vertex.1);
+        z.push(vertex.2);
+    }
+
+    cartesian_arrays(py, (x, y, z), &output_shape, MemoryOrder::C)
+}
+
+/// Split projected vertices into arrays with a trailing dimension for the vertices,
+/// leaving out `z` for two-dimensional systems.
+#[allow(clippy::type_complexity)]
+pub(crate) fn vertices_arrays<'py>(
+    py: Python<'py>,
+    vertices: Vec<Vec<(f64, f64, f64)>>,
+    crs: &Crs,
+    shape: &[usize],
+    step: usize,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+    Option<Bound<'py, PyArrayDyn<f64>>>,
+)> {
+    let (x, y, z) = vertices_cartesian_arrays(py, vertices, shape, step)?;
+
+    Ok((x, y, (crs.dimension() == 3).then_some(z)))
+}
@@ -1,10 +1,19 @@
-use geodesy::ellps::Ellipsoid as GeoEllipsoid;
-use healpix_geo_core::ellipsoid::{Ellipsoid, ReferenceEllipsoid, ReferenceSphere};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use healpix_geo_core::ellipsoid::{Ellipsoid, LatitudeKind, ReferenceBody};
+use numpy::PyArrayDyn;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::error::into_pyerr;
+use crate::indexing_schemes::arrays::Coordinates;
+use crate::indexing_schemes::buffers::to_array;
 
 #[derive(FromPyObject)]
 pub(crate) enum EllipsoidLike {
+    Instance(Py<PyEllipsoid>),
     Named(String),
     EllipsoidParameters {
         #[pyo3(item("semimajor_axis"))]
@@ -12,6 +21,12 @@ pub(crate) enum EllipsoidLike {
         #[pyo3(item("inverse_flattening"))]
         inverse_flattening: f64,
     },
+    FlatteningParameters {
+        #[pyo3(item("semimajor_axis"))]
+        semimajor_axis: f64,
+        #[pyo3(item("flattening"))]
+        flattening: f64,
+    },
     SphereParameters {
         #[pyo3(item("radius"))]
         radius: f64,
@@ -31,16 +46,8 @@ pub(crate) enum EllipsoidLike {
 impl EllipsoidLike {
     pub fn into_ellipsoid(self) -> PyResult<Ellipsoid> {
         match self {
-            Self::Named(name) => {
-                let ellipsoid =
-                    GeoEllipsoid::named(&name).map_err(|e| PyValueError::new_err(e.to_string()))?;
-
-                if name.contains("sphere") {
-                    Ok(Ellipsoid::Sphere(ReferenceSphere::new(ellipsoid)))
-                } else {
-                    Ok(Ellipsoid::Ellipsoid(ReferenceEllipsoid::new(ellipsoid)))
-                }
-            }
+            Self::Instance(ellipsoid) => Ok(ellipsoid.get().ellipsoid.clone()),
+            Self::Named(name) => Ellipsoid::named(&name),
             Self::EllipsoidParameters {
                 semimajor_axis,
                 inverse_flattening,
@@ -48,34 +55,262 @@ impl EllipsoidLike {
             | Self::EllipsoidObject {
                 semimajor_axis,
                 inverse_flattening,
-            } => {
-                if inverse_flattening >= 2.0 && semimajor_axis > 0.0 {
-                    let ellipsoid = GeoEllipsoid::new(semimajor_axis, 1.0f64 / inverse_flattening);
-
-                    Ok(Ellipsoid::Ellipsoid(ReferenceEllipsoid::new(ellipsoid)))
-                } else if inverse_flattening < 2.0 {
-                    Err(PyValueError::new_err(format!(
-                        "The inverse flattening must be greater than or equal to 2, but got {:?}.",
-                        inverse_flattening,
-                    )))
-                } else {
-                    Err(PyValueError::new_err(format!(
-                        "The semimajor axis must be greater than 0, but got {:?}.",
-                        semimajor_axis
-                    )))
-                }
-            }
+            } => Ellipsoid::from_parameters(semimajor_axis, inverse_flattening),
+            Self::FlatteningParameters {
+                semimajor_axis,
+                flattening,
+            } => Ellipsoid::from_semimajor_axis_and_flattening(semimajor_axis, flattening, false),
             Self::SphereParameters { radius } | EllipsoidLike::SphereObject { radius } => {
-                if radius > 0.0 {
-                    let ellipsoid = GeoEllipsoid::new(radius, 0.0f64);
-                    Ok(Ellipsoid::Sphere(ReferenceSphere::new(ellipsoid)))
-                } else {
-                    Err(PyValueError::new_err(format!(
-                        "The radius must be greater than 0, but got {:?}.",
-                        radius
-                    )))
-                }
+                Ellipsoid::sphere(radius)
             }
         }
+        .map_err(into_pyerr)
+    }
+}
+
+pub(crate) fn latitude_kind_from_name(name: &str) -> PyResult<LatitudeKind> {
+    match name {
+        "geographic" => Ok(LatitudeKind::Geographic),
+        "geocentric" => Ok(LatitudeKind::Geocentric),
+        "parametric" => Ok(LatitudeKind::Parametric),
+        "rectifying" => Ok(LatitudeKind::Rectifying),
+        "conformal" => Ok(LatitudeKind::Conformal),
+        "authalic" => Ok(LatitudeKind::Authalic),
+        _ => Err(PyValueError::new_err(format!(
+            "Latitude kind must be one of 'geographic', 'geocentric', 'parametric', 'rectifying', 'conformal' or 'authalic' (got {name:?})"
+        ))),
+    }
+}
+
+/// The parameters of the ellipsoid, in a form accepted as ellipsoid-like.
+///
+/// Ellipsoids are described by their flattening rather than its inverse, so that
+/// the parameters round-trip exactly.
+fn exact_parameters<'py>(py: Python<'py>, ellipsoid: &Ellipsoid) -> PyResult<Bound<'py, PyDict>> {
+    let params = PyDict::new(py);
+    if ellipsoid.is_sphere() {
+        params.set_item("radius", ellipsoid.semimajor_axis())?;
+    } else {
+        params.set_item("semimajor_axis", ellipsoid.semimajor_axis())?;
+        params.set_item("flattening", ellipsoid.flattening())?;
+    }
+
+    Ok(params)
+}
+
+/// Reference ellipsoid
+///
+/// Parameters
+/// ----------
+/// ellipsoid : ellipsoid-like
+///     The name of a known ellipsoid, a dict of parameters with either a ``radius``
+///     key for spheres or ``semimajor_axis`` and one of ``inverse_flattening`` or
+///     ``flattening``, or an object with the same attributes.
+///
+/// Notes
+/// -----
+/// The coefficients of the latitude conversions are computed when the ellipsoid is
+/// created, so passing it instead of its name or parameters avoids computing them
+/// on every call.
+#[derive(PartialEq)]
+#[pyclass(name = "Ellipsoid", module = "healpix_geo", frozen)]
+pub(crate) struct PyEllipsoid {
+    ellipsoid: Ellipsoid,
+}
+
+#[pymethods]
+impl PyEllipsoid {
+    #[new]
+    fn new(ellipsoid: EllipsoidLike) -> PyResult<Self> {
+        Ok(Self {
+            ellipsoid: ellipsoid.into_ellipsoid()?,
+        })
+    }
+
+    /// The semimajor axis.
+    #[getter]
+    fn semimajor_axis(&self) -> f64 {
+        self.ellipsoid.semimajor_axis()
+    }
+
+    /// The semiminor axis.
+    #[getter]
+    fn semiminor_axis(&self) -> f64 {
+        self.ellipsoid.semiminor_axis()
+    }
+
+    /// The flattening.
+    #[getter]
+    fn flattening(&self) -> f64 {
+        self.ellipsoid.flattening()
+    }
+
+    /// The inverse flattening, infinite for spheres.
+    #[getter]
+    fn inverse_flattening(&self) -> f64 {
+        1.0 / self.ellipsoid.flattening()
+    }
+
+    /// The first eccentricity.
+    #[getter]
+    fn eccentricity(&self) -> f64 {
+        self.ellipsoid.eccentricity()
+    }
+
+    /// The radius of the sphere with the same surface area as the ellipsoid.
+    #[getter]
+    fn authalic_radius(&self) -> f64 {
+        self.ellipsoid.authalic_radius()
+    }
+
+    /// Whether the ellipsoid is a sphere.
+    #[getter]
+    fn is_sphere(&self) -> bool {
+        self.ellipsoid.is_sphere()
+    }
+
+    /// Convert geographic latitudes to authalic latitudes
+    ///
+    /// Parameters
+    /// ----------
+    /// latitude : numpy.ndarray
+    ///     The geographic latitudes, in degrees.
+    /// num_threads : int, default: 0
+    ///     The number of threads to use for the computation. Default to 0 means it
+    ///     will choose the number of threads based on the RAYON_NUM_THREADS
+    ///     environment variable (if set), or the number of logical CPUs (otherwise).
+    ///
+    /// Returns
+    /// -------
+    /// authalic : numpy.ndarray
+    ///     The authalic latitudes, in degrees.
+    #[pyo3(signature = (latitude, num_threads=0))]
+    fn geographic_to_authalic<'py>(
+        &self,
+        py: Python<'py>,
+        latitude: Coordinates<'py>,
+        num_threads: u16,
+    ) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+        use healpix_geo_core::vectorized::latitudes as vectorized;
+
+        let order = latitude.order();
+        let latitude_ = latitude.values(order);
+
+        let result =
+            vectorized::geographic_to_authalic(&latitude_, &self.ellipsoid, num_threads as usize);
+
+        to_array(py, result, latitude.shape(), order)
+    }
+
+    /// Convert authalic latitudes to geographic latitudes
+    ///
+    /// Parameters
+    /// ----------
+    /// latitude : numpy.ndarray
+    ///     The authalic latitudes, in degrees.
+    /// num_threads : int, default: 0
+    ///     The number of threads to use for the computation. Default to 0 means it
+    ///     will choose the number of threads based on the RAYON_NUM_THREADS
+    ///     environment variable (if set), or the number of logical CPUs (otherwise).
+    ///
+    /// Returns
+    /// -------
+    /// geographic : numpy.ndarray
+    ///     The geographic latitudes, in degrees.
+    #[pyo3(signature = (latitude, num_threads=0))]
+    fn authalic_to_geographic<'py>(
+        &self,
+        py: Python<'py>,
+        latitude: Coordinates<'py>,
+        num_threads: u16,
+    ) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+        use healpix_geo_core::vectorized::latitudes as vectorized;
+
+        let order = latitude.order();
+        let latitude_ = latitude.values(order);
+
+        let result =
+            vectorized::authalic_to_geographic(&latitude_, &self.ellipsoid, num_threads as usize);
+
+        to_array(py, result, latitude.shape(), order)
+    }
+
+    /// Convert latitudes between two kinds
+    ///
+    /// Parameters
+    /// ----------
+    /// latitude : numpy.ndarray
+    ///     The latitudes to convert, in degrees.
+    /// source, target : {"geographic", "geocentric", "parametric", "rectifying", "conformal", "authalic"}
+    ///     The kind of the latitudes and the kind to convert them to.
+    /// num_threads : int, default: 0
+    ///     The number of threads to use for the computation. Default to 0 means it
+    ///     will choose the number of threads based on the RAYON_NUM_THREADS
+    ///     environment variable (if set), or the number of logical CPUs (otherwise).
+    ///
+    /// Returns
+    /// -------
+    /// converted : numpy.ndarray
+    ///     The converted latitudes, in degrees.
+    #[pyo3(signature = (latitude, source, target, num_threads=0))]
+    fn convert_latitude<'py>(
+        &self,
+        py: Python<'py>,
+        latitude: Coordinates<'py>,
+        source: &str,
+        target: &str,
+        num_threads: u16,
+    ) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+        use healpix_geo_core::vectorized::latitudes as vectorized;
+
+        let source = latitude_kind_from_name(source)?;
+        let target = latitude_kind_from_name(target)?;
+
+        let order = latitude.order();
+        let latitude_ = latitude.values(order);
+
+        let result = vectorized::convert(
+            &latitude_,
+            &self.ellipsoid,
+            source,
+            target,
+            num_threads as usize,
+        );
+
+        to_array(py, result, latitude.shape(), order)
+    }
+
+    fn __repr__(&self) -> String {
+        if self.ellipsoid.is_sphere() {
+            format!("Ellipsoid(radius={:?})", self.ellipsoid.semimajor_axis())
+        } else {
+            format!(
+                "Ellipsoid(semimajor_axis={:?}, inverse_flattening={:?})",
+                self.ellipsoid.semimajor_axis(),
+                1.0 / self.ellipsoid.flattening()
+            )
+        }
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.ellipsoid.is_sphere().hash(&mut hasher);
+        self.ellipsoid.semimajor_axis().to_bits().hash(&mut hasher);
+        self.ellipsoid.flattening().to_bits().hash(&mut hasher);
+
+        hasher.finish()
+    }
+
+    fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+        py: Python<'py>,
+    ) -> PyResult<(Bound<'py, PyAny>, (Bound<'py, PyDict>,))> {
+        let params = exact_parameters(py, &slf.get().ellipsoid)?;
+
+        Ok((slf.get_type().into_any(), (params,)))
     }
 }
@@ -0,0 +1,32 @@
+use healpix_geo_core::Error;
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+create_exception!(
+    healpix_geo,
+    InvalidGeometryError,
+    PyValueError,
+    "Raised when the geometry of a query or neighbourhood is invalid."
+);
+create_exception!(
+    healpix_geo,
+    LengthMismatchError,
+    PyValueError,
+    "Raised when arrays that are processed together have different lengths."
+);
+
+/// Translate errors from the core crate into python exceptions
+pub(crate) fn into_pyerr(err: Error) -> PyErr {
+    match err {
+        Error::InvalidGeometry(_) => InvalidGeometryError::new_err(err.to_string()),
+        Error::LengthMismatch { .. } => LengthMismatchError::new_err(err.to_string()),
+        Error::DepthOutOfRange { .. }
+        | Error::DeltaDepthOutOfRange { .. }
+        | Error::InvalidHash { .. }
+        | Error::InvalidEllipsoid(_)
+        | Error::InvalidCrs(_)
+        | Error::InvalidCoordinates { .. }
+        | Error::EmptyChunks => PyValueError::new_err(err.to_string()),
+    }
+}
@@ -1,13 +1,16 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use healpix_geo_core::vectorized::execution;
+
 #[macro_export]
 macro_rules! maybe_parallelize {
     ($nthreads:ident, $iterable:expr, $func:expr $(,)?) => {
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let pool = rayon::ThreadPoolBuilder::new()
-                .num_threads($nthreads as usize)
-                .build()
-                .unwrap();
-            pool.install(|| $iterable.par_for_each($func));
+            healpix_geo_core::vectorized::execution::install($nthreads as usize, || {
+                $iterable.par_for_each($func)
+            });
         }
         #[cfg(target_arch = "wasm32")]
         {
@@ -16,3 +19,28 @@ macro_rules! maybe_parallelize {
         }
     };
 }
+
+/// Set the number of threads used by functions called with ``num_threads=0``.
+///
+/// ``0`` restores the default: the number of threads is chosen based on the
+/// ``RAYON_NUM_THREADS`` environment variable (if set), or the number of logical CPUs.
+#[pyfunction]
+pub(crate) fn set_num_threads(nthreads: i64) -> PyResult<()> {
+    let nthreads = usize::try_from(nthreads).map_err(|_| {
+        PyValueError::new_err(format!(
+            "the number of threads must be non-negative (got {nthreads})"
+        ))
+    })?;
+
+    execution::set_num_threads(nthreads);
+
+    Ok(())
+}
+
+/// The number of threads used by functions called with ``num_threads=0``.
+///
+/// ``0`` means the default is used.
+#[pyfunction]
+pub(crate) fn get_num_threads() -> usize {
+    execution::num_threads()
+}
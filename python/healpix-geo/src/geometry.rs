@@ -57,6 +57,10 @@ enum ShapelyGeometryTypes {
     Point,
     LineString,
     Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
 }
 
 impl ShapelyGeometryTypes {
@@ -67,8 +71,18 @@ impl ShapelyGeometryTypes {
             Ok(Self::LineString)
         } else if kind == "Polygon" {
             Ok(Self::Polygon)
+        } else if kind == "MultiPoint" {
+            Ok(Self::MultiPoint)
+        } else if kind == "MultiLineString" {
+            Ok(Self::MultiLineString)
+        } else if kind == "MultiPolygon" {
+            Ok(Self::MultiPolygon)
+        } else if kind == "GeometryCollection" {
+            Ok(Self::GeometryCollection)
         } else {
-            Err(PyValueError::new_err("unsupported geometry type: {kind}"))
+            Err(PyValueError::new_err(format!(
+                "unsupported geometry type: {kind}"
+            )))
         }
     }
 }
@@ -79,6 +93,8 @@ pub enum GeometryTypes {
     LineString(Vec<(f64, f64)>),
     Polygon(Vec<(f64, f64)>, Vec<Vec<(f64, f64)>>),
     Bbox(f64, f64, f64, f64),
+    /// Multi-part geometries and geometry collections.
+    Collection(Vec<GeometryTypes>),
 }
 
 impl GeometryTypes {
@@ -140,10 +156,24 @@ impl GeometryTypes {
 
                     let interiors = obj
                         .getattr("interiors")?
-                        .extract::<Vec<Vec<(f64, f64)>>>()?;
+                        .try_iter()?
+                        .map(|ring| ring?.getattr("coords")?.extract::<Vec<(f64, f64)>>())
+                        .collect::<PyResult<Vec<_>>>()?;
 
                     Ok(GeometryTypes::Polygon(exterior, interiors))
                 }
+                ShapelyGeometryTypes::MultiPoint
+                | ShapelyGeometryTypes::MultiLineString
+                | ShapelyGeometryTypes::MultiPolygon
+                | ShapelyGeometryTypes::GeometryCollection => {
+                    let parts = obj
+                        .getattr("geoms")?
+                        .try_iter()?
+                        .map(|part| GeometryTypes::from_pyobject(py, &part?))
+                        .collect::<PyResult<Vec<_>>>()?;
+
+                    Ok(GeometryTypes::Collection(parts))
+                }
             }
         }
     }
@@ -1,25 +1,33 @@
 use numpy::{PyArray1, PyArrayDyn, PyArrayMethods};
-use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::exceptions::{PyKeyError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::type_object::PyTypeInfo;
-use pyo3::types::{PyBytes, PySlice, PyType};
+use pyo3::types::{PyBytes, PyDict, PySlice, PyType};
 
 use cdshealpix::nested;
+use cdshealpix::nested::Layer;
+use healpix_geo_core::ellipsoid::{Ellipsoid, ReferenceBody};
+use healpix_geo_core::scalar::nested::coverage;
 
-use moc::deser::json::from_json_aladin;
 use moc::elemset::range::MocRanges;
-use moc::moc::cell::CellMOC;
+use moc::moc::HasMaxDepth;
 use moc::moc::range::{CellSelection, RangeMOC};
-use moc::moc::{
-    CellMOCIntoIterator, CellMOCIterator, HasMaxDepth, RangeMOCIntoIterator, RangeMOCIterator,
-};
 use moc::qty::Hpx;
 use std::cmp::PartialEq;
 use std::ops::Range;
 
+use crate::ellipsoid::EllipsoidLike;
+use crate::error::into_pyerr;
 use crate::geometry::GeometryTypes;
 use crate::slice_objects::{AsSlice, CellIdSlice, ConcreteSlice, MultiConcreteSlice};
 
+mod encoding;
+mod ring;
+mod zuniq;
+
+pub(crate) use self::ring::RingRangeMOCIndex;
+pub(crate) use self::zuniq::ZuniqRangeMOCIndex;
+
 #[derive(FromPyObject, IntoPyObject)]
 enum IndexKind<'py> {
     #[pyo3(transparent, annotation = "slice")]
@@ -286,11 +294,151 @@ impl IndexSetOps for RangeMOC<u64, Hpx<u64>> {
 /// The idea is to compress cell ids at depth 29 based on run-length encoding (RLE).
 ///
 /// Only works with cell ids following the "nested" scheme.
+///
+/// The index is tied to the ellipsoid the cell ids were computed on: geometry
+/// queries use it to convert geographic to authalic latitudes, and indexes on
+/// different ellipsoids can't be combined.
 #[derive(PartialEq, Debug, Clone)]
 #[pyclass]
 #[pyo3(module = "healpix_geo.nested")]
 pub struct RangeMOCIndex {
     moc: RangeMOC<u64, Hpx<u64>>,
+    ellipsoid: Ellipsoid,
+}
+
+impl RangeMOCIndex {
+    fn with_moc(&self, moc: RangeMOC<u64, Hpx<u64>>) -> Self {
+        RangeMOCIndex {
+            moc,
+            ellipsoid: self.ellipsoid.clone(),
+        }
+    }
+}
+
+fn check_same_ellipsoid(ellipsoid: &Ellipsoid, other: &Ellipsoid) -> PyResult<()> {
+    if ellipsoid != other {
+        Err(PyValueError::new_err(
+            "Cannot combine indexes defined on different ellipsoids.",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// The parameters of the ellipsoid, in a form accepted as ellipsoid-like.
+fn ellipsoid_parameters<'py>(
+    py: Python<'py>,
+    ellipsoid: &Ellipsoid,
+) -> PyResult<Bound<'py, PyDict>> {
+    let params = PyDict::new(py);
+    if ellipsoid.is_sphere() {
+        params.set_item("radius", ellipsoid.semimajor_axis())?;
+    } else {
+        params.set_item("semimajor_axis", ellipsoid.semimajor_axis())?;
+        params.set_item("inverse_flattening", 1.0 / ellipsoid.flattening())?;
+    }
+
+    Ok(params)
+}
+
+/// Decode the state of an index made of a single MOC.
+fn decode_single_moc(state: &[u8]) -> PyResult<(RangeMOC<u64, Hpx<u64>>, Ellipsoid)> {
+    let (mut mocs, ellipsoid) = encoding::decode(state)?;
+
+    match (mocs.pop(), mocs.is_empty()) {
+        (Some(moc), true) => Ok((moc, ellipsoid)),
+        _ => Err(PyValueError::new_err(
+            "Invalid index state: expected exactly one MOC.",
+        )),
+    }
+}
+
+/// Group sorted positions into runs of consecutive positions.
+fn ranges_to_slices(ranges: impl IntoIterator<Item = Range<u64>>) -> Vec<ConcreteSlice> {
+    let mut slices: Vec<ConcreteSlice> = Vec::new();
+
+    for range in ranges {
+        let (start, stop) = (range.start as isize, range.end as isize);
+        match slices.last_mut() {
+            Some(slice) if slice.stop == start => slice.stop = stop,
+            _ => slices.push(ConcreteSlice {
+                start,
+                stop,
+                step: 1,
+            }),
+        }
+    }
+
+    slices
+}
+
+fn to_authalic(ellipsoid: &Ellipsoid, lon: f64, lat: f64) -> (f64, f64) {
+    (
+        lon.rem_euclid(360.0).to_radians(),
+        ellipsoid.latitude_geographic_to_authalic(lat.to_radians()),
+    )
+}
+
+/// Compute the cells of `layer` covered by a geometry.
+fn geometry_moc(
+    geom: GeometryTypes,
+    layer: &Layer,
+    ellipsoid: &Ellipsoid,
+) -> PyResult<RangeMOC<u64, Hpx<u64>>> {
+    let depth = layer.depth();
+
+    let moc = match geom {
+        GeometryTypes::Point(lon, lat) => {
+            let (lon_, lat_) = to_authalic(ellipsoid, lon, lat);
+            let hash = layer.hash(lon_, lat_);
+
+            RangeMOC::from_fixed_depth_cells(depth, vec![hash].into_iter(), None)
+        }
+        GeometryTypes::LineString(coords) => {
+            let hashes = coverage::linestring_coverage(&coords, layer, ellipsoid, false)
+                .map_err(into_pyerr)?;
+
+            RangeMOC::from_fixed_depth_cells(depth, hashes.into_iter(), None)
+        }
+        GeometryTypes::Polygon(exterior, interiors) => {
+            let converted = exterior
+                .into_iter()
+                .map(|(lon, lat)| to_authalic(ellipsoid, lon, lat))
+                .collect::<Vec<(_, _)>>();
+            let moc = RangeMOC::from_polygon(&converted, false, depth, CellSelection::All);
+
+            // only remove cells entirely contained by a hole
+            interiors.into_iter().fold(moc, |moc, interior| {
+                let converted = interior
+                    .into_iter()
+                    .map(|(lon, lat)| to_authalic(ellipsoid, lon, lat))
+                    .collect::<Vec<(_, _)>>();
+                let hole = RangeMOC::from_polygon(&converted, false, depth, CellSelection::Inside);
+
+                moc.minus(&hole)
+            })
+        }
+        GeometryTypes::Bbox(lon_min, lat_min, lon_max, lat_max) => {
+            let (lon_min_, lat_min_) = to_authalic(ellipsoid, lon_min, lat_min);
+            let (lon_max_, lat_max_) = to_authalic(ellipsoid, lon_max, lat_max);
+
+            RangeMOC::from_zone(
+                lon_min_,
+                lat_min_,
+                lon_max_,
+                lat_max_,
+                depth,
+                CellSelection::All,
+            )
+        }
+        GeometryTypes::Collection(parts) => parts
+            .into_iter()
+            .try_fold(RangeMOC::new_empty(depth), |moc, part| {
+                Ok::<_, PyErr>(moc.union(&geometry_moc(part, layer, ellipsoid)?))
+            })?,
+    };
+
+    Ok(moc)
 }
 
 #[pymethods]
@@ -303,10 +451,19 @@ impl RangeMOCIndex {
     /// ----------
     /// depth : int
     ///     The cell depth.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid. Can be the name of a ellipsoid, a
+    ///     dict of parameters or an object with parameter attributes.
     #[classmethod]
-    fn full_domain(_cls: &Bound<'_, PyType>, depth: u8) -> PyResult<Self> {
+    #[pyo3(signature = (depth, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn full_domain(
+        _cls: &Bound<'_, PyType>,
+        depth: u8,
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
         let index = RangeMOCIndex {
             moc: RangeMOC::new_full_domain(depth),
+            ellipsoid: ellipsoid.into_ellipsoid()?,
         };
 
         Ok(index)
@@ -318,10 +475,18 @@ impl RangeMOCIndex {
     /// ----------
     /// depth : int
     ///     The cell depth.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
     #[classmethod]
-    fn create_empty(_cls: &Bound<'_, PyType>, depth: u8) -> PyResult<Self> {
+    #[pyo3(signature = (depth, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn create_empty(
+        _cls: &Bound<'_, PyType>,
+        depth: u8,
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
         let index = RangeMOCIndex {
             moc: RangeMOC::new_empty(depth),
+            ellipsoid: ellipsoid.into_ellipsoid()?,
         };
 
         Ok(index)
@@ -335,20 +500,73 @@ impl RangeMOCIndex {
     ///     The cell depth.
     /// cell_ids : numpy.ndarray
     ///     The cells to construct the the index from.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
     #[classmethod]
+    #[pyo3(signature = (depth, cell_ids, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
     fn from_cell_ids<'a>(
         _cls: &Bound<'a, PyType>,
         _py: Python,
         depth: u8,
         cell_ids: &Bound<'a, PyArray1<u64>>,
+        ellipsoid: EllipsoidLike,
     ) -> PyResult<Self> {
         let index = RangeMOCIndex {
             moc: RangeMOC::from_fixed_depth_cells(depth, cell_ids.to_vec()?.into_iter(), None),
+            ellipsoid: ellipsoid.into_ellipsoid()?,
         };
 
         Ok(index)
     }
 
+    /// Create an index from a FITS MOC
+    ///
+    /// The MOC must be a spatial MOC following the IVOA standard, like the ones
+    /// written by Aladin or MOCpy. The depth of the index is the depth of the MOC.
+    ///
+    /// Parameters
+    /// ----------
+    /// data : bytes
+    ///     The content of the FITS file.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (data, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn from_fits(
+        _cls: &Bound<'_, PyType>,
+        data: &[u8],
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
+        Ok(RangeMOCIndex {
+            moc: encoding::moc_from_fits(data)?,
+            ellipsoid: ellipsoid.into_ellipsoid()?,
+        })
+    }
+
+    /// Create an index from an ASCII MOC
+    ///
+    /// The MOC must follow the ASCII serialization of the IVOA standard, e.g.
+    /// ``"3/1-5 8 4/100"``. The depth of the index is the depth of the MOC.
+    ///
+    /// Parameters
+    /// ----------
+    /// data : str
+    ///     The ASCII representation of the MOC.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (data, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn from_ascii(
+        _cls: &Bound<'_, PyType>,
+        data: &str,
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
+        Ok(RangeMOCIndex {
+            moc: encoding::moc_from_ascii(data)?,
+            ellipsoid: ellipsoid.into_ellipsoid()?,
+        })
+    }
+
     /// Compute the set union of two indexes
     ///
     /// Parameters
@@ -361,10 +579,10 @@ impl RangeMOCIndex {
     /// -------
     /// result : RangeMOCIndex
     ///     The union of the two indexes.
-    fn union(&self, other: &RangeMOCIndex) -> Self {
-        RangeMOCIndex {
-            moc: self.moc.union(&other.moc),
-        }
+    fn union(&self, other: &RangeMOCIndex) -> PyResult<Self> {
+        check_same_ellipsoid(&self.ellipsoid, &other.ellipsoid)?;
+
+        Ok(self.with_moc(self.moc.union(&other.moc)))
     }
 
     /// Compute the set intersection of two indexes
@@ -379,10 +597,10 @@ impl RangeMOCIndex {
     /// -------
     /// result : RangeMOCIndex
     ///     The intersection of the two indexes.
-    fn intersection(&self, other: &RangeMOCIndex) -> Self {
-        RangeMOCIndex {
-            moc: self.moc.intersection(&other.moc),
-        }
+    fn intersection(&self, other: &RangeMOCIndex) -> PyResult<Self> {
+        check_same_ellipsoid(&self.ellipsoid, &other.ellipsoid)?;
+
+        Ok(self.with_moc(self.moc.intersection(&other.moc)))
     }
 
     /// Compute the set difference of two indexes
@@ -403,10 +621,10 @@ impl RangeMOCIndex {
     /// -------
     /// result : RangeMOCIndex
     ///     The set difference of the two indexes.
-    fn difference(&self, other: &RangeMOCIndex) -> Self {
-        RangeMOCIndex {
-            moc: self.moc.minus(&other.moc),
-        }
+    fn difference(&self, other: &RangeMOCIndex) -> PyResult<Self> {
+        check_same_ellipsoid(&self.ellipsoid, &other.ellipsoid)?;
+
+        Ok(self.with_moc(self.moc.minus(&other.moc)))
     }
 
     /// Compute the symmetric set difference of two indexes
@@ -427,10 +645,10 @@ impl RangeMOCIndex {
     /// -------
     /// result : RangeMOCIndex
     ///     The symmetric set difference of the two indexes.
-    fn symmetric_difference(&self, other: &RangeMOCIndex) -> Self {
-        RangeMOCIndex {
-            moc: self.moc.xor(&other.moc),
-        }
+    fn symmetric_difference(&self, other: &RangeMOCIndex) -> PyResult<Self> {
+        check_same_ellipsoid(&self.ellipsoid, &other.ellipsoid)?;
+
+        Ok(self.with_moc(self.moc.xor(&other.moc)))
     }
 
     /// The size of the ranges in bytes, minus any overhead.
@@ -451,41 +669,22 @@ impl RangeMOCIndex {
         self.moc.depth_max()
     }
 
-    pub fn __setstate__(&mut self, state: &[u8]) -> PyResult<()> {
-        // Deserialize the data contained in the PyBytes object
-        // and update the struct with the deserialized values.
-        // serde+bincode version:
-        // *self = deserialize(state).map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
-
-        let cell_moc: CellMOC<u64, Hpx<u64>> = from_json_aladin(
-            std::str::from_utf8(state).map_err(|err| PyRuntimeError::new_err(err.to_string()))?,
-        )
-        .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
-        let reconstructed = RangeMOC::from_cells(
-            cell_moc.depth_max(),
-            cell_moc
-                .into_cell_moc_iter()
-                .map(|c| -> (u8, u64) { (c.depth, c.idx) }),
-            None,
-        );
-        *self = RangeMOCIndex { moc: reconstructed };
+    /// The reference ellipsoid of the index, as a dict of parameters.
+    #[getter]
+    fn ellipsoid<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        ellipsoid_parameters(py, &self.ellipsoid)
+    }
+
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        let (moc, ellipsoid) = decode_single_moc(state.as_bytes())?;
+
+        *self = RangeMOCIndex { moc, ellipsoid };
 
         Ok(())
     }
 
-    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
-        // Serialize the struct and return a PyBytes object
-        // containing the serialized data.
-        let mut serialized: Vec<u8> = Default::default();
-        self.moc
-            .clone()
-            .into_range_moc_iter()
-            .cells()
-            .to_json_aladin(None, &mut serialized)
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        // let serialized = serialize(&self).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let bytes = PyBytes::new(py, &serialized);
-        Ok(bytes)
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &encoding::encode(&[&self.moc], &self.ellipsoid))
     }
 
     pub fn __reduce__(&self, py: Python) -> PyResult<(Py<PyAny>, Py<PyAny>, Py<PyAny>)> {
@@ -494,7 +693,7 @@ impl RangeMOCIndex {
             .getattr("nested")?
             .getattr("create_empty")?;
         let args = (self.moc.depth_max(),);
-        let state = self.__getstate__(py)?;
+        let state = self.__getstate__(py);
 
         Ok((
             create.into_pyobject(py)?.unbind().into_any(),
@@ -503,6 +702,30 @@ impl RangeMOCIndex {
         ))
     }
 
+    /// Serialize the index to a FITS MOC
+    ///
+    /// The ellipsoid is not part of the MOC standard and is not stored.
+    ///
+    /// Returns
+    /// -------
+    /// data : bytes
+    ///     The content of a FITS file following the IVOA MOC standard.
+    fn to_fits<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &encoding::moc_to_fits(&self.moc)?))
+    }
+
+    /// Serialize the index to an ASCII MOC
+    ///
+    /// The ellipsoid is not part of the MOC standard and is not stored.
+    ///
+    /// Returns
+    /// -------
+    /// data : str
+    ///     The ASCII representation of the MOC, following the IVOA MOC standard.
+    fn to_ascii(&self) -> PyResult<String> {
+        encoding::moc_to_ascii(&self.moc)
+    }
+
     /// Retrieve the cell ids from the index.
     ///
     /// Returns
@@ -535,12 +758,12 @@ impl RangeMOCIndex {
 
                 let subset = self.moc.slice(&concrete_slice)?;
 
-                Ok(RangeMOCIndex { moc: subset })
+                Ok(self.with_moc(subset))
             }
             IndexKind::Array(array) => {
                 let subset = self.moc.subset(&array)?;
 
-                Ok(RangeMOCIndex { moc: subset })
+                Ok(self.with_moc(subset))
             }
         }
     }
@@ -593,7 +816,7 @@ impl RangeMOCIndex {
 
                 let new_moc: RangeMOC<u64, Hpx<u64>> =
                     RangeMOC::new(self.moc.depth_max(), MocRanges::new_from(ranges));
-                let new_index = RangeMOCIndex { moc: new_moc };
+                let new_index = self.with_moc(new_moc);
 
                 Ok((IndexKind::Slice(joined_slice.as_pyslice(py)?), new_index))
             }
@@ -648,7 +871,7 @@ impl RangeMOCIndex {
                     cell_ids.into_iter(),
                     None,
                 );
-                let new_index = RangeMOCIndex { moc: new_moc };
+                let new_index = self.with_moc(new_moc);
 
                 Ok((
                     IndexKind::Array(PyArray1::from_vec(py, positions).to_dyn().clone()),
@@ -678,49 +901,16 @@ impl RangeMOCIndex {
         py: Python<'py>,
         geometry: &Bound<'py, PyAny>,
     ) -> PyResult<(MultiConcreteSlice, Self)> {
-        let depth = self.moc.depth_max();
-        let layer = nested::get(depth);
+        let layer = nested::get(self.moc.depth_max());
 
         let geom = GeometryTypes::from_pyobject(py, geometry)?;
 
-        let geometry_moc = match geom {
-            GeometryTypes::Point(lon, lat) => {
-                let hash = layer.hash(lon.rem_euclid(360.0).to_radians(), lat.to_radians());
-
-                RangeMOC::from_fixed_depth_cells(depth, vec![hash].into_iter(), None)
-            }
-            GeometryTypes::LineString(coords) => {
-                let hashes = coords
-                    .into_iter()
-                    .map(|(lon, lat)| {
-                        layer.hash(lon.rem_euclid(360.0).to_radians(), lat.to_radians())
-                    })
-                    .collect::<Vec<u64>>();
-
-                RangeMOC::from_fixed_depth_cells(depth, hashes.into_iter(), None)
-            }
-            GeometryTypes::Polygon(exterior, _interiors) => {
-                let converted = exterior
-                    .into_iter()
-                    .map(|r| (r.0.rem_euclid(360.0).to_radians(), r.1.to_radians()))
-                    .collect::<Vec<(_, _)>>();
-
-                RangeMOC::from_polygon(&converted, false, depth, CellSelection::All)
-            }
-            GeometryTypes::Bbox(lon_min, lat_min, lon_max, lat_max) => RangeMOC::from_zone(
-                lon_min.rem_euclid(360.0).to_radians(),
-                lat_min.to_radians(),
-                lon_max.rem_euclid(360.0).to_radians(),
-                lat_max.to_radians(),
-                depth,
-                CellSelection::All,
-            ),
-        };
+        let geometry_moc = geometry_moc(geom, layer, &self.ellipsoid)?;
 
         let (slices, moc) = self.moc.index_intersection(geometry_moc)?;
 
         let multi_slice = MultiConcreteSlice { slices };
 
-        Ok((multi_slice, RangeMOCIndex { moc }))
+        Ok((multi_slice, self.with_moc(moc)))
     }
 }
@@ -0,0 +1,252 @@
+//! Serialization formats of the range-based indexes.
+//!
+//! The native format is a small versioned binary encoding:
+//!
+//! | field            | type                    |
+//! |------------------|-------------------------|
+//! | magic            | `b"HGMOC"`              |
+//! | version          | `u8`                    |
+//! | semimajor axis   | `f64`                   |
+//! | flattening       | `f64`                   |
+//! | is sphere        | `u8`                    |
+//! | number of MOCs   | `u32`                   |
+//! | MOCs             | see below               |
+//!
+//! where each MOC is encoded as its depth (`u8`), the number of ranges (`u64`) and
+//! the ranges as `(start, end)` pairs of `u64`. The ranges are expressed at depth 29,
+//! like in the `moc` crate. All numbers are little-endian.
+use std::io::{BufReader, Cursor};
+use std::ops::Range;
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+
+use healpix_geo_core::MAX_DEPTH;
+use healpix_geo_core::ellipsoid::{Ellipsoid, ReferenceBody};
+
+use moc::deser::ascii::from_ascii_ivoa;
+use moc::deser::fits::{MocIdxType, MocQtyType, MocType, from_fits_ivoa};
+use moc::deser::json::from_json_aladin;
+use moc::elemset::range::MocRanges;
+use moc::idx::Idx;
+use moc::moc::cell::CellMOC;
+use moc::moc::cellcellrange::CellOrCellRangeMOC;
+use moc::moc::range::RangeMOC;
+use moc::moc::{
+    CellMOCIntoIterator, CellMOCIterator, CellOrCellRangeMOCIntoIterator,
+    CellOrCellRangeMOCIterator, HasMaxDepth, RangeMOCIntoIterator, RangeMOCIterator,
+};
+use moc::qty::{Hpx, MocQty};
+
+use crate::error::into_pyerr;
+
+const MAGIC: &[u8; 5] = b"HGMOC";
+const VERSION: u8 = 1;
+
+/// Largest range bound of a HEALPix MOC: the number of cells at depth 29.
+const MAX_RANGE_BOUND: u64 = 12 << (2 * 29);
+
+/// Encode MOCs together with the reference ellipsoid of the index.
+pub(super) fn encode(mocs: &[&RangeMOC<u64, Hpx<u64>>], ellipsoid: &Ellipsoid) -> Vec<u8> {
+    let n_ranges: usize = mocs.iter().map(|moc| moc.len()).sum();
+    let mut buffer: Vec<u8> = Vec::with_capacity(MAGIC.len() + 22 + mocs.len() * 9 + n_ranges * 16);
+
+    buffer.extend_from_slice(MAGIC);
+    buffer.push(VERSION);
+    buffer.extend_from_slice(&ellipsoid.semimajor_axis().to_le_bytes());
+    buffer.extend_from_slice(&ellipsoid.flattening().to_le_bytes());
+    buffer.push(ellipsoid.is_sphere() as u8);
+    buffer.extend_from_slice(&(mocs.len() as u32).to_le_bytes());
+
+    for moc in mocs {
+        buffer.push(moc.depth_max());
+        buffer.extend_from_slice(&(moc.len() as u64).to_le_bytes());
+        for range in moc.moc_ranges().iter() {
+            buffer.extend_from_slice(&range.start.to_le_bytes());
+            buffer.extend_from_slice(&range.end.to_le_bytes());
+        }
+    }
+
+    buffer
+}
+
+/// Cursor over the encoded bytes that fails on truncated input.
+struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn take<const N: usize>(&mut self) -> PyResult<[u8; N]> {
+        if self.bytes.len() < N {
+            return Err(PyValueError::new_err("Truncated index state."));
+        }
+        let (head, tail) = self.bytes.split_at(N);
+        self.bytes = tail;
+
+        Ok(head.try_into().expect("split at the requested size"))
+    }
+
+    fn u8(&mut self) -> PyResult<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> PyResult<u32> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> PyResult<u64> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    fn f64(&mut self) -> PyResult<f64> {
+        Ok(f64::from_le_bytes(self.take()?))
+    }
+
+    fn moc(&mut self) -> PyResult<RangeMOC<u64, Hpx<u64>>> {
+        let depth = self.u8()?;
+        if depth > MAX_DEPTH {
+            return Err(PyValueError::new_err(format!(
+                "Invalid depth in index state: {depth}"
+            )));
+        }
+
+        let n_ranges = self.u64()? as usize;
+        if self.bytes.len() / 16 < n_ranges {
+            return Err(PyValueError::new_err("Truncated index state."));
+        }
+
+        let mut ranges: Vec<Range<u64>> = Vec::with_capacity(n_ranges);
+        let mut previous_end = 0;
+        for _ in 0..n_ranges {
+            let start = self.u64()?;
+            let end = self.u64()?;
+            if start < previous_end || start >= end || end > MAX_RANGE_BOUND {
+                return Err(PyValueError::new_err(
+                    "Invalid index state: ranges must be sorted, non-empty and non-overlapping.",
+                ));
+            }
+            previous_end = end;
+            ranges.push(start..end);
+        }
+
+        Ok(RangeMOC::new(depth, MocRanges::new_from(ranges)))
+    }
+}
+
+/// Decode MOCs and the reference ellipsoid encoded by [`encode`].
+///
+/// States that do not start with the magic bytes are assumed to be JSON-serialized
+/// MOCs on a sphere, as written by earlier versions.
+pub(super) fn decode(bytes: &[u8]) -> PyResult<(Vec<RangeMOC<u64, Hpx<u64>>>, Ellipsoid)> {
+    let Some(bytes) = bytes.strip_prefix(MAGIC) else {
+        let ellipsoid = Ellipsoid::named("sphere").map_err(into_pyerr)?;
+
+        return Ok((vec![moc_from_json(bytes)?], ellipsoid));
+    };
+    let mut decoder = Decoder { bytes };
+
+    let version = decoder.u8()?;
+    if version != VERSION {
+        return Err(PyValueError::new_err(format!(
+            "Unsupported index state version: {version} (expected {VERSION})"
+        )));
+    }
+
+    let semimajor_axis = decoder.f64()?;
+    let flattening = decoder.f64()?;
+    let is_sphere = decoder.u8()? != 0;
+    let ellipsoid =
+        Ellipsoid::from_semimajor_axis_and_flattening(semimajor_axis, flattening, is_sphere)
+            .map_err(into_pyerr)?;
+
+    let n_mocs = decoder.u32()?;
+    let mocs = (0..n_mocs)
+        .map(|_| decoder.moc())
+        .collect::<PyResult<Vec<_>>>()?;
+
+    if !decoder.bytes.is_empty() {
+        return Err(PyValueError::new_err("Trailing bytes in index state."));
+    }
+
+    Ok((mocs, ellipsoid))
+}
+
+fn moc_from_json(state: &[u8]) -> PyResult<RangeMOC<u64, Hpx<u64>>> {
+    let cell_moc: CellMOC<u64, Hpx<u64>> = from_json_aladin(
+        std::str::from_utf8(state).map_err(|err| PyValueError::new_err(err.to_string()))?,
+    )
+    .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+    Ok(cell_moc.into_cell_moc_iter().ranges().into_range_moc())
+}
+
+/// Serialize a MOC to a FITS file, following the IVOA MOC standard.
+pub(super) fn moc_to_fits(moc: &RangeMOC<u64, Hpx<u64>>) -> PyResult<Vec<u8>> {
+    let mut serialized: Vec<u8> = Default::default();
+    moc.clone()
+        .into_range_moc_iter()
+        .to_fits_ivoa(None, None, &mut serialized)
+        .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+
+    Ok(serialized)
+}
+
+/// Read a spatial MOC from a FITS file.
+///
+/// MOCs stored with 16 or 32-bit indices are converted to 64-bit indices. FITS
+/// tables have no wider integer type.
+pub(super) fn moc_from_fits(data: &[u8]) -> PyResult<RangeMOC<u64, Hpx<u64>>> {
+    let moc = from_fits_ivoa(BufReader::new(Cursor::new(data)))
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+    match moc {
+        MocIdxType::U16(moc) => hpx_moc_to_u64(moc),
+        MocIdxType::U32(moc) => hpx_moc_to_u64(moc),
+        MocIdxType::U64(moc) => hpx_moc_to_u64(moc),
+    }
+}
+
+/// Convert the ranges of a spatial MOC read from a FITS file to 64-bit indices.
+fn hpx_moc_to_u64<T: Idx>(
+    moc: MocQtyType<T, BufReader<Cursor<&[u8]>>>,
+) -> PyResult<RangeMOC<u64, Hpx<u64>>> {
+    // ranges are expressed at the largest depth supported by the index type
+    let shift = 2 * (Hpx::<u64>::MAX_DEPTH - Hpx::<T>::MAX_DEPTH);
+    let to_u64 = |range: Range<T>| (range.start.to_u64() << shift)..(range.end.to_u64() << shift);
+
+    let (depth, ranges): (u8, Vec<Range<u64>>) = match moc {
+        MocQtyType::Hpx(MocType::Ranges(moc)) => (moc.depth_max(), moc.map(to_u64).collect()),
+        MocQtyType::Hpx(MocType::Cells(moc)) => {
+            let moc = moc.into_cell_moc_iter().ranges();
+
+            (moc.depth_max(), moc.map(to_u64).collect())
+        }
+        _ => return Err(PyValueError::new_err("Only spatial MOCs are supported.")),
+    };
+
+    Ok(RangeMOC::new(depth, MocRanges::new_from(ranges)))
+}
+
+/// Serialize a MOC to the IVOA ASCII format.
+pub(super) fn moc_to_ascii(moc: &RangeMOC<u64, Hpx<u64>>) -> PyResult<String> {
+    let mut serialized: Vec<u8> = Default::default();
+    moc.clone()
+        .into_range_moc_iter()
+        .cells()
+        .cellranges()
+        .to_ascii_ivoa(Some(80), false, &mut serialized)
+        .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+
+    String::from_utf8(serialized).map_err(|err| PyRuntimeError::new_err(err.to_string()))
+}
+
+/// Read a spatial MOC from the IVOA ASCII format.
+pub(super) fn moc_from_ascii(data: &str) -> PyResult<RangeMOC<u64, Hpx<u64>>> {
+    let moc: CellOrCellRangeMOC<u64, Hpx<u64>> =
+        from_ascii_ivoa(data).map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+    Ok(moc
+        .into_cell_or_cell_range_moc_iter()
+        .ranges()
+        .into_range_moc())
+}
@@ -0,0 +1,496 @@
+use numpy::{PyArray1, PyArrayMethods};
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyType};
+
+use cdshealpix::nested;
+use healpix_geo_core::ellipsoid::Ellipsoid;
+use healpix_geo_core::scalar::ring::conversion;
+use healpix_geo_core::scalar::ring::ordering::RingOrdering;
+
+use moc::moc::HasMaxDepth;
+use moc::moc::range::RangeMOC;
+use moc::qty::Hpx;
+
+use super::{
+    IndexKind, check_same_ellipsoid, decode_single_moc, ellipsoid_parameters, encoding,
+    geometry_moc, ranges_to_slices,
+};
+use crate::ellipsoid::EllipsoidLike;
+use crate::error::into_pyerr;
+use crate::geometry::GeometryTypes;
+use crate::slice_objects::{AsSlice, ConcreteSlice, MultiConcreteSlice};
+
+/// range-based index of healpix cell ids following the "ring" scheme
+///
+/// The cells are stored as a MOC of the equivalent nested cell ids, but
+/// positions always refer to the cell ids sorted in ring order.
+#[derive(PartialEq, Debug, Clone)]
+#[pyclass(name = "RangeMOCIndex")]
+#[pyo3(module = "healpix_geo.ring")]
+pub struct RingRangeMOCIndex {
+    moc: RangeMOC<u64, Hpx<u64>>,
+    ellipsoid: Ellipsoid,
+}
+
+impl RingRangeMOCIndex {
+    fn with_moc(&self, moc: RangeMOC<u64, Hpx<u64>>) -> Self {
+        RingRangeMOCIndex {
+            moc,
+            ellipsoid: self.ellipsoid.clone(),
+        }
+    }
+
+    fn from_ring_ids(depth: u8, ring_ids: &[u64], ellipsoid: Ellipsoid) -> PyResult<Self> {
+        let nested_ids = ring_ids
+            .iter()
+            .map(|hash| conversion::to_nested(hash, &depth))
+            .collect::<healpix_geo_core::Result<Vec<u64>>>()
+            .map_err(into_pyerr)?;
+
+        Ok(RingRangeMOCIndex {
+            moc: RangeMOC::from_fixed_depth_cells(depth, nested_ids.into_iter(), None),
+            ellipsoid,
+        })
+    }
+
+    /// The ring order of the cells of the index, computed from the ranges.
+    fn ordering(&self) -> RingOrdering {
+        let depth = self.moc.depth_max();
+        let shift = 2 * (29 - depth);
+
+        RingOrdering::new(
+            depth,
+            self.moc
+                .moc_ranges()
+                .iter()
+                .map(|range| (range.start >> shift)..(range.end >> shift)),
+        )
+    }
+}
+
+#[pymethods]
+impl RingRangeMOCIndex {
+    /// Create a full domain index
+    ///
+    /// This is a short-cut for creating an index for the entire sphere.
+    ///
+    /// Parameters
+    /// ----------
+    /// depth : int
+    ///     The cell depth.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (depth, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn full_domain(
+        _cls: &Bound<'_, PyType>,
+        depth: u8,
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
+        Ok(RingRangeMOCIndex {
+            moc: RangeMOC::new_full_domain(depth),
+            ellipsoid: ellipsoid.into_ellipsoid()?,
+        })
+    }
+
+    /// Create an empty index
+    ///
+    /// Parameters
+    /// ----------
+    /// depth : int
+    ///     The cell depth.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (depth, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn create_empty(
+        _cls: &Bound<'_, PyType>,
+        depth: u8,
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
+        Ok(RingRangeMOCIndex {
+            moc: RangeMOC::new_empty(depth),
+            ellipsoid: ellipsoid.into_ellipsoid()?,
+        })
+    }
+
+    /// Create an index from given cell ids.
+    ///
+    /// Parameters
+    /// ----------
+    /// depth : int
+    ///     The cell depth.
+    /// cell_ids : numpy.ndarray
+    ///     The cells to construct the the index from, following the ring scheme.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (depth, cell_ids, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn from_cell_ids<'a>(
+        _cls: &Bound<'a, PyType>,
+        depth: u8,
+        cell_ids: &Bound<'a, PyArray1<u64>>,
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
+        Self::from_ring_ids(depth, &cell_ids.to_vec()?, ellipsoid.into_ellipsoid()?)
+    }
+
+    /// Create an index from a FITS MOC
+    ///
+    /// The MOC must be a spatial MOC following the IVOA standard, like the ones
+    /// written by Aladin or MOCpy. The depth of the index is the depth of the MOC.
+    ///
+    /// Parameters
+    /// ----------
+    /// data : bytes
+    ///     The content of the FITS file.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (data, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn from_fits(
+        _cls: &Bound<'_, PyType>,
+        data: &[u8],
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
+        Ok(RingRangeMOCIndex {
+            moc: encoding::moc_from_fits(data)?,
+            ellipsoid: ellipsoid.into_ellipsoid()?,
+        })
+    }
+
+    /// Create an index from an ASCII MOC
+    ///
+    /// The MOC must follow the ASCII serialization of the IVOA standard, e.g.
+    /// ``"3/1-5 8 4/100"``. The depth of the index is the depth of the MOC.
+    ///
+    /// Parameters
+    /// ----------
+    /// data : str
+    ///     The ASCII representation of the MOC.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (data, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn from_ascii(
+        _cls: &Bound<'_, PyType>,
+        data: &str,
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
+        Ok(RingRangeMOCIndex {
+            moc: encoding::moc_from_ascii(data)?,
+            ellipsoid: ellipsoid.into_ellipsoid()?,
+        })
+    }
+
+    /// Compute the set union of two indexes
+    ///
+    /// Parameters
+    /// ----------
+    /// other : RangeMOCIndex
+    ///     The other index.
+    ///
+    /// Returns
+    /// -------
+    /// result : RangeMOCIndex
+    ///     The union of the two indexes.
+    fn union(&self, other: &RingRangeMOCIndex) -> PyResult<Self> {
+        check_same_ellipsoid(&self.ellipsoid, &other.ellipsoid)?;
+
+        Ok(self.with_moc(self.moc.union(&other.moc)))
+    }
+
+    /// Compute the set intersection of two indexes
+    ///
+    /// Parameters
+    /// ----------
+    /// other : RangeMOCIndex
+    ///     The other index.
+    ///
+    /// Returns
+    /// -------
+    /// result : RangeMOCIndex
+    ///     The intersection of the two indexes.
+    fn intersection(&self, other: &RingRangeMOCIndex) -> PyResult<Self> {
+        check_same_ellipsoid(&self.ellipsoid, &other.ellipsoid)?;
+
+        Ok(self.with_moc(self.moc.intersection(&other.moc)))
+    }
+
+    /// Compute the set difference of two indexes
+    ///
+    /// Parameters
+    /// ----------
+    /// other : RangeMOCIndex
+    ///     The index to subtract.
+    ///
+    /// Returns
+    /// -------
+    /// result : RangeMOCIndex
+    ///     The set difference of the two indexes.
+    fn difference(&self, other: &RingRangeMOCIndex) -> PyResult<Self> {
+        check_same_ellipsoid(&self.ellipsoid, &other.ellipsoid)?;
+
+        Ok(self.with_moc(self.moc.minus(&other.moc)))
+    }
+
+    /// Compute the symmetric set difference of two indexes
+    ///
+    /// Parameters
+    /// ----------
+    /// other : RangeMOCIndex
+    ///     The other index.
+    ///
+    /// Returns
+    /// -------
+    /// result : RangeMOCIndex
+    ///     The symmetric set difference of the two indexes.
+    fn symmetric_difference(&self, other: &RingRangeMOCIndex) -> PyResult<Self> {
+        check_same_ellipsoid(&self.ellipsoid, &other.ellipsoid)?;
+
+        Ok(self.with_moc(self.moc.xor(&other.moc)))
+    }
+
+    /// The size of the ranges in bytes, minus any overhead.
+    #[getter]
+    fn nbytes(&self) -> u64 {
+        self.moc.len() as u64 * 2 * u64::BITS as u64 / 8
+    }
+
+    /// The number of items in the index.
+    #[getter]
+    fn size(&self) -> u64 {
+        self.moc.n_depth_max_cells()
+    }
+
+    /// The depth of the index.
+    #[getter]
+    fn depth(&self) -> u8 {
+        self.moc.depth_max()
+    }
+
+    /// The reference ellipsoid of the index, as a dict of parameters.
+    #[getter]
+    fn ellipsoid<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        ellipsoid_parameters(py, &self.ellipsoid)
+    }
+
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        let (moc, ellipsoid) = decode_single_moc(state.as_bytes())?;
+
+        *self = RingRangeMOCIndex { moc, ellipsoid };
+
+        Ok(())
+    }
+
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &encoding::encode(&[&self.moc], &self.ellipsoid))
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<(Py<PyAny>, Py<PyAny>, Py<PyAny>)> {
+        let create = py
+            .import("healpix_geo")?
+            .getattr("ring")?
+            .getattr("create_empty")?;
+        let args = (self.moc.depth_max(),);
+        let state = self.__getstate__(py);
+
+        Ok((
+            create.into_pyobject(py)?.unbind().into_any(),
+            args.into_pyobject(py)?.unbind().into_any(),
+            state.into_pyobject(py)?.unbind().into_any(),
+        ))
+    }
+
+    /// Serialize the index to a FITS MOC
+    ///
+    /// MOCs always refer to cells of the nested scheme. The ellipsoid is not part of
+    /// the MOC standard and is not stored.
+    ///
+    /// Returns
+    /// -------
+    /// data : bytes
+    ///     The content of a FITS file following the IVOA MOC standard.
+    fn to_fits<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &encoding::moc_to_fits(&self.moc)?))
+    }
+
+    /// Serialize the index to an ASCII MOC
+    ///
+    /// MOCs always refer to cells of the nested scheme. The ellipsoid is not part of
+    /// the MOC standard and is not stored.
+    ///
+    /// Returns
+    /// -------
+    /// data : str
+    ///     The ASCII representation of the MOC, following the IVOA MOC standard.
+    fn to_ascii(&self) -> PyResult<String> {
+        encoding::moc_to_ascii(&self.moc)
+    }
+
+    /// Retrieve the cell ids from the index.
+    ///
+    /// Returns
+    /// -------
+    /// cell_ids : numpy.ndarray
+    ///     The cell ids contained by the index, in ring order.
+    fn cell_ids<'a>(&self, py: Python<'a>) -> PyResult<Bound<'a, PyArray1<u64>>> {
+        let ordering = self.ordering();
+
+        Ok(PyArray1::from_vec(py, ordering.ring_ids(0..ordering.len())))
+    }
+
+    /// Subset the index using positions
+    ///
+    /// Parameters
+    /// ----------
+    /// indexer : slice of int or array-like
+    ///     The integer positions. If an array, must be of dtype uint64.
+    ///
+    /// Returns
+    /// -------
+    /// subset : RangeMOCIndex
+    ///     The resulting subset.
+    fn isel<'a>(&self, py: Python<'a>, indexer: IndexKind<'a>) -> PyResult<Self> {
+        let ordering = self.ordering();
+
+        let selected: Vec<u64> = match indexer {
+            IndexKind::Slice(slice) => {
+                let ConcreteSlice { start, stop, step } = slice
+                    .as_positional_slice()?
+                    .as_concrete(py, ordering.len() as isize)?;
+                if step <= 0 {
+                    return Err(PyValueError::new_err(format!(
+                        "Only positive step sizes are supported, got {step}"
+                    )));
+                }
+
+                ordering
+                    .ring_ids(start.max(0) as u64..stop.max(0) as u64)
+                    .into_iter()
+                    .step_by(step as usize)
+                    .collect()
+            }
+            IndexKind::Array(array) => {
+                let positions = array.readonly();
+
+                positions
+                    .as_array()
+                    .iter()
+                    .map(|&position| {
+                        ordering.select(position).ok_or_else(|| {
+                            PyValueError::new_err(format!("{position} is out of bounds"))
+                        })
+                    })
+                    .collect::<PyResult<Vec<u64>>>()?
+            }
+        };
+
+        Self::from_ring_ids(self.moc.depth_max(), &selected, self.ellipsoid.clone())
+    }
+
+    /// Subset the index using cell ids
+    ///
+    /// Parameters
+    /// ----------
+    /// indexer : slice of int or array-like
+    ///     The cell ids or ranges of cell ids to find. If an array, must be of dtype uint64.
+    ///
+    /// Returns
+    /// -------
+    /// indexer : slice of int or array-like
+    ///     The integer positions of the selected cells.
+    /// subset : RangeMOCIndex
+    ///     The resulting subset.
+    fn sel<'a>(&self, py: Python<'a>, indexer: IndexKind<'a>) -> PyResult<(IndexKind<'a>, Self)> {
+        let ordering = self.ordering();
+
+        match indexer {
+            IndexKind::Slice(pyslice) => {
+                // label slices include the stop value
+                let slice = pyslice.as_label_slice()?;
+                let start = slice.start.map_or(0, |start| ordering.rank(start));
+                let stop = slice
+                    .stop
+                    .map_or(ordering.len(), |stop| ordering.rank(stop.saturating_add(1)))
+                    .max(start);
+
+                let positional = ConcreteSlice {
+                    start: start as isize,
+                    stop: stop as isize,
+                    step: 1,
+                };
+                let new_index = Self::from_ring_ids(
+                    self.moc.depth_max(),
+                    &ordering.ring_ids(start..stop),
+                    self.ellipsoid.clone(),
+                )?;
+
+                Ok((IndexKind::Slice(positional.as_pyslice(py)?), new_index))
+            }
+            IndexKind::Array(array) => {
+                let labels = array.readonly();
+
+                let positions = labels
+                    .as_array()
+                    .iter()
+                    .map(|&hash| {
+                        let position = ordering.rank(hash);
+                        if ordering.rank(hash.saturating_add(1)) == position {
+                            return Err(PyKeyError::new_err(format!("Cannot find {hash}")));
+                        }
+
+                        Ok(position)
+                    })
+                    .collect::<PyResult<Vec<u64>>>()?;
+                let cell_ids: Vec<u64> = labels.as_array().iter().copied().collect();
+
+                let new_index =
+                    Self::from_ring_ids(self.moc.depth_max(), &cell_ids, self.ellipsoid.clone())?;
+
+                Ok((
+                    IndexKind::Array(PyArray1::from_vec(py, positions).to_dyn().clone()),
+                    new_index,
+                ))
+            }
+        }
+    }
+
+    /// Query by geometry
+    ///
+    /// Parameters
+    /// ----------
+    /// geometry : healpix_geo.geometry.Bbox or shapely.Geometry
+    ///     The geometry to query by.
+    ///
+    /// Returns
+    /// -------
+    /// slices : healpix_geo.slices.MultiConcreteSlice
+    ///     The slices necessary for extracting the subdomain, in ring order.
+    /// moc : RangeMOCIndex
+    ///     The index for the queried cell ids.
+    fn query<'py>(
+        &self,
+        py: Python<'py>,
+        geometry: &Bound<'py, PyAny>,
+    ) -> PyResult<(MultiConcreteSlice, Self)> {
+        let layer = nested::get(self.moc.depth_max());
+
+        let geom = GeometryTypes::from_pyobject(py, geometry)?;
+        let geometry_moc = geometry_moc(geom, layer, &self.ellipsoid)?;
+
+        let new_index = self.with_moc(self.moc.intersection(&geometry_moc));
+
+        // the cells of the result are in the index, so runs of consecutive ring ids
+        // are also consecutive positions
+        let ordering = self.ordering();
+        let slices = ranges_to_slices(new_index.ordering().runs().into_iter().map(|run| {
+            let start = ordering.rank(run.start);
+
+            start..start + (run.end - run.start)
+        }));
+
+        Ok((MultiConcreteSlice { slices }, new_index))
+    }
+}
@@ -0,0 +1,790 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+use numpy::{PyArray1, PyArrayMethods};
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyType};
+
+use cdshealpix as healpix;
+use healpix_geo_core::ellipsoid::Ellipsoid;
+use healpix_geo_core::scalar::zuniq::conversion;
+
+use moc::elemset::range::MocRanges;
+use moc::moc::range::RangeMOC;
+use moc::moc::{HasMaxDepth, RangeMOCIntoIterator, RangeMOCIterator};
+use moc::qty::Hpx;
+
+use super::{IndexKind, check_same_ellipsoid, ellipsoid_parameters, encoding, geometry_moc};
+use crate::ellipsoid::EllipsoidLike;
+use crate::error::into_pyerr;
+use crate::geometry::GeometryTypes;
+use crate::slice_objects::{AsSlice, ConcreteSlice, MultiConcreteSlice};
+
+fn shift(depth: u8) -> u8 {
+    (29 - depth) << 1
+}
+
+/// Consecutive cells of the same depth, in the order of the index.
+#[derive(Debug, Clone, PartialEq)]
+struct Run {
+    depth: u8,
+    start: u64,
+    end: u64,
+}
+
+impl Run {
+    fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    /// The range covered by the run, at depth 29.
+    fn range(&self) -> Range<u64> {
+        let shift = shift(self.depth);
+
+        (self.start << shift)..(self.end << shift)
+    }
+}
+
+/// range-based index of healpix cell ids following the "zuniq" scheme
+///
+/// The cells may have different depths, but must not overlap. They are stored
+/// as a multi-order list of runs of cells sorted by their position on the
+/// sphere, which is also the order of their zuniq ids. Positions refer to the
+/// cell ids in ascending order.
+#[derive(PartialEq, Debug, Clone)]
+#[pyclass(name = "RangeMOCIndex")]
+#[pyo3(module = "healpix_geo.zuniq")]
+pub struct ZuniqRangeMOCIndex {
+    runs: Vec<Run>,
+    ellipsoid: Ellipsoid,
+}
+
+impl ZuniqRangeMOCIndex {
+    /// Sort the runs, merge the ones of the same depth that overlap or touch, and
+    /// reject overlapping cells of different depths.
+    fn new(mut runs: Vec<Run>, ellipsoid: Ellipsoid) -> PyResult<Self> {
+        runs.retain(|run| run.len() > 0);
+        runs.sort_unstable_by_key(|run| (run.range().start, run.depth));
+
+        let mut merged: Vec<Run> = Vec::with_capacity(runs.len());
+        for run in runs {
+            match merged.last_mut() {
+                Some(last) if last.depth == run.depth && last.end >= run.start => {
+                    last.end = last.end.max(run.end);
+                }
+                Some(last) if last.range().end > run.range().start => {
+                    return Err(PyValueError::new_err(format!(
+                        "Cells of a zuniq index must not overlap, but cells at depth {} and {} do.",
+                        last.depth, run.depth
+                    )));
+                }
+                _ => merged.push(run),
+            }
+        }
+
+        Ok(ZuniqRangeMOCIndex {
+            runs: merged,
+            ellipsoid,
+        })
+    }
+
+    fn from_cells(cells: impl Iterator<Item = (u8, u64)>, ellipsoid: Ellipsoid) -> PyResult<Self> {
+        let runs = cells
+            .map(|(depth, hash)| Run {
+                depth,
+                start: hash,
+                end: hash + 1,
+            })
+            .collect();
+
+        Self::new(runs, ellipsoid)
+    }
+
+    fn from_zuniq_ids(cell_ids: &[u64], ellipsoid: Ellipsoid) -> PyResult<Self> {
+        let cells = cell_ids
+            .iter()
+            .map(|hash| conversion::to_nested(hash).map(|(hash, depth)| (depth, hash)))
+            .collect::<healpix_geo_core::Result<Vec<(u8, u64)>>>()
+            .map_err(into_pyerr)?;
+
+        Self::from_cells(cells.into_iter(), ellipsoid)
+    }
+
+    /// Index the cells of a MOC, i.e. the largest cells covering its area.
+    fn from_moc(moc: RangeMOC<u64, Hpx<u64>>, ellipsoid: Ellipsoid) -> PyResult<Self> {
+        let cells = moc
+            .into_range_moc_iter()
+            .cells()
+            .map(|cell| (cell.depth, cell.idx));
+
+        Self::from_cells(cells, ellipsoid)
+    }
+
+    /// The depth of the finest cells of the index.
+    fn max_depth(&self) -> u8 {
+        self.runs.iter().map(|run| run.depth).max().unwrap_or(0)
+    }
+
+    /// The area covered by the index, as a MOC at the depth of its finest cells.
+    fn coverage(&self) -> RangeMOC<u64, Hpx<u64>> {
+        let mut ranges: Vec<Range<u64>> = Vec::with_capacity(self.runs.len());
+        for range in self.runs.iter().map(Run::range) {
+            match ranges.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => ranges.push(range),
+            }
+        }
+
+        RangeMOC::new(self.max_depth(), MocRanges::new_from(ranges))
+    }
+
+    /// One MOC per depth, as stored in the serialized state.
+    fn mocs(&self) -> Vec<RangeMOC<u64, Hpx<u64>>> {
+        let mut by_depth = BTreeMap::<u8, Vec<Range<u64>>>::new();
+        for run in &self.runs {
+            by_depth.entry(run.depth).or_default().push(run.range());
+        }
+
+        by_depth
+            .into_iter()
+            .map(|(depth, ranges)| RangeMOC::new(depth, MocRanges::new_from(ranges)))
+            .collect()
+    }
+
+    fn run_offsets(&self) -> Vec<u64> {
+        self.runs
+            .iter()
+            .scan(0, |offset, run| {
+                let current = *offset;
+                *offset += run.len();
+                Some(current)
+            })
+            .collect()
+    }
+
+    fn with_runs(&self, runs: Vec<Run>) -> PyResult<Self> {
+        Self::new(runs, self.ellipsoid.clone())
+    }
+
+    /// Apply a set operation cell by cell.
+    ///
+    /// The runs of both indexes are split at every run boundary, and `op` receives
+    /// the depths of the cells of both indexes covering each piece. It returns the
+    /// depths of the cells to keep for that piece.
+    fn set_operation<F>(&self, other: &ZuniqRangeMOCIndex, op: F) -> PyResult<Self>
+    where
+        F: Fn(Option<u8>, Option<u8>) -> [Option<u8>; 2],
+    {
+        check_same_ellipsoid(&self.ellipsoid, &other.ellipsoid)?;
+
+        let mut boundaries: Vec<u64> = self
+            .runs
+            .iter()
+            .chain(other.runs.iter())
+            .flat_map(|run| {
+                let range = run.range();
+
+                [range.start, range.end]
+            })
+            .collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let covering = |runs: &[Run], index: &mut usize, position: u64| {
+            while runs
+                .get(*index)
+                .is_some_and(|run| run.range().end <= position)
+            {
+                *index += 1;
+            }
+
+            runs.get(*index)
+                .filter(|run| run.range().start <= position)
+                .map(|run| run.depth)
+        };
+
+        let (mut left, mut right) = (0, 0);
+        let mut pieces: Vec<Run> = Vec::new();
+        for piece in boundaries.windows(2) {
+            let depths = op(
+                covering(&self.runs, &mut left, piece[0]),
+                covering(&other.runs, &mut right, piece[0]),
+            );
+
+            // pieces are aligned to the cells they come from, except where cells of
+            // different depths overlap, which is then reported by `new`
+            pieces.extend(depths.into_iter().flatten().map(|depth| {
+                let shift = shift(depth);
+
+                Run {
+                    depth,
+                    start: piece[0] >> shift,
+                    end: piece[1].div_ceil(1 << shift),
+                }
+            }));
+        }
+
+        self.with_runs(pieces)
+    }
+}
+
+#[pymethods]
+impl ZuniqRangeMOCIndex {
+    /// Create a full domain index
+    ///
+    /// This is a short-cut for creating an index for the entire sphere.
+    ///
+    /// Parameters
+    /// ----------
+    /// depth : int
+    ///     The cell depth.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (depth, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn full_domain(
+        _cls: &Bound<'_, PyType>,
+        depth: u8,
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
+        let run = Run {
+            depth,
+            start: 0,
+            end: healpix::n_hash(depth),
+        };
+
+        Self::new(vec![run], ellipsoid.into_ellipsoid()?)
+    }
+
+    /// Create an empty index
+    ///
+    /// Parameters
+    /// ----------
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (*, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn create_empty(_cls: &Bound<'_, PyType>, ellipsoid: EllipsoidLike) -> PyResult<Self> {
+        Self::new(Vec::new(), ellipsoid.into_ellipsoid()?)
+    }
+
+    /// Create an index from given cell ids.
+    ///
+    /// Parameters
+    /// ----------
+    /// cell_ids : numpy.ndarray
+    ///     The zuniq cell ids to construct the the index from. May have
+    ///     different depths, but must not overlap.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (cell_ids, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn from_cell_ids<'a>(
+        _cls: &Bound<'a, PyType>,
+        cell_ids: &Bound<'a, PyArray1<u64>>,
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
+        Self::from_zuniq_ids(&cell_ids.to_vec()?, ellipsoid.into_ellipsoid()?)
+    }
+
+    /// Create an index from a FITS MOC
+    ///
+    /// The MOC must be a spatial MOC following the IVOA standard, like the ones
+    /// written by Aladin or MOCpy. The cells of the index are the cells of the
+    /// MOC, i.e. the largest cells covering its area.
+    ///
+    /// Parameters
+    /// ----------
+    /// data : bytes
+    ///     The content of the FITS file.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (data, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn from_fits(
+        _cls: &Bound<'_, PyType>,
+        data: &[u8],
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
+        Self::from_moc(encoding::moc_from_fits(data)?, ellipsoid.into_ellipsoid()?)
+    }
+
+    /// Create an index from an ASCII MOC
+    ///
+    /// The MOC must follow the ASCII serialization of the IVOA standard, e.g.
+    /// ``"3/1-5 8 4/100"``. The cells of the index are the cells of the MOC,
+    /// i.e. the largest cells covering its area.
+    ///
+    /// Parameters
+    /// ----------
+    /// data : str
+    ///     The ASCII representation of the MOC.
+    /// ellipsoid : ellipsoid-like, default: "sphere"
+    ///     Reference ellipsoid of the grid.
+    #[classmethod]
+    #[pyo3(signature = (data, *, ellipsoid = EllipsoidLike::Named(String::from("sphere"))))]
+    fn from_ascii(
+        _cls: &Bound<'_, PyType>,
+        data: &str,
+        ellipsoid: EllipsoidLike,
+    ) -> PyResult<Self> {
+        Self::from_moc(encoding::moc_from_ascii(data)?, ellipsoid.into_ellipsoid()?)
+    }
+
+    /// Compute the set union of two indexes
+    ///
+    /// Parameters
+    /// ----------
+    /// other : RangeMOCIndex
+    ///     The other index. Cells of one index must not overlap with cells
+    ///     of a different depth in the other index.
+    ///
+    /// Returns
+    /// -------
+    /// result : RangeMOCIndex
+    ///     The union of the two indexes.
+    fn union(&self, other: &ZuniqRangeMOCIndex) -> PyResult<Self> {
+        self.set_operation(other, |left, right| {
+            if left == right {
+                [left, None]
+            } else {
+                [left, right]
+            }
+        })
+    }
+
+    /// Compute the set intersection of two indexes
+    ///
+    /// Only cells that are present with the same depth in both indexes are kept.
+    ///
+    /// Parameters
+    /// ----------
+    /// other : RangeMOCIndex
+    ///     The other index.
+    ///
+    /// Returns
+    /// -------
+    /// result : RangeMOCIndex
+    ///     The intersection of the two indexes.
+    fn intersection(&self, other: &ZuniqRangeMOCIndex) -> PyResult<Self> {
+        self.set_operation(other, |left, right| {
+            if left == right {
+                [left, None]
+            } else {
+                [None, None]
+            }
+        })
+    }
+
+    /// Compute the set difference of two indexes
+    ///
+    /// Parameters
+    /// ----------
+    /// other : RangeMOCIndex
+    ///     The index to subtract.
+    ///
+    /// Returns
+    /// -------
+    /// result : RangeMOCIndex
+    ///     The set difference of the two indexes.
+    fn difference(&self, other: &ZuniqRangeMOCIndex) -> PyResult<Self> {
+        self.set_operation(other, |left, right| {
+            if left == right {
+                [None, None]
+            } else {
+                [left, None]
+            }
+        })
+    }
+
+    /// Compute the symmetric set difference of two indexes
+    ///
+    /// Parameters
+    /// ----------
+    /// other : RangeMOCIndex
+    ///     The other index.
+    ///
+    /// Returns
+    /// -------
+    /// result : RangeMOCIndex
+    ///     The symmetric set difference of the two indexes.
+    fn symmetric_difference(&self, other: &ZuniqRangeMOCIndex) -> PyResult<Self> {
+        self.set_operation(other, |left, right| {
+            if left == right {
+                [None, None]
+            } else {
+                [left, right]
+            }
+        })
+    }
+
+    /// The size of the ranges in bytes, minus any overhead.
+    #[getter]
+    fn nbytes(&self) -> u64 {
+        self.runs.len() as u64 * 2 * u64::BITS as u64 / 8
+    }
+
+    /// The number of items in the index.
+    #[getter]
+    fn size(&self) -> u64 {
+        self.runs.iter().map(Run::len).sum()
+    }
+
+    /// The depths of the cells in the index.
+    #[getter]
+    fn depths(&self) -> Vec<u8> {
+        let depths: BTreeSet<u8> = self.runs.iter().map(|run| run.depth).collect();
+
+        depths.into_iter().collect()
+    }
+
+    /// The reference ellipsoid of the index, as a dict of parameters.
+    #[getter]
+    fn ellipsoid<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        ellipsoid_parameters(py, &self.ellipsoid)
+    }
+
+    pub fn __setstate__(&mut self, state: Bound<'_, PyBytes>) -> PyResult<()> {
+        let (mocs, ellipsoid) = encoding::decode(state.as_bytes())?;
+
+        let runs = mocs
+            .iter()
+            .flat_map(|moc| {
+                let depth = moc.depth_max();
+                let shift = shift(depth);
+
+                moc.moc_ranges().iter().map(move |range| Run {
+                    depth,
+                    start: range.start >> shift,
+                    end: range.end >> shift,
+                })
+            })
+            .collect();
+
+        *self = Self::new(runs, ellipsoid)?;
+
+        Ok(())
+    }
+
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let mocs = self.mocs();
+        let mocs: Vec<&RangeMOC<u64, Hpx<u64>>> = mocs.iter().collect();
+
+        PyBytes::new(py, &encoding::encode(&mocs, &self.ellipsoid))
+    }
+
+    pub fn __reduce__(&self, py: Python) -> PyResult<(Py<PyAny>, Py<PyAny>, Py<PyAny>)> {
+        let create = py
+            .import("healpix_geo")?
+            .getattr("zuniq")?
+            .getattr("create_empty")?;
+        let args = ();
+        let state = self.__getstate__(py);
+
+        Ok((
+            create.into_pyobject(py)?.unbind().into_any(),
+            args.into_pyobject(py)?.unbind().into_any(),
+            state.into_pyobject(py)?.unbind().into_any(),
+        ))
+    }
+
+    /// Serialize the index to a FITS MOC
+    ///
+    /// MOCs describe the area covered by the cells, so cells that together form a
+    /// larger cell are merged. The ellipsoid is not part of the MOC standard and is
+    /// not stored.
+    ///
+    /// Returns
+    /// -------
+    /// data : bytes
+    ///     The content of a FITS file following the IVOA MOC standard.
+    fn to_fits<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &encoding::moc_to_fits(&self.coverage())?))
+    }
+
+    /// Serialize the index to an ASCII MOC
+    ///
+    /// MOCs describe the area covered by the cells, so cells that together form a
+    /// larger cell are merged. The ellipsoid is not part of the MOC standard and is
+    /// not stored.
+    ///
+    /// Returns
+    /// -------
+    /// data : str
+    ///     The ASCII representation of the MOC, following the IVOA MOC standard.
+    fn to_ascii(&self) -> PyResult<String> {
+        encoding::moc_to_ascii(&self.coverage())
+    }
+
+    /// Retrieve the cell ids from the index.
+    ///
+    /// Returns
+    /// -------
+    /// cell_ids : numpy.ndarray
+    ///     The zuniq cell ids contained by the index, in ascending order.
+    fn cell_ids<'a>(&self, py: Python<'a>) -> PyResult<Bound<'a, PyArray1<u64>>> {
+        let cell_ids: Vec<u64> = self
+            .runs
+            .iter()
+            .flat_map(|run| {
+                (run.start..run.end).map(move |hash| healpix::nested::to_zuniq(run.depth, hash))
+            })
+            .collect();
+
+        Ok(PyArray1::from_vec(py, cell_ids))
+    }
+
+    /// Subset the index using positions
+    ///
+    /// Parameters
+    /// ----------
+    /// indexer : slice of int or array-like
+    ///     The integer positions. Slices must have a step size of 1. If an
+    ///     array, must be of dtype uint64.
+    ///
+    /// Returns
+    /// -------
+    /// subset : RangeMOCIndex
+    ///     The resulting subset.
+    fn isel<'a>(&self, py: Python<'a>, indexer: IndexKind<'a>) -> PyResult<Self> {
+        let runs = &self.runs;
+        let offsets = self.run_offsets();
+
+        match indexer {
+            IndexKind::Slice(slice) => {
+                let concrete_slice = slice
+                    .as_positional_slice()?
+                    .as_concrete(py, self.size() as isize)?;
+                if concrete_slice.step != 1 {
+                    return Err(PyValueError::new_err(format!(
+                        "Only step size 1 is supported, got {}",
+                        concrete_slice.step
+                    )));
+                }
+                let start = concrete_slice.start.max(0) as u64;
+                let stop = concrete_slice.stop.max(0) as u64;
+
+                let selected = runs.iter().zip(offsets).map(|(run, offset)| {
+                    let run_start = run.start + start.saturating_sub(offset).min(run.len());
+                    let run_end = run.start + stop.saturating_sub(offset).min(run.len());
+
+                    Run {
+                        depth: run.depth,
+                        start: run_start,
+                        end: run_end.max(run_start),
+                    }
+                });
+
+                self.with_runs(selected.collect())
+            }
+            IndexKind::Array(array) => {
+                let positions = array.readonly();
+
+                let cells = positions
+                    .as_array()
+                    .iter()
+                    .map(|&position| {
+                        let index = offsets.partition_point(|&offset| offset <= position);
+                        match index
+                            .checked_sub(1)
+                            .map(|index| (&runs[index], offsets[index]))
+                        {
+                            Some((run, offset)) if position - offset < run.len() => {
+                                Ok((run.depth, run.start + (position - offset)))
+                            }
+                            _ => Err(PyValueError::new_err(format!(
+                                "{position} is out of bounds"
+                            ))),
+                        }
+                    })
+                    .collect::<PyResult<Vec<(u8, u64)>>>()?;
+
+                Self::from_cells(cells.into_iter(), self.ellipsoid.clone())
+            }
+        }
+    }
+
+    /// Subset the index using cell ids
+    ///
+    /// Parameters
+    /// ----------
+    /// indexer : slice of int or array-like
+    ///     The zuniq cell ids or ranges of cell ids to find. If an array, must be of dtype uint64.
+    ///
+    /// Returns
+    /// -------
+    /// indexer : slice of int or array-like
+    ///     The integer positions of the selected cells.
+    /// subset : RangeMOCIndex
+    ///     The resulting subset.
+    fn sel<'a>(&self, py: Python<'a>, indexer: IndexKind<'a>) -> PyResult<(IndexKind<'a>, Self)> {
+        let runs = &self.runs;
+        let offsets = self.run_offsets();
+
+        match indexer {
+            IndexKind::Slice(pyslice) => {
+                // label slices include the stop value
+                let slice = pyslice.as_label_slice()?;
+                let start = slice.start.unwrap_or(0);
+                let stop = slice.stop.unwrap_or(u64::MAX);
+
+                // the cells are sorted by zuniq id, so the selection is contiguous
+                let mut positions: Option<(u64, u64)> = None;
+                let selected: Vec<Run> = runs
+                    .iter()
+                    .zip(offsets)
+                    .map(|(run, offset)| {
+                        let zuniq = |hash: u64| healpix::nested::to_zuniq(run.depth, hash);
+                        let lower = run.start
+                            + partition_point(run.len(), |i| zuniq(run.start + i) < start);
+                        let upper = run.start
+                            + partition_point(run.len(), |i| zuniq(run.start + i) <= stop);
+                        let upper = upper.max(lower);
+
+                        if upper > lower {
+                            let (first, _) = positions.unwrap_or((offset + lower - run.start, 0));
+                            positions = Some((first, offset + upper - run.start));
+                        }
+
+                        Run {
+                            depth: run.depth,
+                            start: lower,
+                            end: upper,
+                        }
+                    })
+                    .collect();
+
+                let (first, last) = positions.unwrap_or((0, 0));
+                let positional = ConcreteSlice {
+                    start: first as isize,
+                    stop: last as isize,
+                    step: 1,
+                };
+                let new_index = self.with_runs(selected)?;
+
+                Ok((IndexKind::Slice(positional.as_pyslice(py)?), new_index))
+            }
+            IndexKind::Array(array) => {
+                let labels = array.readonly();
+
+                let (positions, cells): (Vec<u64>, Vec<(u8, u64)>) = labels
+                    .as_array()
+                    .iter()
+                    .map(|label| {
+                        let missing = || PyKeyError::new_err(format!("Cannot find {label}"));
+                        let (hash, depth) = conversion::to_nested(label).map_err(|_| missing())?;
+                        let cell_start = hash << shift(depth);
+
+                        let index = runs.partition_point(|run| run.range().end <= cell_start);
+                        match runs.get(index) {
+                            Some(run)
+                                if run.depth == depth && (run.start..run.end).contains(&hash) =>
+                            {
+                                Ok((offsets[index] + hash - run.start, (depth, hash)))
+                            }
+                            _ => Err(missing()),
+                        }
+                    })
+                    .collect::<PyResult<Vec<_>>>()?
+                    .into_iter()
+                    .unzip();
+
+                let new_index = Self::from_cells(cells.into_iter(), self.ellipsoid.clone())?;
+
+                Ok((
+                    IndexKind::Array(PyArray1::from_vec(py, positions).to_dyn().clone()),
+                    new_index,
+                ))
+            }
+        }
+    }
+
+    /// Query by geometry
+    ///
+    /// Cells of any depth are selected if they intersect the geometry.
+    ///
+    /// Parameters
+    /// ----------
+    /// geometry : healpix_geo.geometry.Bbox or shapely.Geometry
+    ///     The geometry to query by.
+    ///
+    /// Returns
+    /// -------
+    /// slices : healpix_geo.slices.MultiConcreteSlice
+    ///     The slices necessary for extracting the subdomain.
+    /// moc : RangeMOCIndex
+    ///     The index for the queried cell ids.
+    fn query<'py>(
+        &self,
+        py: Python<'py>,
+        geometry: &Bound<'py, PyAny>,
+    ) -> PyResult<(MultiConcreteSlice, Self)> {
+        let layer = healpix::nested::get(self.max_depth());
+
+        let geom = GeometryTypes::from_pyobject(py, geometry)?;
+        let geometry_moc = geometry_moc(geom, layer, &self.ellipsoid)?;
+        let geometry_ranges: Vec<Range<u64>> = geometry_moc.moc_ranges().iter().cloned().collect();
+
+        let runs = &self.runs;
+        let offsets = self.run_offsets();
+
+        let mut slices: Vec<ConcreteSlice> = Vec::new();
+        let mut selected: Vec<Run> = Vec::new();
+        for (run, offset) in runs.iter().zip(offsets) {
+            let shift = shift(run.depth);
+            let range = run.range();
+
+            let first = geometry_ranges.partition_point(|r| r.end <= range.start);
+            let mut covered_until = run.start;
+            for geometry_range in geometry_ranges[first..]
+                .iter()
+                .take_while(|r| r.start < range.end)
+            {
+                // any cell touched by the geometry is selected
+                let start = (geometry_range.start >> shift).max(covered_until);
+                let end = geometry_range.end.div_ceil(1 << shift).min(run.end);
+                if end <= start {
+                    continue;
+                }
+
+                let slice_start = (offset + start - run.start) as isize;
+                let slice_stop = (offset + end - run.start) as isize;
+                match slices.last_mut() {
+                    Some(slice) if slice.stop == slice_start => slice.stop = slice_stop,
+                    _ => slices.push(ConcreteSlice {
+                        start: slice_start,
+                        stop: slice_stop,
+                        step: 1,
+                    }),
+                }
+                selected.push(Run {
+                    depth: run.depth,
+                    start,
+                    end,
+                });
+                covered_until = end;
+            }
+        }
+
+        let new_index = self.with_runs(selected)?;
+
+        Ok((MultiConcreteSlice { slices }, new_index))
+    }
+}
+
+/// The number of leading items in `0..len` for which `pred` is true, assuming
+/// the predicate is true for a prefix of the range.
+fn partition_point(len: u64, pred: impl Fn(u64) -> bool) -> u64 {
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if pred(mid) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    low
+}
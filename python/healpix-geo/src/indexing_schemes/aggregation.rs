@@ -0,0 +1,31 @@
+use numpy::PyArray1;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use healpix_geo_core::vectorized::aggregation::Reduction;
+
+pub(crate) fn reduction_from_name(name: &str) -> PyResult<Reduction> {
+    match name {
+        "sum" => Ok(Reduction::Sum),
+        "mean" => Ok(Reduction::Mean),
+        "min" => Ok(Reduction::Min),
+        "max" => Ok(Reduction::Max),
+        "count" => Ok(Reduction::Count),
+        "area_weighted_mean" => Ok(Reduction::AreaWeightedMean),
+        _ => Err(PyValueError::new_err(format!(
+            "Reduction must be one of 'sum', 'mean', 'min', 'max', 'count' or 'area_weighted_mean' (got {name:?})"
+        ))),
+    }
+}
+
+/// Convert cell ids and their values to arrays.
+#[allow(clippy::type_complexity)]
+pub(crate) fn cell_value_arrays<'py>(
+    py: Python<'py>,
+    (cell_ids, values): (Vec<u64>, Vec<f64>),
+) -> (Bound<'py, PyArray1<u64>>, Bound<'py, PyArray1<f64>>) {
+    (
+        PyArray1::from_vec(py, cell_ids),
+        PyArray1::from_vec(py, values),
+    )
+}
@@ -0,0 +1,207 @@
+use std::borrow::Cow;
+
+use numpy::ndarray::{Dimension, IxDyn};
+use numpy::{Element, PyReadonlyArray, PyUntypedArrayMethods};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+
+/// The order in which the elements of an array are read and written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MemoryOrder {
+    C,
+    Fortran,
+}
+
+impl MemoryOrder {
+    /// The order shared by the given inputs, falling back to C order if they differ.
+    pub fn common(orders: &[MemoryOrder]) -> Self {
+        if !orders.is_empty() && orders.iter().all(|order| *order == Self::Fortran) {
+            Self::Fortran
+        } else {
+            Self::C
+        }
+    }
+
+    pub fn is_fortran(&self) -> bool {
+        *self == Self::Fortran
+    }
+
+    fn of<T: Element, D: Dimension>(array: &PyReadonlyArray<'_, T, D>) -> Self {
+        if array.is_fortran_contiguous() && !array.is_c_contiguous() {
+            Self::Fortran
+        } else {
+            Self::C
+        }
+    }
+}
+
+/// The elements of `array` in the given order, borrowed if they already are in that order.
+pub(crate) fn elements<'a, T, D>(
+    array: &'a PyReadonlyArray<'_, T, D>,
+    order: MemoryOrder,
+) -> Cow<'a, [T]>
+where
+    T: Element + Copy,
+    D: Dimension,
+{
+    let contiguous = match order {
+        MemoryOrder::C => array.is_c_contiguous(),
+        MemoryOrder::Fortran => array.is_fortran_contiguous(),
+    };
+    if contiguous && let Ok(slice) = array.as_slice() {
+        return Cow::Borrowed(slice);
+    }
+
+    let view = array.as_array();
+    match order {
+        MemoryOrder::C => Cow::Owned(view.iter().copied().collect()),
+        MemoryOrder::Fortran => Cow::Owned(view.t().iter().copied().collect()),
+    }
+}
+
+/// The number of dimensions required by `D`, for error messages.
+fn dimensions<D: Dimension>() -> String {
+    D::NDIM.map(|ndim| format!("{ndim}-d ")).unwrap_or_default()
+}
+
+/// Cell ids given as an array of any supported integer dtype and memory layout.
+pub(crate) enum CellIds<'py, D: Dimension = IxDyn> {
+    UInt64(PyReadonlyArray<'py, u64, D>),
+    Int64(PyReadonlyArray<'py, i64, D>),
+    UInt32(PyReadonlyArray<'py, u32, D>),
+    Int32(PyReadonlyArray<'py, i32, D>),
+}
+
+impl<'a, 'py, D: Dimension + 'a> FromPyObject<'a, 'py> for CellIds<'py, D> {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        if let Ok(array) = obj.extract() {
+            Ok(Self::UInt64(array))
+        } else if let Ok(array) = obj.extract() {
+            Ok(Self::Int64(array))
+        } else if let Ok(array) = obj.extract() {
+            Ok(Self::UInt32(array))
+        } else if let Ok(array) = obj.extract() {
+            Ok(Self::Int32(array))
+        } else {
+            Err(PyTypeError::new_err(format!(
+                "cell ids must be a {}array of uint64, int64, uint32 or int32",
+                dimensions::<D>()
+            )))
+        }
+    }
+}
+
+impl<D: Dimension> CellIds<'_, D> {
+    pub fn shape(&self) -> &[usize] {
+        match self {
+            Self::UInt64(array) => array.shape(),
+            Self::Int64(array) => array.shape(),
+            Self::UInt32(array) => array.shape(),
+            Self::Int32(array) => array.shape(),
+        }
+    }
+
+    pub fn order(&self) -> MemoryOrder {
+        match self {
+            Self::UInt64(array) => MemoryOrder::of(array),
+            Self::Int64(array) => MemoryOrder::of(array),
+            Self::UInt32(array) => MemoryOrder::of(array),
+            Self::Int32(array) => MemoryOrder::of(array),
+        }
+    }
+
+    /// The cell ids as `u64` in the given order.
+    ///
+    /// Only copies if the array has a different dtype or layout.
+    pub fn values(&self, order: MemoryOrder) -> PyResult<Cow<'_, [u64]>> {
+        self.convert(order, || {
+            Err(PyValueError::new_err("cell ids must be non-negative"))
+        })
+    }
+
+    /// Like [`values`](Self::values), but negative cell ids are replaced by `u64::MAX`,
+    /// which is not a valid cell id in any scheme.
+    pub fn values_or_invalid(&self, order: MemoryOrder) -> PyResult<Cow<'_, [u64]>> {
+        self.convert(order, || Ok(u64::MAX))
+    }
+
+    fn convert(
+        &self,
+        order: MemoryOrder,
+        negative: impl Fn() -> PyResult<u64>,
+    ) -> PyResult<Cow<'_, [u64]>> {
+        fn convert<T: TryInto<u64> + Copy>(
+            values: &[T],
+            negative: impl Fn() -> PyResult<u64>,
+        ) -> PyResult<Cow<'static, [u64]>> {
+            values
+                .iter()
+                .map(|value| (*value).try_into().or_else(|_| negative()))
+                .collect::<PyResult<Vec<_>>>()
+                .map(Cow::Owned)
+        }
+
+        match self {
+            Self::UInt64(array) => Ok(elements(array, order)),
+            Self::Int64(array) => convert(&elements(array, order), negative),
+            Self::UInt32(array) => convert(&elements(array, order), negative),
+            Self::Int32(array) => convert(&elements(array, order), negative),
+        }
+    }
+}
+
+/// Coordinates given as a floating point array of any memory layout.
+pub(crate) enum Coordinates<'py, D: Dimension = IxDyn> {
+    Float64(PyReadonlyArray<'py, f64, D>),
+    Float32(PyReadonlyArray<'py, f32, D>),
+}
+
+impl<'a, 'py, D: Dimension + 'a> FromPyObject<'a, 'py> for Coordinates<'py, D> {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        if let Ok(array) = obj.extract() {
+            Ok(Self::Float64(array))
+        } else if let Ok(array) = obj.extract() {
+            Ok(Self::Float32(array))
+        } else {
+            Err(PyTypeError::new_err(format!(
+                "coordinates must be a {}array of float64 or float32",
+                dimensions::<D>()
+            )))
+        }
+    }
+}
+
+impl<D: Dimension> Coordinates<'_, D> {
+    pub fn shape(&self) -> &[usize] {
+        match self {
+            Self::Float64(array) => array.shape(),
+            Self::Float32(array) => array.shape(),
+        }
+    }
+
+    pub fn order(&self) -> MemoryOrder {
+        match self {
+            Self::Float64(array) => MemoryOrder::of(array),
+            Self::Float32(array) => MemoryOrder::of(array),
+        }
+    }
+
+    /// The coordinates as `f64` in the given order.
+    ///
+    /// Only copies if the array has a different dtype or layout.
+    pub fn values(&self, order: MemoryOrder) -> Cow<'_, [f64]> {
+        match self {
+            Self::Float64(array) => elements(array, order),
+            Self::Float32(array) => Cow::Owned(
+                elements(array, order)
+                    .iter()
+                    .map(|v| f64::from(*v))
+                    .collect(),
+            ),
+        }
+    }
+}
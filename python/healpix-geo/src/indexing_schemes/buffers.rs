@@ -0,0 +1,53 @@
+use numpy::npyffi::NPY_ORDER;
+use numpy::{Element, PyArray1, PyArrayDyn, PyArrayMethods, PyUntypedArrayMethods};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::indexing_schemes::arrays::MemoryOrder;
+
+/// The array to write results into: either the caller-provided buffer or a new array.
+///
+/// Buffers must have the given shape and be contiguous in the given order.
+pub(crate) fn output_array<'py, T: Element>(
+    py: Python<'py>,
+    out: Option<Bound<'py, PyArrayDyn<T>>>,
+    shape: &[usize],
+    order: MemoryOrder,
+) -> PyResult<Bound<'py, PyArrayDyn<T>>> {
+    let Some(out) = out else {
+        return Ok(PyArrayDyn::zeros(py, shape, order.is_fortran()));
+    };
+
+    if out.shape() != shape {
+        return Err(PyValueError::new_err(format!(
+            "output buffer has the wrong shape: expected {shape:?}, got {:?}",
+            out.shape()
+        )));
+    }
+    let contiguous = match order {
+        MemoryOrder::C => out.is_c_contiguous(),
+        MemoryOrder::Fortran => out.is_fortran_contiguous(),
+    };
+    if !contiguous {
+        return Err(PyValueError::new_err(format!(
+            "output buffer must be contiguous in {order:?} order"
+        )));
+    }
+
+    Ok(out)
+}
+
+/// Wrap values computed in the given order into an array of the given shape.
+pub(crate) fn to_array<'py, T: Element>(
+    py: Python<'py>,
+    values: Vec<T>,
+    shape: &[usize],
+    order: MemoryOrder,
+) -> PyResult<Bound<'py, PyArrayDyn<T>>> {
+    let order = match order {
+        MemoryOrder::C => NPY_ORDER::NPY_CORDER,
+        MemoryOrder::Fortran => NPY_ORDER::NPY_FORTRANORDER,
+    };
+
+    PyArray1::from_vec(py, values).reshape_with_order(shape, order)
+}
@@ -1,23 +1,23 @@
-use numpy::{PyArrayDyn, PyArrayMethods};
+use numpy::PyReadonlyArrayDyn;
 use pyo3::prelude::*;
 
+use crate::indexing_schemes::arrays::{MemoryOrder, elements};
 use healpix_geo_core::vectorized::depth::DepthLike as Depth;
 
 #[derive(FromPyObject)]
-pub(crate) enum DepthLike {
+pub(crate) enum DepthLike<'py> {
     Constant(u8),
-    Array(Py<PyArrayDyn<u8>>),
+    Array(PyReadonlyArrayDyn<'py, u8>),
 }
 
-impl DepthLike {
-    pub fn into_depth(self, py: Python) -> PyResult<Depth> {
+impl DepthLike<'_> {
+    /// Call `f` with the depths read in the given order.
+    ///
+    /// The depths are only copied if the array is not contiguous in that order.
+    pub fn with_depth<R>(&self, order: MemoryOrder, f: impl FnOnce(Depth<'_>) -> R) -> R {
         match self {
-            Self::Constant(depth) => Ok(Depth::Scalar(depth)),
-            Self::Array(depths) => {
-                let bound = depths.bind(py);
-                let depths_: Vec<u8> = bound.to_vec()?;
-                Ok(Depth::Array(depths_))
-            }
+            Self::Constant(depth) => f(Depth::Scalar(*depth)),
+            Self::Array(depths) => f(Depth::Array(&elements(depths, order))),
         }
     }
 }
@@ -0,0 +1,107 @@
+use numpy::ndarray::{Dimension, Ix1, Ix2};
+use numpy::{PyArray1, PyArray2};
+use pyo3::prelude::*;
+
+use crate::ellipsoid::EllipsoidLike;
+use crate::error::into_pyerr;
+use crate::indexing_schemes::arrays::{Coordinates, MemoryOrder};
+
+/// Split geodesic results into distance, forward azimuth and back azimuth arrays.
+#[allow(clippy::type_complexity)]
+pub(crate) fn geodesic_arrays<'py>(
+    py: Python<'py>,
+    result: Vec<Vec<(f64, f64, f64)>>,
+) -> PyResult<(
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+)> {
+    let (distance, (forward, back)): (Vec<Vec<f64>>, (Vec<Vec<f64>>, Vec<Vec<f64>>)) = result
+        .into_iter()
+        .map(|row| -> (Vec<f64>, (Vec<f64>, Vec<f64>)) {
+            row.into_iter()
+                .map(|(distance, forward, back)| (distance, (forward, back)))
+                .unzip()
+        })
+        .unzip();
+
+    Ok((
+        PyArray2::from_vec2(py, &distance)?,
+        PyArray2::from_vec2(py, &forward)?,
+        PyArray2::from_vec2(py, &back)?,
+    ))
+}
+
+/// Flatten the neighbours of each cell into cell ids and distances.
+///
+/// The neighbours of the `i`-th cell are between the `i`-th and `i + 1`-th offsets.
+#[allow(clippy::type_complexity)]
+pub(crate) fn ragged_arrays<'py>(
+    py: Python<'py>,
+    result: Vec<Vec<(u64, f64)>>,
+) -> PyResult<(
+    Bound<'py, PyArray1<u64>>,
+    Bound<'py, PyArray1<f64>>,
+    Bound<'py, PyArray1<i64>>,
+)> {
+    let offsets = std::iter::once(0)
+        .chain(result.iter().scan(0, |offset, row| {
+            *offset += row.len() as i64;
+            Some(*offset)
+        }))
+        .collect::<Vec<i64>>();
+    let (neighbours, distances): (Vec<u64>, Vec<f64>) = result.into_iter().flatten().unzip();
+
+    Ok((
+        PyArray1::from_vec(py, neighbours),
+        PyArray1::from_vec(py, distances),
+        PyArray1::from_vec(py, offsets),
+    ))
+}
+
+/// Pair up longitudes and latitudes, in C order.
+pub(crate) fn to_points<D: Dimension>(
+    longitude: &Coordinates<'_, D>,
+    latitude: &Coordinates<'_, D>,
+) -> Vec<(f64, f64)> {
+    let lon = longitude.values(MemoryOrder::C);
+    let lat = latitude.values(MemoryOrder::C);
+
+    lon.iter()
+        .zip(lat.iter())
+        .map(|(&lon, &lat)| (lon, lat))
+        .collect()
+}
+
+/// Geodesic distances between points, with forward and back azimuths.
+///
+/// Every point of `from_*` is paired with a row of `to_*`.
+#[allow(clippy::type_complexity)]
+#[pyfunction]
+#[pyo3(signature = (from_longitude, from_latitude, to_longitude, to_latitude, ellipsoid_like, nthreads=0))]
+pub(crate) fn geodesic_distances<'py>(
+    py: Python<'py>,
+    from_longitude: Coordinates<'py, Ix1>,
+    from_latitude: Coordinates<'py, Ix1>,
+    to_longitude: Coordinates<'py, Ix2>,
+    to_latitude: Coordinates<'py, Ix2>,
+    ellipsoid_like: EllipsoidLike,
+    nthreads: u16,
+) -> PyResult<(
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+)> {
+    use healpix_geo_core::vectorized::distances as vectorized;
+
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let cols = to_longitude.shape()[1];
+
+    let from = to_points(&from_longitude, &from_latitude);
+    let to = to_points(&to_longitude, &to_latitude);
+
+    let result = vectorized::geodesic_distances(&from, &to, cols, &ellipsoid, nthreads as usize)
+        .map_err(into_pyerr)?;
+
+    geodesic_arrays(py, result)
+}
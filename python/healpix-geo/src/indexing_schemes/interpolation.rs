@@ -0,0 +1,21 @@
+use numpy::PyArrayDyn;
+use pyo3::prelude::*;
+
+use crate::indexing_schemes::arrays::MemoryOrder;
+use crate::indexing_schemes::buffers::to_array;
+
+/// Split the weights of each point into arrays of cell ids and weights.
+#[allow(clippy::type_complexity)]
+pub(crate) fn weight_arrays<'py>(
+    py: Python<'py>,
+    weights: Vec<[(u64, f64); 4]>,
+    shape: &[usize],
+) -> PyResult<(Bound<'py, PyArrayDyn<u64>>, Bound<'py, PyArrayDyn<f64>>)> {
+    let shape: Vec<usize> = shape.iter().copied().chain([4]).collect();
+    let (cell_ids, weights): (Vec<u64>, Vec<f64>) = weights.into_iter().flatten().unzip();
+
+    Ok((
+        to_array(py, cell_ids, &shape, MemoryOrder::C)?,
+        to_array(py, weights, &shape, MemoryOrder::C)?,
+    ))
+}
@@ -2,4 +2,12 @@ pub(crate) mod nested;
 pub(crate) mod ring;
 pub(crate) mod zuniq;
 
+pub(crate) mod aggregation;
+pub(crate) mod arrays;
+pub(crate) mod buffers;
 pub(crate) mod depth;
+pub(crate) mod distances;
+pub(crate) mod interpolation;
+pub(crate) mod rasterization;
+pub(crate) mod regridding;
+pub(crate) mod validity;
@@ -1,64 +1,137 @@
-use crate::ellipsoid::EllipsoidLike;
+use crate::crs::{
+    CrsLike, cartesian_arrays, common_order, projected_arrays, vertices_arrays,
+    vertices_cartesian_arrays,
+};
+use crate::ellipsoid::{EllipsoidLike, latitude_kind_from_name};
+use crate::error::into_pyerr;
+use crate::indexing_schemes::arrays::{CellIds, Coordinates, MemoryOrder};
+use crate::indexing_schemes::buffers::{output_array, to_array};
+use crate::indexing_schemes::distances::{geodesic_arrays, ragged_arrays, to_points};
+use crate::indexing_schemes::validity::OnInvalid;
 
 use cdshealpix as healpix;
-use numpy::{PyArray1, PyArray2, PyArrayDyn, PyArrayMethods, PyUntypedArrayMethods};
+use numpy::ndarray::{Ix1, Ix2};
+use numpy::{PyArray1, PyArray2, PyArrayDyn, PyArrayMethods};
 use pyo3::prelude::*;
 
 use healpix_geo_core::vectorized::nested::coordinates as vectorized;
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 #[pyfunction]
+#[pyo3(signature = (depth, ipix, ellipsoid_like, nthreads, out=None, on_invalid="raise", latitude_kind="geographic"))]
 pub(crate) fn healpix_to_lonlat<'py>(
     py: Python<'py>,
     depth: u8,
-    ipix: &Bound<'py, PyArrayDyn<u64>>,
+    ipix: CellIds<'py>,
     ellipsoid_like: EllipsoidLike,
     nthreads: u16,
-) -> PyResult<(Bound<'py, PyArrayDyn<f64>>, Bound<'py, PyArrayDyn<f64>>)> {
+    out: Option<(Bound<'py, PyArrayDyn<f64>>, Bound<'py, PyArrayDyn<f64>>)>,
+    on_invalid: &str,
+    latitude_kind: &str,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+    Option<Bound<'py, PyArrayDyn<bool>>>,
+)> {
     let ellipsoid = ellipsoid_like.into_ellipsoid()?;
-    let input_shape = ipix.shape();
+    let on_invalid = OnInvalid::from_name(on_invalid)?;
+    let latitude_kind = latitude_kind_from_name(latitude_kind)?;
+    let order = ipix.order();
+
+    let (out_lon, out_lat) = out.unzip();
+    let longitude = output_array(py, out_lon, ipix.shape(), order)?;
+    let latitude = output_array(py, out_lat, ipix.shape(), order)?;
 
     let layer = healpix::nested::get(depth);
 
-    let ipix_ = ipix.readonly();
+    {
+        let ipix_ = on_invalid.cell_ids(&ipix, order)?;
+        let mut lon = longitude.readwrite();
+        let mut lat = latitude.readwrite();
 
-    let (lon, lat): (Vec<f64>, Vec<f64>) =
-        vectorized::healpix_to_lonlat(ipix_.as_slice()?, layer, &ellipsoid, nthreads as usize)
-            .into_iter()
-            .unzip();
+        vectorized::healpix_to_lonlat_into(
+            &ipix_,
+            layer,
+            &ellipsoid,
+            latitude_kind,
+            lon.as_slice_mut()?,
+            lat.as_slice_mut()?,
+            on_invalid.policy(),
+            nthreads as usize,
+        )
+        .map_err(into_pyerr)?;
+    }
 
-    Ok((
-        PyArray1::from_vec(py, lon).reshape(input_shape)?,
-        PyArray1::from_vec(py, lat).reshape(input_shape)?,
-    ))
+    let mask = on_invalid.mask(py, &longitude, order, |lon| lon.is_nan())?;
+
+    Ok((longitude, latitude, mask))
 }
 
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 #[pyfunction]
+#[pyo3(signature = (depth, longitude, latitude, ellipsoid_like, nthreads, out=None, on_invalid="raise", latitude_kind="geographic"))]
 pub(crate) fn lonlat_to_healpix<'py>(
     py: Python<'py>,
     depth: u8,
-    longitude: &Bound<'py, PyArrayDyn<f64>>,
-    latitude: &Bound<'py, PyArrayDyn<f64>>,
+    longitude: Coordinates<'py>,
+    latitude: Coordinates<'py>,
     ellipsoid_like: EllipsoidLike,
     nthreads: u16,
-) -> PyResult<Bound<'py, PyArrayDyn<u64>>> {
+    out: Option<Bound<'py, PyArrayDyn<u64>>>,
+    on_invalid: &str,
+    latitude_kind: &str,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<u64>>,
+    Option<Bound<'py, PyArrayDyn<bool>>>,
+)> {
     let ellipsoid = ellipsoid_like.into_ellipsoid()?;
-    let input_shape = longitude.shape();
+    let on_invalid = OnInvalid::from_name(on_invalid)?;
+    let latitude_kind = latitude_kind_from_name(latitude_kind)?;
+    let order = MemoryOrder::common(&[longitude.order(), latitude.order()]);
+
+    let ipix = output_array(py, out, longitude.shape(), order)?;
+
+    let layer = healpix::nested::get(depth);
+
+    {
+        let lon = longitude.values(order);
+        let lat = latitude.values(order);
+        let mut ipix_ = ipix.readwrite();
+
+        vectorized::lonlat_to_healpix_into(
+            &lon,
+            &lat,
+            layer,
+            &ellipsoid,
+            latitude_kind,
+            ipix_.as_slice_mut()?,
+            on_invalid.policy(),
+            nthreads as usize,
+        )
+        .map_err(into_pyerr)?;
+    }
+
+    let mask = on_invalid.mask(py, &ipix, order, |hash| *hash == u64::MAX)?;
+
+    Ok((ipix, mask))
+}
 
-    let lon = longitude.readonly();
-    let lat = latitude.readonly();
-    let coords: Vec<(f64, f64)> = lon
-        .as_slice()?
-        .iter()
-        .zip(lat.as_slice()?)
-        .map(|(&lon, &lat)| (lon, lat))
-        .collect();
+#[pyfunction]
+#[pyo3(signature = (depth, ipix, nthreads=0))]
+pub(crate) fn is_valid_cell<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py>,
+    nthreads: u16,
+) -> PyResult<Bound<'py, PyArrayDyn<bool>>> {
+    let order = ipix.order();
+    let ipix_ = ipix.values_or_invalid(order)?;
 
     let layer = healpix::nested::get(depth);
 
-    let ipix = vectorized::lonlat_to_healpix(&coords, layer, &ellipsoid, nthreads as usize);
+    let valid = vectorized::is_valid_cell(&ipix_, layer, nthreads as usize);
 
-    PyArray1::from_vec(py, ipix).reshape(input_shape)
+    to_array(py, valid, ipix.shape(), order)
 }
 
 #[allow(clippy::type_complexity)]
@@ -67,7 +140,7 @@ pub(crate) fn lonlat_to_healpix<'py>(
 pub(crate) fn vertices<'py>(
     py: Python<'py>,
     depth: u8,
-    ipix: &Bound<'py, PyArrayDyn<u64>>,
+    ipix: CellIds<'py>,
     ellipsoid_like: EllipsoidLike,
     step: usize,
     nthreads: u16,
@@ -75,17 +148,13 @@ pub(crate) fn vertices<'py>(
     let ellipsoid = ellipsoid_like.into_ellipsoid()?;
     let input_shape: &[usize] = ipix.shape();
 
-    let ipix_ = ipix.readonly();
+    let ipix_ = ipix.values(MemoryOrder::C)?;
 
     let layer = healpix::nested::get(depth);
 
-    let vertices: Vec<Vec<(f64, f64)>> = vectorized::vertices(
-        ipix_.as_slice()?,
-        layer,
-        &ellipsoid,
-        step,
-        nthreads as usize,
-    );
+    let vertices: Vec<Vec<(f64, f64)>> =
+        vectorized::vertices(&ipix_, layer, &ellipsoid, step, nthreads as usize)
+            .map_err(into_pyerr)?;
 
     let (lon, lat): (Vec<Vec<f64>>, Vec<Vec<f64>>) = vertices
         .into_iter()
@@ -100,30 +169,408 @@ pub(crate) fn vertices<'py>(
     Ok((longitude, latitude))
 }
 
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (depth, x, y, z, crs, ellipsoid_like, nthreads, on_invalid="raise"))]
+pub(crate) fn projected_to_healpix<'py>(
+    py: Python<'py>,
+    depth: u8,
+    x: Coordinates<'py>,
+    y: Coordinates<'py>,
+    z: Option<Coordinates<'py>>,
+    crs: CrsLike,
+    ellipsoid_like: EllipsoidLike,
+    nthreads: u16,
+    on_invalid: &str,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<u64>>,
+    Option<Bound<'py, PyArrayDyn<bool>>>,
+)> {
+    let crs = crs.into_crs()?;
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let on_invalid = OnInvalid::from_name(on_invalid)?;
+    let order = common_order(&x, &y, z.as_ref());
+
+    let layer = healpix::nested::get(depth);
+
+    let x_ = x.values(order);
+    let y_ = y.values(order);
+    let z_ = z.as_ref().map(|z| z.values(order));
+
+    let ipix = vectorized::projected_to_healpix(
+        &x_,
+        &y_,
+        z_.as_deref(),
+        &crs,
+        layer,
+        &ellipsoid,
+        on_invalid.policy(),
+        nthreads as usize,
+    )
+    .map_err(into_pyerr)?;
+
+    let ipix = to_array(py, ipix, x.shape(), order)?;
+    let mask = on_invalid.mask(py, &ipix, order, |hash| *hash == u64::MAX)?;
+
+    Ok((ipix, mask))
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (depth, ipix, crs, ellipsoid_like, nthreads, on_invalid="raise"))]
+pub(crate) fn healpix_to_projected<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py>,
+    crs: CrsLike,
+    ellipsoid_like: EllipsoidLike,
+    nthreads: u16,
+    on_invalid: &str,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+    Option<Bound<'py, PyArrayDyn<f64>>>,
+    Option<Bound<'py, PyArrayDyn<bool>>>,
+)> {
+    let crs = crs.into_crs()?;
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let on_invalid = OnInvalid::from_name(on_invalid)?;
+    let order = ipix.order();
+    let ipix_ = on_invalid.cell_ids(&ipix, order)?;
+
+    let layer = healpix::nested::get(depth);
+
+    let projected = vectorized::healpix_to_projected(
+        &ipix_,
+        layer,
+        &crs,
+        &ellipsoid,
+        on_invalid.policy(),
+        nthreads as usize,
+    )
+    .map_err(into_pyerr)?;
+
+    let (x, y, z) = projected_arrays(py, projected, &crs, ipix.shape(), order)?;
+    let mask = on_invalid.mask(py, &x, order, |x| x.is_nan())?;
+
+    Ok((x, y, z, mask))
+}
+
+#[allow(clippy::type_complexity)]
+#[pyfunction]
+#[pyo3(signature = (depth, ipix, crs, ellipsoid_like, step=1, nthreads=0))]
+pub(crate) fn vertices_projected<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py>,
+    crs: CrsLike,
+    ellipsoid_like: EllipsoidLike,
+    step: usize,
+    nthreads: u16,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+    Option<Bound<'py, PyArrayDyn<f64>>>,
+)> {
+    let crs = crs.into_crs()?;
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+
+    let layer = healpix::nested::get(depth);
+
+    let vertices =
+        vectorized::vertices_projected(&ipix_, layer, &crs, &ellipsoid, step, nthreads as usize)
+            .map_err(into_pyerr)?;
+
+    vertices_arrays(py, vertices, &crs, ipix.shape(), step)
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (depth, x, y, z, ellipsoid_like, nthreads, on_invalid="raise"))]
+pub(crate) fn xyz_to_healpix<'py>(
+    py: Python<'py>,
+    depth: u8,
+    x: Coordinates<'py>,
+    y: Coordinates<'py>,
+    z: Coordinates<'py>,
+    ellipsoid_like: EllipsoidLike,
+    nthreads: u16,
+    on_invalid: &str,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<u64>>,
+    Option<Bound<'py, PyArrayDyn<bool>>>,
+)> {
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let on_invalid = OnInvalid::from_name(on_invalid)?;
+    let order = MemoryOrder::common(&[x.order(), y.order(), z.order()]);
+
+    let layer = healpix::nested::get(depth);
+
+    let x_ = x.values(order);
+    let y_ = y.values(order);
+    let z_ = z.values(order);
+
+    let ipix = vectorized::xyz_to_healpix(
+        &x_,
+        &y_,
+        &z_,
+        layer,
+        &ellipsoid,
+        on_invalid.policy(),
+        nthreads as usize,
+    )
+    .map_err(into_pyerr)?;
+    let ipix = to_array(py, ipix, x.shape(), order)?;
+    let mask = on_invalid.mask(py, &ipix, order, |hash| *hash == u64::MAX)?;
+
+    Ok((ipix, mask))
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (depth, ipix, ellipsoid_like, height, nthreads, on_invalid="raise"))]
+pub(crate) fn healpix_to_xyz<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py>,
+    ellipsoid_like: EllipsoidLike,
+    height: f64,
+    nthreads: u16,
+    on_invalid: &str,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+    Option<Bound<'py, PyArrayDyn<bool>>>,
+)> {
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let on_invalid = OnInvalid::from_name(on_invalid)?;
+    let order = ipix.order();
+    let ipix_ = on_invalid.cell_ids(&ipix, order)?;
+
+    let layer = healpix::nested::get(depth);
+
+    let cartesian = vectorized::healpix_to_xyz(
+        &ipix_,
+        layer,
+        &ellipsoid,
+        height,
+        on_invalid.policy(),
+        nthreads as usize,
+    )
+    .map_err(into_pyerr)?;
+
+    let (x, y, z) = cartesian_arrays(py, cartesian, ipix.shape(), order)?;
+    let mask = on_invalid.mask(py, &x, order, |x| x.is_nan())?;
+
+    Ok((x, y, z, mask))
+}
+
+#[allow(clippy::type_complexity)]
+#[pyfunction]
+#[pyo3(signature = (depth, ipix, ellipsoid_like, height=0.0, step=1, nthreads=0))]
+pub(crate) fn vertices_xyz<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py>,
+    ellipsoid_like: EllipsoidLike,
+    height: f64,
+    step: usize,
+    nthreads: u16,
+) -> PyResult<(
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+    Bound<'py, PyArrayDyn<f64>>,
+)> {
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+
+    let layer = healpix::nested::get(depth);
+
+    let vertices =
+        vectorized::vertices_xyz(&ipix_, layer, &ellipsoid, height, step, nthreads as usize)
+            .map_err(into_pyerr)?;
+
+    vertices_cartesian_arrays(py, vertices, ipix.shape(), step)
+}
+
 /// Wrapper of `UnitVect3.ang_dist`
 /// The given array must be of the same size as `ipix`.
 #[pyfunction]
 pub(crate) fn angular_distances<'py>(
     py: Python<'py>,
     depth: u8,
-    from: &Bound<'py, PyArray1<u64>>,
-    to: &Bound<'py, PyArray2<u64>>,
+    from: CellIds<'py, Ix1>,
+    to: CellIds<'py, Ix2>,
     nthreads: u16,
 ) -> PyResult<Bound<'py, PyArray2<f64>>> {
     use healpix_geo_core::vectorized::nested::distances as vectorized;
 
-    let from_ = from.readonly();
-    let to_ = to.readonly();
+    let from_ = from.values(MemoryOrder::C)?;
+    let to_ = to.values(MemoryOrder::C)?;
+    let cols = to.shape()[1];
+
+    let layer = cdshealpix::nested::get(depth);
+    let result = vectorized::angular_distances(&from_, &to_, cols, layer, nthreads as usize)
+        .map_err(into_pyerr)?;
+
+    Ok(PyArray2::from_vec2(py, &result)?)
+}
+
+#[pyfunction]
+#[pyo3(signature = (depth, ipix, ellipsoid_like, nthreads=0))]
+pub(crate) fn cell_area<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py>,
+    ellipsoid_like: EllipsoidLike,
+    nthreads: u16,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    use healpix_geo_core::vectorized::nested::measures;
+
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let order = ipix.order();
+    let ipix_ = ipix.values(order)?;
+
+    let layer = healpix::nested::get(depth);
+
+    let area =
+        measures::cell_area(&ipix_, layer, &ellipsoid, nthreads as usize).map_err(into_pyerr)?;
+
+    to_array(py, area, ipix.shape(), order)
+}
+
+#[pyfunction]
+#[pyo3(signature = (depth, ipix, ellipsoid_like, step=1, nthreads=0))]
+pub(crate) fn cell_perimeter<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py>,
+    ellipsoid_like: EllipsoidLike,
+    step: usize,
+    nthreads: u16,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    use healpix_geo_core::vectorized::nested::measures;
+
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let order = ipix.order();
+    let ipix_ = ipix.values(order)?;
+
+    let layer = healpix::nested::get(depth);
+
+    let perimeter = measures::cell_perimeter(&ipix_, layer, &ellipsoid, step, nthreads as usize)
+        .map_err(into_pyerr)?;
+
+    to_array(py, perimeter, ipix.shape(), order)
+}
+
+/// Geodesic distances between cell centers, with forward and back azimuths.
+///
+/// Follows the same layout as `angular_distances`.
+#[allow(clippy::type_complexity)]
+#[pyfunction]
+#[pyo3(signature = (depth, from, to, ellipsoid_like, nthreads=0))]
+pub(crate) fn geodesic_distances<'py>(
+    py: Python<'py>,
+    depth: u8,
+    from: CellIds<'py, Ix1>,
+    to: CellIds<'py, Ix2>,
+    ellipsoid_like: EllipsoidLike,
+    nthreads: u16,
+) -> PyResult<(
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+)> {
+    use healpix_geo_core::vectorized::nested::distances as vectorized;
+
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let from_ = from.values(MemoryOrder::C)?;
+    let to_ = to.values(MemoryOrder::C)?;
     let cols = to.shape()[1];
 
     let layer = cdshealpix::nested::get(depth);
-    let result = vectorized::angular_distances(
-        from_.as_slice()?,
-        to_.as_slice()?,
+    let result =
+        vectorized::geodesic_distances(&from_, &to_, cols, layer, &ellipsoid, nthreads as usize)
+            .map_err(into_pyerr)?;
+
+    geodesic_arrays(py, result)
+}
+
+/// Geodesic distances between cell centers and points, with forward and back azimuths.
+///
+/// `longitude` and `latitude` must have one row per cell of `from`.
+#[allow(clippy::type_complexity)]
+#[pyfunction]
+#[pyo3(signature = (depth, from, longitude, latitude, ellipsoid_like, nthreads=0))]
+pub(crate) fn geodesic_distances_to_points<'py>(
+    py: Python<'py>,
+    depth: u8,
+    from: CellIds<'py, Ix1>,
+    longitude: Coordinates<'py, Ix2>,
+    latitude: Coordinates<'py, Ix2>,
+    ellipsoid_like: EllipsoidLike,
+    nthreads: u16,
+) -> PyResult<(
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+)> {
+    use healpix_geo_core::vectorized::nested::distances as vectorized;
+
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let from_ = from.values(MemoryOrder::C)?;
+    let cols = longitude.shape()[1];
+    let points = to_points(&longitude, &latitude);
+
+    let layer = cdshealpix::nested::get(depth);
+    let result = vectorized::geodesic_distances_to_points(
+        &from_,
+        &points,
         cols,
         layer,
+        &ellipsoid,
         nthreads as usize,
-    );
+    )
+    .map_err(into_pyerr)?;
 
-    Ok(PyArray2::from_vec2(py, &result)?)
+    geodesic_arrays(py, result)
+}
+
+/// The cells whose center is within `radius` of the center of each cell.
+///
+/// Returns the neighbours and their geodesic distances, flattened, with the offsets of
+/// the neighbours of each cell.
+#[allow(clippy::type_complexity)]
+#[pyfunction]
+#[pyo3(signature = (depth, ipix, radius, ellipsoid_like, nthreads=0))]
+pub(crate) fn neighbours_within_distance<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py, Ix1>,
+    radius: f64,
+    ellipsoid_like: EllipsoidLike,
+    nthreads: u16,
+) -> PyResult<(
+    Bound<'py, PyArray1<u64>>,
+    Bound<'py, PyArray1<f64>>,
+    Bound<'py, PyArray1<i64>>,
+)> {
+    use healpix_geo_core::vectorized::nested::distances as vectorized;
+
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+
+    let layer = cdshealpix::nested::get(depth);
+    let result = vectorized::neighbours_within_distance(
+        &ipix_,
+        layer,
+        radius,
+        &ellipsoid,
+        nthreads as usize,
+    )
+    .map_err(into_pyerr)?;
+
+    ragged_arrays(py, result)
 }
@@ -1,4 +1,5 @@
 use crate::ellipsoid::EllipsoidLike;
+use crate::error::into_pyerr;
 use cdshealpix as healpix;
 use numpy::{PyArray1, PyArray2, PyArrayMethods, PyUntypedArrayMethods};
 use pyo3::exceptions::PyValueError;
@@ -23,7 +24,8 @@ pub(crate) fn zone_coverage<'py>(
     let ellipsoid_ = ellipsoid.into_ellipsoid()?;
     let layer = healpix::nested::get(depth);
 
-    let (ipix, depths, fully_covered) = scalar::zone_coverage(bbox, layer, &ellipsoid_, flat);
+    let (ipix, depths, fully_covered) =
+        scalar::zone_coverage(bbox, layer, &ellipsoid_, flat).map_err(into_pyerr)?;
 
     Ok((
         PyArray1::from_vec(py, ipix),
@@ -52,7 +54,7 @@ pub(crate) fn box_coverage<'py>(
     let layer = healpix::nested::get(depth);
 
     let (ipix, depths, fully_covered) =
-        scalar::box_coverage(center, size, angle, layer, &ellipsoid_, flat);
+        scalar::box_coverage(center, size, angle, layer, &ellipsoid_, flat).map_err(into_pyerr)?;
 
     Ok((
         PyArray1::from_vec(py, ipix),
@@ -94,7 +96,8 @@ pub(crate) fn polygon_coverage<'py>(
         .collect();
 
     let (ipix, depths, fully_covered) =
-        scalar::polygon_coverage(&vertices_, layer, &ellipsoid_, exact, flat);
+        scalar::polygon_coverage(&vertices_, layer, &ellipsoid_, exact, flat)
+            .map_err(into_pyerr)?;
 
     Ok((
         PyArray1::from_vec(py, ipix),
@@ -123,17 +126,14 @@ pub(crate) fn cone_coverage<'py>(
         return Err(PyValueError::new_err(
             "depth must be between 0 and 29, inclusive.",
         ));
-    } else if depth + delta_depth > 29 {
-        return Err(PyValueError::new_err(
-            "delta_depth must chosen such that depth + delta_depth <= 29",
-        ));
     }
 
     let ellipsoid_ = ellipsoid.into_ellipsoid()?;
     let layer = healpix::nested::get(depth);
 
     let (ipix, depths, fully_covered) =
-        scalar::cone_coverage(center, radius, layer, &ellipsoid_, delta_depth, flat);
+        scalar::cone_coverage(center, radius, layer, &ellipsoid_, delta_depth, flat)
+            .map_err(into_pyerr)?;
 
     Ok((
         PyArray1::from_vec(py, ipix),
@@ -163,10 +163,6 @@ pub(crate) fn elliptical_cone_coverage<'py>(
         return Err(PyValueError::new_err(
             "depth must be between 0 and 29, inclusive.",
         ));
-    } else if depth + delta_depth > 29 {
-        return Err(PyValueError::new_err(
-            "delta_depth must chosen such that depth + delta_depth <= 29",
-        ));
     }
 
     let ellipsoid_ = ellipsoid.into_ellipsoid()?;
@@ -180,7 +176,8 @@ pub(crate) fn elliptical_cone_coverage<'py>(
         &ellipsoid_,
         delta_depth,
         flat,
-    );
+    )
+    .map_err(into_pyerr)?;
 
     Ok((
         PyArray1::from_vec(py, ipix),
@@ -188,3 +185,35 @@ pub(crate) fn elliptical_cone_coverage<'py>(
         PyArray1::from_vec(py, fully_covered),
     ))
 }
+
+#[pyfunction]
+#[pyo3(signature = (depth, vertices, *, ellipsoid, rhumb = false))]
+pub(crate) fn linestring_coverage<'py>(
+    py: Python<'py>,
+    depth: u8,
+    vertices: &Bound<PyArray2<f64>>,
+    ellipsoid: EllipsoidLike,
+    rhumb: bool,
+) -> PyResult<Bound<'py, PyArray1<u64>>> {
+    let ellipsoid_ = ellipsoid.into_ellipsoid()?;
+    let layer = healpix::nested::get(depth);
+
+    let shape = vertices.shape();
+    if shape[1] != 2 {
+        return Err(PyValueError::new_err(format!(
+            "The last dimension of the vertices array must have a size of 2, got shape ({}, {})",
+            shape[0], shape[1]
+        )));
+    }
+
+    let vertices_: Vec<(f64, f64)> = vertices
+        .to_vec()?
+        .chunks(2)
+        .map(|row| (row[0], row[1]))
+        .collect();
+
+    let ipix =
+        scalar::linestring_coverage(&vertices_, layer, &ellipsoid_, rhumb).map_err(into_pyerr)?;
+
+    Ok(PyArray1::from_vec(py, ipix))
+}
@@ -1,13 +1,25 @@
 mod coordinates;
 mod coverage;
 mod hierarchy;
+mod interpolation;
+mod rasterization;
+mod regridding;
 mod sets;
 
 pub(crate) use self::coordinates::{
-    angular_distances, healpix_to_lonlat, lonlat_to_healpix, vertices,
+    angular_distances, cell_area, cell_perimeter, geodesic_distances, geodesic_distances_to_points,
+    healpix_to_lonlat, healpix_to_projected, healpix_to_xyz, is_valid_cell, lonlat_to_healpix,
+    neighbours_within_distance, projected_to_healpix, vertices, vertices_projected, vertices_xyz,
+    xyz_to_healpix,
 };
 pub(crate) use self::coverage::{
-    box_coverage, cone_coverage, elliptical_cone_coverage, polygon_coverage, zone_coverage,
+    box_coverage, cone_coverage, elliptical_cone_coverage, linestring_coverage, polygon_coverage,
+    zone_coverage,
 };
-pub(crate) use self::hierarchy::{kth_neighbourhood, siblings, zoom_to};
-pub(crate) use self::sets::internal_boundary;
+pub(crate) use self::hierarchy::{
+    aggregate_to_parent, broadcast_to_children, kth_neighbourhood, neighbours, siblings, zoom_to,
+};
+pub(crate) use self::interpolation::{interpolate, interpolation_weights};
+pub(crate) use self::rasterization::rasterize;
+pub(crate) use self::regridding::regridding_weights;
+pub(crate) use self::sets::{external_boundary, internal_boundary};
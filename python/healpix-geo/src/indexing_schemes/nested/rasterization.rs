@@ -0,0 +1,55 @@
+use crate::ellipsoid::EllipsoidLike;
+use crate::error::into_pyerr;
+use crate::indexing_schemes::arrays::{CellIds, MemoryOrder, elements};
+use crate::indexing_schemes::buffers::to_array;
+use crate::indexing_schemes::rasterization::projection_from_name;
+
+use cdshealpix as healpix;
+use numpy::ndarray::Ix1;
+use numpy::{PyArrayDyn, PyReadonlyArray1};
+use pyo3::prelude::*;
+
+use healpix_geo_core::vectorized::nested::rasterization as vectorized;
+
+/// Render the map `values` defined on `cell_ids` onto an image of the given shape.
+///
+/// The image has its first row in the north and its first column in the west.
+#[allow(clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (depth, values, cell_ids, ellipsoid_like, shape, projection, central_longitude, central_latitude, fill_value, nthreads=0))]
+pub(crate) fn rasterize<'py>(
+    py: Python<'py>,
+    depth: u8,
+    values: PyReadonlyArray1<'py, f64>,
+    cell_ids: CellIds<'py, Ix1>,
+    ellipsoid_like: EllipsoidLike,
+    shape: (usize, usize),
+    projection: &str,
+    central_longitude: f64,
+    central_latitude: f64,
+    fill_value: f64,
+    nthreads: u16,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let projection = projection_from_name(projection, central_longitude, central_latitude)?;
+    let values_ = elements(&values, MemoryOrder::C);
+    let cell_ids_ = cell_ids.values(MemoryOrder::C)?;
+    let (height, width) = shape;
+
+    let layer = healpix::nested::get(depth);
+
+    let result = vectorized::rasterize(
+        &values_,
+        &cell_ids_,
+        layer,
+        &ellipsoid,
+        &projection,
+        width,
+        height,
+        fill_value,
+        nthreads as usize,
+    )
+    .map_err(into_pyerr)?;
+
+    to_array(py, result, &[height, width], MemoryOrder::C)
+}
@@ -0,0 +1,53 @@
+use crate::ellipsoid::EllipsoidLike;
+use crate::error::into_pyerr;
+use crate::indexing_schemes::arrays::Coordinates;
+use crate::indexing_schemes::regridding::{csr_arrays, direction_from_name, source_vertices};
+
+use cdshealpix as healpix;
+use numpy::PyArray1;
+use numpy::ndarray::Ix2;
+use pyo3::prelude::*;
+
+use healpix_geo_core::vectorized::nested::regridding as vectorized;
+
+/// Area-weighted regridding weights between source cells and the cells of `depth`.
+///
+/// The source cells are the rows of `lon_bounds` and `lat_bounds`. Returns the cell
+/// ids and the data, indices and indptr arrays of the weights in CSR format.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (depth, lon_bounds, lat_bounds, ellipsoid_like, refinement=3, direction="to_healpix", nthreads=0))]
+pub(crate) fn regridding_weights<'py>(
+    py: Python<'py>,
+    depth: u8,
+    lon_bounds: Coordinates<'py, Ix2>,
+    lat_bounds: Coordinates<'py, Ix2>,
+    ellipsoid_like: EllipsoidLike,
+    refinement: u8,
+    direction: &str,
+    nthreads: u16,
+) -> PyResult<(
+    Bound<'py, PyArray1<u64>>,
+    Bound<'py, PyArray1<f64>>,
+    Bound<'py, PyArray1<i64>>,
+    Bound<'py, PyArray1<i64>>,
+)> {
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let direction = direction_from_name(direction)?;
+    let (vertices, vertices_per_cell) = source_vertices(&lon_bounds, &lat_bounds)?;
+
+    let layer = healpix::nested::get(depth);
+
+    let weights = vectorized::regridding_weights(
+        &vertices,
+        vertices_per_cell,
+        layer,
+        &ellipsoid,
+        refinement,
+        direction,
+        nthreads as usize,
+    )
+    .map_err(into_pyerr)?;
+
+    csr_arrays(py, weights)
+}
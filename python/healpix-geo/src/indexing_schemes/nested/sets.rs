@@ -1,8 +1,11 @@
 use moc::moc::range::RangeMOC;
-use numpy::{PyArray1, PyArrayMethods};
+use numpy::PyArray1;
+use numpy::ndarray::Ix1;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
+use crate::indexing_schemes::arrays::{CellIds, MemoryOrder};
+
 /// Extract the internal boundary from a list of cells
 ///
 /// Parameters
@@ -20,7 +23,7 @@ use pyo3::prelude::*;
 pub(crate) fn internal_boundary<'py>(
     py: Python<'py>,
     depth: u8,
-    ipix: &Bound<'py, PyArray1<u64>>,
+    ipix: CellIds<'py, Ix1>,
 ) -> PyResult<Bound<'py, PyArray1<u64>>> {
     if depth > 29 {
         return Err(PyValueError::new_err(format!(
@@ -29,8 +32,8 @@ pub(crate) fn internal_boundary<'py>(
         )));
     }
 
-    let ipix_ = ipix.readonly();
-    let moc = RangeMOC::from_fixed_depth_cells(depth, ipix_.as_slice()?.iter().copied(), None);
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let moc = RangeMOC::from_fixed_depth_cells(depth, ipix_.iter().copied(), None);
 
     let border = moc.internal_border();
 
@@ -39,3 +42,40 @@ pub(crate) fn internal_boundary<'py>(
         border.flatten_to_fixed_depth_cells().collect::<Vec<u64>>(),
     ))
 }
+
+/// Extract the external boundary from a list of cells
+///
+/// Parameters
+/// ----------
+/// depth : int
+///     The cell depth
+/// cell_ids : numpy.ndarray
+///     The cells describing a connected area
+///
+/// Returns
+/// -------
+/// external_boundary : numpy.ndarray
+///     The cells outside the area that touch its boundary
+#[pyfunction]
+pub(crate) fn external_boundary<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py, Ix1>,
+) -> PyResult<Bound<'py, PyArray1<u64>>> {
+    if depth > 29 {
+        return Err(PyValueError::new_err(format!(
+            "depth must be between 0 and 29, inclusive (got {})",
+            depth
+        )));
+    }
+
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let moc = RangeMOC::from_fixed_depth_cells(depth, ipix_.iter().copied(), None);
+
+    let border = moc.external_border();
+
+    Ok(PyArray1::from_vec(
+        py,
+        border.flatten_to_fixed_depth_cells().collect::<Vec<u64>>(),
+    ))
+}
@@ -0,0 +1,22 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use healpix_geo_core::vectorized::rasterization::Projection;
+
+pub(crate) fn projection_from_name(
+    name: &str,
+    central_longitude: f64,
+    central_latitude: f64,
+) -> PyResult<Projection> {
+    match name {
+        "equirectangular" => Ok(Projection::Equirectangular { central_longitude }),
+        "mollweide" => Ok(Projection::Mollweide { central_longitude }),
+        "orthographic" => Ok(Projection::Orthographic {
+            central_longitude,
+            central_latitude,
+        }),
+        _ => Err(PyValueError::new_err(format!(
+            "Projection must be one of 'equirectangular', 'mollweide' or 'orthographic' (got {name:?})"
+        ))),
+    }
+}
@@ -0,0 +1,55 @@
+use numpy::PyArray1;
+use numpy::ndarray::Ix2;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::indexing_schemes::arrays::Coordinates;
+use crate::indexing_schemes::distances::to_points;
+use healpix_geo_core::vectorized::regridding::{Direction, SparseWeights};
+
+pub(crate) fn direction_from_name(name: &str) -> PyResult<Direction> {
+    match name {
+        "to_healpix" => Ok(Direction::ToHealpix),
+        "from_healpix" => Ok(Direction::FromHealpix),
+        _ => Err(PyValueError::new_err(format!(
+            "Direction must be one of 'to_healpix' or 'from_healpix' (got {name:?})"
+        ))),
+    }
+}
+
+/// The vertices of the source cells, with one row per cell.
+pub(crate) fn source_vertices(
+    lon_bounds: &Coordinates<'_, Ix2>,
+    lat_bounds: &Coordinates<'_, Ix2>,
+) -> PyResult<(Vec<(f64, f64)>, usize)> {
+    if lon_bounds.shape() != lat_bounds.shape() {
+        return Err(PyValueError::new_err(format!(
+            "The longitude and latitude bounds must have the same shape, got {:?} and {:?}",
+            lon_bounds.shape(),
+            lat_bounds.shape()
+        )));
+    }
+
+    Ok((to_points(lon_bounds, lat_bounds), lon_bounds.shape()[1]))
+}
+
+/// Split the weights into cell ids and the data, indices and indptr arrays of CSR.
+#[allow(clippy::type_complexity)]
+pub(crate) fn csr_arrays<'py>(
+    py: Python<'py>,
+    weights: SparseWeights,
+) -> PyResult<(
+    Bound<'py, PyArray1<u64>>,
+    Bound<'py, PyArray1<f64>>,
+    Bound<'py, PyArray1<i64>>,
+    Bound<'py, PyArray1<i64>>,
+)> {
+    let as_index = |values: Vec<usize>| values.into_iter().map(|value| value as i64);
+
+    Ok((
+        PyArray1::from_vec(py, weights.cell_ids),
+        PyArray1::from_vec(py, weights.weights),
+        PyArray1::from_iter(py, as_index(weights.indices)),
+        PyArray1::from_iter(py, as_index(weights.indptr)),
+    ))
+}
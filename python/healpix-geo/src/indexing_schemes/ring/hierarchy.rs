@@ -1,7 +1,13 @@
 use cdshealpix as healpix;
-use numpy::{PyArray2, PyArrayDyn, PyArrayMethods, PyUntypedArrayMethods};
+use numpy::ndarray::Ix1;
+use numpy::{PyArray1, PyArray2, PyArrayDyn, PyArrayMethods, PyReadonlyArray1};
 use pyo3::prelude::*;
 
+use crate::error::into_pyerr;
+use crate::indexing_schemes::aggregation::{cell_value_arrays, reduction_from_name};
+use crate::indexing_schemes::arrays::{CellIds, MemoryOrder, elements};
+use crate::indexing_schemes::buffers::to_array;
+
 use healpix_geo_core::vectorized::ring::hierarchy as vectorized;
 
 /// Wrapper of `kth_neighbourhood`
@@ -10,17 +16,18 @@ use healpix_geo_core::vectorized::ring::hierarchy as vectorized;
 pub(crate) fn kth_neighbourhood<'py>(
     py: Python<'py>,
     depth: u8,
-    ipix: &Bound<'py, PyArrayDyn<u64>>,
+    ipix: CellIds<'py>,
     ring: u32,
     nthreads: u16,
 ) -> PyResult<Bound<'py, PyArrayDyn<i64>>> {
-    let ipix_ = ipix.readonly();
+    let ipix_ = ipix.values(MemoryOrder::C)?;
     let input_shape = ipix.shape();
 
     let nside = healpix::nside(depth);
-    let result = vectorized::kth_neighbourhood(ipix_.as_slice()?, &nside, &ring, nthreads as usize);
+    let result = vectorized::kth_neighbourhood(&ipix_, &nside, &ring, nthreads as usize)
+        .map_err(into_pyerr)?;
 
-    let output_shape: Vec<usize> = if ipix.len() == 0 {
+    let output_shape: Vec<usize> = if ipix_.is_empty() {
         input_shape.to_vec()
     } else {
         input_shape
@@ -32,3 +39,159 @@ pub(crate) fn kth_neighbourhood<'py>(
 
     PyArray2::from_vec2(py, &result)?.reshape(output_shape.as_slice())
 }
+
+/// The 8 neighbours of each cell, stacked along a new trailing axis.
+#[pyfunction]
+pub(crate) fn neighbours<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py>,
+    nthreads: u16,
+) -> PyResult<Bound<'py, PyArrayDyn<i64>>> {
+    let input_shape = ipix.shape();
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+
+    let nside = healpix::nside(depth);
+    let result = vectorized::neighbours(&ipix_, &nside, nthreads as usize).map_err(into_pyerr)?;
+
+    let output_shape: Vec<usize> = input_shape.iter().copied().chain([8]).collect();
+
+    to_array(
+        py,
+        result.into_iter().flatten().collect(),
+        &output_shape,
+        MemoryOrder::C,
+    )
+}
+
+#[pyfunction]
+pub(crate) fn zoom_to<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py>,
+    new_depth: u8,
+    nthreads: u16,
+) -> PyResult<Bound<'py, PyArrayDyn<u64>>> {
+    use std::cmp::Ordering;
+
+    let input_shape = ipix.shape();
+    // children are stacked along a new trailing axis, which requires C order
+    let order = match depth.cmp(&new_depth) {
+        Ordering::Less => MemoryOrder::C,
+        _ => ipix.order(),
+    };
+
+    let ipix_ = ipix.values(order)?;
+    let nside = healpix::nside(depth);
+    let delta_depth = (depth as i8 - new_depth as i8).unsigned_abs();
+
+    let result = match depth.cmp(&new_depth) {
+        Ordering::Equal => to_array(py, ipix_.into_owned(), input_shape, order)?,
+        Ordering::Less => {
+            let result = vectorized::children(&ipix_, &nside, delta_depth, nthreads as usize)
+                .map_err(into_pyerr)?;
+
+            let output_shape: Vec<usize> = if ipix_.is_empty() {
+                input_shape.to_vec()
+            } else {
+                input_shape
+                    .iter()
+                    .copied()
+                    .chain([result[0].len()])
+                    .collect()
+            };
+
+            PyArray2::from_vec2(py, &result)?.reshape(output_shape.as_slice())?
+        }
+        Ordering::Greater => {
+            let result = vectorized::parents(&ipix_, &nside, delta_depth, nthreads as usize)
+                .map_err(into_pyerr)?;
+
+            to_array(py, result, input_shape, order)?
+        }
+    };
+
+    Ok(result)
+}
+
+#[pyfunction]
+pub(crate) fn siblings<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py>,
+    nthreads: u16,
+) -> PyResult<Bound<'py, PyArrayDyn<u64>>> {
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let input_shape = ipix.shape();
+    let nside = healpix::nside(depth);
+
+    let siblings = vectorized::siblings(&ipix_, &nside, nthreads as usize).map_err(into_pyerr)?;
+
+    let output_shape: Vec<usize> = if ipix_.is_empty() {
+        input_shape.to_vec()
+    } else {
+        input_shape
+            .iter()
+            .copied()
+            .chain([siblings[0].len()])
+            .collect()
+    };
+
+    PyArray2::from_vec2(py, &siblings)?.reshape(output_shape.as_slice())
+}
+
+/// Reduce the values of the cells sharing a parent at `new_depth`.
+#[allow(clippy::type_complexity)]
+#[pyfunction]
+#[pyo3(signature = (depth, ipix, values, new_depth, reduction, nthreads=0))]
+pub(crate) fn aggregate_to_parent<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py, Ix1>,
+    values: PyReadonlyArray1<'py, f64>,
+    new_depth: u8,
+    reduction: &str,
+    nthreads: u16,
+) -> PyResult<(Bound<'py, PyArray1<u64>>, Bound<'py, PyArray1<f64>>)> {
+    let reduction = reduction_from_name(reduction)?;
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let values_ = elements(&values, MemoryOrder::C);
+
+    let nside = healpix::nside(depth);
+
+    let result = vectorized::aggregate_to_parent(
+        &ipix_,
+        &values_,
+        &nside,
+        new_depth,
+        reduction,
+        nthreads as usize,
+    )
+    .map_err(into_pyerr)?;
+
+    Ok(cell_value_arrays(py, result))
+}
+
+/// Repeat the values of the cells for each of their children at `new_depth`.
+#[allow(clippy::type_complexity)]
+#[pyfunction]
+#[pyo3(signature = (depth, ipix, values, new_depth, nthreads=0))]
+pub(crate) fn broadcast_to_children<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py, Ix1>,
+    values: PyReadonlyArray1<'py, f64>,
+    new_depth: u8,
+    nthreads: u16,
+) -> PyResult<(Bound<'py, PyArray1<u64>>, Bound<'py, PyArray1<f64>>)> {
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let values_ = elements(&values, MemoryOrder::C);
+
+    let nside = healpix::nside(depth);
+
+    let result =
+        vectorized::broadcast_to_children(&ipix_, &values_, &nside, new_depth, nthreads as usize)
+            .map_err(into_pyerr)?;
+
+    Ok(cell_value_arrays(py, result))
+}
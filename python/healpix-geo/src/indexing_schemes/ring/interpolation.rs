@@ -0,0 +1,78 @@
+use crate::ellipsoid::EllipsoidLike;
+use crate::error::into_pyerr;
+use crate::indexing_schemes::arrays::{CellIds, Coordinates, MemoryOrder, elements};
+use crate::indexing_schemes::buffers::to_array;
+use crate::indexing_schemes::interpolation::weight_arrays;
+
+use cdshealpix as healpix;
+use numpy::ndarray::Ix1;
+use numpy::{PyArrayDyn, PyReadonlyArray1};
+use pyo3::prelude::*;
+
+use healpix_geo_core::vectorized::ring::interpolation as vectorized;
+
+/// The 4 cells surrounding each point and their bilinear interpolation weights.
+///
+/// Both arrays have the shape of `longitude` with an additional axis of size 4.
+#[allow(clippy::type_complexity)]
+#[pyfunction]
+#[pyo3(signature = (depth, longitude, latitude, ellipsoid_like, nthreads=0))]
+pub(crate) fn interpolation_weights<'py>(
+    py: Python<'py>,
+    depth: u8,
+    longitude: Coordinates<'py>,
+    latitude: Coordinates<'py>,
+    ellipsoid_like: EllipsoidLike,
+    nthreads: u16,
+) -> PyResult<(Bound<'py, PyArrayDyn<u64>>, Bound<'py, PyArrayDyn<f64>>)> {
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let lon = longitude.values(MemoryOrder::C);
+    let lat = latitude.values(MemoryOrder::C);
+
+    let nside = healpix::nside(depth);
+
+    let weights =
+        vectorized::interpolation_weights(&lon, &lat, &nside, &ellipsoid, nthreads as usize)
+            .map_err(into_pyerr)?;
+
+    weight_arrays(py, weights, longitude.shape())
+}
+
+/// Bilinear interpolation of the field `values` defined on `cell_ids`.
+///
+/// The result has the shape and memory order of `longitude`.
+#[allow(clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (depth, values, cell_ids, longitude, latitude, ellipsoid_like, nthreads=0))]
+pub(crate) fn interpolate<'py>(
+    py: Python<'py>,
+    depth: u8,
+    values: PyReadonlyArray1<'py, f64>,
+    cell_ids: CellIds<'py, Ix1>,
+    longitude: Coordinates<'py>,
+    latitude: Coordinates<'py>,
+    ellipsoid_like: EllipsoidLike,
+    nthreads: u16,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let ellipsoid = ellipsoid_like.into_ellipsoid()?;
+    let order = MemoryOrder::common(&[longitude.order(), latitude.order()]);
+    let values_ = elements(&values, MemoryOrder::C);
+    let cell_ids_ = cell_ids.values(MemoryOrder::C)?;
+    let lon = longitude.values(order);
+    let lat = latitude.values(order);
+
+    let nside = healpix::nside(depth);
+
+    let result = vectorized::interpolate(
+        &values_,
+        &cell_ids_,
+        &lon,
+        &lat,
+        &nside,
+        &ellipsoid,
+        nthreads as usize,
+    )
+    .map_err(into_pyerr)?;
+
+    to_array(py, result, longitude.shape(), order)
+}
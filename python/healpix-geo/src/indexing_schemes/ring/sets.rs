@@ -0,0 +1,95 @@
+use cdshealpix as healpix;
+use moc::moc::range::RangeMOC;
+use moc::qty::Hpx;
+use numpy::PyArray1;
+use numpy::ndarray::Ix1;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::indexing_schemes::arrays::{CellIds, MemoryOrder};
+
+/// Apply a boundary operation on the nested representation of ring cells.
+fn ring_boundary(
+    depth: u8,
+    ipix: &[u64],
+    boundary: impl FnOnce(&RangeMOC<u64, Hpx<u64>>) -> RangeMOC<u64, Hpx<u64>>,
+) -> PyResult<Vec<u64>> {
+    if depth > 29 {
+        return Err(PyValueError::new_err(format!(
+            "depth must be between 0 and 29, inclusive (got {})",
+            depth
+        )));
+    }
+
+    let layer = healpix::nested::get(depth);
+    let n_hash = healpix::n_hash(depth);
+    if let Some(hash) = ipix.iter().find(|&&hash| hash >= n_hash) {
+        return Err(PyValueError::new_err(format!(
+            "invalid cell id {hash} at depth {depth}: must be in the range [0, {n_hash})"
+        )));
+    }
+
+    let moc = RangeMOC::from_fixed_depth_cells(
+        depth,
+        ipix.iter().map(|&hash| layer.from_ring(hash)),
+        None,
+    );
+
+    let mut cells: Vec<u64> = boundary(&moc)
+        .flatten_to_fixed_depth_cells()
+        .map(|hash| layer.to_ring(hash))
+        .collect();
+    cells.sort_unstable();
+
+    Ok(cells)
+}
+
+/// Extract the internal boundary from a list of cells
+///
+/// Parameters
+/// ----------
+/// depth : int
+///     The cell depth
+/// cell_ids : numpy.ndarray
+///     The cells describing a connected area
+///
+/// Returns
+/// -------
+/// internal_boundary : numpy.ndarray
+///     The boundary cells
+#[pyfunction]
+pub(crate) fn internal_boundary<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py, Ix1>,
+) -> PyResult<Bound<'py, PyArray1<u64>>> {
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let cells = ring_boundary(depth, &ipix_, |moc| moc.internal_border())?;
+
+    Ok(PyArray1::from_vec(py, cells))
+}
+
+/// Extract the external boundary from a list of cells
+///
+/// Parameters
+/// ----------
+/// depth : int
+///     The cell depth
+/// cell_ids : numpy.ndarray
+///     The cells describing a connected area
+///
+/// Returns
+/// -------
+/// external_boundary : numpy.ndarray
+///     The cells outside the area that touch its boundary
+#[pyfunction]
+pub(crate) fn external_boundary<'py>(
+    py: Python<'py>,
+    depth: u8,
+    ipix: CellIds<'py, Ix1>,
+) -> PyResult<Bound<'py, PyArray1<u64>>> {
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let cells = ring_boundary(depth, &ipix_, |moc| moc.external_border())?;
+
+    Ok(PyArray1::from_vec(py, cells))
+}
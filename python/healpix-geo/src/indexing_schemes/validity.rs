@@ -0,0 +1,75 @@
+use std::borrow::Cow;
+
+use numpy::{Element, PyArrayDyn, PyArrayMethods, PyUntypedArrayMethods};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::indexing_schemes::arrays::{CellIds, MemoryOrder};
+use crate::indexing_schemes::buffers::to_array;
+use healpix_geo_core::InvalidPolicy;
+
+/// How the conversions handle invalid cell ids and coordinates.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OnInvalid {
+    /// Raise a `ValueError`.
+    Raise,
+    /// Return `u64::MAX` for cell ids and NaN for coordinates.
+    Sentinel,
+    /// Like `Sentinel`, but also return a mask of the invalid elements.
+    Mask,
+}
+
+impl OnInvalid {
+    pub fn from_name(name: &str) -> PyResult<Self> {
+        match name {
+            "raise" => Ok(Self::Raise),
+            "sentinel" => Ok(Self::Sentinel),
+            "mask" => Ok(Self::Mask),
+            _ => Err(PyValueError::new_err(format!(
+                "on_invalid must be one of 'raise', 'sentinel' or 'mask' (got {name:?})"
+            ))),
+        }
+    }
+
+    pub fn policy(&self) -> InvalidPolicy {
+        match self {
+            Self::Raise => InvalidPolicy::Raise,
+            Self::Sentinel | Self::Mask => InvalidPolicy::Sentinel,
+        }
+    }
+
+    /// The cell ids to convert.
+    ///
+    /// Negative cell ids are invalid instead of an error, unless invalid cell ids are
+    /// raised anyway.
+    pub fn cell_ids<'a>(
+        &self,
+        ipix: &'a CellIds<'_>,
+        order: MemoryOrder,
+    ) -> PyResult<Cow<'a, [u64]>> {
+        match self {
+            Self::Raise => ipix.values(order),
+            Self::Sentinel | Self::Mask => ipix.values_or_invalid(order),
+        }
+    }
+
+    /// The mask of the elements of `values` that are sentinels, if requested.
+    ///
+    /// `values` must be contiguous in the given order.
+    pub fn mask<'py, T: Element>(
+        &self,
+        py: Python<'py>,
+        values: &Bound<'py, PyArrayDyn<T>>,
+        order: MemoryOrder,
+        is_sentinel: impl Fn(&T) -> bool,
+    ) -> PyResult<Option<Bound<'py, PyArrayDyn<bool>>>> {
+        if *self != Self::Mask {
+            return Ok(None);
+        }
+
+        let values_ = values.readonly();
+        let mask = values_.as_slice()?.iter().map(is_sentinel).collect();
+
+        to_array(py, mask, values.shape(), order).map(Some)
+    }
+}
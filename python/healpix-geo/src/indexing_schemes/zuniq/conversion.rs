@@ -1,51 +1,45 @@
-use numpy::{PyArray1, PyArrayDyn, PyArrayMethods, PyUntypedArrayMethods};
+use numpy::PyArrayDyn;
 use pyo3::prelude::*;
 
+use crate::error::into_pyerr;
+use crate::indexing_schemes::arrays::CellIds;
+use crate::indexing_schemes::buffers::to_array;
 use crate::indexing_schemes::depth::DepthLike;
 use healpix_geo_core::vectorized::zuniq::conversion as vectorized;
 
 #[pyfunction]
 pub(crate) fn from_nested<'py>(
     py: Python<'py>,
-    nested: &Bound<'py, PyArrayDyn<u64>>,
-    depth: DepthLike,
+    nested: CellIds<'py>,
+    depth: DepthLike<'py>,
     nthreads: u16,
 ) -> PyResult<Bound<'py, PyArrayDyn<u64>>> {
-    let input_shape = nested.shape();
-    let depth_ = depth.into_depth(py)?;
+    let order = nested.order();
+    let nested_ = nested.values(order)?;
 
-    let flattened = nested.reshape([nested.len()])?;
-    let flattened_ = flattened.readonly();
-    let result = vectorized::from_nested(flattened_.as_slice()?, depth_, nthreads as usize);
+    let result = depth
+        .with_depth(order, |depth| {
+            vectorized::from_nested(&nested_, depth, nthreads as usize)
+        })
+        .map_err(into_pyerr)?;
 
-    Ok(PyArray1::from_vec(py, result)
-        .reshape(input_shape)?
-        .to_dyn()
-        .clone())
+    to_array(py, result, nested.shape(), order)
 }
 
 #[allow(clippy::type_complexity)]
 #[pyfunction]
 pub(crate) fn to_nested<'py>(
     py: Python<'py>,
-    zuniq: &Bound<'py, PyArrayDyn<u64>>,
+    zuniq: CellIds<'py>,
     nthreads: u16,
 ) -> PyResult<(Bound<'py, PyArrayDyn<u64>>, Bound<'py, PyArrayDyn<u8>>)> {
-    let input_shape = zuniq.shape();
+    let order = zuniq.order();
+    let zuniq_ = zuniq.values(order)?;
 
-    let flattened = zuniq.reshape([zuniq.len()])?;
-    let flattened_ = flattened.readonly();
-
-    let (nested, depths) = vectorized::to_nested(flattened_.as_slice()?, nthreads as usize);
+    let (nested, depths) = vectorized::to_nested(&zuniq_, nthreads as usize).map_err(into_pyerr)?;
 
     Ok((
-        PyArray1::from_vec(py, nested)
-            .reshape(input_shape)?
-            .to_dyn()
-            .clone(),
-        PyArray1::from_vec(py, depths)
-            .reshape(input_shape)?
-            .to_dyn()
-            .clone(),
+        to_array(py, nested, zuniq.shape(), order)?,
+        to_array(py, depths, zuniq.shape(), order)?,
     ))
 }
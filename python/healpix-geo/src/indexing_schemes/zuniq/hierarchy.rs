@@ -1,6 +1,13 @@
-use numpy::{PyArray2, PyArrayDyn, PyArrayMethods, PyUntypedArrayMethods};
+use numpy::ndarray::Ix1;
+use numpy::{PyArray1, PyArray2, PyArrayDyn, PyArrayMethods, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
+use crate::error::into_pyerr;
+use crate::indexing_schemes::aggregation::{cell_value_arrays, reduction_from_name};
+use crate::indexing_schemes::arrays::{CellIds, MemoryOrder, elements};
+use crate::indexing_schemes::buffers::to_array;
+
 use healpix_geo_core::vectorized::zuniq::hierarchy as vectorized;
 
 /// Wrapper of `kth_neighbourhood`
@@ -8,16 +15,17 @@ use healpix_geo_core::vectorized::zuniq::hierarchy as vectorized;
 #[pyfunction]
 pub(crate) fn kth_neighbourhood<'py>(
     py: Python<'py>,
-    ipix: &Bound<'py, PyArrayDyn<u64>>,
+    ipix: CellIds<'py>,
     ring: u32,
     nthreads: u16,
 ) -> PyResult<Bound<'py, PyArrayDyn<i64>>> {
-    let ipix_ = ipix.readonly();
+    let ipix_ = ipix.values(MemoryOrder::C)?;
     let input_shape = ipix.shape();
 
-    let result = vectorized::kth_neighbourhood(ipix_.as_slice()?, &ring, nthreads as usize);
+    let result =
+        vectorized::kth_neighbourhood(&ipix_, &ring, nthreads as usize).map_err(into_pyerr)?;
 
-    let output_shape: Vec<usize> = if ipix.len() == 0 {
+    let output_shape: Vec<usize> = if ipix_.is_empty() {
         input_shape.to_vec()
     } else {
         input_shape
@@ -29,3 +37,136 @@ pub(crate) fn kth_neighbourhood<'py>(
 
     PyArray2::from_vec2(py, &result)?.reshape(output_shape.as_slice())
 }
+
+/// The 8 neighbours of each cell, stacked along a new trailing axis.
+#[pyfunction]
+pub(crate) fn neighbours<'py>(
+    py: Python<'py>,
+    ipix: CellIds<'py>,
+    nthreads: u16,
+) -> PyResult<Bound<'py, PyArrayDyn<i64>>> {
+    let input_shape = ipix.shape();
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+
+    let result = vectorized::neighbours(&ipix_, nthreads as usize).map_err(into_pyerr)?;
+
+    let output_shape: Vec<usize> = input_shape.iter().copied().chain([8]).collect();
+
+    to_array(
+        py,
+        result.into_iter().flatten().collect(),
+        &output_shape,
+        MemoryOrder::C,
+    )
+}
+
+/// Stack the cells of every input cell along a new trailing axis.
+///
+/// Since the depth is encoded in the cell ids, the number of cells may differ between
+/// input cells, in which case the result cannot be represented as an array.
+fn stack_cells<'py>(
+    py: Python<'py>,
+    input_shape: &[usize],
+    cells: Vec<Vec<u64>>,
+    message: &str,
+) -> PyResult<Bound<'py, PyArrayDyn<u64>>> {
+    let Some(n_cells) = cells.first().map(|row| row.len()) else {
+        return PyArray1::from_vec(py, Vec::new()).reshape(input_shape);
+    };
+    if cells.iter().any(|row| row.len() != n_cells) {
+        return Err(PyValueError::new_err(message.to_string()));
+    }
+
+    let output_shape: Vec<usize> = input_shape.iter().copied().chain([n_cells]).collect();
+
+    PyArray2::from_vec2(py, &cells)?.reshape(output_shape.as_slice())
+}
+
+#[pyfunction]
+pub(crate) fn zoom_to<'py>(
+    py: Python<'py>,
+    ipix: CellIds<'py>,
+    new_depth: u8,
+    nthreads: u16,
+) -> PyResult<Bound<'py, PyArrayDyn<u64>>> {
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let input_shape = ipix.shape();
+
+    let result = vectorized::zoom_to(&ipix_, &new_depth, nthreads as usize).map_err(into_pyerr)?;
+
+    if result.iter().all(|cells| cells.len() == 1) {
+        let parents: Vec<u64> = result.into_iter().flatten().collect();
+
+        return PyArray1::from_vec(py, parents).reshape(input_shape);
+    }
+
+    stack_cells(
+        py,
+        input_shape,
+        result,
+        "cannot zoom to a finer depth: all cells coarser than the new depth must have the same depth, and no cell may be finer",
+    )
+}
+
+#[pyfunction]
+pub(crate) fn siblings<'py>(
+    py: Python<'py>,
+    ipix: CellIds<'py>,
+    nthreads: u16,
+) -> PyResult<Bound<'py, PyArrayDyn<u64>>> {
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let input_shape = ipix.shape();
+
+    let siblings = vectorized::siblings(&ipix_, nthreads as usize).map_err(into_pyerr)?;
+
+    stack_cells(
+        py,
+        input_shape,
+        siblings,
+        "cannot compute the siblings of base cells together with cells of other depths",
+    )
+}
+
+/// Reduce the values of the cells, possibly of different depths, sharing a parent at
+/// `new_depth`.
+#[allow(clippy::type_complexity)]
+#[pyfunction]
+#[pyo3(signature = (ipix, values, new_depth, reduction, nthreads=0))]
+pub(crate) fn aggregate_to_parent<'py>(
+    py: Python<'py>,
+    ipix: CellIds<'py, Ix1>,
+    values: PyReadonlyArray1<'py, f64>,
+    new_depth: u8,
+    reduction: &str,
+    nthreads: u16,
+) -> PyResult<(Bound<'py, PyArray1<u64>>, Bound<'py, PyArray1<f64>>)> {
+    let reduction = reduction_from_name(reduction)?;
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let values_ = elements(&values, MemoryOrder::C);
+
+    let result =
+        vectorized::aggregate_to_parent(&ipix_, &values_, &new_depth, reduction, nthreads as usize)
+            .map_err(into_pyerr)?;
+
+    Ok(cell_value_arrays(py, result))
+}
+
+/// Repeat the values of the cells for each of their children at `new_depth`.
+#[allow(clippy::type_complexity)]
+#[pyfunction]
+#[pyo3(signature = (ipix, values, new_depth, nthreads=0))]
+pub(crate) fn broadcast_to_children<'py>(
+    py: Python<'py>,
+    ipix: CellIds<'py, Ix1>,
+    values: PyReadonlyArray1<'py, f64>,
+    new_depth: u8,
+    nthreads: u16,
+) -> PyResult<(Bound<'py, PyArray1<u64>>, Bound<'py, PyArray1<f64>>)> {
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let values_ = elements(&values, MemoryOrder::C);
+
+    let result = vectorized::broadcast_to_children(&ipix_, &values_, &new_depth, nthreads as usize)
+        .map_err(into_pyerr)?;
+
+    Ok(cell_value_arrays(py, result))
+}
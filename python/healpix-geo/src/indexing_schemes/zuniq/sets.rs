@@ -0,0 +1,95 @@
+use std::ops::Range;
+
+use cdshealpix as healpix;
+use healpix_geo_core::MAX_DEPTH;
+use healpix_geo_core::scalar::zuniq::conversion;
+use moc::elemset::range::MocRanges;
+use moc::moc::range::RangeMOC;
+use moc::qty::Hpx;
+use numpy::PyArray1;
+use numpy::ndarray::Ix1;
+use pyo3::prelude::*;
+
+use crate::error::into_pyerr;
+use crate::indexing_schemes::arrays::{CellIds, MemoryOrder};
+
+/// Apply a boundary operation on the MOC made of zuniq cells.
+///
+/// The boundary cells are returned at the depth of the finest input cell.
+fn zuniq_boundary(
+    ipix: &[u64],
+    boundary: impl FnOnce(&RangeMOC<u64, Hpx<u64>>) -> RangeMOC<u64, Hpx<u64>>,
+) -> PyResult<Vec<u64>> {
+    let cells = ipix
+        .iter()
+        .map(conversion::to_nested)
+        .collect::<healpix_geo_core::Result<Vec<(u64, u8)>>>()
+        .map_err(into_pyerr)?;
+
+    let max_depth = cells.iter().map(|&(_, depth)| depth).max().unwrap_or(0);
+    let ranges: Vec<Range<u64>> = cells
+        .into_iter()
+        .map(|(hash, depth)| {
+            let shift = 2 * (MAX_DEPTH - depth);
+
+            (hash << shift)..((hash + 1) << shift)
+        })
+        .collect();
+
+    let moc = RangeMOC::new(max_depth, MocRanges::new_from(ranges));
+
+    Ok(boundary(&moc)
+        .flatten_to_fixed_depth_cells()
+        .map(|hash| healpix::nested::to_zuniq(max_depth, hash))
+        .collect())
+}
+
+/// Extract the internal boundary from a list of cells
+///
+/// The cells may have different depths. The boundary cells are given at the depth of
+/// the finest cell.
+///
+/// Parameters
+/// ----------
+/// cell_ids : numpy.ndarray
+///     The cells describing a connected area
+///
+/// Returns
+/// -------
+/// internal_boundary : numpy.ndarray
+///     The boundary cells
+#[pyfunction]
+pub(crate) fn internal_boundary<'py>(
+    py: Python<'py>,
+    ipix: CellIds<'py, Ix1>,
+) -> PyResult<Bound<'py, PyArray1<u64>>> {
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let cells = zuniq_boundary(&ipix_, |moc| moc.internal_border())?;
+
+    Ok(PyArray1::from_vec(py, cells))
+}
+
+/// Extract the external boundary from a list of cells
+///
+/// The cells may have different depths. The boundary cells are given at the depth of
+/// the finest cell.
+///
+/// Parameters
+/// ----------
+/// cell_ids : numpy.ndarray
+///     The cells describing a connected area
+///
+/// Returns
+/// -------
+/// external_boundary : numpy.ndarray
+///     The cells outside the area that touch its boundary
+#[pyfunction]
+pub(crate) fn external_boundary<'py>(
+    py: Python<'py>,
+    ipix: CellIds<'py, Ix1>,
+) -> PyResult<Bound<'py, PyArray1<u64>>> {
+    let ipix_ = ipix.values(MemoryOrder::C)?;
+    let cells = zuniq_boundary(&ipix_, |moc| moc.external_border())?;
+
+    Ok(PyArray1::from_vec(py, cells))
+}
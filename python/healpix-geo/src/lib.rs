@@ -1,6 +1,8 @@
 use pyo3::prelude::*;
 
+mod crs;
 mod ellipsoid;
+mod error;
 mod execution;
 mod geometry;
 mod index;
@@ -14,31 +16,58 @@ mod nested {
 
     #[pymodule_export]
     use crate::indexing_schemes::nested::{
-        angular_distances, box_coverage, cone_coverage, elliptical_cone_coverage,
-        healpix_to_lonlat, internal_boundary, kth_neighbourhood, lonlat_to_healpix,
-        polygon_coverage, siblings, vertices, zone_coverage, zoom_to,
+        aggregate_to_parent, angular_distances, box_coverage, broadcast_to_children, cell_area,
+        cell_perimeter, cone_coverage, elliptical_cone_coverage, external_boundary,
+        geodesic_distances, geodesic_distances_to_points, healpix_to_lonlat, healpix_to_projected,
+        healpix_to_xyz, internal_boundary, interpolate, interpolation_weights, is_valid_cell,
+        kth_neighbourhood, linestring_coverage, lonlat_to_healpix, neighbours,
+        neighbours_within_distance, polygon_coverage, projected_to_healpix, rasterize,
+        regridding_weights, siblings, vertices, vertices_projected, vertices_xyz, xyz_to_healpix,
+        zone_coverage, zoom_to,
     };
 }
 
 #[pymodule]
 mod ring {
+    #[pymodule_export]
+    use super::index::RingRangeMOCIndex;
+
     #[pymodule_export]
     use crate::indexing_schemes::ring::{
-        angular_distances, box_coverage, cone_coverage, elliptical_cone_coverage,
-        healpix_to_lonlat, kth_neighbourhood, lonlat_to_healpix, polygon_coverage, vertices,
-        zone_coverage,
+        aggregate_to_parent, angular_distances, box_coverage, broadcast_to_children, cell_area,
+        cell_perimeter, cone_coverage, elliptical_cone_coverage, external_boundary,
+        geodesic_distances, geodesic_distances_to_points, healpix_to_lonlat, healpix_to_projected,
+        healpix_to_xyz, internal_boundary, interpolate, interpolation_weights, is_valid_cell,
+        kth_neighbourhood, linestring_coverage, lonlat_to_healpix, neighbours,
+        neighbours_within_distance, polygon_coverage, projected_to_healpix, rasterize,
+        regridding_weights, siblings, vertices, vertices_projected, vertices_xyz, xyz_to_healpix,
+        zone_coverage, zoom_to,
     };
 }
 
 #[pymodule]
 mod zuniq {
+    #[pymodule_export]
+    use super::index::ZuniqRangeMOCIndex;
+
     #[pymodule_export]
     use crate::indexing_schemes::zuniq::{
-        box_coverage, cone_coverage, elliptical_cone_coverage, from_nested, healpix_to_lonlat,
-        kth_neighbourhood, lonlat_to_healpix, polygon_coverage, to_nested, vertices, zone_coverage,
+        aggregate_to_parent, angular_distances, box_coverage, broadcast_to_children, cell_area,
+        cell_perimeter, cone_coverage, elliptical_cone_coverage, external_boundary, from_nested,
+        healpix_to_lonlat, healpix_to_projected, healpix_to_xyz, internal_boundary, interpolate,
+        interpolation_weights, is_valid_cell, kth_neighbourhood, linestring_coverage,
+        lonlat_to_healpix, neighbours, neighbours_within_distance, polygon_coverage,
+        projected_to_healpix, rasterize, regridding_weights, siblings, to_nested, vertices,
+        vertices_projected, vertices_xyz, xyz_to_healpix, zone_coverage, zoom_to,
     };
 }
 
+#[pymodule]
+mod geodesic {
+    #[pymodule_export]
+    use crate::indexing_schemes::distances::geodesic_distances;
+}
+
 #[pymodule]
 mod slices {
     #[pymodule_export]
@@ -63,9 +92,21 @@ mod healpix_geo {
     #[pymodule_export]
     use super::zuniq;
 
+    #[pymodule_export]
+    use super::geodesic;
+
     #[pymodule_export]
     use super::slices;
 
     #[pymodule_export]
     use crate::geometry_;
+
+    #[pymodule_export]
+    use crate::ellipsoid::PyEllipsoid;
+
+    #[pymodule_export]
+    use crate::error::{InvalidGeometryError, LengthMismatchError};
+
+    #[pymodule_export]
+    use crate::execution::{get_num_threads, set_num_threads};
 }
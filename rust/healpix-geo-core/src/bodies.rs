@@ -0,0 +1,84 @@
+//! Reference shapes of planetary bodies.
+//!
+//! The radii are the ones recommended by the IAU Working Group on Cartographic
+//! Coordinates and Rotational Elements: B. A. Archinal et al., "Report of the IAU
+//! Working Group on Cartographic Coordinates and Rotational Elements: 2015",
+//! Celest. Mech. Dyn. Astr. 130, 22 (2018).
+
+/// The shape of a planetary body, as an ellipsoid of revolution.
+///
+/// Radii are in meters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlanetaryBody {
+    pub name: &'static str,
+    pub equatorial_radius: f64,
+    pub polar_radius: f64,
+    /// The radius of the sphere commonly used instead of the ellipsoid.
+    pub mean_radius: f64,
+}
+
+impl PlanetaryBody {
+    const fn new(
+        name: &'static str,
+        equatorial_radius: f64,
+        polar_radius: f64,
+        mean_radius: f64,
+    ) -> Self {
+        Self {
+            name,
+            equatorial_radius,
+            polar_radius,
+            mean_radius,
+        }
+    }
+
+    pub fn flattening(&self) -> f64 {
+        (self.equatorial_radius - self.polar_radius) / self.equatorial_radius
+    }
+
+    /// Whether the body is modelled as a sphere.
+    pub fn is_sphere(&self) -> bool {
+        self.equatorial_radius == self.polar_radius
+    }
+}
+
+/// The known planetary bodies.
+pub const PLANETARY_BODIES: [PlanetaryBody; 14] = [
+    PlanetaryBody::new("mercury", 2_440_530.0, 2_438_260.0, 2_439_400.0),
+    PlanetaryBody::new("venus", 6_051_800.0, 6_051_800.0, 6_051_800.0),
+    PlanetaryBody::new("moon", 1_737_400.0, 1_737_400.0, 1_737_400.0),
+    PlanetaryBody::new("mars", 3_396_190.0, 3_376_200.0, 3_389_500.0),
+    PlanetaryBody::new("jupiter", 71_492_000.0, 66_854_000.0, 69_911_000.0),
+    PlanetaryBody::new("io", 1_821_490.0, 1_821_490.0, 1_821_490.0),
+    PlanetaryBody::new("europa", 1_560_800.0, 1_560_800.0, 1_560_800.0),
+    PlanetaryBody::new("ganymede", 2_631_200.0, 2_631_200.0, 2_631_200.0),
+    PlanetaryBody::new("callisto", 2_410_300.0, 2_410_300.0, 2_410_300.0),
+    PlanetaryBody::new("saturn", 60_268_000.0, 54_364_000.0, 58_232_000.0),
+    PlanetaryBody::new("titan", 2_575_000.0, 2_575_000.0, 2_575_000.0),
+    PlanetaryBody::new("uranus", 25_559_000.0, 24_973_000.0, 25_362_000.0),
+    PlanetaryBody::new("neptune", 24_764_000.0, 24_341_000.0, 24_622_000.0),
+    PlanetaryBody::new("pluto", 1_188_300.0, 1_188_300.0, 1_188_300.0),
+];
+
+/// Look up a planetary body by name, ignoring case.
+pub fn planetary_body(name: &str) -> Option<&'static PlanetaryBody> {
+    PLANETARY_BODIES
+        .iter()
+        .find(|body| body.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_planetary_body() {
+        let mars = planetary_body("Mars").unwrap();
+        assert_eq!(mars.name, "mars");
+        assert!(!mars.is_sphere());
+        assert!((1.0 / mars.flattening() - 169.894).abs() < 1e-3);
+
+        assert!(planetary_body("MOON").unwrap().is_sphere());
+        assert_eq!(planetary_body("vulcan"), None);
+    }
+}
@@ -0,0 +1,481 @@
+//! Coordinate reference systems of projected and cartesian coordinates.
+//!
+//! Conversions go through [geodesy] operators, except for the polar stereographic
+//! projection, which geodesy does not provide.
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use geodesy::prelude::{Context, Coor4D, Direction, Minimal};
+use itertools::{MultiUnzip, izip};
+
+use crate::ellipsoid::{Ellipsoid, LatitudeKind, ReferenceBody};
+use crate::error::{Error, Result, check_length};
+
+/// A coordinate reference system.
+///
+/// Geographic coordinates are on the ellipsoid of the grid, in degrees. Systems
+/// defined on another ellipsoid are converted to it through body-centered cartesian
+/// coordinates, assuming both ellipsoids share their center and axes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Crs {
+    /// A geodesy operator definition, with the number of coordinates of the system.
+    ///
+    /// In its forward direction, the operator converts geographic coordinates in
+    /// radians to coordinates of the system, including datum shifts if it contains
+    /// any. The geographic coordinates are on `ellipsoid`, or on the ellipsoid of
+    /// the grid if `None`.
+    Operator {
+        definition: String,
+        dimension: usize,
+        ellipsoid: Option<Ellipsoid>,
+    },
+    PolarStereographic(PolarStereographic),
+}
+
+impl Crs {
+    pub fn operator(definition: &str, dimension: usize) -> Result<Self> {
+        if !(2..=3).contains(&dimension) {
+            return Err(Error::InvalidCrs(format!(
+                "the dimension must be 2 or 3, got {dimension}"
+            )));
+        }
+        Minimal::new()
+            .op(definition)
+            .map_err(|e| Error::InvalidCrs(e.to_string()))?;
+
+        Ok(Self::Operator {
+            definition: definition.to_string(),
+            dimension,
+            ellipsoid: None,
+        })
+    }
+
+    /// The system with the given EPSG code.
+    ///
+    /// Supported are WGS84 cartesian coordinates (4978), Web Mercator (3857), the
+    /// WGS84 (326xx, 327xx) and ETRS89 (25828 to 25838) UTM zones, the European
+    /// LAEA grid (3035) and the polar stereographic projections 3413, 3031, 3995 and
+    /// 3976. The systems keep their own ellipsoid, without datum shift to the grid.
+    pub fn epsg(code: u32) -> Result<Self> {
+        let on_ellipsoid = |definition: &str, dimension, name| {
+            Ok(Self::Operator {
+                definition: definition.to_string(),
+                dimension,
+                ellipsoid: Some(Ellipsoid::named(name)?),
+            })
+        };
+        let polar_stereographic = |standard_parallel, central_meridian| {
+            PolarStereographic::new(
+                Ellipsoid::named("WGS84")?,
+                standard_parallel,
+                central_meridian,
+                0.0,
+                0.0,
+            )
+            .map(Self::PolarStereographic)
+        };
+
+        match code {
+            4978 => on_ellipsoid("cart ellps=WGS84", 3, "WGS84"),
+            3857 => on_ellipsoid("webmerc", 2, "WGS84"),
+            3035 => on_ellipsoid(
+                "laea ellps=GRS80 lat_0=52 lon_0=10 x_0=4321000 y_0=3210000",
+                2,
+                "GRS80",
+            ),
+            32601..=32660 => on_ellipsoid(
+                &format!("utm zone={} ellps=WGS84", code - 32600),
+                2,
+                "WGS84",
+            ),
+            32701..=32760 => on_ellipsoid(
+                &format!("utm zone={} south ellps=WGS84", code - 32700),
+                2,
+                "WGS84",
+            ),
+            25828..=25838 => on_ellipsoid(
+                &format!("utm zone={} ellps=GRS80", code - 25800),
+                2,
+                "GRS80",
+            ),
+            3413 => polar_stereographic(70.0, -45.0),
+            3031 => polar_stereographic(-71.0, 0.0),
+            3995 => polar_stereographic(71.0, 0.0),
+            3976 => polar_stereographic(-70.0, 0.0),
+            _ => Err(Error::InvalidCrs(format!("unsupported EPSG code {code}"))),
+        }
+    }
+
+    /// The number of coordinates of the system.
+    pub fn dimension(&self) -> usize {
+        match self {
+            Self::Operator { dimension, .. } => *dimension,
+            Self::PolarStereographic(_) => 2,
+        }
+    }
+
+    /// The ellipsoid of the geographic coordinates of the system, if it differs from
+    /// `body`.
+    fn ellipsoid_unless<R: ReferenceBody>(&self, body: &R) -> Option<&Ellipsoid> {
+        let ellipsoid = match self {
+            Self::Operator { ellipsoid, .. } => ellipsoid.as_ref()?,
+            Self::PolarStereographic(projection) => &projection.ellipsoid,
+        };
+        let same = ellipsoid.semimajor_axis() == body.semimajor_axis()
+            && ellipsoid.flattening() == body.flattening();
+
+        (!same).then_some(ellipsoid)
+    }
+
+    /// Convert coordinates of the system to longitudes and latitudes on `body`.
+    ///
+    /// `z` is required by three-dimensional systems, and is the height otherwise.
+    /// Coordinates that can't be converted become NaN.
+    pub fn to_geographic<R: ReferenceBody>(
+        &self,
+        x: &[f64],
+        y: &[f64],
+        z: Option<&[f64]>,
+        body: &R,
+    ) -> Result<(Vec<f64>, Vec<f64>)> {
+        check_length(x.len(), y.len())?;
+        if let Some(z) = z {
+            check_length(x.len(), z.len())?;
+        } else if self.dimension() == 3 {
+            return Err(Error::InvalidCrs(
+                "three-dimensional coordinates need a z coordinate".to_string(),
+            ));
+        }
+        let ellipsoid = self.ellipsoid_unless(body);
+
+        match self {
+            Self::Operator { definition, .. } => {
+                let mut coordinates: Vec<Coor4D> = (0..x.len())
+                    .map(|index| {
+                        let z = z.map_or(0.0, |z| z[index]);
+                        Coor4D::raw(x[index], y[index], z, 0.0)
+                    })
+                    .collect();
+                apply_operator(definition, Direction::Inv, &mut coordinates)?;
+
+                Ok(coordinates
+                    .iter()
+                    .map(|coordinate| {
+                        let (lon, lat, _) = match ellipsoid {
+                            Some(ellipsoid) => {
+                                shift(ellipsoid, body, coordinate[0], coordinate[1], coordinate[2])
+                            }
+                            None => (coordinate[0], coordinate[1], coordinate[2]),
+                        };
+
+                        (lon.to_degrees(), lat.to_degrees())
+                    })
+                    .unzip())
+            }
+            Self::PolarStereographic(projection) => Ok(x
+                .iter()
+                .zip(y)
+                .enumerate()
+                .map(|(index, (x, y))| {
+                    let (lon, lat) = projection.inverse(*x, *y);
+                    let Some(ellipsoid) = ellipsoid else {
+                        return (lon, lat);
+                    };
+                    let height = z.map_or(0.0, |z| z[index]);
+                    let (lon, lat, _) =
+                        shift(ellipsoid, body, lon.to_radians(), lat.to_radians(), height);
+
+                    (lon.to_degrees(), lat.to_degrees())
+                })
+                .unzip()),
+        }
+    }
+
+    /// Convert longitudes and latitudes on `body` to coordinates of the system.
+    ///
+    /// The third coordinate is the height above the ellipsoid of the system for
+    /// two-dimensional systems. Coordinates that can't be converted become NaN.
+    pub fn from_geographic<R: ReferenceBody>(
+        &self,
+        lon: &[f64],
+        lat: &[f64],
+        body: &R,
+    ) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+        check_length(lon.len(), lat.len())?;
+        let ellipsoid = self.ellipsoid_unless(body);
+
+        // longitudes, latitudes in radians and heights on the ellipsoid of the system
+        let geodetic = lon.iter().zip(lat).map(|(lon, lat)| {
+            let (lon, lat) = (lon.to_radians(), lat.to_radians());
+
+            match ellipsoid {
+                Some(ellipsoid) => shift(body, ellipsoid, lon, lat, 0.0),
+                None => (lon, lat, 0.0),
+            }
+        });
+
+        match self {
+            Self::Operator { definition, .. } => {
+                let mut coordinates: Vec<Coor4D> = geodetic
+                    .map(|(lon, lat, height)| Coor4D::raw(lon, lat, height, 0.0))
+                    .collect();
+                apply_operator(definition, Direction::Fwd, &mut coordinates)?;
+
+                Ok(coordinates
+                    .iter()
+                    .map(|coordinate| (coordinate[0], coordinate[1], coordinate[2]))
+                    .multiunzip())
+            }
+            Self::PolarStereographic(projection) => Ok(geodetic
+                .map(|(lon, lat, height)| {
+                    let (x, y) = projection.forward(lon.to_degrees(), lat.to_degrees());
+                    (x, y, height)
+                })
+                .multiunzip()),
+        }
+    }
+
+    /// Convert the vertices of cells on `body` to coordinates of the system.
+    pub(crate) fn vertices_from_geographic<R: ReferenceBody>(
+        &self,
+        vertices: Vec<Vec<(f64, f64)>>,
+        body: &R,
+    ) -> Result<Vec<Vec<(f64, f64, f64)>>> {
+        let (lon, lat): (Vec<f64>, Vec<f64>) = vertices.iter().flatten().copied().unzip();
+        let (x, y, z) = self.from_geographic(&lon, &lat, body)?;
+
+        let mut points = izip!(x, y, z);
+
+        Ok(vertices
+            .iter()
+            .map(|row| points.by_ref().take(row.len()).collect())
+            .collect())
+    }
+}
+
+/// Move a longitude and latitude in radians and a height from the body `from` to the
+/// body `to`, through body-centered cartesian coordinates.
+fn shift<F: ReferenceBody, T: ReferenceBody>(
+    from: &F,
+    to: &T,
+    lon: f64,
+    lat: f64,
+    height: f64,
+) -> (f64, f64, f64) {
+    let (x, y, z) = from.geodetic_to_cartesian(lon, lat, height);
+
+    to.cartesian_to_geodetic(x, y, z)
+}
+
+fn apply_operator(
+    definition: &str,
+    direction: Direction,
+    coordinates: &mut Vec<Coor4D>,
+) -> Result<()> {
+    let mut context = Minimal::new();
+    let operator = context
+        .op(definition)
+        .map_err(|e| Error::InvalidCrs(e.to_string()))?;
+
+    context
+        .apply(operator, direction, coordinates)
+        .map_err(|e| Error::InvalidCrs(e.to_string()))?;
+
+    Ok(())
+}
+
+/// The polar stereographic projection, defined by a standard parallel (EPSG
+/// variant B).
+///
+/// The projection is centered on the pole of the hemisphere of the standard
+/// parallel. Angles are in degrees and the false easting and northing in meters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolarStereographic {
+    ellipsoid: Ellipsoid,
+    standard_parallel: f64,
+    central_meridian: f64,
+    false_easting: f64,
+    false_northing: f64,
+}
+
+impl PolarStereographic {
+    pub fn new(
+        ellipsoid: Ellipsoid,
+        standard_parallel: f64,
+        central_meridian: f64,
+        false_easting: f64,
+        false_northing: f64,
+    ) -> Result<Self> {
+        if standard_parallel == 0.0 || standard_parallel.abs() > 90.0 {
+            return Err(Error::InvalidCrs(format!(
+                "the standard parallel must be in [-90, 0) or (0, 90], got {standard_parallel}"
+            )));
+        }
+
+        Ok(Self {
+            ellipsoid,
+            standard_parallel,
+            central_meridian,
+            false_easting,
+            false_northing,
+        })
+    }
+
+    /// The hemisphere of the projection, as a sign.
+    fn sign(&self) -> f64 {
+        self.standard_parallel.signum()
+    }
+
+    /// `tan(π/4 - χ/2)` for the conformal latitude `χ` of the given latitude, in
+    /// radians, mirrored on the southern hemisphere.
+    fn polar_tangent(&self, lat: f64) -> f64 {
+        let conformal = self
+            .ellipsoid
+            .latitude_geographic_to(lat, LatitudeKind::Conformal);
+
+        (FRAC_PI_4 - self.sign() * conformal / 2.0).tan()
+    }
+
+    /// The ratio of the distance to the pole to the polar tangent.
+    fn scale(&self) -> f64 {
+        let a = self.ellipsoid.semimajor_axis();
+        let e = self.ellipsoid.eccentricity();
+
+        if self.standard_parallel.abs() == 90.0 {
+            // the limit at the pole, where the scale factor is 1
+            return 2.0 * a / ((1.0 + e).powf(1.0 + e) * (1.0 - e).powf(1.0 - e)).sqrt();
+        }
+
+        let phi = self.standard_parallel.to_radians();
+        let m = phi.cos() / (1.0 - (e * phi.sin()).powi(2)).sqrt();
+
+        a * m / self.polar_tangent(phi)
+    }
+
+    /// Project a longitude and latitude.
+    pub fn forward(&self, lon: f64, lat: f64) -> (f64, f64) {
+        let rho = self.scale() * self.polar_tangent(lat.to_radians());
+        let (sin, cos) = (lon - self.central_meridian).to_radians().sin_cos();
+
+        (
+            self.false_easting + rho * sin,
+            self.false_northing - self.sign() * rho * cos,
+        )
+    }
+
+    /// The longitude and latitude of a projected point.
+    pub fn inverse(&self, x: f64, y: f64) -> (f64, f64) {
+        let dx = x - self.false_easting;
+        let dy = y - self.false_northing;
+
+        let conformal = self.sign() * (FRAC_PI_2 - 2.0 * (dx.hypot(dy) / self.scale()).atan());
+        let lat = self
+            .ellipsoid
+            .latitude_to_geographic(conformal, LatitudeKind::Conformal);
+        let lon = self.central_meridian + dx.atan2(-self.sign() * dy).to_degrees();
+
+        (lon, lat.to_degrees())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polar_stereographic() {
+        // the example of the EPSG guidance note 7-2
+        let wgs84 = Ellipsoid::named("WGS84").unwrap();
+        let projection = PolarStereographic::new(wgs84, -71.0, 70.0, 6e6, 6e6).unwrap();
+
+        let (x, y) = projection.forward(120.0, -75.0);
+        assert!((x - 7255380.79).abs() < 1e-2);
+        assert!((y - 7053389.56).abs() < 1e-2);
+
+        let (lon, lat) = projection.inverse(x, y);
+        assert!((lon - 120.0).abs() < 1e-9);
+        assert!((lat + 75.0).abs() < 1e-9);
+
+        let (x, y) = projection.forward(0.0, -90.0);
+        assert!((x - 6e6).abs() < 1e-6 && (y - 6e6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_epsg() {
+        assert_eq!(Crs::epsg(3413).unwrap().dimension(), 2);
+        assert_eq!(Crs::epsg(4978).unwrap().dimension(), 3);
+        assert!(matches!(Crs::epsg(4326), Err(Error::InvalidCrs(_))));
+
+        let wgs84 = Ellipsoid::named("WGS84").unwrap();
+        let utm = Crs::epsg(32632).unwrap();
+        let (x, y, _) = utm.from_geographic(&[12.0], &[55.0], &wgs84).unwrap();
+        assert!((x[0] - 691875.632).abs() < 1e-2);
+        assert!((y[0] - 6098907.825).abs() < 1e-2);
+
+        let (lon, lat) = utm.to_geographic(&x, &y, None, &wgs84).unwrap();
+        assert!((lon[0] - 12.0).abs() < 1e-9);
+        assert!((lat[0] - 55.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cartesian() {
+        let wgs84 = Ellipsoid::named("WGS84").unwrap();
+        let ecef = Crs::epsg(4978).unwrap();
+
+        let (x, y, z) = ecef
+            .from_geographic(&[0.0, 90.0], &[0.0, 0.0], &wgs84)
+            .unwrap();
+        assert!((x[0] - 6378137.0).abs() < 1e-6 && y[0].abs() < 1e-6 && z[0].abs() < 1e-6);
+        assert!(x[1].abs() < 1e-6 && (y[1] - 6378137.0).abs() < 1e-6);
+
+        let (lon, lat) = ecef
+            .to_geographic(&[0.0], &[0.0], Some(&[6356752.314245]), &wgs84)
+            .unwrap();
+        assert!(lon[0].is_finite() && (lat[0] - 90.0).abs() < 1e-9);
+
+        assert!(matches!(
+            ecef.to_geographic(&[0.0], &[0.0], None, &wgs84),
+            Err(Error::InvalidCrs(_))
+        ));
+    }
+
+    #[test]
+    fn test_other_ellipsoid() {
+        let sphere = Ellipsoid::sphere(6371007.0).unwrap();
+        let ecef = Crs::epsg(4978).unwrap();
+
+        // the points stay on the sphere of the grid
+        let (x, y, z) = ecef.from_geographic(&[0.0], &[45.0], &sphere).unwrap();
+        let radius = 6371007.0 * 45f64.to_radians().cos();
+        assert!((x[0] - radius).abs() < 1e-4 && y[0].abs() < 1e-4);
+        assert!((z[0] - radius).abs() < 1e-4);
+
+        let (lon, lat) = ecef.to_geographic(&x, &y, Some(&z), &sphere).unwrap();
+        assert!(lon[0].abs() < 1e-9 && (lat[0] - 45.0).abs() < 1e-9);
+
+        for crs in [Crs::epsg(32632).unwrap(), Crs::epsg(3413).unwrap()] {
+            let (x, y, height) = crs.from_geographic(&[12.0], &[75.0], &sphere).unwrap();
+            assert!(height[0].abs() > 1.0);
+
+            let (lon, lat) = crs.to_geographic(&x, &y, Some(&height), &sphere).unwrap();
+            assert!((lon[0] - 12.0).abs() < 1e-9 && (lat[0] - 75.0).abs() < 1e-9);
+
+            // without the height, the point moves along the normal of the system
+            let (_, moved) = crs.to_geographic(&x, &y, None, &sphere).unwrap();
+            assert!((moved[0] - 75.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_vertices_from_geographic() {
+        let crs = Crs::epsg(3031).unwrap();
+        let vertices = vec![vec![(0.0, -90.0), (90.0, -80.0)], vec![(180.0, -80.0)]];
+
+        let wgs84 = Ellipsoid::named("WGS84").unwrap();
+        let actual = crs.vertices_from_geographic(vertices, &wgs84).unwrap();
+
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual[0].len(), 2);
+        assert!(actual[0][0].0.abs() < 1e-6 && actual[0][0].1.abs() < 1e-6);
+        assert!(actual[0][1].0 > 0.0 && actual[1][0].1 < 0.0);
+    }
+}
@@ -1,13 +1,142 @@
+use std::fmt;
+use std::sync::Arc;
+
 use geodesy::authoring::FourierCoefficients;
-use geodesy::ellps::{Ellipsoid as GeodesyEllipsoid, Latitudes};
+use geodesy::ellps::{Ellipsoid as GeodesyEllipsoid, EllipsoidBase, Latitudes};
+
+use crate::bodies::planetary_body;
+use crate::error::{Error, Result};
+use crate::geodesic::Geodesic;
+
+/// The kinds of latitude of a point on the reference body.
+///
+/// On a sphere all of them are equal to the geographic latitude.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LatitudeKind {
+    /// The angle between the normal to the ellipsoid and the equatorial plane.
+    Geographic,
+    /// The angle between the radius from the center and the equatorial plane.
+    Geocentric,
+    /// The latitude on the circumscribed sphere of the point projected parallel to
+    /// the minor axis, also called the reduced latitude.
+    Parametric,
+    /// The latitude on the sphere with the same meridian length.
+    Rectifying,
+    /// The latitude on the sphere of the conformal mapping of the ellipsoid.
+    Conformal,
+    /// The latitude on the sphere with the same surface area.
+    Authalic,
+}
+
+/// A reference body of revolution, with latitudes in radians.
+///
+/// The functions of this crate are generic over the reference body, so other crates
+/// can define their own bodies, e.g. with a more precise authalic series.
+pub trait ReferenceBody: Sync {
+    fn semimajor_axis(&self) -> f64;
+    fn flattening(&self) -> f64;
+
+    fn semiminor_axis(&self) -> f64 {
+        self.semimajor_axis() * (1.0 - self.flattening())
+    }
+
+    /// The first eccentricity.
+    fn eccentricity(&self) -> f64 {
+        let flattening = self.flattening();
+
+        (flattening * (2.0 - flattening)).sqrt()
+    }
+
+    /// Radius of the sphere with the same surface area as the body.
+    fn authalic_radius(&self) -> f64 {
+        let semimajor_axis = self.semimajor_axis();
+        let flattening = self.flattening();
+        if flattening == 0.0 {
+            return semimajor_axis;
+        }
+
+        let e2 = flattening * (2.0 - flattening);
+        let e = e2.sqrt();
+        let q_p = 1.0 + (1.0 - e2) * e.atanh() / e;
+
+        semimajor_axis * (q_p / 2.0).sqrt()
+    }
+
+    /// Geodesic solver for the body.
+    fn geodesic(&self) -> Geodesic {
+        Geodesic::new(self.semimajor_axis(), self.flattening())
+    }
 
-pub trait ReferenceBody {
     fn latitude_authalic_to_geographic(&self, latitude: f64) -> f64;
     fn latitude_geographic_to_authalic(&self, latitude: f64) -> f64;
+
+    /// Convert a geographic latitude to a latitude of the given kind.
+    fn latitude_geographic_to(&self, latitude: f64, kind: LatitudeKind) -> f64;
+    /// Convert a latitude of the given kind to a geographic latitude.
+    fn latitude_to_geographic(&self, latitude: f64, kind: LatitudeKind) -> f64;
+
+    /// Convert a latitude between any two kinds.
+    fn convert_latitude(&self, latitude: f64, from: LatitudeKind, to: LatitudeKind) -> f64 {
+        if from == to {
+            return latitude;
+        }
+
+        self.latitude_geographic_to(self.latitude_to_geographic(latitude, from), to)
+    }
+
+    /// Convert geographic coordinates and a height above the body to body-centered
+    /// cartesian coordinates.
+    fn geodetic_to_cartesian(&self, lon: f64, lat: f64, height: f64) -> (f64, f64, f64) {
+        let semimajor_axis = self.semimajor_axis();
+        let e2 = self.eccentricity().powi(2);
+
+        let (sin_lat, cos_lat) = lat.sin_cos();
+        let (sin_lon, cos_lon) = lon.sin_cos();
+        let normal = semimajor_axis / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+
+        (
+            (normal + height) * cos_lat * cos_lon,
+            (normal + height) * cos_lat * sin_lon,
+            (normal * (1.0 - e2) + height) * sin_lat,
+        )
+    }
+
+    /// Convert body-centered cartesian coordinates to geographic coordinates and a
+    /// height above the body.
+    ///
+    /// Uses Bowring's iteration. The center of the body has no latitude, which is
+    /// returned as NaN.
+    fn cartesian_to_geodetic(&self, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        let semimajor_axis = self.semimajor_axis();
+        let semiminor_axis = self.semiminor_axis();
+        let flattening = self.flattening();
+        let e2 = self.eccentricity().powi(2);
+        let ep2 = e2 / (1.0 - e2);
+
+        let p = x.hypot(y);
+        if p == 0.0 && z == 0.0 {
+            return (0.0, f64::NAN, -semimajor_axis);
+        }
+
+        let mut parametric = z.atan2((1.0 - flattening) * p);
+        let mut lat = 0.0;
+        for _ in 0..3 {
+            let (sin, cos) = parametric.sin_cos();
+            lat = (z + ep2 * semiminor_axis * sin.powi(3))
+                .atan2(p - e2 * semimajor_axis * cos.powi(3));
+            parametric = ((1.0 - flattening) * lat.sin()).atan2(lat.cos());
+        }
+
+        let (sin_lat, cos_lat) = lat.sin_cos();
+        let height =
+            p * cos_lat + z * sin_lat - semimajor_axis * (1.0 - e2 * sin_lat * sin_lat).sqrt();
+
+        (y.atan2(x), lat, height)
+    }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct ReferenceSphere {
-    #[allow(dead_code)]
     ellipsoid: GeodesyEllipsoid,
 }
 
@@ -18,6 +147,14 @@ impl ReferenceSphere {
 }
 
 impl ReferenceBody for ReferenceSphere {
+    fn semimajor_axis(&self) -> f64 {
+        self.ellipsoid.semimajor_axis()
+    }
+
+    fn flattening(&self) -> f64 {
+        0.0
+    }
+
     fn latitude_authalic_to_geographic(&self, latitude: f64) -> f64 {
         latitude
     }
@@ -25,42 +162,234 @@ impl ReferenceBody for ReferenceSphere {
     fn latitude_geographic_to_authalic(&self, latitude: f64) -> f64 {
         latitude
     }
+
+    fn latitude_geographic_to(&self, latitude: f64, _kind: LatitudeKind) -> f64 {
+        latitude
+    }
+
+    fn latitude_to_geographic(&self, latitude: f64, _kind: LatitudeKind) -> f64 {
+        latitude
+    }
+}
+
+/// The coefficients of the latitude conversions computed as Fourier series.
+struct LatitudeCoefficients {
+    authalic: FourierCoefficients,
+    conformal: FourierCoefficients,
+    rectifying: FourierCoefficients,
 }
 
+// The coefficients are shared between clones, so that they are only computed once
+// per ellipsoid.
+#[derive(Clone)]
 pub struct ReferenceEllipsoid {
     ellipsoid: GeodesyEllipsoid,
-    coefficients: FourierCoefficients,
+    coefficients: Arc<LatitudeCoefficients>,
 }
 
 impl ReferenceEllipsoid {
     pub fn new(ellipsoid: GeodesyEllipsoid) -> Self {
-        let coefficients = ellipsoid.coefficients_for_authalic_latitude_computations();
+        let coefficients = LatitudeCoefficients {
+            authalic: ellipsoid.coefficients_for_authalic_latitude_computations(),
+            conformal: ellipsoid.coefficients_for_conformal_latitude_computations(),
+            rectifying: ellipsoid.coefficients_for_rectifying_latitude_computations(),
+        };
 
         Self {
             ellipsoid,
-            coefficients,
+            coefficients: Arc::new(coefficients),
         }
     }
 }
 
+// The coefficients are fully determined by the ellipsoid parameters, so
+// comparisons only need to look at the ellipsoid.
+impl PartialEq for ReferenceEllipsoid {
+    fn eq(&self, other: &Self) -> bool {
+        self.ellipsoid == other.ellipsoid
+    }
+}
+
+impl fmt::Debug for ReferenceEllipsoid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReferenceEllipsoid")
+            .field("ellipsoid", &self.ellipsoid)
+            .finish()
+    }
+}
+
 impl ReferenceBody for ReferenceEllipsoid {
+    fn semimajor_axis(&self) -> f64 {
+        self.ellipsoid.semimajor_axis()
+    }
+
+    fn flattening(&self) -> f64 {
+        self.ellipsoid.flattening()
+    }
+
     fn latitude_authalic_to_geographic(&self, latitude: f64) -> f64 {
         self.ellipsoid
-            .latitude_authalic_to_geographic(latitude, &self.coefficients)
+            .latitude_authalic_to_geographic(latitude, &self.coefficients.authalic)
     }
 
     fn latitude_geographic_to_authalic(&self, latitude: f64) -> f64 {
         self.ellipsoid
-            .latitude_geographic_to_authalic(latitude, &self.coefficients)
+            .latitude_geographic_to_authalic(latitude, &self.coefficients.authalic)
+    }
+
+    fn latitude_geographic_to(&self, latitude: f64, kind: LatitudeKind) -> f64 {
+        let ellipsoid = &self.ellipsoid;
+        let coefficients = &self.coefficients;
+
+        match kind {
+            LatitudeKind::Geographic => latitude,
+            LatitudeKind::Geocentric => ellipsoid.latitude_geographic_to_geocentric(latitude),
+            LatitudeKind::Parametric => ellipsoid.latitude_geographic_to_reduced(latitude),
+            LatitudeKind::Rectifying => {
+                ellipsoid.latitude_geographic_to_rectifying(latitude, &coefficients.rectifying)
+            }
+            LatitudeKind::Conformal => {
+                ellipsoid.latitude_geographic_to_conformal(latitude, &coefficients.conformal)
+            }
+            LatitudeKind::Authalic => self.latitude_geographic_to_authalic(latitude),
+        }
+    }
+
+    fn latitude_to_geographic(&self, latitude: f64, kind: LatitudeKind) -> f64 {
+        let ellipsoid = &self.ellipsoid;
+        let coefficients = &self.coefficients;
+
+        match kind {
+            LatitudeKind::Geographic => latitude,
+            LatitudeKind::Geocentric => ellipsoid.latitude_geocentric_to_geographic(latitude),
+            LatitudeKind::Parametric => ellipsoid.latitude_reduced_to_geographic(latitude),
+            LatitudeKind::Rectifying => {
+                ellipsoid.latitude_rectifying_to_geographic(latitude, &coefficients.rectifying)
+            }
+            LatitudeKind::Conformal => {
+                ellipsoid.latitude_conformal_to_geographic(latitude, &coefficients.conformal)
+            }
+            LatitudeKind::Authalic => self.latitude_authalic_to_geographic(latitude),
+        }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum Ellipsoid {
     Ellipsoid(ReferenceEllipsoid),
     Sphere(ReferenceSphere),
 }
 
+impl Ellipsoid {
+    /// Look up an ellipsoid by name.
+    ///
+    /// Planetary bodies are looked up first, ignoring case, with the `_sphere`
+    /// suffix selecting the sphere of their mean radius. Other names are those
+    /// known to `geodesy`.
+    pub fn named(name: &str) -> Result<Self> {
+        if let Some(body) = name.strip_suffix("_sphere").and_then(planetary_body) {
+            return Self::sphere(body.mean_radius);
+        }
+        if let Some(body) = planetary_body(name) {
+            return Self::from_semimajor_axis_and_flattening(
+                body.equatorial_radius,
+                body.flattening(),
+                body.is_sphere(),
+            );
+        }
+
+        let ellipsoid =
+            GeodesyEllipsoid::named(name).map_err(|e| Error::InvalidEllipsoid(e.to_string()))?;
+
+        if ellipsoid.flattening() == 0.0 {
+            Ok(Self::Sphere(ReferenceSphere::new(ellipsoid)))
+        } else {
+            Ok(Self::Ellipsoid(ReferenceEllipsoid::new(ellipsoid)))
+        }
+    }
+
+    pub fn from_parameters(semimajor_axis: f64, inverse_flattening: f64) -> Result<Self> {
+        if inverse_flattening >= 2.0 && semimajor_axis > 0.0 {
+            let ellipsoid = GeodesyEllipsoid::new(semimajor_axis, 1.0f64 / inverse_flattening);
+
+            Ok(Self::Ellipsoid(ReferenceEllipsoid::new(ellipsoid)))
+        } else if inverse_flattening < 2.0 {
+            Err(Error::InvalidEllipsoid(format!(
+                "The inverse flattening must be greater than or equal to 2, but got {:?}.",
+                inverse_flattening,
+            )))
+        } else {
+            Err(Error::InvalidEllipsoid(format!(
+                "The semimajor axis must be greater than 0, but got {:?}.",
+                semimajor_axis
+            )))
+        }
+    }
+
+    pub fn sphere(radius: f64) -> Result<Self> {
+        if radius > 0.0 {
+            let ellipsoid = GeodesyEllipsoid::new(radius, 0.0f64);
+
+            Ok(Self::Sphere(ReferenceSphere::new(ellipsoid)))
+        } else {
+            Err(Error::InvalidEllipsoid(format!(
+                "The radius must be greater than 0, but got {:?}.",
+                radius
+            )))
+        }
+    }
+
+    /// Reconstruct an ellipsoid from its semimajor axis and flattening.
+    ///
+    /// Unlike `from_parameters`, this takes the flattening itself so that
+    /// parameters obtained from `semimajor_axis` and `flattening` round-trip
+    /// exactly.
+    pub fn from_semimajor_axis_and_flattening(
+        semimajor_axis: f64,
+        flattening: f64,
+        is_sphere: bool,
+    ) -> Result<Self> {
+        if semimajor_axis.is_nan() || semimajor_axis <= 0.0 {
+            return Err(Error::InvalidEllipsoid(format!(
+                "The semimajor axis must be greater than 0, but got {:?}.",
+                semimajor_axis
+            )));
+        }
+        if !(0.0..=0.5).contains(&flattening) {
+            return Err(Error::InvalidEllipsoid(format!(
+                "The flattening must be between 0 and 0.5, but got {:?}.",
+                flattening
+            )));
+        }
+
+        let ellipsoid = GeodesyEllipsoid::new(semimajor_axis, flattening);
+        if is_sphere {
+            Ok(Self::Sphere(ReferenceSphere::new(ellipsoid)))
+        } else {
+            Ok(Self::Ellipsoid(ReferenceEllipsoid::new(ellipsoid)))
+        }
+    }
+
+    pub fn is_sphere(&self) -> bool {
+        matches!(self, Self::Sphere(_))
+    }
+}
+
 impl ReferenceBody for Ellipsoid {
+    fn semimajor_axis(&self) -> f64 {
+        match self {
+            Self::Ellipsoid(wrapped) => wrapped.semimajor_axis(),
+            Self::Sphere(wrapped) => wrapped.semimajor_axis(),
+        }
+    }
+
+    fn flattening(&self) -> f64 {
+        match self {
+            Self::Ellipsoid(wrapped) => wrapped.flattening(),
+            Self::Sphere(wrapped) => wrapped.flattening(),
+        }
+    }
+
     fn latitude_authalic_to_geographic(&self, latitude: f64) -> f64 {
         match self {
             Self::Ellipsoid(wrapped) => wrapped.latitude_authalic_to_geographic(latitude),
@@ -74,6 +403,20 @@ impl ReferenceBody for Ellipsoid {
             Self::Sphere(wrapped) => wrapped.latitude_geographic_to_authalic(latitude),
         }
     }
+
+    fn latitude_geographic_to(&self, latitude: f64, kind: LatitudeKind) -> f64 {
+        match self {
+            Self::Ellipsoid(wrapped) => wrapped.latitude_geographic_to(latitude, kind),
+            Self::Sphere(wrapped) => wrapped.latitude_geographic_to(latitude, kind),
+        }
+    }
+
+    fn latitude_to_geographic(&self, latitude: f64, kind: LatitudeKind) -> f64 {
+        match self {
+            Self::Ellipsoid(wrapped) => wrapped.latitude_to_geographic(latitude, kind),
+            Self::Sphere(wrapped) => wrapped.latitude_to_geographic(latitude, kind),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -132,4 +475,196 @@ mod tests {
 
         assert_eq!(geographic, lat);
     }
+
+    #[test]
+    fn test_latitude_kinds() {
+        use LatitudeKind::*;
+
+        let kinds = [
+            Geographic, Geocentric, Parametric, Rectifying, Conformal, Authalic,
+        ];
+        let latitude = 45f64.to_radians();
+
+        let sphere = Ellipsoid::named("sphere").unwrap();
+        for kind in kinds {
+            assert_eq!(sphere.latitude_geographic_to(latitude, kind), latitude);
+            assert_eq!(sphere.latitude_to_geographic(latitude, kind), latitude);
+        }
+
+        let wgs84 = Ellipsoid::named("WGS84").unwrap();
+        let f = wgs84.flattening();
+        let geocentric = wgs84.latitude_geographic_to(latitude, Geocentric);
+        assert!((geocentric.tan() - (1.0 - f).powi(2) * latitude.tan()).abs() < 1e-12);
+        let parametric = wgs84.latitude_geographic_to(latitude, Parametric);
+        assert!((parametric.tan() - (1.0 - f) * latitude.tan()).abs() < 1e-12);
+        assert_eq!(
+            wgs84.latitude_geographic_to(latitude, Authalic),
+            wgs84.latitude_geographic_to_authalic(latitude)
+        );
+
+        for kind in kinds {
+            let converted = wgs84.latitude_geographic_to(latitude, kind);
+            assert!(kind == Geographic || converted < latitude);
+            assert!((wgs84.latitude_to_geographic(converted, kind) - latitude).abs() < 1e-12);
+        }
+
+        let conformal = wgs84.convert_latitude(geocentric, Geocentric, Conformal);
+        let expected = wgs84.latitude_geographic_to(latitude, Conformal);
+        assert!((conformal - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_named_planetary_bodies() {
+        let mars = Ellipsoid::named("Mars").unwrap();
+        assert!(!mars.is_sphere());
+        assert_eq!(mars.semimajor_axis(), 3396190.0);
+        assert!((mars.semiminor_axis() - 3376200.0).abs() < 1e-6);
+
+        let mars_sphere = Ellipsoid::named("mars_sphere").unwrap();
+        assert!(mars_sphere.is_sphere());
+        assert_eq!(mars_sphere.semimajor_axis(), 3389500.0);
+
+        let moon = Ellipsoid::named("moon").unwrap();
+        assert!(moon.is_sphere());
+        assert_eq!(moon.authalic_radius(), 1737400.0);
+
+        assert!(Ellipsoid::named("unitsphere").unwrap().is_sphere());
+        assert!(!Ellipsoid::named("GRS80").unwrap().is_sphere());
+        assert!(Ellipsoid::named("vulcan").is_err());
+    }
+
+    #[test]
+    fn test_cartesian_conversions() {
+        use std::f64::consts::FRAC_PI_2;
+
+        let wgs84 = Ellipsoid::named("WGS84").unwrap();
+        let a = wgs84.semimajor_axis();
+        let b = wgs84.semiminor_axis();
+
+        let (x, y, z) = wgs84.geodetic_to_cartesian(0.0, 0.0, 0.0);
+        assert_eq!((x, y, z), (a, 0.0, 0.0));
+        let (x, y, z) = wgs84.geodetic_to_cartesian(0.0, FRAC_PI_2, 100.0);
+        assert!(x.abs() < 1e-6 && y == 0.0 && (z - b - 100.0).abs() < 1e-6);
+
+        for (lon, lat, height) in [(-2.5, -1.2, 0.0), (0.3, 0.7, 8848.0), (3.0, 1.5, -1e4)] {
+            let (x, y, z) = wgs84.geodetic_to_cartesian(lon, lat, height);
+            let actual = wgs84.cartesian_to_geodetic(x, y, z);
+
+            assert!((actual.0 - lon).abs() < 1e-12);
+            assert!((actual.1 - lat).abs() < 1e-12);
+            assert!((actual.2 - height).abs() < 1e-6);
+        }
+
+        assert!(wgs84.cartesian_to_geodetic(0.0, 0.0, 0.0).1.is_nan());
+    }
+
+    /// A body defined outside of the crate, with a radius of 2.
+    struct CustomSphere;
+
+    impl ReferenceBody for CustomSphere {
+        fn semimajor_axis(&self) -> f64 {
+            2.0
+        }
+
+        fn flattening(&self) -> f64 {
+            0.0
+        }
+
+        fn latitude_authalic_to_geographic(&self, latitude: f64) -> f64 {
+            latitude
+        }
+
+        fn latitude_geographic_to_authalic(&self, latitude: f64) -> f64 {
+            latitude
+        }
+
+        fn latitude_geographic_to(&self, latitude: f64, _kind: LatitudeKind) -> f64 {
+            latitude
+        }
+
+        fn latitude_to_geographic(&self, latitude: f64, _kind: LatitudeKind) -> f64 {
+            latitude
+        }
+    }
+
+    #[test]
+    fn test_custom_reference_body() {
+        use crate::scalar::nested::{coordinates, measures};
+
+        let layer = cdshealpix::nested::get(2);
+        let sphere = Ellipsoid::sphere(2.0).unwrap();
+
+        assert_eq!(CustomSphere.authalic_radius(), 2.0);
+        assert_eq!(
+            coordinates::healpix_to_lonlat(&17, layer, &CustomSphere).unwrap(),
+            coordinates::healpix_to_lonlat(&17, layer, &sphere).unwrap()
+        );
+        assert_eq!(
+            measures::cell_area(&17, layer, &CustomSphere).unwrap(),
+            measures::cell_area(&17, layer, &sphere).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ellipsoid_invalid_parameters() {
+        let err = Ellipsoid::from_parameters(6378137.0, 0.5).err().unwrap();
+        assert_eq!(
+            err,
+            Error::InvalidEllipsoid(
+                "The inverse flattening must be greater than or equal to 2, but got 0.5."
+                    .to_string()
+            )
+        );
+
+        let err = Ellipsoid::sphere(-1.0).err().unwrap();
+        assert_eq!(
+            err,
+            Error::InvalidEllipsoid("The radius must be greater than 0, but got -1.0.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_authalic_radius() {
+        let sphere = Ellipsoid::sphere(6371000.0).unwrap();
+        assert_eq!(sphere.authalic_radius(), 6371000.0);
+
+        let wgs84 = Ellipsoid::named("WGS84").unwrap();
+        assert!((wgs84.authalic_radius() - 6371007.1809).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_axes_and_eccentricity() {
+        let sphere = Ellipsoid::sphere(6371000.0).unwrap();
+        assert_eq!(sphere.semiminor_axis(), 6371000.0);
+        assert_eq!(sphere.eccentricity(), 0.0);
+
+        let wgs84 = Ellipsoid::named("WGS84").unwrap();
+        assert!((wgs84.semiminor_axis() - 6356752.314245).abs() < 1e-6);
+        assert!((wgs84.eccentricity() - 0.0818191908426).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_geodesic() {
+        let wgs84 = Ellipsoid::named("WGS84").unwrap();
+
+        // a quarter of the equator
+        let (distance, _, _) = wgs84.geodesic().inverse(0.0, 0.0, 90.0, 0.0);
+        let expected = std::f64::consts::FRAC_PI_2 * 6378137.0;
+        assert!((distance - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ellipsoid_parameters_roundtrip() {
+        let ellipsoid = Ellipsoid::named("WGS84").unwrap();
+
+        let reconstructed = Ellipsoid::from_semimajor_axis_and_flattening(
+            ellipsoid.semimajor_axis(),
+            ellipsoid.flattening(),
+            ellipsoid.is_sphere(),
+        )
+        .unwrap();
+
+        assert_eq!(reconstructed, ellipsoid);
+        assert_ne!(reconstructed, Ellipsoid::named("sphere").unwrap());
+    }
 }
@@ -0,0 +1,265 @@
+use std::fmt;
+
+use cdshealpix as healpix;
+
+pub const MAX_DEPTH: u8 = 29;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The depth is greater than the maximum supported depth.
+    DepthOutOfRange {
+        depth: u8,
+        max_depth: u8,
+    },
+    /// The cells at `depth` have no parents `delta_depth` levels coarser.
+    DeltaDepthOutOfRange {
+        delta_depth: u8,
+        depth: u8,
+    },
+    /// The cell id does not exist at the given depth. `depth` is `None` if
+    /// the depth is encoded in the cell id (zuniq).
+    InvalidHash {
+        hash: u64,
+        depth: Option<u8>,
+    },
+    InvalidGeometry(String),
+    InvalidEllipsoid(String),
+    /// The coordinate reference system is unknown or its operator is invalid.
+    InvalidCrs(String),
+    /// The coordinates are not finite or the latitude is outside of [-90°, 90°].
+    InvalidCoordinates {
+        lon: f64,
+        lat: f64,
+    },
+    /// Slices that are processed together have different lengths.
+    LengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// Elements are paired with chunks of another slice, but the chunks are empty.
+    EmptyChunks,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DepthOutOfRange { depth, max_depth } => write!(
+                f,
+                "depth must be between 0 and {max_depth}, inclusive (got {depth})"
+            ),
+            Self::DeltaDepthOutOfRange { delta_depth, depth } => write!(
+                f,
+                "delta depth must be at most the depth of the cells, {depth} (got {delta_depth})"
+            ),
+            Self::InvalidHash {
+                hash,
+                depth: Some(depth),
+            } => write!(
+                f,
+                "invalid cell id {hash} at depth {depth}: must be in the range [0, {})",
+                healpix::n_hash(*depth)
+            ),
+            Self::InvalidHash { hash, depth: None } => write!(f, "invalid zuniq cell id {hash}"),
+            Self::InvalidGeometry(message) => write!(f, "invalid geometry: {message}"),
+            Self::InvalidEllipsoid(message) => write!(f, "{message}"),
+            Self::InvalidCrs(message) => write!(f, "invalid CRS: {message}"),
+            Self::InvalidCoordinates { lon, lat } => {
+                write!(f, "invalid coordinates: longitude {lon}, latitude {lat}")
+            }
+            Self::LengthMismatch { expected, actual } => write!(
+                f,
+                "length mismatch: expected {expected} elements, got {actual}"
+            ),
+            Self::EmptyChunks => write!(f, "chunks must contain at least one element"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// How the vectorized conversions handle invalid cell ids and coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InvalidPolicy {
+    /// Fail with the error of the first invalid element.
+    #[default]
+    Raise,
+    /// Replace the result of invalid elements by a sentinel: `u64::MAX` for cell ids
+    /// and NaN for coordinates.
+    Sentinel,
+}
+
+impl InvalidPolicy {
+    /// Apply the policy to the result of a single element.
+    ///
+    /// Errors that are not caused by the element itself, like an invalid depth, are
+    /// always returned.
+    pub(crate) fn apply<T>(self, result: Result<T>, sentinel: T) -> Result<T> {
+        match result {
+            Err(Error::InvalidHash { .. } | Error::InvalidCoordinates { .. })
+                if self == Self::Sentinel =>
+            {
+                Ok(sentinel)
+            }
+            result => result,
+        }
+    }
+}
+
+pub(crate) fn check_depth(depth: u8) -> Result<()> {
+    if depth > MAX_DEPTH {
+        Err(Error::DepthOutOfRange {
+            depth,
+            max_depth: MAX_DEPTH,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn check_delta_depth(depth: u8, delta_depth: u8) -> Result<()> {
+    check_depth(depth.saturating_add(delta_depth))
+}
+
+pub(crate) fn check_length(expected: usize, actual: usize) -> Result<()> {
+    if expected != actual {
+        Err(Error::LengthMismatch { expected, actual })
+    } else {
+        Ok(())
+    }
+}
+
+/// Check that each of the `n` elements can be paired with a chunk of `chunks` elements
+/// of a slice of length `actual`.
+pub(crate) fn check_chunks(n: usize, chunks: usize, actual: usize) -> Result<()> {
+    if chunks == 0 {
+        return Err(Error::EmptyChunks);
+    }
+
+    check_length(n * chunks, actual)
+}
+
+pub(crate) fn check_lonlat(lon: f64, lat: f64) -> Result<()> {
+    if lon.is_finite() && (-90.0..=90.0).contains(&lat) {
+        Ok(())
+    } else {
+        Err(Error::InvalidCoordinates { lon, lat })
+    }
+}
+
+pub(crate) fn check_hash(hash: u64, depth: u8) -> Result<()> {
+    check_depth(depth)?;
+
+    if hash >= healpix::n_hash(depth) {
+        Err(Error::InvalidHash {
+            hash,
+            depth: Some(depth),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Decode a zuniq cell id into `(depth, nested hash)`, validating it on the way.
+pub(crate) fn check_zuniq(hash: u64) -> Result<(u8, u64)> {
+    let n_trailing_zeros = hash.trailing_zeros();
+    let invalid = Error::InvalidHash { hash, depth: None };
+
+    if n_trailing_zeros > 2 * MAX_DEPTH as u32 || !n_trailing_zeros.is_multiple_of(2) {
+        return Err(invalid);
+    }
+
+    let (depth, hash_nested) = healpix::nested::from_zuniq(hash);
+    if hash_nested >= healpix::n_hash(depth) {
+        return Err(invalid);
+    }
+
+    Ok((depth, hash_nested))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_hash() {
+        assert_eq!(check_hash(47, 1), Ok(()));
+        assert_eq!(
+            check_hash(48, 1),
+            Err(Error::InvalidHash {
+                hash: 48,
+                depth: Some(1)
+            })
+        );
+        assert_eq!(
+            check_hash(0, 30),
+            Err(Error::DepthOutOfRange {
+                depth: 30,
+                max_depth: 29
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_lonlat() {
+        assert_eq!(check_lonlat(-180.0, 90.0), Ok(()));
+        assert!(check_lonlat(f64::NAN, 0.0).is_err());
+        assert!(check_lonlat(0.0, f64::NAN).is_err());
+        assert!(check_lonlat(f64::INFINITY, 0.0).is_err());
+        assert_eq!(
+            check_lonlat(10.0, 95.0),
+            Err(Error::InvalidCoordinates {
+                lon: 10.0,
+                lat: 95.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_chunks() {
+        assert_eq!(check_chunks(3, 2, 6), Ok(()));
+        assert_eq!(check_chunks(0, 2, 0), Ok(()));
+        assert_eq!(check_chunks(2, 0, 0), Err(Error::EmptyChunks));
+        assert_eq!(
+            check_chunks(3, 2, 5),
+            Err(Error::LengthMismatch {
+                expected: 6,
+                actual: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_policy() {
+        let invalid = || -> Result<u64> { check_hash(48, 1).map(|_| 48) };
+
+        assert_eq!(InvalidPolicy::Raise.apply(invalid(), u64::MAX), invalid());
+        assert_eq!(
+            InvalidPolicy::Sentinel.apply(invalid(), u64::MAX),
+            Ok(u64::MAX)
+        );
+        assert_eq!(InvalidPolicy::Sentinel.apply(Ok(3), u64::MAX), Ok(3));
+        assert_eq!(
+            InvalidPolicy::Sentinel.apply(check_hash(0, 30).map(|_| 0), u64::MAX),
+            Err(Error::DepthOutOfRange {
+                depth: 30,
+                max_depth: 29
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_zuniq() {
+        let zuniq = healpix::nested::to_zuniq(3, 42);
+        assert_eq!(check_zuniq(zuniq), Ok((3, 42)));
+
+        assert_eq!(
+            check_zuniq(0),
+            Err(Error::InvalidHash {
+                hash: 0,
+                depth: None
+            })
+        );
+    }
+}
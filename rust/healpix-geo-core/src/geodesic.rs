@@ -0,0 +1,772 @@
+//! Geodesics on an ellipsoid of revolution.
+//!
+//! This is a port of the solution of the inverse geodesic problem by C. F. F. Karney,
+//! "Algorithms for geodesics", J. Geodesy 87, 43–55 (2013), following the reference
+//! implementation in GeographicLib. Only distances and azimuths are computed. The
+//! series are expanded to 6th order, which is accurate to round-off for |f| < 0.01.
+use std::f64::consts::PI;
+
+const ORDER: usize = 6;
+const N_C3X: usize = (ORDER * (ORDER - 1)) / 2;
+
+const TOL0: f64 = f64::EPSILON;
+const TOL1: f64 = 200.0 * TOL0;
+const MAXIT1: usize = 20;
+const MAXIT2: usize = MAXIT1 + f64::MANTISSA_DIGITS as usize + 10;
+
+fn tiny() -> f64 {
+    f64::MIN_POSITIVE.sqrt()
+}
+
+fn tol2() -> f64 {
+    TOL0.sqrt()
+}
+
+/// Evaluate the polynomial of degree `n` with coefficients `p` (highest degree first).
+fn polyval(n: usize, p: &[f64], x: f64) -> f64 {
+    p[1..=n].iter().fold(p[0], |y, &c| y * x + c)
+}
+
+/// Error-free sum of two numbers: returns the rounded sum and the rounding error.
+fn sum(u: f64, v: f64) -> (f64, f64) {
+    let s = u + v;
+    let up = s - v;
+    let vpp = s - up;
+
+    (s, -((up - u) + (vpp - v)))
+}
+
+/// Reduce an angle to the range (-180, 180].
+fn ang_normalize(x: f64) -> f64 {
+    let y = x % 360.0;
+    let y = if y < -180.0 {
+        y + 360.0
+    } else if y > 180.0 {
+        y - 360.0
+    } else {
+        y
+    };
+
+    if y == -180.0 { 180.0 } else { y }
+}
+
+/// Exact difference of two angles, reduced to (-180, 180], plus the rounding error.
+fn ang_diff(x: f64, y: f64) -> (f64, f64) {
+    let (d, t) = sum(ang_normalize(-x), ang_normalize(y));
+    let d = ang_normalize(d);
+
+    sum(if d == 180.0 && t > 0.0 { -180.0 } else { d }, t)
+}
+
+/// Coarsen tiny angles so that the computations are symmetric around zero.
+fn ang_round(x: f64) -> f64 {
+    let z = 1.0 / 16.0;
+    let y = x.abs();
+    let y = if y < z { z - (z - y) } else { y };
+
+    y.copysign(x)
+}
+
+/// Sine and cosine of an angle in degrees, exact for multiples of 90°.
+fn sincosd(x: f64) -> (f64, f64) {
+    let r = x % 360.0;
+    let q = (r / 90.0).round();
+    let (s, c) = (r - 90.0 * q).to_radians().sin_cos();
+
+    match (q as i64).rem_euclid(4) {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    }
+}
+
+/// `atan2` in degrees, exact for multiples of 45°.
+fn atan2d(y: f64, x: f64) -> f64 {
+    let (mut y, mut x, mut q) = (y, x, 0);
+    if y.abs() > x.abs() {
+        std::mem::swap(&mut x, &mut y);
+        q = 2;
+    }
+    if x < 0.0 {
+        x = -x;
+        q += 1;
+    }
+
+    let angle = y.atan2(x).to_degrees();
+    match q {
+        1 => (if y >= 0.0 { 180.0 } else { -180.0 }) - angle,
+        2 => 90.0 - angle,
+        3 => -90.0 + angle,
+        _ => angle,
+    }
+}
+
+fn norm(x: f64, y: f64) -> (f64, f64) {
+    let r = x.hypot(y);
+
+    (x / r, y / r)
+}
+
+/// Evaluate `sum(c[l] * sin(2 * l * x), l = 1..n)` using Clenshaw summation.
+///
+/// `c[0]` is unused.
+fn sin_cos_series(sinx: f64, cosx: f64, c: &[f64]) -> f64 {
+    let mut k = c.len();
+    let n = k - 1;
+    let ar = 2.0 * (cosx - sinx) * (cosx + sinx);
+
+    let mut y0 = if n % 2 == 1 {
+        k -= 1;
+        c[k]
+    } else {
+        0.0
+    };
+    let mut y1 = 0.0;
+    for _ in 0..n / 2 {
+        k -= 1;
+        y1 = ar * y0 - y1 + c[k];
+        k -= 1;
+        y0 = ar * y1 - y0 + c[k];
+    }
+
+    2.0 * sinx * cosx * y0
+}
+
+fn a1m1f(eps: f64) -> f64 {
+    const COEFF: [f64; 5] = [1.0, 4.0, 64.0, 0.0, 256.0];
+    let t = polyval(3, &COEFF, eps * eps) / COEFF[4];
+
+    (t + eps) / (1.0 - eps)
+}
+
+fn a2m1f(eps: f64) -> f64 {
+    const COEFF: [f64; 5] = [-11.0, -28.0, -192.0, 0.0, 256.0];
+    let t = polyval(3, &COEFF, eps * eps) / COEFF[4];
+
+    (t - eps) / (1.0 + eps)
+}
+
+/// Evaluate the coefficients of a series in `eps` whose terms are even polynomials.
+fn even_series_coefficients(coeff: &[f64], eps: f64) -> [f64; ORDER + 1] {
+    let mut c = [0.0; ORDER + 1];
+
+    let eps2 = eps * eps;
+    let mut d = eps;
+    let mut o = 0;
+    for (l, c_l) in c.iter_mut().enumerate().skip(1) {
+        let m = (ORDER - l) / 2;
+        *c_l = d * polyval(m, &coeff[o..], eps2) / coeff[o + m + 1];
+        o += m + 2;
+        d *= eps;
+    }
+
+    c
+}
+
+fn c1f(eps: f64) -> [f64; ORDER + 1] {
+    const COEFF: [f64; 18] = [
+        -1.0, 6.0, -16.0, 32.0, -9.0, 64.0, -128.0, 2048.0, 9.0, -16.0, 768.0, 3.0, -5.0, 512.0,
+        -7.0, 1280.0, -7.0, 2048.0,
+    ];
+
+    even_series_coefficients(&COEFF, eps)
+}
+
+fn c2f(eps: f64) -> [f64; ORDER + 1] {
+    const COEFF: [f64; 18] = [
+        1.0, 2.0, 16.0, 32.0, 35.0, 64.0, 384.0, 2048.0, 15.0, 80.0, 768.0, 7.0, 35.0, 512.0, 63.0,
+        1280.0, 77.0, 2048.0,
+    ];
+
+    even_series_coefficients(&COEFF, eps)
+}
+
+/// Solve the astroid equation `k^4 + 2 k^3 - (x^2 + y^2 - 1) k^2 - 2 y^2 k - y^2 = 0`
+/// for its positive root.
+fn astroid(x: f64, y: f64) -> f64 {
+    let p = x * x;
+    let q = y * y;
+    let r = (p + q - 1.0) / 6.0;
+
+    if q == 0.0 && r <= 0.0 {
+        return 0.0;
+    }
+
+    let s = p * q / 4.0;
+    let r2 = r * r;
+    let r3 = r * r2;
+    let disc = s * (s + 2.0 * r3);
+    let mut u = r;
+    if disc >= 0.0 {
+        let mut t3 = s + r3;
+        t3 += if t3 < 0.0 { -disc.sqrt() } else { disc.sqrt() };
+        let t = t3.cbrt();
+        u += t + if t != 0.0 { r2 / t } else { 0.0 };
+    } else {
+        let angle = (-disc).sqrt().atan2(-(s + r3));
+        u += 2.0 * r * (angle / 3.0).cos();
+    }
+
+    let v = (u * u + q).sqrt();
+    let uv = if u < 0.0 { q / (v - u) } else { u + v };
+    let w = (uv - q) / (2.0 * v);
+
+    uv / ((uv + w * w).sqrt() + w)
+}
+
+/// Intermediate results of the evaluation of the longitude difference.
+struct Lambda12 {
+    lam12: f64,
+    salp2: f64,
+    calp2: f64,
+    sig12: f64,
+    ssig1: f64,
+    csig1: f64,
+    ssig2: f64,
+    csig2: f64,
+    eps: f64,
+    dlam12: f64,
+}
+
+/// A geodesic solver for a given ellipsoid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Geodesic {
+    a: f64,
+    f: f64,
+    f1: f64,
+    ep2: f64,
+    n: f64,
+    b: f64,
+    etol2: f64,
+    a3x: [f64; ORDER],
+    c3x: [f64; N_C3X],
+}
+
+impl Geodesic {
+    pub fn new(semimajor_axis: f64, flattening: f64) -> Self {
+        const A3_COEFF: [f64; 18] = [
+            -3.0, 128.0, -2.0, -3.0, 64.0, -1.0, -3.0, -1.0, 16.0, 3.0, -1.0, -2.0, 8.0, 1.0, -1.0,
+            2.0, 1.0, 1.0,
+        ];
+        const C3_COEFF: [f64; 45] = [
+            3.0, 128.0, 2.0, 5.0, 128.0, -1.0, 3.0, 3.0, 64.0, -1.0, 0.0, 1.0, 8.0, -1.0, 1.0, 4.0,
+            5.0, 256.0, 1.0, 3.0, 128.0, -3.0, -2.0, 3.0, 64.0, 1.0, -3.0, 2.0, 32.0, 7.0, 512.0,
+            -10.0, 9.0, 384.0, 5.0, -9.0, 5.0, 192.0, 7.0, 512.0, -14.0, 7.0, 512.0, 21.0, 2560.0,
+        ];
+
+        let a = semimajor_axis;
+        let f = flattening;
+        let f1 = 1.0 - f;
+        let e2 = f * (2.0 - f);
+        let n = f / (2.0 - f);
+
+        let mut a3x = [0.0; ORDER];
+        let mut o = 0;
+        for (k, j) in (0..ORDER).rev().enumerate() {
+            let m = (ORDER - j - 1).min(j);
+            a3x[k] = polyval(m, &A3_COEFF[o..], n) / A3_COEFF[o + m + 1];
+            o += m + 2;
+        }
+
+        let mut c3x = [0.0; N_C3X];
+        let mut o = 0;
+        let mut k = 0;
+        for l in 1..ORDER {
+            for j in (l..ORDER).rev() {
+                let m = (ORDER - j - 1).min(j);
+                c3x[k] = polyval(m, &C3_COEFF[o..], n) / C3_COEFF[o + m + 1];
+                o += m + 2;
+                k += 1;
+            }
+        }
+
+        Self {
+            a,
+            f,
+            f1,
+            ep2: e2 / (f1 * f1),
+            n,
+            b: a * f1,
+            etol2: 0.1 * tol2() / ((f.abs().max(0.001)) * (1.0 - f / 2.0).min(1.0) / 2.0).sqrt(),
+            a3x,
+            c3x,
+        }
+    }
+
+    fn a3f(&self, eps: f64) -> f64 {
+        polyval(ORDER - 1, &self.a3x, eps)
+    }
+
+    fn c3f(&self, eps: f64) -> [f64; ORDER] {
+        let mut c = [0.0; ORDER];
+
+        let mut mult = 1.0;
+        let mut o = 0;
+        for (l, c_l) in c.iter_mut().enumerate().skip(1) {
+            let m = ORDER - l - 1;
+            mult *= eps;
+            *c_l = mult * polyval(m, &self.c3x[o..], eps);
+            o += m + 1;
+        }
+
+        c
+    }
+
+    /// Distance and reduced length of a geodesic, scaled to `b = 1`.
+    fn lengths(
+        &self,
+        eps: f64,
+        sig12: f64,
+        (ssig1, csig1, dn1): (f64, f64, f64),
+        (ssig2, csig2, dn2): (f64, f64, f64),
+    ) -> (f64, f64) {
+        let c1a = c1f(eps);
+        let c2a = c2f(eps);
+        let a1 = a1m1f(eps);
+        let a2 = a2m1f(eps);
+
+        let m0x = a1 - a2;
+        let a1 = 1.0 + a1;
+        let a2 = 1.0 + a2;
+
+        let b1 = sin_cos_series(ssig2, csig2, &c1a) - sin_cos_series(ssig1, csig1, &c1a);
+        let s12b = a1 * (sig12 + b1);
+        let b2 = sin_cos_series(ssig2, csig2, &c2a) - sin_cos_series(ssig1, csig1, &c2a);
+        let j12 = m0x * sig12 + (a1 * b1 - a2 * b2);
+        let m12b = dn2 * (csig1 * ssig2) - dn1 * (ssig1 * csig2) - csig1 * csig2 * j12;
+
+        (s12b, m12b)
+    }
+
+    /// Starting point for Newton's method.
+    ///
+    /// Returns `(sig12, salp1, calp1, salp2, calp2, dnm)`, with `sig12 >= 0` if the
+    /// result is already accurate (short lines).
+    fn inverse_start(
+        &self,
+        (sbet1, cbet1): (f64, f64),
+        (sbet2, cbet2): (f64, f64),
+        lam12: f64,
+        slam12: f64,
+        clam12: f64,
+    ) -> (f64, f64, f64, f64, f64, f64) {
+        let mut sig12 = -1.0;
+        let (mut salp2, mut calp2, mut dnm) = (f64::NAN, f64::NAN, f64::NAN);
+
+        let sbet12 = sbet2 * cbet1 - cbet2 * sbet1;
+        let cbet12 = cbet2 * cbet1 + sbet2 * sbet1;
+        let sbet12a = sbet2 * cbet1 + cbet2 * sbet1;
+
+        let shortline = cbet12 >= 0.0 && sbet12 < 0.5 && cbet2 * lam12 < 0.5;
+        let (mut somg12, mut comg12) = if shortline {
+            let mut sbetm2 = (sbet1 + sbet2).powi(2);
+            sbetm2 /= sbetm2 + (cbet1 + cbet2).powi(2);
+            dnm = (1.0 + self.ep2 * sbetm2).sqrt();
+
+            (lam12 / (self.f1 * dnm)).sin_cos()
+        } else {
+            (slam12, clam12)
+        };
+
+        let mut salp1 = cbet2 * somg12;
+        let mut calp1 = if comg12 >= 0.0 {
+            sbet12 + cbet2 * sbet1 * somg12 * somg12 / (1.0 + comg12)
+        } else {
+            sbet12a - cbet2 * sbet1 * somg12 * somg12 / (1.0 - comg12)
+        };
+
+        let ssig12 = salp1.hypot(calp1);
+        let csig12 = sbet1 * sbet2 + cbet1 * cbet2 * comg12;
+
+        if shortline && ssig12 < self.etol2 {
+            salp2 = cbet1 * somg12;
+            calp2 = sbet12
+                - cbet1
+                    * sbet2
+                    * if comg12 >= 0.0 {
+                        somg12 * somg12 / (1.0 + comg12)
+                    } else {
+                        1.0 - comg12
+                    };
+            (salp2, calp2) = norm(salp2, calp2);
+            sig12 = ssig12.atan2(csig12);
+        } else if self.n.abs() > 0.1
+            || csig12 >= 0.0
+            || ssig12 >= 6.0 * self.n.abs() * PI * cbet1 * cbet1
+        {
+            // nothing to do, zeroth order spherical approximation is OK
+        } else {
+            // nearly antipodal points: solve the astroid problem
+            let lam12x = (-slam12).atan2(-clam12);
+
+            let k2 = sbet1 * sbet1 * self.ep2;
+            let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+            let lamscale = self.f * cbet1 * self.a3f(eps) * PI;
+            let betscale = lamscale * cbet1;
+
+            let x = lam12x / lamscale;
+            let y = sbet12a / betscale;
+
+            if y > -TOL1 && x > -1.0 - 1000.0 * tol2() {
+                salp1 = (-x).min(1.0);
+                calp1 = -(1.0 - salp1 * salp1).sqrt();
+            } else {
+                let k = astroid(x, y);
+                let omg12a = lamscale * (-x * k / (1.0 + k));
+                (somg12, comg12) = omg12a.sin_cos();
+                comg12 = -comg12;
+
+                salp1 = cbet2 * somg12;
+                calp1 = sbet12a - cbet2 * sbet1 * somg12 * somg12 / (1.0 - comg12);
+            }
+        }
+
+        if salp1 > 0.0 {
+            (salp1, calp1) = norm(salp1, calp1);
+        } else {
+            (salp1, calp1) = (1.0, 0.0);
+        }
+
+        (sig12, salp1, calp1, salp2, calp2, dnm)
+    }
+
+    /// Longitude difference of the geodesic starting with azimuth `alp1`, minus the
+    /// target longitude difference, and its derivative with respect to `alp1`.
+    #[allow(clippy::too_many_arguments)]
+    fn lambda12(
+        &self,
+        (sbet1, cbet1, dn1): (f64, f64, f64),
+        (sbet2, cbet2, dn2): (f64, f64, f64),
+        salp1: f64,
+        calp1: f64,
+        slam120: f64,
+        clam120: f64,
+        diffp: bool,
+    ) -> Lambda12 {
+        let calp1 = if sbet1 == 0.0 && calp1 == 0.0 {
+            -tiny()
+        } else {
+            calp1
+        };
+
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+
+        let somg1 = salp0 * sbet1;
+        let comg1 = calp1 * cbet1;
+        let (ssig1, csig1) = norm(sbet1, comg1);
+
+        let salp2 = if cbet2 != cbet1 { salp0 / cbet2 } else { salp1 };
+        let calp2 = if cbet2 != cbet1 || sbet2.abs() != -sbet1 {
+            ((calp1 * cbet1).powi(2)
+                + if cbet1 < -sbet1 {
+                    (cbet2 - cbet1) * (cbet1 + cbet2)
+                } else {
+                    (sbet1 - sbet2) * (sbet1 + sbet2)
+                })
+            .sqrt()
+                / cbet2
+        } else {
+            calp1.abs()
+        };
+
+        let somg2 = salp0 * sbet2;
+        let comg2 = calp2 * cbet2;
+        let (ssig2, csig2) = norm(sbet2, comg2);
+
+        let sig12 = (csig1 * ssig2 - ssig1 * csig2)
+            .max(0.0)
+            .atan2(csig1 * csig2 + ssig1 * ssig2);
+        let somg12 = (comg1 * somg2 - somg1 * comg2).max(0.0);
+        let comg12 = comg1 * comg2 + somg1 * somg2;
+        let eta = (somg12 * clam120 - comg12 * slam120).atan2(comg12 * clam120 + somg12 * slam120);
+
+        let k2 = calp0 * calp0 * self.ep2;
+        let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+        let c3a = self.c3f(eps);
+        let b312 = sin_cos_series(ssig2, csig2, &c3a) - sin_cos_series(ssig1, csig1, &c3a);
+        let domg12 = -self.f * self.a3f(eps) * salp0 * (sig12 + b312);
+
+        let dlam12 = if !diffp {
+            f64::NAN
+        } else if calp2 == 0.0 {
+            -2.0 * self.f1 * dn1 / sbet1
+        } else {
+            let (_, m12b) = self.lengths(eps, sig12, (ssig1, csig1, dn1), (ssig2, csig2, dn2));
+
+            m12b * self.f1 / (calp2 * cbet2)
+        };
+
+        Lambda12 {
+            lam12: eta + domg12,
+            salp2,
+            calp2,
+            sig12,
+            ssig1,
+            csig1,
+            ssig2,
+            csig2,
+            eps,
+            dlam12,
+        }
+    }
+
+    /// Solve the inverse geodesic problem.
+    ///
+    /// Takes geographic coordinates in degrees, and returns the length of the geodesic
+    /// (in units of the semimajor axis) together with the azimuths of the geodesic at
+    /// both points (in degrees, clockwise from north).
+    pub fn inverse(&self, lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> (f64, f64, f64) {
+        if [lon1, lat1, lon2, lat2].iter().any(|v| v.is_nan())
+            || lat1.abs() > 90.0
+            || lat2.abs() > 90.0
+        {
+            return (f64::NAN, f64::NAN, f64::NAN);
+        }
+
+        let (lon12, lon12s) = ang_diff(lon1, lon2);
+        let mut lonsign = if lon12 >= 0.0 { 1.0 } else { -1.0 };
+        let lon12 = lonsign * ang_round(lon12);
+        let lon12s = ang_round((180.0 - lon12) - lonsign * lon12s);
+        let lam12 = lon12.to_radians();
+        let (slam12, clam12) = if lon12 > 90.0 {
+            let (s, c) = sincosd(lon12s);
+            (s, -c)
+        } else {
+            sincosd(lon12)
+        };
+
+        // make lat1 the point with the largest absolute latitude, and make it negative
+        let mut lat1 = ang_round(lat1);
+        let mut lat2 = ang_round(lat2);
+        let swapp = if lat1.abs() < lat2.abs() { -1.0 } else { 1.0 };
+        if swapp < 0.0 {
+            lonsign = -lonsign;
+            std::mem::swap(&mut lat1, &mut lat2);
+        }
+        let latsign = if lat1 < 0.0 { 1.0 } else { -1.0 };
+        lat1 *= latsign;
+        lat2 *= latsign;
+
+        let (sbet1, cbet1) = sincosd(lat1);
+        let (sbet1, cbet1) = norm(self.f1 * sbet1, cbet1);
+        let cbet1 = cbet1.max(tiny());
+        let (sbet2, cbet2) = sincosd(lat2);
+        let (mut sbet2, cbet2) = norm(self.f1 * sbet2, cbet2);
+        let mut cbet2 = cbet2.max(tiny());
+
+        if cbet1 < -sbet1 {
+            if cbet2 == cbet1 {
+                sbet2 = sbet1.copysign(sbet2);
+            }
+        } else if sbet2.abs() == -sbet1 {
+            cbet2 = cbet1;
+        }
+
+        let dn1 = (1.0 + self.ep2 * sbet1 * sbet1).sqrt();
+        let dn2 = (1.0 + self.ep2 * sbet2 * sbet2).sqrt();
+
+        let (mut salp1, mut calp1) = (f64::NAN, f64::NAN);
+        let (mut salp2, mut calp2) = (f64::NAN, f64::NAN);
+        let mut s12 = f64::NAN;
+
+        let mut meridian = lat1 == -90.0 || slam12 == 0.0;
+        if meridian {
+            // the geodesic is a meridian (or passes through a pole)
+            (salp1, calp1) = (slam12, clam12);
+            (salp2, calp2) = (0.0, 1.0);
+
+            let (ssig1, csig1) = (sbet1, calp1 * cbet1);
+            let (ssig2, csig2) = (sbet2, calp2 * cbet2);
+            let sig12 = (csig1 * ssig2 - ssig1 * csig2)
+                .max(0.0)
+                .atan2(csig1 * csig2 + ssig1 * ssig2);
+            let (mut s12x, m12x) =
+                self.lengths(self.n, sig12, (ssig1, csig1, dn1), (ssig2, csig2, dn2));
+
+            if sig12 < 1.0 || m12x >= 0.0 {
+                if sig12 < 3.0 * tiny() || (sig12 < TOL0 && (s12x < 0.0 || m12x < 0.0)) {
+                    s12x = 0.0;
+                }
+                s12 = s12x * self.b;
+            } else {
+                // the meridian is not the shortest path
+                meridian = false;
+            }
+        }
+
+        if !meridian && sbet1 == 0.0 && (self.f <= 0.0 || lon12s >= self.f * 180.0) {
+            // the geodesic runs along the equator
+            (salp1, calp1) = (1.0, 0.0);
+            (salp2, calp2) = (1.0, 0.0);
+            s12 = self.a * lam12;
+        } else if !meridian {
+            let (sig12, salp1_, calp1_, salp2_, calp2_, dnm) =
+                self.inverse_start((sbet1, cbet1), (sbet2, cbet2), lam12, slam12, clam12);
+            (salp1, calp1) = (salp1_, calp1_);
+
+            if sig12 >= 0.0 {
+                // short line, the starting point is accurate enough
+                (salp2, calp2) = (salp2_, calp2_);
+                s12 = sig12 * self.b * dnm;
+            } else {
+                // Newton's method, with a bisection fallback
+                let tolb = TOL0 * tol2();
+                let (mut tripn, mut tripb) = (false, false);
+                let (mut salp1a, mut calp1a) = (tiny(), 1.0);
+                let (mut salp1b, mut calp1b) = (tiny(), -1.0);
+
+                let mut result = self.lambda12(
+                    (sbet1, cbet1, dn1),
+                    (sbet2, cbet2, dn2),
+                    salp1,
+                    calp1,
+                    slam12,
+                    clam12,
+                    true,
+                );
+                for numit in 0..MAXIT2 {
+                    if numit > 0 {
+                        result = self.lambda12(
+                            (sbet1, cbet1, dn1),
+                            (sbet2, cbet2, dn2),
+                            salp1,
+                            calp1,
+                            slam12,
+                            clam12,
+                            numit < MAXIT1,
+                        );
+                    }
+                    let v = result.lam12;
+                    let dv = result.dlam12;
+
+                    if tripb || v.abs() < (if tripn { 8.0 } else { 1.0 }) * TOL0 {
+                        break;
+                    }
+
+                    // update the bracketing range
+                    if v > 0.0 && (numit > MAXIT1 || calp1 / salp1 > calp1b / salp1b) {
+                        (salp1b, calp1b) = (salp1, calp1);
+                    } else if v < 0.0 && (numit > MAXIT1 || calp1 / salp1 < calp1a / salp1a) {
+                        (salp1a, calp1a) = (salp1, calp1);
+                    }
+
+                    if numit < MAXIT1 && dv > 0.0 {
+                        let dalp1 = -v / dv;
+                        let (sdalp1, cdalp1) = dalp1.sin_cos();
+                        let nsalp1 = salp1 * cdalp1 + calp1 * sdalp1;
+                        if nsalp1 > 0.0 && dalp1.abs() < PI {
+                            calp1 = calp1 * cdalp1 - salp1 * sdalp1;
+                            (salp1, calp1) = norm(nsalp1, calp1);
+                            tripn = v.abs() <= 16.0 * TOL0;
+                            continue;
+                        }
+                    }
+
+                    // Newton's method failed, bisect the bracketing range instead
+                    (salp1, calp1) = norm((salp1a + salp1b) / 2.0, (calp1a + calp1b) / 2.0);
+                    tripn = false;
+                    tripb = (salp1a - salp1).abs() + (calp1a - calp1) < tolb
+                        || (salp1 - salp1b).abs() + (calp1 - calp1b) < tolb;
+                }
+
+                let (s12x, _) = self.lengths(
+                    result.eps,
+                    result.sig12,
+                    (result.ssig1, result.csig1, dn1),
+                    (result.ssig2, result.csig2, dn2),
+                );
+                (salp2, calp2) = (result.salp2, result.calp2);
+                s12 = s12x * self.b;
+            }
+        }
+
+        // undo the normalization of the points
+        if swapp < 0.0 {
+            std::mem::swap(&mut salp1, &mut salp2);
+            std::mem::swap(&mut calp1, &mut calp2);
+        }
+        let azi1 = atan2d(swapp * lonsign * salp1, swapp * latsign * calp1);
+        let azi2 = atan2d(swapp * lonsign * salp2, swapp * latsign * calp2);
+
+        (0.0 + s12, azi1, azi2)
+    }
+}
+
+/// Azimuth pointing in the opposite direction, in (-180, 180].
+pub(crate) fn reverse_azimuth(azimuth: f64) -> f64 {
+    ang_normalize(azimuth + 180.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wgs84() -> Geodesic {
+        Geodesic::new(6378137.0, 1.0 / 298.257223563)
+    }
+
+    #[test]
+    fn test_inverse_reference_values() {
+        // values computed by GeographicLib
+        let (s12, azi1, azi2) = wgs84().inverse(-73.8, 40.6, -0.5, 51.6);
+        assert!((s12 - 5551759.400319).abs() < 1e-6);
+        assert!((azi1 - 51.198882845579824).abs() < 1e-12);
+        assert!((azi2 - 107.82177673551424).abs() < 1e-12);
+
+        // nearly antipodal points
+        let (s12, azi1, azi2) = wgs84().inverse(174.81, -41.32, -5.50, 40.96);
+        assert!((s12 - 19959679.26735382).abs() < 1e-6);
+        assert!((azi1 - 161.06766998615882).abs() < 1e-9);
+        assert!((azi2 - 18.825195123248392).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_inverse_special_cases() {
+        let geodesic = wgs84();
+
+        // equator
+        let (s12, azi1, azi2) = geodesic.inverse(0.0, 0.0, 90.0, 0.0);
+        assert!((s12 - PI / 2.0 * 6378137.0).abs() < 1e-6);
+        assert_eq!((azi1, azi2), (90.0, 90.0));
+
+        // meridian
+        let (s12, azi1, azi2) = geodesic.inverse(0.0, 0.0, 0.0, 90.0);
+        assert!((s12 - 10001965.729312724).abs() < 1e-6);
+        assert_eq!((azi1, azi2), (0.0, 0.0));
+
+        // coincident points
+        let (s12, _, _) = geodesic.inverse(12.0, 10.0, 12.0, 10.0);
+        assert_eq!(s12, 0.0);
+
+        // invalid latitudes
+        let (s12, _, _) = geodesic.inverse(0.0, 91.0, 0.0, 0.0);
+        assert!(s12.is_nan());
+    }
+
+    #[test]
+    fn test_inverse_sphere() {
+        let geodesic = Geodesic::new(1.0, 0.0);
+
+        let (s12, azi1, _) = geodesic.inverse(0.0, 0.0, 0.0, 90.0);
+        assert!((s12 - PI / 2.0).abs() < 1e-15);
+        assert_eq!(azi1, 0.0);
+
+        let (s12, _, _) = geodesic.inverse(10.0, 20.0, 130.0, -35.0);
+        let (lat1, lat2, dlon) = (
+            20f64.to_radians(),
+            (-35f64).to_radians(),
+            120f64.to_radians(),
+        );
+        let expected = (lat1.sin() * lat2.sin() + lat1.cos() * lat2.cos() * dlon.cos()).acos();
+        assert!((s12 - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_reverse_azimuth() {
+        assert_eq!(reverse_azimuth(30.0), -150.0);
+        assert_eq!(reverse_azimuth(-30.0), 150.0);
+        assert_eq!(reverse_azimuth(0.0), 180.0);
+    }
+}
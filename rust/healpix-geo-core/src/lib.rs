@@ -1,3 +1,9 @@
+pub mod bodies;
+pub mod crs;
 pub mod ellipsoid;
+mod error;
+pub mod geodesic;
 pub mod scalar;
 pub mod vectorized;
+
+pub use error::{Error, InvalidPolicy, MAX_DEPTH, Result};
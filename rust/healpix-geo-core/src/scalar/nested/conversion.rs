@@ -1,19 +1,27 @@
 use cdshealpix as healpix;
 
-pub fn from_zuniq(hash: &u64) -> (u64, u8) {
-    let (depth, hash_nested) = healpix::nested::from_zuniq(*hash);
+use crate::error::{Result, check_hash, check_zuniq};
 
-    (hash_nested, depth)
+pub fn from_zuniq(hash: &u64) -> Result<(u64, u8)> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
+
+    Ok((hash_nested, depth))
 }
 
-pub fn from_ring(hash: &u64, depth: &u8) -> u64 {
-    healpix::nested::get(*depth).from_ring(*hash)
+pub fn from_ring(hash: &u64, depth: &u8) -> Result<u64> {
+    check_hash(*hash, *depth)?;
+
+    Ok(healpix::nested::get(*depth).from_ring(*hash))
 }
 
-pub fn to_zuniq(hash: &u64, depth: &u8) -> u64 {
-    healpix::nested::to_zuniq_unsafe(*depth, *hash)
+pub fn to_zuniq(hash: &u64, depth: &u8) -> Result<u64> {
+    check_hash(*hash, *depth)?;
+
+    Ok(healpix::nested::to_zuniq_unsafe(*depth, *hash))
 }
 
-pub fn to_ring(hash: &u64, depth: &u8) -> u64 {
-    healpix::nested::get(*depth).to_ring(*hash)
+pub fn to_ring(hash: &u64, depth: &u8) -> Result<u64> {
+    check_hash(*hash, *depth)?;
+
+    Ok(healpix::nested::get(*depth).to_ring(*hash))
 }
@@ -1,9 +1,16 @@
-use crate::ellipsoid::{Ellipsoid, ReferenceBody};
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_hash, check_lonlat};
 
 use cdshealpix::compass_point::Cardinal;
 use cdshealpix::nested::Layer;
 
-pub fn healpix_to_lonlat(hash: &u64, layer: &Layer, ellipsoid: &Ellipsoid) -> (f64, f64) {
+pub fn healpix_to_lonlat<R: ReferenceBody>(
+    hash: &u64,
+    layer: &Layer,
+    ellipsoid: &R,
+) -> Result<(f64, f64)> {
+    check_hash(*hash, layer.depth())?;
+
     let center = layer.center(*hash);
 
     let lon = center.0.to_degrees().rem_euclid(360.0);
@@ -11,17 +18,36 @@ pub fn healpix_to_lonlat(hash: &u64, layer: &Layer, ellipsoid: &Ellipsoid) -> (f
         .latitude_authalic_to_geographic(center.1)
         .to_degrees();
 
-    (lon, lat)
+    Ok((lon, lat))
 }
 
-pub fn lonlat_to_healpix(lon: &f64, lat: &f64, layer: &Layer, ellipsoid: &Ellipsoid) -> u64 {
+pub fn lonlat_to_healpix<R: ReferenceBody>(
+    lon: &f64,
+    lat: &f64,
+    layer: &Layer,
+    ellipsoid: &R,
+) -> Result<u64> {
+    check_lonlat(*lon, *lat)?;
+
     let lon_ = lon.rem_euclid(360.0).to_radians();
     let lat_ = ellipsoid.latitude_geographic_to_authalic(lat.to_radians());
 
-    layer.hash(lon_, lat_)
+    Ok(layer.hash(lon_, lat_))
+}
+
+/// Whether `hash` is a cell of the given layer.
+pub fn is_valid_cell(hash: &u64, layer: &Layer) -> bool {
+    check_hash(*hash, layer.depth()).is_ok()
 }
 
-pub fn vertices(hash: &u64, layer: &Layer, ellipsoid: &Ellipsoid, step: &usize) -> Vec<(f64, f64)> {
+pub fn vertices<R: ReferenceBody>(
+    hash: &u64,
+    layer: &Layer,
+    ellipsoid: &R,
+    step: &usize,
+) -> Result<Vec<(f64, f64)>> {
+    check_hash(*hash, layer.depth())?;
+
     let vertices: Vec<(f64, f64)> = if *step == 1 {
         layer.vertices(*hash).into()
     } else {
@@ -30,7 +56,7 @@ pub fn vertices(hash: &u64, layer: &Layer, ellipsoid: &Ellipsoid, step: &usize)
             .into()
     };
 
-    vertices
+    Ok(vertices
         .into_iter()
         .map(|(lon, lat): (f64, f64)| {
             (
@@ -38,13 +64,14 @@ pub fn vertices(hash: &u64, layer: &Layer, ellipsoid: &Ellipsoid, step: &usize)
                 ellipsoid.latitude_authalic_to_geographic(lat).to_degrees(),
             )
         })
-        .collect()
+        .collect())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ellipsoid::ReferenceEllipsoid;
+    use crate::ellipsoid::{Ellipsoid, ReferenceEllipsoid};
+    use crate::error::Error;
     use cdshealpix as healpix;
     use geodesy::ellps::Ellipsoid as GeodesyEllipsoid;
 
@@ -58,25 +85,70 @@ mod tests {
         let lon: f64 = -180.0;
         let lat: f64 = 75.0;
 
-        let actual = lonlat_to_healpix(&lon, &lat, layer, &ellipsoid);
+        let actual = lonlat_to_healpix(&lon, &lat, layer, &ellipsoid).unwrap();
         assert_eq!(actual, 2);
 
         let lon: f64 = 180.0;
         let lat: f64 = 75.0;
 
-        let actual = lonlat_to_healpix(&lon, &lat, layer, &ellipsoid);
+        let actual = lonlat_to_healpix(&lon, &lat, layer, &ellipsoid).unwrap();
         assert_eq!(actual, 2);
 
         let lon: f64 = 0.0;
         let lat: f64 = 75.0;
 
-        let actual = lonlat_to_healpix(&lon, &lat, layer, &ellipsoid);
+        let actual = lonlat_to_healpix(&lon, &lat, layer, &ellipsoid).unwrap();
         assert_eq!(actual, 0);
 
         let lon: f64 = 360.0;
         let lat: f64 = 75.0;
 
-        let actual = lonlat_to_healpix(&lon, &lat, layer, &ellipsoid);
+        let actual = lonlat_to_healpix(&lon, &lat, layer, &ellipsoid).unwrap();
         assert_eq!(actual, 0);
     }
+
+    #[test]
+    fn test_healpix_to_lonlat_invalid_hash() {
+        let layer = healpix::nested::get(0);
+        let ellipsoid = Ellipsoid::Ellipsoid(ReferenceEllipsoid::new(
+            GeodesyEllipsoid::named("WGS84").unwrap(),
+        ));
+
+        let actual = healpix_to_lonlat(&12, layer, &ellipsoid);
+        assert_eq!(
+            actual,
+            Err(Error::InvalidHash {
+                hash: 12,
+                depth: Some(0)
+            })
+        );
+    }
+
+    #[test]
+    fn test_lonlat_to_healpix_invalid_coordinates() {
+        let layer = healpix::nested::get(0);
+        let ellipsoid = Ellipsoid::Ellipsoid(ReferenceEllipsoid::new(
+            GeodesyEllipsoid::named("WGS84").unwrap(),
+        ));
+
+        let actual = lonlat_to_healpix(&10.0, &95.0, layer, &ellipsoid);
+        assert_eq!(
+            actual,
+            Err(Error::InvalidCoordinates {
+                lon: 10.0,
+                lat: 95.0
+            })
+        );
+
+        let actual = lonlat_to_healpix(&f64::NAN, &0.0, layer, &ellipsoid);
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_is_valid_cell() {
+        let layer = healpix::nested::get(1);
+
+        assert!(is_valid_cell(&47, layer));
+        assert!(!is_valid_cell(&48, layer));
+    }
 }
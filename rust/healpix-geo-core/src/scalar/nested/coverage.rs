@@ -1,4 +1,10 @@
-use crate::ellipsoid::{Ellipsoid, ReferenceBody};
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Error, Result, check_delta_depth};
+use crate::geodesic::Geodesic;
+use crate::scalar::nested::coordinates::healpix_to_lonlat;
+use cdshealpix::compass_point::Cardinal;
 use cdshealpix::nested::Layer;
 use cdshealpix::nested::bmoc::BMOC;
 
@@ -41,12 +47,12 @@ fn get_flat_cells(bmoc: BMOC) -> (Vec<u64>, Vec<u8>, Vec<bool>) {
     (ipix, depth, fully_covered)
 }
 
-pub fn zone_coverage(
+pub fn zone_coverage<R: ReferenceBody>(
     bbox: (f64, f64, f64, f64),
     layer: &Layer,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     flat: bool,
-) -> (Vec<u64>, Vec<u8>, Vec<bool>) {
+) -> Result<(Vec<u64>, Vec<u8>, Vec<bool>)> {
     let (lon_min, lat_min, lon_max, lat_max) = bbox;
 
     let bmoc = layer.zone_coverage(
@@ -57,20 +63,20 @@ pub fn zone_coverage(
     );
 
     if flat {
-        get_flat_cells(bmoc)
+        Ok(get_flat_cells(bmoc))
     } else {
-        get_cells(bmoc)
+        Ok(get_cells(bmoc))
     }
 }
 
-pub fn box_coverage(
+pub fn box_coverage<R: ReferenceBody>(
     center: (f64, f64),
     size: (f64, f64),
     angle: f64,
     layer: &Layer,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     flat: bool,
-) -> (Vec<u64>, Vec<u8>, Vec<bool>) {
+) -> Result<(Vec<u64>, Vec<u8>, Vec<bool>)> {
     let (lon, lat) = center;
     let (size_lon, size_lat) = size;
 
@@ -83,19 +89,25 @@ pub fn box_coverage(
     );
 
     if flat {
-        get_flat_cells(bmoc)
+        Ok(get_flat_cells(bmoc))
     } else {
-        get_cells(bmoc)
+        Ok(get_cells(bmoc))
     }
 }
 
-pub fn polygon_coverage(
+fn polygon_bmoc<R: ReferenceBody>(
     vertices: &[(f64, f64)],
     layer: &Layer,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     exact: bool,
-    flat: bool,
-) -> (Vec<u64>, Vec<u8>, Vec<bool>) {
+) -> Result<BMOC> {
+    if vertices.len() < 3 {
+        return Err(Error::InvalidGeometry(format!(
+            "a polygon needs at least 3 vertices, got {}",
+            vertices.len()
+        )));
+    }
+
     let converted_vertices: Vec<(f64, f64)> = vertices
         .iter()
         .map(|v| {
@@ -108,26 +120,65 @@ pub fn polygon_coverage(
         })
         .collect();
 
-    let bmoc = layer.polygon_coverage(&converted_vertices, exact);
+    Ok(layer.polygon_coverage(&converted_vertices, exact))
+}
+
+pub fn polygon_coverage<R: ReferenceBody>(
+    vertices: &[(f64, f64)],
+    layer: &Layer,
+    ellipsoid: &R,
+    exact: bool,
+    flat: bool,
+) -> Result<(Vec<u64>, Vec<u8>, Vec<bool>)> {
+    let bmoc = polygon_bmoc(vertices, layer, ellipsoid, exact)?;
+
+    if flat {
+        Ok(get_flat_cells(bmoc))
+    } else {
+        Ok(get_cells(bmoc))
+    }
+}
+
+/// Coverage of a polygon with holes.
+///
+/// The holes are subtracted from the coverage of the exterior ring. Cells
+/// crossing the boundary of a hole are kept, but are not flagged as fully
+/// covered.
+pub fn polygon_with_holes_coverage<R: ReferenceBody>(
+    exterior: &[(f64, f64)],
+    holes: &[Vec<(f64, f64)>],
+    layer: &Layer,
+    ellipsoid: &R,
+    exact: bool,
+    flat: bool,
+) -> Result<(Vec<u64>, Vec<u8>, Vec<bool>)> {
+    let bmoc = holes.iter().try_fold(
+        polygon_bmoc(exterior, layer, ellipsoid, exact)?,
+        |bmoc, hole| -> Result<BMOC> {
+            Ok(bmoc.minus(&polygon_bmoc(hole, layer, ellipsoid, exact)?))
+        },
+    )?;
 
     if flat {
-        get_flat_cells(bmoc)
+        Ok(get_flat_cells(bmoc))
     } else {
-        get_cells(bmoc)
+        Ok(get_cells(bmoc))
     }
 }
 
-pub fn cone_coverage(
+pub fn cone_coverage<R: ReferenceBody>(
     center: (f64, f64),
     radius: f64,
     layer: &Layer,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     delta_depth: u8,
     flat: bool,
-) -> (Vec<u64>, Vec<u8>, Vec<bool>) {
-    if layer.depth() + delta_depth > 29 {
-        // TODO: return a Result object
-        panic!("delta_depth must be chosen such that layer.depth() + delta_depth <= 29");
+) -> Result<(Vec<u64>, Vec<u8>, Vec<bool>)> {
+    check_delta_depth(layer.depth(), delta_depth)?;
+    if !(radius.is_finite() && radius > 0.0) {
+        return Err(Error::InvalidGeometry(format!(
+            "the radius must be a positive number, got {radius}"
+        )));
     }
 
     let (lon, lat) = center;
@@ -140,29 +191,31 @@ pub fn cone_coverage(
     );
 
     if flat {
-        get_flat_cells(bmoc)
+        Ok(get_flat_cells(bmoc))
     } else {
-        get_cells(bmoc)
+        Ok(get_cells(bmoc))
     }
 }
 
-pub fn elliptical_cone_coverage(
+pub fn elliptical_cone_coverage<R: ReferenceBody>(
     center: (f64, f64),
     ellipse_geometry: (f64, f64),
     position_angle: f64,
     layer: &Layer,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     delta_depth: u8,
     flat: bool,
-) -> (Vec<u64>, Vec<u8>, Vec<bool>) {
-    if layer.depth() + delta_depth > 29 {
-        // TODO: return a Result object
-        panic!("delta_depth must be chosen such that layer.depth() + delta_depth <= 29");
-    }
+) -> Result<(Vec<u64>, Vec<u8>, Vec<bool>)> {
+    check_delta_depth(layer.depth(), delta_depth)?;
 
     let (lon, lat) = center;
 
     let (a, b) = ellipse_geometry;
+    if !(a.is_finite() && b.is_finite() && a > 0.0 && b > 0.0) {
+        return Err(Error::InvalidGeometry(format!(
+            "the ellipse axes must be positive numbers, got ({a}, {b})"
+        )));
+    }
 
     let bmoc = layer.elliptical_cone_coverage_custom(
         delta_depth,
@@ -174,8 +227,481 @@ pub fn elliptical_cone_coverage(
     );
 
     if flat {
-        get_flat_cells(bmoc)
+        Ok(get_flat_cells(bmoc))
+    } else {
+        Ok(get_cells(bmoc))
+    }
+}
+
+/// The cells whose center is within `radius` of the center of a cell.
+///
+/// The candidates are the cells covering a cone around the center, widened to account
+/// for the distortion of distances on the authalic sphere, and are kept if their
+/// geodesic distance is within `radius`. Returns the cells, including the cell itself,
+/// with their distance in the unit of the ellipsoid axes, sorted by distance.
+pub fn neighbours_within_distance<R: ReferenceBody>(
+    hash: &u64,
+    layer: &Layer,
+    radius: f64,
+    ellipsoid: &R,
+    geodesic: &Geodesic,
+) -> Result<Vec<(u64, f64)>> {
+    if !(radius.is_finite() && radius > 0.0) {
+        return Err(Error::InvalidGeometry(format!(
+            "the radius must be a positive number, got {radius}"
+        )));
+    }
+
+    let center = healpix_to_lonlat(hash, layer, ellipsoid)?;
+
+    let angle = radius / ellipsoid.authalic_radius() * (1.0 + 2.0 * ellipsoid.flattening());
+    let (candidates, _, _) = cone_coverage(
+        center,
+        angle.to_degrees().min(180.0),
+        layer,
+        ellipsoid,
+        0,
+        true,
+    )?;
+
+    let mut neighbours = Vec::new();
+    for candidate in candidates {
+        let (lon, lat) = healpix_to_lonlat(&candidate, layer, ellipsoid)?;
+        let (distance, _, _) = geodesic.inverse(center.0, center.1, lon, lat);
+
+        if distance <= radius {
+            neighbours.push((candidate, distance));
+        }
+    }
+    neighbours.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+
+    Ok(neighbours)
+}
+
+type Vec3 = [f64; 3];
+
+fn vec3(lon: f64, lat: f64) -> Vec3 {
+    let (sin_lon, cos_lon) = lon.sin_cos();
+    let (sin_lat, cos_lat) = lat.sin_cos();
+
+    [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat]
+}
+
+fn lonlat(v: &Vec3) -> (f64, f64) {
+    (v[1].atan2(v[0]), v[2].clamp(-1.0, 1.0).asin())
+}
+
+fn cross(a: &Vec3, b: &Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: &Vec3, b: &Vec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(a: &Vec3) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// Whether `p`, a point on the great circle with normal `n = a × b`, lies on
+/// the minor arc between `a` and `b`.
+fn arc_contains(a: &Vec3, b: &Vec3, n: &Vec3, p: &Vec3) -> bool {
+    dot(&cross(a, p), n) >= 0.0 && dot(&cross(p, b), n) >= 0.0
+}
+
+fn normalized(a: &Vec3) -> Option<Vec3> {
+    let length = norm(a);
+
+    if length < 1e-15 {
+        None
+    } else {
+        Some([a[0] / length, a[1] / length, a[2] / length])
+    }
+}
+
+fn arcs_intersect(a: &Vec3, b: &Vec3, c: &Vec3, d: &Vec3) -> bool {
+    // zero-length arcs don't cross anything
+    let (Some(n1), Some(n2)) = (normalized(&cross(a, b)), normalized(&cross(c, d))) else {
+        return false;
+    };
+    let t = cross(&n1, &n2);
+
+    if norm(&t) < 1e-12 {
+        // both arcs are on the same great circle
+        return arc_contains(a, b, &n1, c)
+            || arc_contains(a, b, &n1, d)
+            || arc_contains(c, d, &n2, a);
+    }
+
+    let neg_t = [-t[0], -t[1], -t[2]];
+    [t, neg_t]
+        .iter()
+        .any(|p| arc_contains(a, b, &n1, p) && arc_contains(c, d, &n2, p))
+}
+
+/// Angle between two unit vectors, in radians.
+fn angular_distance(a: &Vec3, b: &Vec3) -> f64 {
+    norm(&cross(a, b)).atan2(dot(a, b))
+}
+
+/// The point of the minor arc from `a` to `b` closest to `p`, with its angular
+/// distance to `p`.
+fn closest_on_arc(a: &Vec3, b: &Vec3, p: &Vec3) -> (Vec3, f64) {
+    let (from_a, from_b) = (angular_distance(a, p), angular_distance(b, p));
+    let mut closest = if from_a <= from_b {
+        (*a, from_a)
+    } else {
+        (*b, from_b)
+    };
+
+    // the projection of `p` on the great circle, if it falls within the arc
+    if let Some(n) = normalized(&cross(a, b)) {
+        let offset = dot(p, &n);
+        let projection = [
+            p[0] - offset * n[0],
+            p[1] - offset * n[1],
+            p[2] - offset * n[2],
+        ];
+
+        if let Some(q) = normalized(&projection)
+            && arc_contains(a, b, &n, &q)
+        {
+            let distance = angular_distance(&q, p);
+            if distance < closest.1 {
+                closest = (q, distance);
+            }
+        }
+    }
+
+    closest
+}
+
+/// Whether the arc from `a` to `b` comes close enough to a cell to cross it.
+///
+/// The vertices are the points of a cell farthest from its center, up to the
+/// curvature of the edges which the margin accounts for.
+fn arc_reaches_cell(a: &Vec3, b: &Vec3, layer: &Layer, hash: u64) -> bool {
+    let (lon, lat) = layer.center(hash);
+    let center = vec3(lon, lat);
+
+    let radius = layer
+        .vertices(hash)
+        .iter()
+        .map(|(lon, lat)| angular_distance(&center, &vec3(*lon, *lat)))
+        .fold(0.0, f64::max);
+
+    closest_on_arc(a, b, &center).1 <= 1.1 * radius
+}
+
+/// Add the cells of `layer` crossed by the minor great-circle arc from `a` to `b`
+/// to `cells`.
+///
+/// The cells are refined from the base cells down to the depth of `layer`, keeping
+/// the cells the arc may cross at each depth. The work and memory therefore scale
+/// with the number of crossed cells, not with the length of the arc.
+fn arc_coverage(a: &Vec3, b: &Vec3, layer: &Layer, cells: &mut Vec<u64>) {
+    // number of segments used to approximate each side of a cell
+    let n_segments_by_side = 3;
+
+    let mut candidates: Vec<u64> = (0..cdshealpix::n_hash(0)).collect();
+    for depth in 0..layer.depth() {
+        let coarse = cdshealpix::nested::get(depth);
+
+        candidates = candidates
+            .into_iter()
+            .filter(|hash| arc_reaches_cell(a, b, coarse, *hash))
+            .flat_map(|hash| cdshealpix::nested::children(hash, 1))
+            .collect();
+    }
+
+    let hash = |v: &Vec3| {
+        let (lon, lat) = lonlat(v);
+        layer.hash(lon.rem_euclid(2.0 * PI), lat)
+    };
+    let ends = [hash(a), hash(b)];
+
+    cells.extend(candidates.into_iter().filter(|h| {
+        if ends.contains(h) {
+            return true;
+        }
+        if !arc_reaches_cell(a, b, layer, *h) {
+            return false;
+        }
+
+        // the arc either passes through the cell or cuts through its boundary
+        let (lon, lat) = layer.center(*h);
+        let (closest, _) = closest_on_arc(a, b, &vec3(lon, lat));
+        if hash(&closest) == *h {
+            return true;
+        }
+
+        let boundary: Vec<Vec3> = layer
+            .path_along_cell_edge(*h, &Cardinal::S, false, n_segments_by_side)
+            .iter()
+            .map(|(lon, lat)| vec3(*lon, *lat))
+            .collect();
+
+        boundary
+            .iter()
+            .zip(boundary.iter().cycle().skip(1))
+            .any(|(c, d)| arcs_intersect(a, b, c, d))
+    }));
+}
+
+/// Points along the rhumb line between two geographic positions (in
+/// radians), separated by at most `step` radians.
+fn densify_rhumb_line(from: (f64, f64), to: (f64, f64), step: f64) -> Vec<(f64, f64)> {
+    // avoid the singularity of the mercator projection at the poles
+    let limit = FRAC_PI_2 - 1e-9;
+    let (lon1, lat1) = (from.0, from.1.clamp(-limit, limit));
+    let (lon2, lat2) = (to.0, to.1.clamp(-limit, limit));
+
+    let isometric = |lat: f64| (FRAC_PI_4 + lat / 2.0).tan().ln();
+    let psi1 = isometric(lat1);
+    let psi2 = isometric(lat2);
+
+    let delta_lon = (lon2 - lon1 + PI).rem_euclid(2.0 * PI) - PI;
+    let delta_lat = lat2 - lat1;
+    let mean_cos_lat = ((lat1 + lat2) / 2.0).cos();
+
+    let length = delta_lat.hypot(delta_lon * mean_cos_lat);
+    let n_steps = (length / step).ceil().max(1.0) as usize;
+
+    (0..=n_steps)
+        .map(|i| {
+            let t = i as f64 / n_steps as f64;
+            let lon = lon1 + t * delta_lon;
+            let lat = if (psi2 - psi1).abs() < 1e-15 {
+                lat1 + t * delta_lat
+            } else {
+                (psi1 + t * (psi2 - psi1)).sinh().atan()
+            };
+
+            (lon, lat)
+        })
+        .collect()
+}
+
+/// Length of the great-circle chords approximating a rhumb line between two
+/// geographic positions (in radians), within `tolerance` radians.
+///
+/// A chord of length `l` deviates by about `κ l² / 8` from a curve of geodesic
+/// curvature `κ`, which is at most `tan(φ)` for rhumb lines at latitude `φ`.
+fn rhumb_chord_length(from: (f64, f64), to: (f64, f64), tolerance: f64) -> f64 {
+    let curvature = from.1.abs().max(to.1.abs()).tan();
+
+    (8.0 * tolerance / curvature).sqrt().min(0.1)
+}
+
+/// Cells crossed by a line.
+///
+/// The line connects the vertices by great-circle arcs or, if `rhumb` is
+/// true, by rhumb lines (loxodromes). Returns the sorted cell ids at the depth
+/// of `layer`.
+pub fn linestring_coverage<R: ReferenceBody>(
+    vertices: &[(f64, f64)],
+    layer: &Layer,
+    ellipsoid: &R,
+    rhumb: bool,
+) -> Result<Vec<u64>> {
+    if vertices.len() < 2 {
+        return Err(Error::InvalidGeometry(format!(
+            "a linestring needs at least 2 vertices, got {}",
+            vertices.len()
+        )));
+    }
+
+    let geographic: Vec<(f64, f64)> = vertices
+        .iter()
+        .map(|(lon, lat)| (lon.to_radians(), lat.to_radians()))
+        .collect();
+    let geographic = if rhumb {
+        // rhumb lines are followed by chords, well within the size of the cells
+        let tolerance = (4.0 * PI / cdshealpix::n_hash(layer.depth()) as f64).sqrt() / 1000.0;
+
+        let mut densified: Vec<(f64, f64)> = geographic
+            .windows(2)
+            .flat_map(|w| {
+                let step = rhumb_chord_length(w[0], w[1], tolerance);
+                let mut points = densify_rhumb_line(w[0], w[1], step);
+                points.pop();
+                points
+            })
+            .collect();
+        densified.push(geographic[geographic.len() - 1]);
+
+        densified
     } else {
-        get_cells(bmoc)
+        geographic
+    };
+
+    let points: Vec<Vec3> = geographic
+        .into_iter()
+        .map(|(lon, lat)| vec3(lon, ellipsoid.latitude_geographic_to_authalic(lat)))
+        .collect();
+
+    let mut cells = Vec::new();
+    for segment in points.windows(2) {
+        arc_coverage(&segment[0], &segment[1], layer, &mut cells);
+    }
+    cells.sort_unstable();
+    cells.dedup();
+
+    Ok(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ellipsoid::{Ellipsoid, ReferenceSphere};
+    use cdshealpix as healpix;
+    use geodesy::ellps::Ellipsoid as GeodesyEllipsoid;
+
+    fn sphere() -> Ellipsoid {
+        Ellipsoid::Sphere(ReferenceSphere::new(
+            GeodesyEllipsoid::named("sphere").unwrap(),
+        ))
+    }
+
+    /// Points along the great-circle arc from `a` to `b`, including both ends,
+    /// separated by at most `step` radians.
+    fn densify_great_circle(a: &Vec3, b: &Vec3, step: f64) -> Vec<Vec3> {
+        let angle = angular_distance(a, b);
+        if angle < 1e-15 {
+            return vec![*a, *b];
+        }
+
+        let n_steps = (angle / step).ceil().max(1.0) as usize;
+
+        let sin_angle = angle.sin();
+        (0..=n_steps)
+            .map(|i| {
+                let t = i as f64 / n_steps as f64;
+                let wa = ((1.0 - t) * angle).sin() / sin_angle;
+                let wb = (t * angle).sin() / sin_angle;
+
+                [
+                    wa * a[0] + wb * b[0],
+                    wa * a[1] + wb * b[1],
+                    wa * a[2] + wb * b[2],
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_neighbours_within_distance() {
+        let ellipsoid = Ellipsoid::named("WGS84").unwrap();
+        let geodesic = ellipsoid.geodesic();
+        let layer = healpix::nested::get(4);
+        let radius = 1_500_000.0;
+
+        for hash in [0, 1000, 1535, 3071] {
+            let actual =
+                neighbours_within_distance(&hash, layer, radius, &ellipsoid, &geodesic).unwrap();
+            assert_eq!(actual[0], (hash, 0.0));
+
+            // compare with the distances to all the cells of the layer
+            let center = healpix_to_lonlat(&hash, layer, &ellipsoid).unwrap();
+            let mut expected: Vec<u64> = (0..healpix::n_hash(layer.depth()))
+                .filter(|other| {
+                    let (lon, lat) = healpix_to_lonlat(other, layer, &ellipsoid).unwrap();
+                    geodesic.inverse(center.0, center.1, lon, lat).0 <= radius
+                })
+                .collect();
+            expected.sort_unstable();
+
+            let mut cells: Vec<u64> = actual.iter().map(|(cell, _)| *cell).collect();
+            cells.sort_unstable();
+            assert_eq!(cells, expected);
+        }
+    }
+
+    #[test]
+    fn test_neighbours_within_distance_invalid_radius() {
+        let layer = healpix::nested::get(4);
+        let ellipsoid = sphere();
+
+        let err = neighbours_within_distance(&0, layer, -1.0, &ellipsoid, &ellipsoid.geodesic())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Error::InvalidGeometry("the radius must be a positive number, got -1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_linestring_coverage_contains_line() {
+        let layer = healpix::nested::get(6);
+        let vertices = [(10.0, -40.0), (35.0, 20.0), (-20.0, 60.0)];
+
+        let actual = linestring_coverage(&vertices, layer, &sphere(), false).unwrap();
+
+        // every cell hit by a dense sampling of the line has to be covered
+        let points: Vec<Vec3> = vertices
+            .iter()
+            .map(|(lon, lat)| vec3(lon.to_radians(), lat.to_radians()))
+            .collect();
+        for segment in points.windows(2) {
+            for p in densify_great_circle(&segment[0], &segment[1], 1e-4) {
+                let (lon, lat) = lonlat(&p);
+                let hash = layer.hash(lon.rem_euclid(2.0 * PI), lat);
+
+                assert!(actual.binary_search(&hash).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_linestring_coverage_rhumb_along_parallel() {
+        let layer = healpix::nested::get(4);
+        let vertices = [(0.0, 45.0), (90.0, 45.0)];
+
+        let actual = linestring_coverage(&vertices, layer, &sphere(), true).unwrap();
+
+        for lon in 0..=90 {
+            let hash = layer.hash((lon as f64).to_radians(), 45f64.to_radians());
+            assert!(actual.binary_search(&hash).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_linestring_coverage_deepest_layer() {
+        let layer = healpix::nested::get(29);
+        let vertices = [(10.0, 20.0), (10.001, 20.0005)];
+
+        for rhumb in [false, true] {
+            let actual = linestring_coverage(&vertices, layer, &sphere(), rhumb).unwrap();
+
+            for (lon, lat) in vertices {
+                let hash = layer.hash(f64::to_radians(lon), f64::to_radians(lat));
+                assert!(actual.binary_search(&hash).is_ok());
+            }
+
+            // the number of cells follows the length of the line in cells
+            let (a, b) = (vertices[0], vertices[1]);
+            let length = angular_distance(
+                &vec3(a.0.to_radians(), a.1.to_radians()),
+                &vec3(b.0.to_radians(), b.1.to_radians()),
+            );
+            let cell_size = (4.0 * PI / healpix::n_hash(layer.depth()) as f64).sqrt();
+            let n_cells = actual.len() as f64;
+            assert!(n_cells >= length / cell_size && n_cells <= 2.0 * length / cell_size);
+        }
+    }
+
+    #[test]
+    fn test_linestring_coverage_invalid() {
+        let layer = healpix::nested::get(4);
+
+        let err = linestring_coverage(&[(0.0, 0.0)], layer, &sphere(), false).unwrap_err();
+        assert_eq!(
+            err,
+            Error::InvalidGeometry("a linestring needs at least 2 vertices, got 1".to_string())
+        );
     }
 }
@@ -1,6 +1,22 @@
+use cdshealpix::compass_point::MainWind;
 use cdshealpix::nested::Layer;
 
-pub fn kth_neighbourhood(hash: &u64, layer: &Layer, ring: &u32) -> Vec<i64> {
+use crate::error::{Error, Result, check_hash};
+
+pub(crate) fn check_ring(ring: u32, nside: u32) -> Result<()> {
+    if ring > nside {
+        Err(Error::InvalidGeometry(format!(
+            "crossing base cell boundaries more than once is not supported: ring must be in the range [0, {nside}], got {ring}"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn kth_neighbourhood(hash: &u64, layer: &Layer, ring: &u32) -> Result<Vec<i64>> {
+    check_hash(*hash, layer.depth())?;
+    check_ring(*ring, layer.nside())?;
+
     let mut neighbours: Vec<i64> = layer
         .kth_neighbourhood(*hash, *ring)
         .into_iter()
@@ -12,5 +28,52 @@ pub fn kth_neighbourhood(hash: &u64, layer: &Layer, ring: &u32) -> Vec<i64> {
         neighbours.resize(expected_size, -1);
     }
 
-    neighbours
+    Ok(neighbours)
+}
+
+/// The directions of the neighbours returned by [`neighbours`], in order.
+pub const NEIGHBOUR_DIRECTIONS: [MainWind; 8] = [
+    MainWind::N,
+    MainWind::NE,
+    MainWind::E,
+    MainWind::SE,
+    MainWind::S,
+    MainWind::SW,
+    MainWind::W,
+    MainWind::NW,
+];
+
+/// The 8 neighbours of a cell, in the order of [`NEIGHBOUR_DIRECTIONS`].
+///
+/// The directions are relative to the base cell containing the cell: the neighbours
+/// in the cardinal directions only share a vertex with the cell, and the ones in the
+/// ordinal directions share an edge. Around the 8 vertices of base cells touching
+/// only 3 base cells, one of the neighbours is missing and set to `-1`.
+pub fn neighbours(hash: &u64, layer: &Layer) -> Result<[i64; 8]> {
+    check_hash(*hash, layer.depth())?;
+
+    let neighbours = layer.neighbours(*hash, false);
+
+    Ok(NEIGHBOUR_DIRECTIONS.map(|direction| {
+        neighbours
+            .get(direction)
+            .map_or(-1, |neighbour| *neighbour as i64)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cdshealpix as healpix;
+
+    #[test]
+    fn test_neighbours_base_cell() {
+        let layer = healpix::nested::get(0);
+
+        // the east and west vertices of the northern base cells touch 3 base cells
+        let actual = neighbours(&0, layer).unwrap();
+        assert_eq!(actual, [2, 1, -1, 5, 8, 4, -1, 3]);
+
+        assert!(neighbours(&12, layer).is_err());
+    }
 }
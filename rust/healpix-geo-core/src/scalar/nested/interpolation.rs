@@ -0,0 +1,61 @@
+use cdshealpix::nested::Layer;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_lonlat};
+
+/// The 4 cells surrounding a point, with their bilinear interpolation weights.
+///
+/// The weights are computed on the authalic sphere and sum to 1. Around the corners
+/// of the base cells, where a cell only has 7 neighbours, the missing cell is
+/// replaced by the cell containing the point with a weight of 0.
+pub fn interpolation_weights<R: ReferenceBody>(
+    lon: &f64,
+    lat: &f64,
+    layer: &Layer,
+    ellipsoid: &R,
+) -> Result<[(u64, f64); 4]> {
+    check_lonlat(*lon, *lat)?;
+
+    let lon_ = lon.rem_euclid(360.0).to_radians();
+    let lat_ = ellipsoid.latitude_geographic_to_authalic(lat.to_radians());
+
+    Ok(layer.bilinear_interpolation(lon_, lat_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ellipsoid::Ellipsoid;
+    use cdshealpix as healpix;
+
+    #[test]
+    fn test_interpolation_weights_sum_to_one() {
+        let ellipsoid = Ellipsoid::named("WGS84").unwrap();
+        let layer = healpix::nested::get(4);
+
+        for (lon, lat) in [(0.0, 0.0), (12.3, 45.6), (-170.0, -89.5), (45.0, 41.8)] {
+            let weights = interpolation_weights(&lon, &lat, layer, &ellipsoid).unwrap();
+            let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+
+            assert!((total - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_interpolation_weights_at_cell_center() {
+        let ellipsoid = Ellipsoid::named("WGS84").unwrap();
+        let layer = healpix::nested::get(4);
+
+        let (lon, lat) =
+            crate::scalar::nested::coordinates::healpix_to_lonlat(&1234, layer, &ellipsoid)
+                .unwrap();
+        let weights = interpolation_weights(&lon, &lat, layer, &ellipsoid).unwrap();
+
+        let weight: f64 = weights
+            .iter()
+            .filter(|(hash, _)| *hash == 1234)
+            .map(|(_, weight)| weight)
+            .sum();
+        assert!((weight - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,84 @@
+use std::f64::consts::PI;
+
+use cdshealpix as healpix;
+use cdshealpix::nested::Layer;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_hash};
+use crate::geodesic::Geodesic;
+
+/// Area of the cells of a depth, in squared units of the semimajor axis.
+///
+/// All cells of a depth have the same area on the authalic sphere, which by
+/// construction is also their area on the ellipsoid.
+pub(crate) fn depth_area<R: ReferenceBody>(depth: u8, ellipsoid: &R) -> f64 {
+    let radius = ellipsoid.authalic_radius();
+
+    4.0 * PI * radius * radius / healpix::n_hash(depth) as f64
+}
+
+/// Length of the closed geodesic polygon through the given vertices.
+///
+/// The vertices are longitudes and geographic latitudes in degrees.
+pub(crate) fn polygon_perimeter(vertices: &[(f64, f64)], geodesic: &Geodesic) -> f64 {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&(lon1, lat1), &(lon2, lat2))| geodesic.inverse(lon1, lat1, lon2, lat2).0)
+        .sum()
+}
+
+pub fn cell_area<R: ReferenceBody>(hash: &u64, layer: &Layer, ellipsoid: &R) -> Result<f64> {
+    check_hash(*hash, layer.depth())?;
+
+    Ok(depth_area(layer.depth(), ellipsoid))
+}
+
+/// Length of the boundary of a cell, through `step` points per edge.
+///
+/// `geodesic` is the geodesic solver of `ellipsoid`, built once by the callers.
+pub fn cell_perimeter<R: ReferenceBody>(
+    hash: &u64,
+    layer: &Layer,
+    ellipsoid: &R,
+    geodesic: &Geodesic,
+    step: &usize,
+) -> Result<f64> {
+    let vertices = crate::scalar::nested::coordinates::vertices(hash, layer, ellipsoid, step)?;
+
+    Ok(polygon_perimeter(&vertices, geodesic))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ellipsoid::Ellipsoid;
+
+    #[test]
+    fn test_cell_area_sums_to_surface() {
+        let ellipsoid = Ellipsoid::named("WGS84").unwrap();
+        let layer = healpix::nested::get(2);
+
+        let total: f64 = (0..healpix::n_hash(layer.depth()))
+            .map(|hash| cell_area(&hash, layer, &ellipsoid).unwrap())
+            .sum();
+        let radius = ellipsoid.authalic_radius();
+
+        assert!((total / (4.0 * PI * radius * radius) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cell_perimeter_refines() {
+        let ellipsoid = Ellipsoid::named("WGS84").unwrap();
+        let layer = healpix::nested::get(3);
+
+        let geodesic = ellipsoid.geodesic();
+
+        let coarse = cell_perimeter(&17, layer, &ellipsoid, &geodesic, &1).unwrap();
+        let fine = cell_perimeter(&17, layer, &ellipsoid, &geodesic, &8).unwrap();
+
+        // the cell edges are not geodesics, so densifying them lengthens the path
+        assert!(fine > coarse);
+        assert!((fine - coarse) / fine < 0.05);
+    }
+}
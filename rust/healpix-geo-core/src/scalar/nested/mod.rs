@@ -2,3 +2,6 @@ pub mod conversion;
 pub mod coordinates;
 pub mod coverage;
 pub mod hierarchy;
+pub mod interpolation;
+pub mod measures;
+pub mod regridding;
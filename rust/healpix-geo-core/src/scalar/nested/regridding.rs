@@ -0,0 +1,368 @@
+use std::f64::consts::{FRAC_PI_4, PI, TAU};
+
+use cdshealpix as healpix;
+use cdshealpix::nested::Layer;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Error, Result, check_delta_depth, check_lonlat};
+use crate::scalar::nested::measures::depth_area;
+
+/// Shift `lon` by a multiple of 360° to be within 180° of `reference`.
+fn unwrap_longitude(lon: f64, reference: f64) -> f64 {
+    reference + (lon - reference + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// A source cell whose edges are straight lines in longitude and latitude.
+///
+/// The longitudes are unwrapped so that cells crossing the antimeridian stay
+/// contiguous. Cells must not contain a pole, except for cells of rectilinear grids
+/// bounded by it.
+struct LonLatPolygon {
+    vertices: Vec<(f64, f64)>,
+    center: f64,
+}
+
+impl LonLatPolygon {
+    fn new(vertices: &[(f64, f64)]) -> Result<Self> {
+        if vertices.len() < 3 {
+            return Err(Error::InvalidGeometry(format!(
+                "a polygon needs at least 3 vertices, got {}",
+                vertices.len()
+            )));
+        }
+
+        let reference = vertices[0].0;
+        let vertices = vertices
+            .iter()
+            .map(|&(lon, lat)| {
+                check_lonlat(lon, lat)?;
+
+                Ok((unwrap_longitude(lon, reference), lat))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let (lon_min, lon_max) = vertices.iter().fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(min, max), (lon, _)| (min.min(*lon), max.max(*lon)),
+        );
+
+        Ok(Self {
+            vertices,
+            center: (lon_min + lon_max) / 2.0,
+        })
+    }
+
+    /// The bounding box, as `(lon_min, lat_min, lon_max, lat_max)`.
+    fn bbox(&self) -> (f64, f64, f64, f64) {
+        self.vertices.iter().fold(
+            (
+                f64::INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::NEG_INFINITY,
+            ),
+            |(lon_min, lat_min, lon_max, lat_max), &(lon, lat)| {
+                (
+                    lon_min.min(lon),
+                    lat_min.min(lat),
+                    lon_max.max(lon),
+                    lat_max.max(lat),
+                )
+            },
+        )
+    }
+
+    /// Points along the edges, as longitudes in radians and sines of the authalic
+    /// latitude.
+    ///
+    /// Meridians and parallels are straight lines in the HEALPix projection, except
+    /// where they leave the equatorial zone, so the edges are split there. Other edges
+    /// are followed in steps of at most `step` radians.
+    fn boundary<R: ReferenceBody>(&self, ellipsoid: &R, step: f64) -> Vec<(f64, f64)> {
+        let transition = ellipsoid
+            .latitude_authalic_to_geographic((2.0f64 / 3.0).asin())
+            .to_degrees();
+
+        let edges = self
+            .vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1));
+
+        let mut boundary = Vec::new();
+        for (&(lon1, lat1), &(lon2, lat2)) in edges {
+            let mut breaks = vec![0.0];
+            if lat1 != lat2 {
+                breaks.extend(
+                    [-transition, transition]
+                        .map(|lat| (lat - lat1) / (lat2 - lat1))
+                        .into_iter()
+                        .filter(|t| *t > 0.0 && *t < 1.0),
+                );
+            }
+            breaks.push(1.0);
+            breaks.sort_by(f64::total_cmp);
+
+            let length = ((lon2 - lon1) * ((lat1 + lat2) / 2.0).to_radians().cos())
+                .hypot(lat2 - lat1)
+                .to_radians();
+
+            for (start, end) in breaks.iter().zip(breaks.iter().skip(1)) {
+                let n_steps = if lon1 == lon2 || lat1 == lat2 {
+                    1
+                } else {
+                    ((end - start) * length / step).ceil().max(1.0) as usize
+                };
+
+                boundary.extend((0..n_steps).map(|i| {
+                    let t = start + (end - start) * i as f64 / n_steps as f64;
+                    let lon = lon1 + t * (lon2 - lon1);
+                    let lat = lat1 + t * (lat2 - lat1);
+
+                    (
+                        lon.to_radians(),
+                        ellipsoid
+                            .latitude_geographic_to_authalic(lat.to_radians())
+                            .sin(),
+                    )
+                }));
+            }
+        }
+
+        boundary
+    }
+}
+
+/// HEALPix projection of a point on the authalic sphere, in units of π/4.
+///
+/// `lon` is in radians and `z` is the sine of the authalic latitude. In the polar
+/// caps, longitudes are scaled around the meridian `x_center` instead of the center
+/// of their quadrant, which keeps the projection continuous and one-to-one around a
+/// base cell centered on that meridian.
+fn project(lon: f64, z: f64, x_center: f64) -> (f64, f64) {
+    let x = lon / FRAC_PI_4;
+
+    if z.abs() <= 2.0 / 3.0 {
+        (x, 1.5 * z)
+    } else {
+        let sigma = (3.0 * (1.0 - z.abs())).max(0.0).sqrt();
+
+        (
+            x_center + (x - x_center) * sigma,
+            z.signum() * (2.0 - sigma),
+        )
+    }
+}
+
+/// Clip a polygon to the half-plane where `distance` is positive, using the
+/// Sutherland-Hodgman algorithm.
+fn clip(polygon: &[(f64, f64)], distance: impl Fn(&(f64, f64)) -> f64) -> Vec<(f64, f64)> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+
+    for (p, q) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+        let (dp, dq) = (distance(p), distance(q));
+
+        if dp >= 0.0 {
+            clipped.push(*p);
+        }
+        if (dp >= 0.0) != (dq >= 0.0) {
+            let t = dp / (dp - dq);
+            clipped.push((p.0 + t * (q.0 - p.0), p.1 + t * (q.1 - p.1)));
+        }
+    }
+
+    clipped
+}
+
+/// Area of a polygon, using the shoelace formula.
+fn area(polygon: &[(f64, f64)]) -> f64 {
+    let twice_area: f64 = polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(p, q)| p.0 * q.1 - q.0 * p.1)
+        .sum();
+
+    twice_area.abs() / 2.0
+}
+
+/// Areas of the overlaps between a source cell and the cells of `layer`.
+///
+/// The source cell is a polygon given by its vertices in longitude and geographic
+/// latitude, with edges that are straight lines in that plane like the cells of
+/// rectilinear grids. The HEALPix projection preserves areas and maps the cells to
+/// squares, so each overlap is the area of the source cell clipped to the square of
+/// the cell in that projection. Edges along meridians and parallels are exact, while
+/// other edges are followed in steps the size of the cells of depth
+/// `layer.depth() + refinement`.
+///
+/// Returns the overlapping cells, sorted, with their overlap area in squared units of
+/// the semimajor axis of the ellipsoid.
+pub fn overlap_areas<R: ReferenceBody>(
+    vertices: &[(f64, f64)],
+    layer: &Layer,
+    ellipsoid: &R,
+    refinement: u8,
+) -> Result<Vec<(u64, f64)>> {
+    check_delta_depth(layer.depth(), refinement)?;
+    let polygon = LonLatPolygon::new(vertices)?;
+
+    let step = (4.0 * PI / healpix::n_hash(layer.depth() + refinement) as f64).sqrt();
+    let boundary = polygon.boundary(ellipsoid, step);
+
+    let (lon_min, lat_min, lon_max, lat_max) = polygon.bbox();
+    let (lon_min, lon_max) = if lon_max - lon_min >= 360.0 {
+        (0.0, TAU)
+    } else {
+        // keep the zone from wrapping around if it ends at 360°
+        let start = lon_min.rem_euclid(360.0);
+        let end = start + (lon_max - lon_min);
+
+        (
+            start.to_radians(),
+            if end > 360.0 { end - 360.0 } else { end }.to_radians(),
+        )
+    };
+    let candidates = layer.zone_coverage(
+        lon_min,
+        ellipsoid.latitude_geographic_to_authalic(lat_min.to_radians()),
+        lon_max,
+        ellipsoid.latitude_geographic_to_authalic(lat_max.to_radians()),
+    );
+
+    let base = healpix::nested::get(0);
+    let nside = layer.nside() as f64;
+    let cell_area = depth_area(layer.depth(), ellipsoid);
+
+    let mut overlaps = Vec::new();
+    for cell in candidates.flat_iter_cell() {
+        // coordinates within the base cell, scaled so that the cells are unit squares
+        let (lon, lat) = base.center(cell.hash >> (2 * layer.depth()));
+        let x_center = lon / FRAC_PI_4;
+        let (xc, yc) = project(lon, lat.sin(), x_center);
+        let local = |lon: f64, z: f64| {
+            let (x, y) = project(lon, z, x_center);
+            let (dx, dy) = (x - xc, y - yc);
+
+            ((dx + dy + 1.0) / 2.0 * nside, (dy - dx + 1.0) / 2.0 * nside)
+        };
+
+        let (lon, lat) = layer.center(cell.hash);
+        let lon = unwrap_longitude(lon.to_degrees(), x_center * 45.0).to_radians();
+        let (u, v) = local(lon, lat.sin());
+        let (u, v) = (u.floor(), v.floor());
+
+        // the source cell may reach the cell from either side of its unwrapped longitudes
+        let turns = ((lon - polygon.center.to_radians()) / TAU).round();
+        let fraction: f64 = [turns - 1.0, turns, turns + 1.0]
+            .into_iter()
+            .map(|turns| {
+                let projected: Vec<(f64, f64)> = boundary
+                    .iter()
+                    .map(|(lon, z)| local(lon + turns * TAU, *z))
+                    .collect();
+
+                let clipped = clip(&projected, |p| p.0 - u);
+                let clipped = clip(&clipped, |p| u + 1.0 - p.0);
+                let clipped = clip(&clipped, |p| p.1 - v);
+                let clipped = clip(&clipped, |p| v + 1.0 - p.1);
+
+                area(&clipped)
+            })
+            .sum();
+
+        // ignore cells only touching the source cell, up to rounding errors
+        if fraction > 1e-12 {
+            overlaps.push((cell.hash, fraction * cell_area));
+        }
+    }
+    overlaps.sort_unstable_by_key(|(hash, _)| *hash);
+
+    Ok(overlaps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ellipsoid::Ellipsoid;
+
+    #[test]
+    fn test_overlap_areas_conserve_area() {
+        let ellipsoid = Ellipsoid::named("WGS84").unwrap();
+        let layer = healpix::nested::get(2);
+
+        // a global 30° rectilinear grid
+        let total: f64 = (0..12)
+            .flat_map(|i| (0..6).map(move |j| (i as f64 * 30.0, j as f64 * 30.0 - 90.0)))
+            .map(|(lon, lat)| {
+                let vertices = [
+                    (lon, lat),
+                    (lon + 30.0, lat),
+                    (lon + 30.0, lat + 30.0),
+                    (lon, lat + 30.0),
+                ];
+
+                overlap_areas(&vertices, layer, &ellipsoid, 3)
+                    .unwrap()
+                    .iter()
+                    .map(|(_, area)| area)
+                    .sum::<f64>()
+            })
+            .sum();
+        let radius = ellipsoid.authalic_radius();
+
+        assert!((total / (4.0 * std::f64::consts::PI * radius * radius) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_overlap_areas_of_cell() {
+        let ellipsoid = Ellipsoid::named("WGS84").unwrap();
+        let layer = healpix::nested::get(3);
+
+        let vertices =
+            crate::scalar::nested::coordinates::vertices(&300, layer, &ellipsoid, &4).unwrap();
+        let overlaps = overlap_areas(&vertices, layer, &ellipsoid, 4).unwrap();
+
+        let (hash, area) = overlaps.iter().max_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+        assert_eq!(*hash, 300);
+        assert!((area / depth_area(3, &ellipsoid) - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_overlap_areas_sum_to_source_area() {
+        let ellipsoid = Ellipsoid::named("WGS84").unwrap();
+        let radius = ellipsoid.authalic_radius();
+
+        // ((lon_min, lat_min), (lon_max, lat_max), depth)
+        let cases = [
+            // much smaller than the cells
+            ((10.0, 10.0), (10.1, 10.1), 0),
+            // across the antimeridian
+            ((170.0, 40.0), (-170.0, 50.0), 4),
+            // bounded by the pole
+            ((0.0, 60.0), (30.0, 90.0), 3),
+            // across the boundary of the polar cap
+            ((100.0, 30.0), (120.0, 60.0), 5),
+        ];
+
+        for ((lon_min, lat_min), (lon_max, lat_max), depth) in cases {
+            let vertices = [
+                (lon_min, lat_min),
+                (lon_max, lat_min),
+                (lon_max, lat_max),
+                (lon_min, lat_max),
+            ];
+            let overlaps =
+                overlap_areas(&vertices, healpix::nested::get(depth), &ellipsoid, 3).unwrap();
+            let total: f64 = overlaps.iter().map(|(_, area)| area).sum();
+
+            let z = |lat: f64| {
+                ellipsoid
+                    .latitude_geographic_to_authalic(f64::to_radians(lat))
+                    .sin()
+            };
+            let width = unwrap_longitude(lon_max, lon_min) - lon_min;
+            let expected = radius * radius * width.to_radians() * (z(lat_max) - z(lat_min));
+
+            assert!((total / expected - 1.0).abs() < 1e-9);
+        }
+    }
+}
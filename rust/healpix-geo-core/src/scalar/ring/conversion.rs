@@ -1,22 +1,30 @@
 use cdshealpix as healpix;
 
-pub fn from_zuniq(hash: &u64) -> (u64, u8) {
-    let (depth, hash_nested) = healpix::nested::from_zuniq(*hash);
+use crate::error::{Result, check_hash, check_zuniq};
+
+pub fn from_zuniq(hash: &u64) -> Result<(u64, u8)> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
     let hash_ring = healpix::nested::get(depth).to_ring(hash_nested);
 
-    (hash_ring, depth)
+    Ok((hash_ring, depth))
 }
 
-pub fn from_nested(hash: &u64, depth: &u8) -> u64 {
-    healpix::nested::get(*depth).to_ring(*hash)
+pub fn from_nested(hash: &u64, depth: &u8) -> Result<u64> {
+    check_hash(*hash, *depth)?;
+
+    Ok(healpix::nested::get(*depth).to_ring(*hash))
 }
 
-pub fn to_zuniq(hash: &u64, depth: &u8) -> u64 {
+pub fn to_zuniq(hash: &u64, depth: &u8) -> Result<u64> {
+    check_hash(*hash, *depth)?;
+
     let hash_nested = healpix::nested::get(*depth).from_ring(*hash);
 
-    healpix::nested::to_zuniq_unsafe(*depth, hash_nested)
+    Ok(healpix::nested::to_zuniq_unsafe(*depth, hash_nested))
 }
 
-pub fn to_nested(hash: &u64, depth: &u8) -> u64 {
-    healpix::nested::get(*depth).from_ring(*hash)
+pub fn to_nested(hash: &u64, depth: &u8) -> Result<u64> {
+    check_hash(*hash, *depth)?;
+
+    Ok(healpix::nested::get(*depth).from_ring(*hash))
 }
@@ -1,9 +1,16 @@
-use crate::ellipsoid::{Ellipsoid, ReferenceBody};
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_depth, check_hash, check_lonlat};
 
 use cdshealpix as healpix;
 use cdshealpix::compass_point::Cardinal;
 
-pub fn healpix_to_lonlat(hash: &u64, nside: &u32, ellipsoid: &Ellipsoid) -> (f64, f64) {
+pub fn healpix_to_lonlat<R: ReferenceBody>(
+    hash: &u64,
+    nside: &u32,
+    ellipsoid: &R,
+) -> Result<(f64, f64)> {
+    check_hash(*hash, healpix::depth(*nside))?;
+
     let center = healpix::ring::center(*nside, *hash);
 
     let lon = center.0.to_degrees().rem_euclid(360.0);
@@ -11,17 +18,37 @@ pub fn healpix_to_lonlat(hash: &u64, nside: &u32, ellipsoid: &Ellipsoid) -> (f64
         .latitude_authalic_to_geographic(center.1)
         .to_degrees();
 
-    (lon, lat)
+    Ok((lon, lat))
 }
 
-pub fn lonlat_to_healpix(lon: &f64, lat: &f64, nside: &u32, ellipsoid: &Ellipsoid) -> u64 {
+pub fn lonlat_to_healpix<R: ReferenceBody>(
+    lon: &f64,
+    lat: &f64,
+    nside: &u32,
+    ellipsoid: &R,
+) -> Result<u64> {
+    check_depth(healpix::depth(*nside))?;
+    check_lonlat(*lon, *lat)?;
+
     let lon_ = lon.rem_euclid(360.0).to_radians();
     let lat_ = ellipsoid.latitude_geographic_to_authalic(lat.to_radians());
 
-    healpix::ring::hash(*nside, lon_, lat_)
+    Ok(healpix::ring::hash(*nside, lon_, lat_))
 }
 
-pub fn vertices(hash: &u64, nside: &u32, ellipsoid: &Ellipsoid, step: &usize) -> Vec<(f64, f64)> {
+/// Whether `hash` is a cell of the ring scheme with the given `nside`.
+pub fn is_valid_cell(hash: &u64, nside: &u32) -> bool {
+    check_hash(*hash, healpix::depth(*nside)).is_ok()
+}
+
+pub fn vertices<R: ReferenceBody>(
+    hash: &u64,
+    nside: &u32,
+    ellipsoid: &R,
+    step: &usize,
+) -> Result<Vec<(f64, f64)>> {
+    check_hash(*hash, healpix::depth(*nside))?;
+
     let vertices: Vec<(f64, f64)> = if *step == 1 {
         healpix::ring::vertices(*nside, *hash).into()
     } else {
@@ -32,7 +59,7 @@ pub fn vertices(hash: &u64, nside: &u32, ellipsoid: &Ellipsoid, step: &usize) ->
             .into()
     };
 
-    vertices
+    Ok(vertices
         .into_iter()
         .map(|(lon, lat): (f64, f64)| {
             (
@@ -40,13 +67,13 @@ pub fn vertices(hash: &u64, nside: &u32, ellipsoid: &Ellipsoid, step: &usize) ->
                 ellipsoid.latitude_authalic_to_geographic(lat).to_degrees(),
             )
         })
-        .collect()
+        .collect())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ellipsoid::ReferenceEllipsoid;
+    use crate::ellipsoid::{Ellipsoid, ReferenceEllipsoid};
     use cdshealpix as healpix;
     use geodesy::ellps::Ellipsoid as GeodesyEllipsoid;
 
@@ -60,25 +87,25 @@ mod tests {
         let lon: f64 = -180.0;
         let lat: f64 = 75.0;
 
-        let actual = lonlat_to_healpix(&lon, &lat, &nside, &ellipsoid);
+        let actual = lonlat_to_healpix(&lon, &lat, &nside, &ellipsoid).unwrap();
         assert_eq!(actual, 2);
 
         let lon: f64 = 180.0;
         let lat: f64 = 75.0;
 
-        let actual = lonlat_to_healpix(&lon, &lat, &nside, &ellipsoid);
+        let actual = lonlat_to_healpix(&lon, &lat, &nside, &ellipsoid).unwrap();
         assert_eq!(actual, 2);
 
         let lon: f64 = 0.0;
         let lat: f64 = 75.0;
 
-        let actual = lonlat_to_healpix(&lon, &lat, &nside, &ellipsoid);
+        let actual = lonlat_to_healpix(&lon, &lat, &nside, &ellipsoid).unwrap();
         assert_eq!(actual, 0);
 
         let lon: f64 = 360.0;
         let lat: f64 = 75.0;
 
-        let actual = lonlat_to_healpix(&lon, &lat, &nside, &ellipsoid);
+        let actual = lonlat_to_healpix(&lon, &lat, &nside, &ellipsoid).unwrap();
         assert_eq!(actual, 0);
     }
 }
@@ -1,19 +1,23 @@
-use crate::ellipsoid::Ellipsoid;
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_depth, check_hash};
+use crate::geodesic::Geodesic;
 use cdshealpix as healpix;
 use itertools::{MultiUnzip, izip};
 
-pub fn box_coverage(
+pub fn box_coverage<R: ReferenceBody>(
     center: (f64, f64),
     size: (f64, f64),
     angle: f64,
     nside: &u32,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     flat: bool,
-) -> (Vec<u64>, Vec<u8>, Vec<bool>) {
-    let layer = healpix::nested::get(healpix::depth(*nside));
+) -> Result<(Vec<u64>, Vec<u8>, Vec<bool>)> {
+    let depth = healpix::depth(*nside);
+    check_depth(depth)?;
+    let layer = healpix::nested::get(depth);
 
     let (ipix, depths, fully_covered) =
-        crate::scalar::nested::coverage::box_coverage(center, size, angle, layer, ellipsoid, flat);
+        crate::scalar::nested::coverage::box_coverage(center, size, angle, layer, ellipsoid, flat)?;
 
     let mut result: Vec<(u64, u8, bool)> = izip!(
         ipix.into_iter(),
@@ -24,19 +28,21 @@ pub fn box_coverage(
     .collect::<Vec<_>>();
     result.sort_by_key(|it| it.0);
 
-    result.into_iter().multiunzip()
+    Ok(result.into_iter().multiunzip())
 }
 
-pub fn zone_coverage(
+pub fn zone_coverage<R: ReferenceBody>(
     bbox: (f64, f64, f64, f64),
     nside: &u32,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     flat: bool,
-) -> (Vec<u64>, Vec<u8>, Vec<bool>) {
-    let layer = healpix::nested::get(healpix::depth(*nside));
+) -> Result<(Vec<u64>, Vec<u8>, Vec<bool>)> {
+    let depth = healpix::depth(*nside);
+    check_depth(depth)?;
+    let layer = healpix::nested::get(depth);
 
     let (ipix, depths, fully_covered) =
-        crate::scalar::nested::coverage::zone_coverage(bbox, layer, ellipsoid, flat);
+        crate::scalar::nested::coverage::zone_coverage(bbox, layer, ellipsoid, flat)?;
 
     let mut result: Vec<(u64, u8, bool)> = izip!(
         ipix.into_iter(),
@@ -47,20 +53,22 @@ pub fn zone_coverage(
     .collect::<Vec<_>>();
     result.sort_by_key(|it| it.0);
 
-    result.into_iter().multiunzip()
+    Ok(result.into_iter().multiunzip())
 }
 
-pub fn polygon_coverage(
+pub fn polygon_coverage<R: ReferenceBody>(
     vertices: &[(f64, f64)],
     nside: &u32,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     exact: bool,
     flat: bool,
-) -> (Vec<u64>, Vec<u8>, Vec<bool>) {
-    let layer = healpix::nested::get(healpix::depth(*nside));
+) -> Result<(Vec<u64>, Vec<u8>, Vec<bool>)> {
+    let depth = healpix::depth(*nside);
+    check_depth(depth)?;
+    let layer = healpix::nested::get(depth);
 
     let (ipix, depths, fully_covered) =
-        crate::scalar::nested::coverage::polygon_coverage(vertices, layer, ellipsoid, exact, flat);
+        crate::scalar::nested::coverage::polygon_coverage(vertices, layer, ellipsoid, exact, flat)?;
 
     let mut result: Vec<(u64, u8, bool)> = izip!(
         ipix.into_iter(),
@@ -71,18 +79,20 @@ pub fn polygon_coverage(
     .collect::<Vec<_>>();
     result.sort_by_key(|it| it.0);
 
-    result.into_iter().multiunzip()
+    Ok(result.into_iter().multiunzip())
 }
 
-pub fn cone_coverage(
+pub fn cone_coverage<R: ReferenceBody>(
     center: (f64, f64),
     radius: f64,
     nside: &u32,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     delta_depth: u8,
     flat: bool,
-) -> (Vec<u64>, Vec<u8>, Vec<bool>) {
-    let layer = healpix::nested::get(healpix::depth(*nside));
+) -> Result<(Vec<u64>, Vec<u8>, Vec<bool>)> {
+    let depth = healpix::depth(*nside);
+    check_depth(depth)?;
+    let layer = healpix::nested::get(depth);
 
     let (ipix, depths, fully_covered) = crate::scalar::nested::coverage::cone_coverage(
         center,
@@ -91,7 +101,7 @@ pub fn cone_coverage(
         ellipsoid,
         delta_depth,
         flat,
-    );
+    )?;
 
     let mut result: Vec<(u64, u8, bool)> = izip!(
         ipix.into_iter(),
@@ -102,19 +112,21 @@ pub fn cone_coverage(
     .collect::<Vec<_>>();
     result.sort_by_key(|it| it.0);
 
-    result.into_iter().multiunzip()
+    Ok(result.into_iter().multiunzip())
 }
 
-pub fn elliptical_cone_coverage(
+pub fn elliptical_cone_coverage<R: ReferenceBody>(
     center: (f64, f64),
     ellipse_geometry: (f64, f64),
     position_angle: f64,
     nside: &u32,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     delta_depth: u8,
     flat: bool,
-) -> (Vec<u64>, Vec<u8>, Vec<bool>) {
-    let layer = healpix::nested::get(healpix::depth(*nside));
+) -> Result<(Vec<u64>, Vec<u8>, Vec<bool>)> {
+    let depth = healpix::depth(*nside);
+    check_depth(depth)?;
+    let layer = healpix::nested::get(depth);
 
     let (ipix, depths, fully_covered) = crate::scalar::nested::coverage::elliptical_cone_coverage(
         center,
@@ -124,7 +136,7 @@ pub fn elliptical_cone_coverage(
         ellipsoid,
         delta_depth,
         flat,
-    );
+    )?;
 
     let mut result: Vec<(u64, u8, bool)> = izip!(
         ipix.into_iter(),
@@ -135,5 +147,52 @@ pub fn elliptical_cone_coverage(
     .collect::<Vec<_>>();
     result.sort_by_key(|it| it.0);
 
-    result.into_iter().multiunzip()
+    Ok(result.into_iter().multiunzip())
+}
+
+/// The cells whose center is within `radius` of the center of a cell, with their
+/// distance, sorted by distance.
+pub fn neighbours_within_distance<R: ReferenceBody>(
+    hash: &u64,
+    nside: &u32,
+    radius: f64,
+    ellipsoid: &R,
+    geodesic: &Geodesic,
+) -> Result<Vec<(u64, f64)>> {
+    let depth = healpix::depth(*nside);
+    check_hash(*hash, depth)?;
+    let layer = healpix::nested::get(depth);
+
+    let neighbours = crate::scalar::nested::coverage::neighbours_within_distance(
+        &layer.from_ring(*hash),
+        layer,
+        radius,
+        ellipsoid,
+        geodesic,
+    )?;
+
+    Ok(neighbours
+        .into_iter()
+        .map(|(h, distance)| (layer.to_ring(h), distance))
+        .collect())
+}
+
+pub fn linestring_coverage<R: ReferenceBody>(
+    vertices: &[(f64, f64)],
+    nside: &u32,
+    ellipsoid: &R,
+    rhumb: bool,
+) -> Result<Vec<u64>> {
+    let depth = healpix::depth(*nside);
+    check_depth(depth)?;
+    let layer = healpix::nested::get(depth);
+
+    let mut ipix: Vec<u64> =
+        crate::scalar::nested::coverage::linestring_coverage(vertices, layer, ellipsoid, rhumb)?
+            .into_iter()
+            .map(|h| layer.to_ring(h))
+            .collect();
+    ipix.sort();
+
+    Ok(ipix)
 }
@@ -1,7 +1,14 @@
 use cdshealpix as healpix;
 
-pub fn kth_neighbourhood(hash: &u64, nside: &u32, ring: &u32) -> Vec<i64> {
-    let layer = healpix::nested::get(healpix::depth(*nside));
+use crate::error::{Result, check_hash};
+use crate::scalar::nested::hierarchy::check_ring;
+
+pub fn kth_neighbourhood(hash: &u64, nside: &u32, ring: &u32) -> Result<Vec<i64>> {
+    let depth = healpix::depth(*nside);
+    check_hash(*hash, depth)?;
+    check_ring(*ring, *nside)?;
+
+    let layer = healpix::nested::get(depth);
 
     let hash_nested = layer.from_ring(*hash);
 
@@ -23,5 +30,25 @@ pub fn kth_neighbourhood(hash: &u64, nside: &u32, ring: &u32) -> Vec<i64> {
         neighbours.resize(expected_size, -1);
     }
 
-    neighbours
+    Ok(neighbours)
+}
+
+/// The 8 neighbours of a cell, in the order of
+/// [`crate::scalar::nested::hierarchy::NEIGHBOUR_DIRECTIONS`].
+///
+/// Missing neighbours are set to `-1`.
+pub fn neighbours(hash: &u64, nside: &u32) -> Result<[i64; 8]> {
+    let depth = healpix::depth(*nside);
+    check_hash(*hash, depth)?;
+
+    let layer = healpix::nested::get(depth);
+    let neighbours = crate::scalar::nested::hierarchy::neighbours(&layer.from_ring(*hash), layer)?;
+
+    Ok(neighbours.map(|v| {
+        if v == -1 {
+            v
+        } else {
+            layer.to_ring(v as u64) as i64
+        }
+    }))
 }
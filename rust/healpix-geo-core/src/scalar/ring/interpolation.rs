@@ -0,0 +1,22 @@
+use cdshealpix as healpix;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_depth};
+
+/// The 4 cells surrounding a point, with their bilinear interpolation weights.
+///
+/// See [`crate::scalar::nested::interpolation::interpolation_weights`].
+pub fn interpolation_weights<R: ReferenceBody>(
+    lon: &f64,
+    lat: &f64,
+    nside: &u32,
+    ellipsoid: &R,
+) -> Result<[(u64, f64); 4]> {
+    check_depth(healpix::depth(*nside))?;
+    let layer = healpix::nested::get(healpix::depth(*nside));
+
+    let weights =
+        crate::scalar::nested::interpolation::interpolation_weights(lon, lat, layer, ellipsoid)?;
+
+    Ok(weights.map(|(hash, weight)| (layer.to_ring(hash), weight)))
+}
@@ -0,0 +1,25 @@
+use cdshealpix as healpix;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_hash};
+use crate::geodesic::Geodesic;
+use crate::scalar::nested::measures::{depth_area, polygon_perimeter};
+
+pub fn cell_area<R: ReferenceBody>(hash: &u64, nside: &u32, ellipsoid: &R) -> Result<f64> {
+    let depth = healpix::depth(*nside);
+    check_hash(*hash, depth)?;
+
+    Ok(depth_area(depth, ellipsoid))
+}
+
+pub fn cell_perimeter<R: ReferenceBody>(
+    hash: &u64,
+    nside: &u32,
+    ellipsoid: &R,
+    geodesic: &Geodesic,
+    step: &usize,
+) -> Result<f64> {
+    let vertices = crate::scalar::ring::coordinates::vertices(hash, nside, ellipsoid, step)?;
+
+    Ok(polygon_perimeter(&vertices, geodesic))
+}
@@ -0,0 +1,385 @@
+//! Positions of sets of cells in ring order.
+//!
+//! Sets of cells are stored as ranges of nested cell ids, which are not sorted in ring
+//! order. [`RingOrdering`] computes the position of ring cell ids within such a set and
+//! the ring cell ids found at given positions directly from the ranges, without
+//! enumerating and sorting the whole set.
+use std::ops::{ControlFlow, Range};
+
+/// Ring number of the southernmost vertex of each base cell, in units of `nside`.
+const JRLL: [i64; 12] = [2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4];
+/// Position of the southernmost vertex of each base cell in its ring, in units of `nside / 2`.
+const JPLL: [i64; 12] = [1, 3, 5, 7, 0, 2, 4, 6, 1, 3, 5, 7];
+
+/// Square block of `size * size` cells of a base cell, as found in nested ranges.
+#[derive(Debug, Clone, Copy)]
+struct Block {
+    face: usize,
+    x: i64,
+    y: i64,
+    size: i64,
+    first_ring: i64,
+    last_ring: i64,
+}
+
+/// Ring order of a set of cells at a fixed depth.
+#[derive(Debug, Clone)]
+pub struct RingOrdering {
+    nside: i64,
+    len: u64,
+    /// Blocks sorted by their first ring.
+    blocks: Vec<Block>,
+}
+
+impl RingOrdering {
+    /// Build the ring order of the cells covered by the given nested ranges.
+    ///
+    /// The ranges are expressed in nested cell ids at `depth` and must not overlap.
+    pub fn new(depth: u8, ranges: impl IntoIterator<Item = Range<u64>>) -> Self {
+        let nside = 1i64 << depth;
+        let face_size = 1u64 << (2 * depth);
+
+        let mut blocks: Vec<Block> = Vec::new();
+        let mut len = 0;
+        for range in ranges {
+            len += range.end - range.start;
+
+            let mut start = range.start;
+            while start < range.end {
+                let mut level = (start.trailing_zeros() / 2).min(depth as u32);
+                while start + (1 << (2 * level)) > range.end {
+                    level -= 1;
+                }
+
+                let face = (start / face_size) as usize;
+                let (x, y) = deinterleave(start % face_size);
+                let size = 1i64 << level;
+                let diagonal = JRLL[face] * nside - 1 - (x + y);
+                blocks.push(Block {
+                    face,
+                    x,
+                    y,
+                    size,
+                    first_ring: diagonal - 2 * (size - 1),
+                    last_ring: diagonal,
+                });
+
+                start += 1 << (2 * level);
+            }
+        }
+        blocks.sort_unstable_by_key(|block| block.first_ring);
+
+        Self { nside, len, blocks }
+    }
+
+    /// The number of cells in the set.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of cells of the set with a ring cell id smaller than `hash`.
+    pub fn rank(&self, hash: u64) -> u64 {
+        if hash >= self.n_hash() {
+            return self.len;
+        }
+        let (ring, jp) = self.locate(hash);
+
+        self.blocks
+            .iter()
+            .map(|block| {
+                let before = block.count_before(self.nside, ring);
+                let in_ring: u64 = self
+                    .segments(block, ring)
+                    .map(|(start, length)| (jp - start).clamp(0, length as i64) as u64)
+                    .sum();
+
+                before + in_ring
+            })
+            .sum()
+    }
+
+    /// Whether the cell with the ring cell id `hash` is part of the set.
+    pub fn contains(&self, hash: u64) -> bool {
+        hash < self.n_hash() && self.rank(hash + 1) > self.rank(hash)
+    }
+
+    /// The ring cell id found at `position`, if within the set.
+    pub fn select(&self, position: u64) -> Option<u64> {
+        if position >= self.len {
+            return None;
+        }
+
+        let (mut low, mut high) = (0, self.n_hash());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.rank(middle + 1) > position {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+
+        Some(low)
+    }
+
+    /// The ring cell ids found at the given positions, in ring order.
+    pub fn ring_ids(&self, positions: Range<u64>) -> Vec<u64> {
+        let end = positions.end.min(self.len);
+        let Some(first) = self
+            .select(positions.start)
+            .filter(|_| positions.start < end)
+        else {
+            return Vec::new();
+        };
+
+        let mut remaining = end - positions.start;
+        let mut ids: Vec<u64> = Vec::with_capacity(remaining as usize);
+        self.for_each_run(first, |run| {
+            let count = remaining.min(run.end - run.start);
+            ids.extend(run.start..run.start + count);
+            remaining -= count;
+
+            if remaining == 0 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+        ids
+    }
+
+    /// The ranges of consecutive ring cell ids covered by the set, in ring order.
+    pub fn runs(&self) -> Vec<Range<u64>> {
+        let mut runs: Vec<Range<u64>> = Vec::new();
+        self.for_each_run(0, |run| {
+            runs.push(run);
+
+            ControlFlow::Continue(())
+        });
+
+        runs
+    }
+
+    fn n_hash(&self) -> u64 {
+        12 * (self.nside * self.nside) as u64
+    }
+
+    /// The number of cells north of `ring`, the number of cells in `ring` divided by
+    /// 4, and the shift of the cell positions in `ring`.
+    fn ring_parameters(&self, ring: i64) -> (i64, i64, i64) {
+        let nside = self.nside;
+        if ring < nside {
+            (2 * ring * (ring - 1), ring, 0)
+        } else if ring > 3 * nside {
+            let nr = 4 * nside - ring;
+
+            (12 * nside * nside - 2 * nr * (nr + 1), nr, 0)
+        } else {
+            let n_cap = 2 * nside * (nside - 1);
+
+            (
+                n_cap + (ring - nside) * 4 * nside,
+                nside,
+                (ring - nside) & 1,
+            )
+        }
+    }
+
+    /// Ring number and 1-based position in the ring of a ring cell id.
+    fn locate(&self, hash: u64) -> (i64, i64) {
+        let nside = self.nside;
+        let n_cap = 2 * nside * (nside - 1);
+        let hash = hash as i64;
+        let n_hash = self.n_hash() as i64;
+
+        let ring = if hash < n_cap {
+            north_ring(hash)
+        } else if hash < n_hash - n_cap {
+            (hash - n_cap) / (4 * nside) + nside
+        } else {
+            4 * nside - north_ring(n_hash - 1 - hash)
+        };
+        let (n_before, _, _) = self.ring_parameters(ring);
+
+        (ring, hash - n_before + 1)
+    }
+
+    /// The cells of `block` in `ring`, as (1-based position, length) pairs.
+    fn segments(&self, block: &Block, ring: i64) -> impl Iterator<Item = (i64, u64)> {
+        let diagonal = JRLL[block.face] * self.nside - 1 - ring;
+        let low = block.x.max(diagonal - (block.y + block.size - 1));
+        let high = (block.x + block.size - 1).min(diagonal - block.y);
+
+        let (_, nr, kshift) = self.ring_parameters(ring);
+        let start = (JPLL[block.face] * nr + 2 * low - diagonal + 1 + kshift) / 2;
+        let end = start + (high - low + 1).max(0);
+
+        // positions wrap around the ring in the equatorial region
+        let n_ring = 4 * self.nside;
+        [
+            (start, end.min(1), n_ring),
+            (start.max(1), end.min(n_ring + 1), 0),
+            (start.max(n_ring + 1), end, -n_ring),
+        ]
+        .into_iter()
+        .filter(|(start, end, _)| start < end)
+        .map(|(start, end, shift)| (start + shift, (end - start) as u64))
+    }
+
+    /// Call `f` with the ranges of consecutive ring cell ids of the set, in ring
+    /// order, starting from `hash`.
+    fn for_each_run(&self, hash: u64, mut f: impl FnMut(Range<u64>) -> ControlFlow<()>) {
+        if self.is_empty() {
+            return;
+        }
+        let (mut ring, _) = self.locate(hash);
+
+        let mut next = self
+            .blocks
+            .partition_point(|block| block.first_ring <= ring);
+        let mut active: Vec<Block> = self.blocks[..next]
+            .iter()
+            .filter(|block| block.last_ring >= ring)
+            .copied()
+            .collect();
+
+        let mut current: Option<Range<u64>> = None;
+        while ring < 4 * self.nside {
+            if active.is_empty() {
+                let Some(block) = self.blocks.get(next) else {
+                    break;
+                };
+                ring = ring.max(block.first_ring);
+            }
+            while let Some(block) = self.blocks.get(next).filter(|b| b.first_ring <= ring) {
+                active.push(*block);
+                next += 1;
+            }
+
+            let (n_before, _, _) = self.ring_parameters(ring);
+            let mut segments: Vec<Range<u64>> = active
+                .iter()
+                .flat_map(|block| self.segments(block, ring))
+                .map(|(start, length)| {
+                    let start = (n_before + start - 1) as u64;
+
+                    start.max(hash)..start + length
+                })
+                .filter(|segment| !segment.is_empty())
+                .collect();
+            segments.sort_unstable_by_key(|segment| segment.start);
+
+            for segment in segments {
+                match current.as_mut() {
+                    Some(run) if run.end == segment.start => run.end = segment.end,
+                    _ => {
+                        if let Some(run) = current.replace(segment)
+                            && f(run).is_break()
+                        {
+                            return;
+                        }
+                    }
+                }
+            }
+
+            ring += 1;
+            active.retain(|block| block.last_ring >= ring);
+        }
+
+        if let Some(run) = current {
+            let _ = f(run);
+        }
+    }
+}
+
+impl Block {
+    /// The number of cells of the block north of `ring`.
+    fn count_before(&self, nside: i64, ring: i64) -> u64 {
+        // cells north of the ring are on diagonals farther from the southern vertex
+        let threshold = JRLL[self.face] * nside - 1 - ring - (self.x + self.y);
+        let n = self.size;
+
+        let up_to = if threshold < 0 {
+            0
+        } else if threshold >= 2 * n - 2 {
+            n * n
+        } else if threshold < n {
+            (threshold + 1) * (threshold + 2) / 2
+        } else {
+            n * n - (2 * n - 2 - threshold) * (2 * n - 1 - threshold) / 2
+        };
+
+        (n * n - up_to) as u64
+    }
+}
+
+/// Ring number of a cell id in the northern polar cap.
+fn north_ring(hash: i64) -> i64 {
+    let mut ring = (1 + ((1 + 2 * hash) as f64).sqrt() as i64) / 2;
+    while 2 * ring * (ring - 1) > hash {
+        ring -= 1;
+    }
+    while 2 * (ring + 1) * ring <= hash {
+        ring += 1;
+    }
+
+    ring
+}
+
+/// Split the position of a cell within its base cell into its `x` (even bits) and `y`
+/// (odd bits) coordinates.
+fn deinterleave(hash: u64) -> (i64, i64) {
+    let compress = |mut bits: u64| {
+        bits &= 0x5555_5555_5555_5555;
+        bits = (bits | (bits >> 1)) & 0x3333_3333_3333_3333;
+        bits = (bits | (bits >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+        bits = (bits | (bits >> 4)) & 0x00ff_00ff_00ff_00ff;
+        bits = (bits | (bits >> 8)) & 0x0000_ffff_0000_ffff;
+        bits = (bits | (bits >> 16)) & 0x0000_0000_ffff_ffff;
+
+        bits as i64
+    };
+
+    (compress(hash), compress(hash >> 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cdshealpix as healpix;
+
+    #[test]
+    fn test_ring_ordering() {
+        let depth = 3;
+        let layer = healpix::nested::get(depth);
+        let ranges = [0..1, 5..21, 64..128, 300..301, 512..700, 764..768];
+
+        let mut expected: Vec<u64> = ranges
+            .iter()
+            .flat_map(|range| range.clone())
+            .map(|hash| layer.to_ring(hash))
+            .collect();
+        expected.sort_unstable();
+
+        let ordering = RingOrdering::new(depth, ranges);
+        assert_eq!(ordering.len(), expected.len() as u64);
+        assert_eq!(ordering.ring_ids(0..ordering.len()), expected);
+        assert_eq!(ordering.ring_ids(10..20), expected[10..20]);
+
+        for (position, hash) in expected.iter().enumerate() {
+            assert_eq!(ordering.rank(*hash), position as u64);
+            assert_eq!(ordering.select(position as u64), Some(*hash));
+        }
+        let n_contained = (0..768).filter(|hash| ordering.contains(*hash)).count();
+        assert_eq!(n_contained, expected.len());
+
+        let flattened: Vec<u64> = ordering.runs().into_iter().flatten().collect();
+        assert_eq!(flattened, expected);
+    }
+}
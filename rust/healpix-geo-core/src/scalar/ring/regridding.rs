@@ -0,0 +1,26 @@
+use cdshealpix as healpix;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_depth};
+
+/// Areas of the overlaps between a source cell and the cells of the ring scheme.
+///
+/// See [`crate::scalar::nested::regridding::overlap_areas`]. The cells are not
+/// sorted.
+pub fn overlap_areas<R: ReferenceBody>(
+    vertices: &[(f64, f64)],
+    nside: &u32,
+    ellipsoid: &R,
+    refinement: u8,
+) -> Result<Vec<(u64, f64)>> {
+    check_depth(healpix::depth(*nside))?;
+    let layer = healpix::nested::get(healpix::depth(*nside));
+
+    let overlaps =
+        crate::scalar::nested::regridding::overlap_areas(vertices, layer, ellipsoid, refinement)?;
+
+    Ok(overlaps
+        .into_iter()
+        .map(|(hash, area)| (layer.to_ring(hash), area))
+        .collect())
+}
@@ -1,24 +1,39 @@
-use crate::ellipsoid::Ellipsoid;
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_zuniq};
 
 use cdshealpix as healpix;
 use cdshealpix::nested::Layer;
 
-pub fn healpix_to_lonlat(hash: &u64, ellipsoid: &Ellipsoid) -> (f64, f64) {
-    let (depth, hash_nested) = healpix::nested::from_zuniq(*hash);
+pub fn healpix_to_lonlat<R: ReferenceBody>(hash: &u64, ellipsoid: &R) -> Result<(f64, f64)> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
     let layer = healpix::nested::get(depth);
 
     crate::scalar::nested::coordinates::healpix_to_lonlat(&hash_nested, layer, ellipsoid)
 }
 
-pub fn lonlat_to_healpix(lon: &f64, lat: &f64, layer: &Layer, ellipsoid: &Ellipsoid) -> u64 {
+pub fn lonlat_to_healpix<R: ReferenceBody>(
+    lon: &f64,
+    lat: &f64,
+    layer: &Layer,
+    ellipsoid: &R,
+) -> Result<u64> {
     let hash_nested =
-        crate::scalar::nested::coordinates::lonlat_to_healpix(lon, lat, layer, ellipsoid);
+        crate::scalar::nested::coordinates::lonlat_to_healpix(lon, lat, layer, ellipsoid)?;
 
-    healpix::nested::to_zuniq(layer.depth(), hash_nested)
+    Ok(healpix::nested::to_zuniq(layer.depth(), hash_nested))
 }
 
-pub fn vertices(hash: &u64, ellipsoid: &Ellipsoid, step: &usize) -> Vec<(f64, f64)> {
-    let (depth, hash_nested) = healpix::nested::from_zuniq(*hash);
+/// Whether `hash` is a valid zuniq cell id.
+pub fn is_valid_cell(hash: &u64) -> bool {
+    check_zuniq(*hash).is_ok()
+}
+
+pub fn vertices<R: ReferenceBody>(
+    hash: &u64,
+    ellipsoid: &R,
+    step: &usize,
+) -> Result<Vec<(f64, f64)>> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
     let layer = healpix::nested::get(depth);
 
     crate::scalar::nested::coordinates::vertices(&hash_nested, layer, ellipsoid, step)
@@ -1,17 +1,19 @@
-use crate::ellipsoid::Ellipsoid;
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_zuniq};
+use crate::geodesic::Geodesic;
 use cdshealpix as healpix;
 use cdshealpix::nested::Layer;
 
-pub fn box_coverage(
+pub fn box_coverage<R: ReferenceBody>(
     center: (f64, f64),
     size: (f64, f64),
     angle: f64,
     layer: &Layer,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     flat: bool,
-) -> (Vec<u64>, Vec<bool>) {
+) -> Result<(Vec<u64>, Vec<bool>)> {
     let (ipix_nested, depths, fully_covered) =
-        crate::scalar::nested::coverage::box_coverage(center, size, angle, layer, ellipsoid, flat);
+        crate::scalar::nested::coverage::box_coverage(center, size, angle, layer, ellipsoid, flat)?;
 
     let ipix: Vec<u64> = ipix_nested
         .into_iter()
@@ -19,17 +21,17 @@ pub fn box_coverage(
         .map(|(h, d)| healpix::nested::to_zuniq(d, h))
         .collect();
 
-    (ipix, fully_covered)
+    Ok((ipix, fully_covered))
 }
 
-pub fn zone_coverage(
+pub fn zone_coverage<R: ReferenceBody>(
     bbox: (f64, f64, f64, f64),
     layer: &Layer,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     flat: bool,
-) -> (Vec<u64>, Vec<bool>) {
+) -> Result<(Vec<u64>, Vec<bool>)> {
     let (ipix_nested, depths, fully_covered) =
-        crate::scalar::nested::coverage::zone_coverage(bbox, layer, ellipsoid, flat);
+        crate::scalar::nested::coverage::zone_coverage(bbox, layer, ellipsoid, flat)?;
 
     let ipix: Vec<u64> = ipix_nested
         .into_iter()
@@ -37,18 +39,18 @@ pub fn zone_coverage(
         .map(|(h, d)| healpix::nested::to_zuniq(d, h))
         .collect();
 
-    (ipix, fully_covered)
+    Ok((ipix, fully_covered))
 }
 
-pub fn polygon_coverage(
+pub fn polygon_coverage<R: ReferenceBody>(
     vertices: &[(f64, f64)],
     layer: &Layer,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     exact: bool,
     flat: bool,
-) -> (Vec<u64>, Vec<bool>) {
+) -> Result<(Vec<u64>, Vec<bool>)> {
     let (ipix_nested, depths, fully_covered) =
-        crate::scalar::nested::coverage::polygon_coverage(vertices, layer, ellipsoid, exact, flat);
+        crate::scalar::nested::coverage::polygon_coverage(vertices, layer, ellipsoid, exact, flat)?;
 
     let ipix: Vec<u64> = ipix_nested
         .into_iter()
@@ -56,17 +58,17 @@ pub fn polygon_coverage(
         .map(|(h, d)| healpix::nested::to_zuniq(d, h))
         .collect();
 
-    (ipix, fully_covered)
+    Ok((ipix, fully_covered))
 }
 
-pub fn cone_coverage(
+pub fn cone_coverage<R: ReferenceBody>(
     center: (f64, f64),
     radius: f64,
     layer: &Layer,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     delta_depth: u8,
     flat: bool,
-) -> (Vec<u64>, Vec<bool>) {
+) -> Result<(Vec<u64>, Vec<bool>)> {
     let (ipix_nested, depths, fully_covered) = crate::scalar::nested::coverage::cone_coverage(
         center,
         radius,
@@ -74,7 +76,7 @@ pub fn cone_coverage(
         ellipsoid,
         delta_depth,
         flat,
-    );
+    )?;
 
     let ipix: Vec<u64> = ipix_nested
         .into_iter()
@@ -82,18 +84,18 @@ pub fn cone_coverage(
         .map(|(h, d)| healpix::nested::to_zuniq(d, h))
         .collect();
 
-    (ipix, fully_covered)
+    Ok((ipix, fully_covered))
 }
 
-pub fn elliptical_cone_coverage(
+pub fn elliptical_cone_coverage<R: ReferenceBody>(
     center: (f64, f64),
     ellipse_geometry: (f64, f64),
     position_angle: f64,
     layer: &Layer,
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     delta_depth: u8,
     flat: bool,
-) -> (Vec<u64>, Vec<bool>) {
+) -> Result<(Vec<u64>, Vec<bool>)> {
     let (ipix_nested, depths, fully_covered) =
         crate::scalar::nested::coverage::elliptical_cone_coverage(
             center,
@@ -103,7 +105,7 @@ pub fn elliptical_cone_coverage(
             ellipsoid,
             delta_depth,
             flat,
-        );
+        )?;
 
     let ipix: Vec<u64> = ipix_nested
         .into_iter()
@@ -111,5 +113,47 @@ pub fn elliptical_cone_coverage(
         .map(|(h, d)| healpix::nested::to_zuniq(d, h))
         .collect();
 
-    (ipix, fully_covered)
+    Ok((ipix, fully_covered))
+}
+
+/// The cells whose center is within `radius` of the center of a cell, with their
+/// distance, sorted by distance.
+pub fn neighbours_within_distance<R: ReferenceBody>(
+    hash: &u64,
+    radius: f64,
+    ellipsoid: &R,
+    geodesic: &Geodesic,
+) -> Result<Vec<(u64, f64)>> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
+    let layer = healpix::nested::get(depth);
+
+    let neighbours = crate::scalar::nested::coverage::neighbours_within_distance(
+        &hash_nested,
+        layer,
+        radius,
+        ellipsoid,
+        geodesic,
+    )?;
+
+    Ok(neighbours
+        .into_iter()
+        .map(|(h, distance)| (healpix::nested::to_zuniq(depth, h), distance))
+        .collect())
+}
+
+pub fn linestring_coverage<R: ReferenceBody>(
+    vertices: &[(f64, f64)],
+    layer: &Layer,
+    ellipsoid: &R,
+    rhumb: bool,
+) -> Result<Vec<u64>> {
+    let depth = layer.depth();
+
+    let ipix: Vec<u64> =
+        crate::scalar::nested::coverage::linestring_coverage(vertices, layer, ellipsoid, rhumb)?
+            .into_iter()
+            .map(|h| healpix::nested::to_zuniq(depth, h))
+            .collect();
+
+    Ok(ipix)
 }
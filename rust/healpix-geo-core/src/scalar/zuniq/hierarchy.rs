@@ -1,7 +1,12 @@
 use cdshealpix as healpix;
 
-pub fn kth_neighbourhood(hash: &u64, ring: &u32) -> Vec<i64> {
-    let (depth, hash_nested) = healpix::nested::from_zuniq(*hash);
+use crate::error::{Error, Result, check_depth, check_zuniq};
+use crate::scalar::nested::hierarchy::check_ring;
+
+pub fn kth_neighbourhood(hash: &u64, ring: &u32) -> Result<Vec<i64>> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
+    check_ring(*ring, healpix::nside(depth))?;
+
     let layer = healpix::nested::get(depth);
 
     let mut neighbours: Vec<i64> = layer
@@ -22,5 +27,119 @@ pub fn kth_neighbourhood(hash: &u64, ring: &u32) -> Vec<i64> {
         neighbours.resize(expected_size, -1);
     }
 
-    neighbours
+    Ok(neighbours)
+}
+
+/// The 8 neighbours of a cell, in the order of
+/// [`crate::scalar::nested::hierarchy::NEIGHBOUR_DIRECTIONS`].
+///
+/// Missing neighbours are set to `-1`.
+pub fn neighbours(hash: &u64) -> Result<[i64; 8]> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
+
+    let layer = healpix::nested::get(depth);
+    let neighbours = crate::scalar::nested::hierarchy::neighbours(&hash_nested, layer)?;
+
+    Ok(neighbours.map(|v| {
+        if v == -1 {
+            v
+        } else {
+            healpix::nested::to_zuniq(depth, v as u64) as i64
+        }
+    }))
+}
+
+/// The parent of a cell at `new_depth`, which must not be finer than the depth of the cell.
+pub fn parent(hash: &u64, new_depth: &u8) -> Result<u64> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
+    if *new_depth > depth {
+        return Err(Error::DepthOutOfRange {
+            depth: *new_depth,
+            max_depth: depth,
+        });
+    }
+
+    let parent = healpix::nested::parent(hash_nested, depth - new_depth);
+
+    Ok(healpix::nested::to_zuniq(*new_depth, parent))
+}
+
+/// The children of a cell at `new_depth`, which must not be coarser than the depth of the cell.
+pub fn children(hash: &u64, new_depth: &u8) -> Result<Vec<u64>> {
+    check_depth(*new_depth)?;
+
+    let (depth, hash_nested) = check_zuniq(*hash)?;
+    if depth > *new_depth {
+        return Err(Error::DepthOutOfRange {
+            depth,
+            max_depth: *new_depth,
+        });
+    }
+
+    Ok(healpix::nested::children(hash_nested, new_depth - depth)
+        .map(|child| healpix::nested::to_zuniq(*new_depth, child))
+        .collect())
+}
+
+/// The cells sharing the parent of a cell, including the cell itself.
+///
+/// At depth 0, these are the 12 base cells.
+pub fn siblings(hash: &u64) -> Result<Vec<u64>> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
+
+    Ok(healpix::nested::siblings(depth, hash_nested)
+        .map(|sibling| healpix::nested::to_zuniq(depth, sibling))
+        .collect())
+}
+
+/// The parent or the children of a cell at `new_depth`, depending on the depth of the cell.
+///
+/// Cells coarser than `new_depth` are replaced by their children, all others by
+/// their parent (or themselves).
+pub fn zoom_to(hash: &u64, new_depth: &u8) -> Result<Vec<u64>> {
+    let (depth, _) = check_zuniq(*hash)?;
+
+    if depth < *new_depth {
+        children(hash, new_depth)
+    } else {
+        parent(hash, new_depth).map(|parent| vec![parent])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_to_roundtrip() {
+        let hash = healpix::nested::to_zuniq(3, 125);
+
+        let children = zoom_to(&hash, &5).unwrap();
+        assert_eq!(children.len(), 16);
+        assert_eq!(children[0], healpix::nested::to_zuniq(5, 125 << 4));
+
+        for child in children {
+            assert_eq!(zoom_to(&child, &3).unwrap(), vec![hash]);
+        }
+
+        assert_eq!(zoom_to(&hash, &3).unwrap(), vec![hash]);
+        assert_eq!(
+            zoom_to(&hash, &1).unwrap(),
+            vec![healpix::nested::to_zuniq(1, 125 >> 4)]
+        );
+    }
+
+    #[test]
+    fn test_parent_invalid_depth() {
+        let hash = healpix::nested::to_zuniq(3, 125);
+
+        assert_eq!(
+            parent(&hash, &4),
+            Err(Error::DepthOutOfRange {
+                depth: 4,
+                max_depth: 3
+            })
+        );
+        assert!(children(&hash, &30).is_err());
+    }
 }
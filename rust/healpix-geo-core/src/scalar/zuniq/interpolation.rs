@@ -0,0 +1,20 @@
+use cdshealpix as healpix;
+use cdshealpix::nested::Layer;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::Result;
+
+/// The 4 cells surrounding a point, with their bilinear interpolation weights.
+///
+/// See [`crate::scalar::nested::interpolation::interpolation_weights`].
+pub fn interpolation_weights<R: ReferenceBody>(
+    lon: &f64,
+    lat: &f64,
+    layer: &Layer,
+    ellipsoid: &R,
+) -> Result<[(u64, f64); 4]> {
+    let weights =
+        crate::scalar::nested::interpolation::interpolation_weights(lon, lat, layer, ellipsoid)?;
+
+    Ok(weights.map(|(hash, weight)| (healpix::nested::to_zuniq(layer.depth(), hash), weight)))
+}
@@ -0,0 +1,24 @@
+use cdshealpix as healpix;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_zuniq};
+use crate::geodesic::Geodesic;
+
+pub fn cell_area<R: ReferenceBody>(hash: &u64, ellipsoid: &R) -> Result<f64> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
+    let layer = healpix::nested::get(depth);
+
+    crate::scalar::nested::measures::cell_area(&hash_nested, layer, ellipsoid)
+}
+
+pub fn cell_perimeter<R: ReferenceBody>(
+    hash: &u64,
+    ellipsoid: &R,
+    geodesic: &Geodesic,
+    step: &usize,
+) -> Result<f64> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
+    let layer = healpix::nested::get(depth);
+
+    crate::scalar::nested::measures::cell_perimeter(&hash_nested, layer, ellipsoid, geodesic, step)
+}
@@ -0,0 +1,23 @@
+use cdshealpix as healpix;
+use cdshealpix::nested::Layer;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::Result;
+
+/// Areas of the overlaps between a source cell and the cells of `layer`.
+///
+/// See [`crate::scalar::nested::regridding::overlap_areas`].
+pub fn overlap_areas<R: ReferenceBody>(
+    vertices: &[(f64, f64)],
+    layer: &Layer,
+    ellipsoid: &R,
+    refinement: u8,
+) -> Result<Vec<(u64, f64)>> {
+    let overlaps =
+        crate::scalar::nested::regridding::overlap_areas(vertices, layer, ellipsoid, refinement)?;
+
+    Ok(overlaps
+        .into_iter()
+        .map(|(hash, area)| (healpix::nested::to_zuniq(layer.depth(), hash), area))
+        .collect())
+}
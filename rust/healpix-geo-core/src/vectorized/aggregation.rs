@@ -0,0 +1,112 @@
+//! Aggregation of cell values between depths.
+
+/// The reduction applied to the values of cells sharing a parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reduction {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// The number of cells.
+    Count,
+    /// The mean weighted by the area of the cells.
+    ///
+    /// Cells of the same depth have the same area, so this only differs from
+    /// [`Reduction::Mean`] for cells of different depths.
+    AreaWeightedMean,
+}
+
+impl Reduction {
+    /// Reduce pairs of values and cell areas.
+    fn reduce(self, values: impl Iterator<Item = (f64, f64)>) -> f64 {
+        let (count, sum, weighted, area, min, max) = values.fold(
+            (0usize, 0.0, 0.0, 0.0, f64::INFINITY, f64::NEG_INFINITY),
+            |(count, sum, weighted, total, min, max), (value, area)| {
+                (
+                    count + 1,
+                    sum + value,
+                    weighted + value * area,
+                    total + area,
+                    min.min(value),
+                    max.max(value),
+                )
+            },
+        );
+
+        match self {
+            Self::Sum => sum,
+            Self::Mean => sum / count as f64,
+            Self::Min => min,
+            Self::Max => max,
+            Self::Count => count as f64,
+            Self::AreaWeightedMean => weighted / area,
+        }
+    }
+}
+
+/// Reduce the values of the cells sharing a parent.
+///
+/// `parents` holds the parent of each cell and `areas` their relative area, with
+/// `None` for cells of the same area. Returns the sorted parents with their reduced
+/// value.
+pub(crate) fn reduce_by_parent(
+    parents: &[u64],
+    values: &[f64],
+    areas: Option<&[f64]>,
+    reduction: Reduction,
+) -> (Vec<u64>, Vec<f64>) {
+    let mut sorted: Vec<usize> = (0..parents.len()).collect();
+    sorted.sort_unstable_by_key(|index| parents[*index]);
+
+    sorted
+        .chunk_by(|a, b| parents[*a] == parents[*b])
+        .map(|group| {
+            let cells = group
+                .iter()
+                .map(|index| (values[*index], areas.map_or(1.0, |areas| areas[*index])));
+
+            (parents[group[0]], reduction.reduce(cells))
+        })
+        .unzip()
+}
+
+/// Repeat the value of each cell for each of its children.
+pub(crate) fn broadcast(children: Vec<Vec<u64>>, values: &[f64]) -> (Vec<u64>, Vec<f64>) {
+    children
+        .into_iter()
+        .zip(values)
+        .flat_map(|(children, value)| children.into_iter().map(move |child| (child, *value)))
+        .unzip()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reduce_by_parent() {
+        let parents = [7, 3, 7, 7];
+        let values = [1.0, 2.0, 3.0, 8.0];
+        let areas = [1.0, 1.0, 1.0, 2.0];
+
+        let reduce = |reduction| reduce_by_parent(&parents, &values, None, reduction);
+        assert_eq!(reduce(Reduction::Sum), (vec![3, 7], vec![2.0, 12.0]));
+        assert_eq!(reduce(Reduction::Mean), (vec![3, 7], vec![2.0, 4.0]));
+        assert_eq!(reduce(Reduction::Min), (vec![3, 7], vec![2.0, 1.0]));
+        assert_eq!(reduce(Reduction::Max), (vec![3, 7], vec![2.0, 8.0]));
+        assert_eq!(reduce(Reduction::Count), (vec![3, 7], vec![1.0, 3.0]));
+
+        let actual = reduce_by_parent(&parents, &values, Some(&areas), Reduction::AreaWeightedMean);
+        assert_eq!(actual, (vec![3, 7], vec![2.0, 5.0]));
+    }
+
+    #[test]
+    fn test_broadcast() {
+        let children = vec![vec![4, 5], vec![], vec![8]];
+
+        assert_eq!(
+            broadcast(children, &[1.0, 2.0, 3.0]),
+            (vec![4, 5, 8], vec![1.0, 1.0, 3.0])
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Conversions between geographic and body-centered cartesian coordinates.
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_length};
+use crate::maybe_parallelize_zip;
+
+/// Convert geographic coordinates in degrees to cartesian coordinates at `height`
+/// above the reference body.
+pub fn geodetic_to_cartesian<R: ReferenceBody>(
+    lon: &[f64],
+    lat: &[f64],
+    height: f64,
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    check_length(lon.len(), lat.len())?;
+
+    let mut x = vec![0.0; lon.len()];
+    let mut y = vec![0.0; lon.len()];
+    let mut z = vec![0.0; lon.len()];
+
+    maybe_parallelize_zip!(nthreads, (lon, lat, &mut x, &mut y, &mut z), |(
+        lon,
+        lat,
+        x,
+        y,
+        z,
+    )| {
+        let (lon, lat) = (lon.to_radians(), lat.to_radians());
+        (*x, *y, *z) = ellipsoid.geodetic_to_cartesian(lon, lat, height);
+
+        Ok(())
+    })?;
+
+    Ok((x, y, z))
+}
+
+/// Convert cartesian coordinates to geographic coordinates in degrees and the
+/// height above the reference body.
+pub fn cartesian_to_geodetic<R: ReferenceBody>(
+    x: &[f64],
+    y: &[f64],
+    z: &[f64],
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    check_length(x.len(), y.len())?;
+    check_length(x.len(), z.len())?;
+
+    let mut lon = vec![0.0; x.len()];
+    let mut lat = vec![0.0; x.len()];
+    let mut height = vec![0.0; x.len()];
+
+    maybe_parallelize_zip!(
+        nthreads,
+        (x, y, z, &mut lon, &mut lat, &mut height),
+        |(x, y, z, lon, lat, height)| {
+            let (lon_, lat_, height_) = ellipsoid.cartesian_to_geodetic(*x, *y, *z);
+            (*lon, *lat, *height) = (lon_.to_degrees(), lat_.to_degrees(), height_);
+
+            Ok(())
+        }
+    )?;
+
+    Ok((lon, lat, height))
+}
+
+/// Convert the vertices of cells to cartesian coordinates at `height` above the
+/// reference body.
+pub(crate) fn vertices_to_cartesian<R: ReferenceBody>(
+    vertices: Vec<Vec<(f64, f64)>>,
+    height: f64,
+    ellipsoid: &R,
+) -> Vec<Vec<(f64, f64, f64)>> {
+    vertices
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|(lon, lat)| {
+                    ellipsoid.geodetic_to_cartesian(lon.to_radians(), lat.to_radians(), height)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ellipsoid::Ellipsoid;
+
+    #[test]
+    fn test_roundtrip() {
+        let ellipsoid = Ellipsoid::named("GRS80").unwrap();
+        let lon = [-170.0, 0.0, 45.0, 120.0];
+        let lat = [-90.0, 0.0, 30.0, 89.5];
+
+        let (x, y, z) = geodetic_to_cartesian(&lon, &lat, 250.0, &ellipsoid, 1).unwrap();
+        assert_eq!(
+            (x[1], y[1], z[1]),
+            (ellipsoid.semimajor_axis() + 250.0, 0.0, 0.0)
+        );
+
+        let (actual_lon, actual_lat, height) =
+            cartesian_to_geodetic(&x, &y, &z, &ellipsoid, 1).unwrap();
+        for (actual, expected) in actual_lat.iter().zip(lat) {
+            assert!((actual - expected).abs() < 1e-9);
+        }
+        for (actual, expected) in actual_lon[1..].iter().zip(&lon[1..]) {
+            assert!((actual - expected).abs() < 1e-9);
+        }
+        for height in height {
+            assert!((height - 250.0).abs() < 1e-6);
+        }
+
+        assert!(cartesian_to_geodetic(&x, &y[1..], &z, &ellipsoid, 1).is_err());
+    }
+}
@@ -1,5 +1,4 @@
-pub enum DepthLike {
-    // TODO: figure out how to avoid copying the data
+pub enum DepthLike<'a> {
     Scalar(u8),
-    Array(Vec<u8>),
+    Array(&'a [u8]),
 }
@@ -0,0 +1,46 @@
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_chunks};
+use crate::geodesic::{Geodesic, reverse_azimuth};
+use crate::maybe_parallelize;
+
+/// Distance, forward azimuth and back azimuth of the geodesic between two points.
+///
+/// The back azimuth is the azimuth at the second point towards the first one.
+pub(crate) fn geodesic_inverse(
+    geodesic: &Geodesic,
+    from: (f64, f64),
+    to: (f64, f64),
+) -> (f64, f64, f64) {
+    let (distance, azimuth1, azimuth2) = geodesic.inverse(from.0, from.1, to.0, to.1);
+
+    (distance, azimuth1, reverse_azimuth(azimuth2))
+}
+
+/// Geodesic distances between points.
+///
+/// Every point of `from` is paired with a chunk of `chunks` points of `to`.
+#[allow(clippy::type_complexity)]
+pub fn geodesic_distances<R: ReferenceBody>(
+    from: &[(f64, f64)],
+    to: &[(f64, f64)],
+    chunks: usize,
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<Vec<Vec<(f64, f64, f64)>>> {
+    check_chunks(from.len(), chunks, to.len())?;
+
+    let geodesic = ellipsoid.geodesic();
+    let data: Vec<(&(f64, f64), &[(f64, f64)])> = from.iter().zip(to.chunks(chunks)).collect();
+
+    let mut result = Vec::<Vec<(f64, f64, f64)>>::with_capacity(from.len());
+    maybe_parallelize!(nthreads, data, result, |(from_, to_)| {
+        to_.iter()
+            .map(|point| geodesic_inverse(&geodesic, **from_, *point))
+            .collect::<Vec<_>>()
+    });
+
+    Ok(result)
+}
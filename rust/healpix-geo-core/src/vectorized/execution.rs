@@ -1,13 +1,130 @@
+//! Thread pools used by the vectorized functions.
+//!
+//! Building a rayon thread pool spawns its threads, which is expensive compared to
+//! the work done on small inputs. Pools are therefore reused by subsequent calls
+//! with the same number of threads. Only the most recently used pools are kept, so
+//! that calls with many different numbers of threads do not accumulate idle threads.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+static NUM_THREADS: AtomicUsize = AtomicUsize::new(0);
+static THREAD_POOL: RwLock<Option<Arc<ThreadPool>>> = RwLock::new(None);
+static POOLS: Mutex<PoolCache> = Mutex::new(PoolCache::new(MAX_CACHED_POOLS));
+
+/// The maximum number of cached pools.
+const MAX_CACHED_POOLS: usize = 4;
+
+/// Set the number of threads used by calls with `nthreads = 0`.
+///
+/// `0` restores the default, the global rayon thread pool.
+pub fn set_num_threads(nthreads: usize) {
+    NUM_THREADS.store(nthreads, Ordering::Relaxed);
+}
+
+/// The number of threads used by calls with `nthreads = 0`, or `0` for the default.
+pub fn num_threads() -> usize {
+    NUM_THREADS.load(Ordering::Relaxed)
+}
+
+/// Use the given thread pool for calls with `nthreads = 0`.
+///
+/// Takes precedence over [`set_num_threads`]. `None` removes the pool.
+pub fn set_thread_pool(pool: Option<Arc<ThreadPool>>) {
+    *THREAD_POOL.write().unwrap_or_else(PoisonError::into_inner) = pool;
+}
+
+/// Thread pools keyed by their number of threads, keeping the most recently used.
+#[derive(Debug)]
+struct PoolCache {
+    capacity: usize,
+    /// Pools with their number of threads, from least to most recently used.
+    pools: Vec<(usize, Arc<ThreadPool>)>,
+}
+
+impl PoolCache {
+    const fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            pools: Vec::new(),
+        }
+    }
+
+    /// The pool with `nthreads` threads, built if not cached.
+    ///
+    /// Building a pool evicts the least recently used one once `capacity` pools are
+    /// cached. Returns `None` if the pool cannot be built.
+    fn get(&mut self, nthreads: usize) -> Option<Arc<ThreadPool>> {
+        if let Some(index) = self.pools.iter().position(|(n, _)| *n == nthreads) {
+            let entry = self.pools.remove(index);
+            let pool = entry.1.clone();
+            self.pools.push(entry);
+
+            return Some(pool);
+        }
+
+        let pool = Arc::new(
+            ThreadPoolBuilder::new()
+                .num_threads(nthreads)
+                .build()
+                .ok()?,
+        );
+        if self.pools.len() == self.capacity {
+            self.pools.remove(0);
+        }
+        self.pools.push((nthreads, pool.clone()));
+
+        Some(pool)
+    }
+}
+
+/// The pool with `nthreads` threads from the process-wide cache.
+fn cached_pool(nthreads: usize) -> Option<Arc<ThreadPool>> {
+    POOLS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(nthreads)
+}
+
+fn select_pool(nthreads: usize) -> Option<Arc<ThreadPool>> {
+    if nthreads != 0 {
+        return cached_pool(nthreads);
+    }
+
+    let configured = THREAD_POOL
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+
+    configured.or_else(|| match num_threads() {
+        0 => None,
+        nthreads => cached_pool(nthreads),
+    })
+}
+
+/// Run `op` in the thread pool selected by `nthreads`.
+///
+/// `nthreads = 0` selects the pool configured with [`set_thread_pool`] or
+/// [`set_num_threads`]. Without configuration, or if the pool cannot be built,
+/// `op` runs in the global rayon thread pool.
+pub fn install<OP, R>(nthreads: usize, op: OP) -> R
+where
+    OP: FnOnce() -> R + Send,
+    R: Send,
+{
+    match select_pool(nthreads) {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
 #[macro_export]
 macro_rules! maybe_parallelize {
     ($nthreads:ident, $iterable:expr, $buffer:ident, $func:expr $(,)?) => {
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let pool = rayon::ThreadPoolBuilder::new()
-                .num_threads($nthreads as usize)
-                .build()
-                .unwrap();
-            pool.install(|| {
+            $crate::vectorized::execution::install($nthreads as usize, || {
                 $iterable
                     .par_iter()
                     .map($func)
@@ -22,3 +139,74 @@ macro_rules! maybe_parallelize {
         $buffer.shrink_to_fit();
     };
 }
+
+/// Apply a fallible function to the zipped elements of several slices.
+///
+/// Mutable slices can be used to write the results into caller-provided buffers.
+#[macro_export]
+macro_rules! maybe_parallelize_zip {
+    ($nthreads:ident, ($($slice:expr),+ $(,)?), $func:expr $(,)?) => {{
+        #[cfg(not(target_arch = "wasm32"))]
+        let result = $crate::vectorized::execution::install($nthreads as usize, || {
+            ($($slice,)+).into_par_iter().try_for_each($func)
+        });
+        #[cfg(target_arch = "wasm32")]
+        let result = {
+            let _ = &$nthreads; // no-op
+            itertools::multizip(($(IntoIterator::into_iter($slice),)+)).try_for_each($func)
+        };
+
+        result
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_cache() {
+        let mut cache = PoolCache::new(2);
+        let pool = cache.get(3).unwrap();
+
+        assert_eq!(pool.current_num_threads(), 3);
+        assert!(Arc::ptr_eq(&pool, &cache.get(3).unwrap()));
+    }
+
+    #[test]
+    fn test_pool_cache_eviction() {
+        let mut cache = PoolCache::new(2);
+        let first = cache.get(1).unwrap();
+        let second = cache.get(2).unwrap();
+
+        // using the first pool makes the second the least recently used
+        assert!(Arc::ptr_eq(&first, &cache.get(1).unwrap()));
+        cache.get(3).unwrap();
+
+        assert_eq!(cache.pools.len(), 2);
+        assert!(Arc::ptr_eq(&first, &cache.get(1).unwrap()));
+        assert!(!Arc::ptr_eq(&second, &cache.get(2).unwrap()));
+    }
+
+    // the only test changing the process-wide settings, which would race otherwise
+    #[test]
+    fn test_thread_pool_precedence() {
+        let pool = Arc::new(ThreadPoolBuilder::new().num_threads(2).build().unwrap());
+
+        assert_eq!(install(5, rayon::current_num_threads), 5);
+
+        set_num_threads(4);
+        assert_eq!(install(0, rayon::current_num_threads), 4);
+
+        set_thread_pool(Some(pool));
+        assert_eq!(install(0, rayon::current_num_threads), 2);
+        assert_eq!(install(5, rayon::current_num_threads), 5);
+
+        set_thread_pool(None);
+        set_num_threads(0);
+        assert_eq!(
+            install(0, rayon::current_num_threads),
+            rayon::current_num_threads()
+        );
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+use crate::error::{Result, check_length};
+use crate::maybe_parallelize_zip;
+
+/// Interpolate a field at the given points.
+///
+/// `values` holds the field at the cells `cell_ids`, and `weights` gives the cells
+/// surrounding a point with their weights. Cells missing from `cell_ids` are skipped
+/// and the weights of the remaining cells renormalized, so points without any known
+/// surrounding cell are NaN.
+pub(crate) fn interpolate<F>(
+    values: &[f64],
+    cell_ids: &[u64],
+    lon: &[f64],
+    lat: &[f64],
+    weights: F,
+    nthreads: usize,
+) -> Result<Vec<f64>>
+where
+    F: Fn(&f64, &f64) -> Result<[(u64, f64); 4]> + Sync,
+{
+    check_length(cell_ids.len(), values.len())?;
+    check_length(lon.len(), lat.len())?;
+
+    let lookup = cell_lookup(values, cell_ids);
+
+    let mut result = vec![0.0; lon.len()];
+    let out = result.as_mut_slice();
+    maybe_parallelize_zip!(nthreads, (lon, lat, out), |(lon, lat, value)| {
+        *value = weighted_mean(weights(lon, lat)?, &lookup);
+
+        Ok(())
+    })?;
+
+    Ok(result)
+}
+
+/// Look up the value of a cell, or `None` if the cell is not in `cell_ids`.
+pub(crate) fn cell_lookup<'a>(
+    values: &'a [f64],
+    cell_ids: &'a [u64],
+) -> impl Fn(u64) -> Option<f64> + Sync + 'a {
+    let mut sorted: Vec<usize> = (0..cell_ids.len()).collect();
+    sorted.sort_unstable_by_key(|index| cell_ids[*index]);
+
+    move |hash| {
+        sorted
+            .binary_search_by_key(&hash, |index| cell_ids[*index])
+            .ok()
+            .map(|position| values[sorted[position]])
+    }
+}
+
+/// Mean of the known values weighted by `weights`, or NaN if none are known.
+fn weighted_mean(weights: [(u64, f64); 4], lookup: impl Fn(u64) -> Option<f64>) -> f64 {
+    let (total, sum) = weights
+        .into_iter()
+        .filter_map(|(hash, weight)| lookup(hash).map(|value| (value * weight, weight)))
+        .fold((0.0, 0.0), |(total, sum), (value, weight)| {
+            (total + value, sum + weight)
+        });
+
+    if sum > 0.0 { total / sum } else { f64::NAN }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolate_renormalizes_missing_cells() {
+        let weights = |lon: &f64, _: &f64| -> Result<[(u64, f64); 4]> {
+            Ok([(0, *lon), (1, 1.0 - *lon), (2, 0.0), (3, 0.0)])
+        };
+
+        let actual = interpolate(
+            &[10.0, 20.0],
+            &[1, 0],
+            &[0.25, 1.0, 0.0],
+            &[0.0, 0.0, 0.0],
+            weights,
+            1,
+        )
+        .unwrap();
+
+        assert_eq!(actual[0], 0.25 * 20.0 + 0.75 * 10.0);
+        assert_eq!(actual[1], 20.0);
+
+        let actual = interpolate(&[10.0], &[1], &[1.0], &[0.0], weights, 1).unwrap();
+        assert!(actual[0].is_nan());
+    }
+}
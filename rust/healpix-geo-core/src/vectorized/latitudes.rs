@@ -0,0 +1,143 @@
+//! Conversions between the latitudes of the reference ellipsoid.
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+use crate::ellipsoid::{LatitudeKind, ReferenceBody};
+use crate::maybe_parallelize;
+
+/// Convert geographic latitudes to authalic latitudes, in degrees.
+pub fn geographic_to_authalic<R: ReferenceBody>(
+    latitudes: &[f64],
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Vec<f64> {
+    let mut result = Vec::<f64>::with_capacity(latitudes.len());
+    maybe_parallelize!(nthreads, latitudes, result, |latitude| {
+        ellipsoid
+            .latitude_geographic_to_authalic(latitude.to_radians())
+            .to_degrees()
+    });
+
+    result
+}
+
+/// Convert authalic latitudes to geographic latitudes, in degrees.
+pub fn authalic_to_geographic<R: ReferenceBody>(
+    latitudes: &[f64],
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Vec<f64> {
+    let mut result = Vec::<f64>::with_capacity(latitudes.len());
+    maybe_parallelize!(nthreads, latitudes, result, |latitude| {
+        ellipsoid
+            .latitude_authalic_to_geographic(latitude.to_radians())
+            .to_degrees()
+    });
+
+    result
+}
+
+/// Convert latitudes between two kinds, in degrees.
+pub fn convert<R: ReferenceBody>(
+    latitudes: &[f64],
+    ellipsoid: &R,
+    from: LatitudeKind,
+    to: LatitudeKind,
+    nthreads: usize,
+) -> Vec<f64> {
+    let mut result = Vec::<f64>::with_capacity(latitudes.len());
+    maybe_parallelize!(nthreads, latitudes, result, |latitude| {
+        ellipsoid
+            .convert_latitude(latitude.to_radians(), from, to)
+            .to_degrees()
+    });
+
+    result
+}
+
+/// Convert a geographic latitude to the given kind, in degrees.
+///
+/// Geographic latitudes are returned unchanged, without a round trip through
+/// radians.
+pub(crate) fn geographic_to_kind<R: ReferenceBody>(
+    latitude: f64,
+    ellipsoid: &R,
+    kind: LatitudeKind,
+) -> f64 {
+    match kind {
+        LatitudeKind::Geographic => latitude,
+        _ => ellipsoid
+            .latitude_geographic_to(latitude.to_radians(), kind)
+            .to_degrees(),
+    }
+}
+
+/// Convert a latitude of the given kind to a geographic latitude, in degrees.
+pub(crate) fn kind_to_geographic<R: ReferenceBody>(
+    latitude: f64,
+    ellipsoid: &R,
+    kind: LatitudeKind,
+) -> f64 {
+    match kind {
+        LatitudeKind::Geographic => latitude,
+        _ => ellipsoid
+            .latitude_to_geographic(latitude.to_radians(), kind)
+            .to_degrees(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ellipsoid::Ellipsoid;
+
+    #[test]
+    fn test_roundtrip() {
+        let ellipsoid = Ellipsoid::named("WGS84").unwrap();
+        let latitudes = [-90.0, -45.0, 0.0, 30.0, 89.5];
+
+        let authalic = geographic_to_authalic(&latitudes, &ellipsoid, 1);
+        assert_eq!(authalic[2], 0.0);
+        assert!(authalic[1] > -45.0 && authalic[3] < 30.0);
+
+        let actual = authalic_to_geographic(&authalic, &ellipsoid, 1);
+        for (actual, expected) in actual.iter().zip(latitudes) {
+            assert!((actual - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_convert() {
+        let ellipsoid = Ellipsoid::named("WGS84").unwrap();
+        let latitudes = [-60.0, 0.0, 45.0, 90.0];
+
+        let geocentric = convert(
+            &latitudes,
+            &ellipsoid,
+            LatitudeKind::Geographic,
+            LatitudeKind::Geocentric,
+            1,
+        );
+        for (actual, latitude) in geocentric.iter().zip(latitudes) {
+            let expected = geographic_to_kind(latitude, &ellipsoid, LatitudeKind::Geocentric);
+            assert_eq!(*actual, expected);
+        }
+        assert!(geocentric[2] < 45.0 && (geocentric[3] - 90.0).abs() < 1e-9);
+
+        let actual = convert(
+            &geocentric,
+            &ellipsoid,
+            LatitudeKind::Geocentric,
+            LatitudeKind::Geographic,
+            1,
+        );
+        for (actual, expected) in actual.iter().zip(latitudes) {
+            assert!((actual - expected).abs() < 1e-9);
+        }
+
+        assert_eq!(
+            kind_to_geographic(12.5, &ellipsoid, LatitudeKind::Geographic),
+            12.5
+        );
+    }
+}
@@ -1,5 +1,12 @@
+pub mod aggregation;
+pub mod cartesian;
 pub mod depth;
-mod execution;
+pub mod distances;
+pub mod execution;
+pub(crate) mod interpolation;
+pub mod latitudes;
 pub mod nested;
+pub mod rasterization;
+pub mod regridding;
 pub mod ring;
 pub mod zuniq;
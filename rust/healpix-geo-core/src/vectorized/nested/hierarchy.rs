@@ -4,60 +4,147 @@ use rayon::prelude::*;
 use cdshealpix as healpix;
 use cdshealpix::nested::Layer;
 
+use crate::error::{Error, Result, check_delta_depth, check_hash, check_length};
 use crate::maybe_parallelize;
 use crate::scalar::nested::hierarchy as scalar;
+use crate::vectorized::aggregation::{Reduction, broadcast, reduce_by_parent};
 
 pub fn kth_neighbourhood(
     ipix: &[u64],
     layer: &Layer,
     ring: &u32,
     nthreads: usize,
-) -> Vec<Vec<i64>> {
-    let mut result = Vec::<Vec<i64>>::with_capacity(ipix.len());
+) -> Result<Vec<Vec<i64>>> {
+    let mut result = Vec::<Result<Vec<i64>>>::with_capacity(ipix.len());
 
     maybe_parallelize!(nthreads, ipix, result, |hash| scalar::kth_neighbourhood(
         hash, layer, ring
     ));
 
-    result
+    result.into_iter().collect()
 }
 
-pub fn parents(ipix: &[u64], delta_depth: u8, nthreads: usize) -> Vec<u64> {
-    let mut result = Vec::<u64>::with_capacity(ipix.len());
-    if delta_depth > 0 {
-        maybe_parallelize!(nthreads, ipix, result, |hash| healpix::nested::parent(
-            *hash,
-            delta_depth
-        ));
-    } else {
-        result[..].clone_from_slice(ipix);
-    }
+/// The 8 neighbours of each cell, see [`scalar::neighbours`].
+pub fn neighbours(ipix: &[u64], layer: &Layer, nthreads: usize) -> Result<Vec<[i64; 8]>> {
+    let mut result = Vec::<Result<[i64; 8]>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, |hash| scalar::neighbours(
+        hash, layer
+    ));
 
-    result
+    result.into_iter().collect()
 }
 
-pub fn children(ipix: &[u64], delta_depth: u8, nthreads: usize) -> Vec<Vec<u64>> {
-    if delta_depth == 0 {
-        panic!("cannot query children at the same depth as the input");
+pub fn parents(ipix: &[u64], layer: &Layer, delta_depth: u8, nthreads: usize) -> Result<Vec<u64>> {
+    let depth = layer.depth();
+    if delta_depth > depth {
+        return Err(Error::DeltaDepthOutOfRange { delta_depth, depth });
     }
-    let mut result = Vec::<Vec<u64>>::with_capacity(ipix.len());
-    maybe_parallelize!(nthreads, ipix, result, |hash| healpix::nested::children(
-        *hash,
-        delta_depth
-    )
-    .collect::<Vec<u64>>());
 
-    result
+    let mut result = Vec::<Result<u64>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, |hash| -> Result<_> {
+        check_hash(*hash, depth)?;
+
+        Ok(healpix::nested::parent(*hash, delta_depth))
+    });
+
+    result.into_iter().collect()
+}
+
+pub fn children(
+    ipix: &[u64],
+    layer: &Layer,
+    delta_depth: u8,
+    nthreads: usize,
+) -> Result<Vec<Vec<u64>>> {
+    let depth = layer.depth();
+    check_delta_depth(depth, delta_depth)?;
+
+    let mut result = Vec::<Result<Vec<u64>>>::with_capacity(ipix.len());
+    maybe_parallelize!(nthreads, ipix, result, |hash| -> Result<_> {
+        check_hash(*hash, depth)?;
+
+        Ok(healpix::nested::children(*hash, delta_depth).collect::<Vec<u64>>())
+    });
+
+    result.into_iter().collect()
 }
 
-pub fn siblings(ipix: &[u64], layer: &Layer, nthreads: usize) -> Vec<Vec<u64>> {
+pub fn siblings(ipix: &[u64], layer: &Layer, nthreads: usize) -> Result<Vec<Vec<u64>>> {
     let depth = layer.depth();
-    let mut result = Vec::<Vec<u64>>::with_capacity(ipix.len());
+    let mut result = Vec::<Result<Vec<u64>>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, |hash| -> Result<_> {
+        check_hash(*hash, depth)?;
+
+        Ok(healpix::nested::siblings(depth, *hash).collect::<Vec<u64>>())
+    });
 
-    maybe_parallelize!(nthreads, ipix, result, |hash| healpix::nested::siblings(
-        depth, *hash
-    )
-    .collect::<Vec<u64>>());
+    result.into_iter().collect()
+}
+
+/// Reduce the values of the cells of `layer` sharing a parent at `new_depth`.
+///
+/// Returns the sorted parents with their reduced value.
+pub fn aggregate_to_parent(
+    ipix: &[u64],
+    values: &[f64],
+    layer: &Layer,
+    new_depth: u8,
+    reduction: Reduction,
+    nthreads: usize,
+) -> Result<(Vec<u64>, Vec<f64>)> {
+    check_length(ipix.len(), values.len())?;
+    let delta_depth = layer
+        .depth()
+        .checked_sub(new_depth)
+        .ok_or(Error::DepthOutOfRange {
+            depth: new_depth,
+            max_depth: layer.depth(),
+        })?;
+
+    let parents = parents(ipix, layer, delta_depth, nthreads)?;
+
+    Ok(reduce_by_parent(&parents, values, None, reduction))
+}
+
+/// Repeat the values of the cells of `layer` for each of their children at `new_depth`.
+pub fn broadcast_to_children(
+    ipix: &[u64],
+    values: &[f64],
+    layer: &Layer,
+    new_depth: u8,
+    nthreads: usize,
+) -> Result<(Vec<u64>, Vec<f64>)> {
+    check_length(ipix.len(), values.len())?;
+    let delta_depth = new_depth
+        .checked_sub(layer.depth())
+        .ok_or(Error::DepthOutOfRange {
+            depth: layer.depth(),
+            max_depth: new_depth,
+        })?;
 
-    result
+    let children = children(ipix, layer, delta_depth, nthreads)?;
+
+    Ok(broadcast(children, values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parents_delta_depth() {
+        let layer = healpix::nested::get(3);
+
+        assert_eq!(parents(&[42], layer, 3, 1), Ok(vec![0]));
+        assert_eq!(
+            parents(&[42], layer, 5, 1),
+            Err(Error::DeltaDepthOutOfRange {
+                delta_depth: 5,
+                depth: 3
+            })
+        );
+    }
 }
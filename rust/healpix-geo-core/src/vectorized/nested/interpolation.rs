@@ -0,0 +1,52 @@
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+use cdshealpix::nested::Layer;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_length};
+use crate::maybe_parallelize;
+use crate::scalar::nested::interpolation as scalar;
+
+/// The 4 cells surrounding each point, with their bilinear interpolation weights.
+pub fn interpolation_weights<R: ReferenceBody>(
+    lon: &[f64],
+    lat: &[f64],
+    layer: &Layer,
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<Vec<[(u64, f64); 4]>> {
+    check_length(lon.len(), lat.len())?;
+
+    let data: Vec<(&f64, &f64)> = lon.iter().zip(lat).collect();
+
+    let mut result = Vec::<Result<[(u64, f64); 4]>>::with_capacity(lon.len());
+    maybe_parallelize!(nthreads, data, result, |(lon, lat)| {
+        scalar::interpolation_weights(lon, lat, layer, ellipsoid)
+    });
+
+    result.into_iter().collect()
+}
+
+/// Bilinear interpolation of a field known at the cells `cell_ids`.
+///
+/// Cells missing from `cell_ids` are skipped and the weights of the remaining cells
+/// renormalized. Points without any known surrounding cell are NaN.
+pub fn interpolate<R: ReferenceBody>(
+    values: &[f64],
+    cell_ids: &[u64],
+    lon: &[f64],
+    lat: &[f64],
+    layer: &Layer,
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<Vec<f64>> {
+    crate::vectorized::interpolation::interpolate(
+        values,
+        cell_ids,
+        lon,
+        lat,
+        |lon, lat| scalar::interpolation_weights(lon, lat, layer, ellipsoid),
+        nthreads,
+    )
+}
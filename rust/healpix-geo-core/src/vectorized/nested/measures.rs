@@ -0,0 +1,41 @@
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+use cdshealpix::nested::Layer;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::Result;
+use crate::maybe_parallelize;
+use crate::scalar::nested::measures as scalar;
+
+pub fn cell_area<R: ReferenceBody>(
+    ipix: &[u64],
+    layer: &Layer,
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<Vec<f64>> {
+    let mut result = Vec::<Result<f64>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, |hash| scalar::cell_area(
+        hash, layer, ellipsoid
+    ));
+
+    result.into_iter().collect()
+}
+
+pub fn cell_perimeter<R: ReferenceBody>(
+    ipix: &[u64],
+    layer: &Layer,
+    ellipsoid: &R,
+    step: usize,
+    nthreads: usize,
+) -> Result<Vec<f64>> {
+    let geodesic = ellipsoid.geodesic();
+    let mut result = Vec::<Result<f64>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, |hash| scalar::cell_perimeter(
+        hash, layer, ellipsoid, &geodesic, &step
+    ));
+
+    result.into_iter().collect()
+}
@@ -0,0 +1,159 @@
+//! Rendering of HEALPix maps onto images.
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+use crate::error::{Result, check_length};
+use crate::maybe_parallelize_zip;
+use crate::vectorized::interpolation::cell_lookup;
+
+/// The map projection of an image.
+///
+/// Longitudes and latitudes are in degrees, and the image covers the whole sphere.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+    /// Plate carrée, with longitudes and latitudes spaced linearly.
+    Equirectangular { central_longitude: f64 },
+    /// The equal-area Mollweide projection.
+    Mollweide { central_longitude: f64 },
+    /// The hemisphere centered on a point, as seen from infinitely far away.
+    Orthographic {
+        central_longitude: f64,
+        central_latitude: f64,
+    },
+}
+
+impl Projection {
+    /// The longitude and latitude of a point of the image, if it is on the sphere.
+    ///
+    /// `x` and `y` span the extent of the projection from `-1` to `1`, with `x`
+    /// increasing eastwards and `y` northwards.
+    pub fn inverse(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        match *self {
+            Self::Equirectangular { central_longitude } => {
+                Some((central_longitude + 180.0 * x, 90.0 * y))
+            }
+            Self::Mollweide { central_longitude } => {
+                if x * x + y * y > 1.0 {
+                    return None;
+                }
+
+                let theta = y.asin();
+                let lat = ((2.0 * theta + (2.0 * theta).sin()) / std::f64::consts::PI)
+                    .clamp(-1.0, 1.0)
+                    .asin();
+                let lon = if theta.cos() > 0.0 {
+                    180.0 * x / theta.cos()
+                } else {
+                    0.0
+                };
+
+                Some((central_longitude + lon, lat.to_degrees()))
+            }
+            Self::Orthographic {
+                central_longitude,
+                central_latitude,
+            } => {
+                let rho = x.hypot(y);
+                if rho > 1.0 {
+                    return None;
+                }
+
+                // the sine of the angular distance to the center is `rho`
+                let cos_c = (1.0 - rho * rho).sqrt();
+                let (sin_lat0, cos_lat0) = central_latitude.to_radians().sin_cos();
+
+                let lat = (cos_c * sin_lat0 + y * cos_lat0).clamp(-1.0, 1.0).asin();
+                let lon = x.atan2(cos_c * cos_lat0 - y * sin_lat0);
+
+                Some((central_longitude + lon.to_degrees(), lat.to_degrees()))
+            }
+        }
+    }
+}
+
+/// Render a map onto an image of `width` by `height` pixels.
+///
+/// `values` holds the map at the cells `cell_ids`, and `cell` gives the cell
+/// containing a point. The pixels are in row-major order, starting from the
+/// north-western corner. Pixels outside of the sphere or whose cell is missing from
+/// `cell_ids` are set to `fill_value`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn rasterize<F>(
+    values: &[f64],
+    cell_ids: &[u64],
+    projection: &Projection,
+    width: usize,
+    height: usize,
+    fill_value: f64,
+    cell: F,
+    nthreads: usize,
+) -> Result<Vec<f64>>
+where
+    F: Fn(&f64, &f64) -> Result<u64> + Sync,
+{
+    check_length(cell_ids.len(), values.len())?;
+
+    let lookup = cell_lookup(values, cell_ids);
+    let pixels: Vec<usize> = (0..width * height).collect();
+
+    let mut result = vec![fill_value; width * height];
+    let out = result.as_mut_slice();
+    maybe_parallelize_zip!(nthreads, (pixels.as_slice(), out), |(pixel, value)| {
+        let x = 2.0 * ((pixel % width) as f64 + 0.5) / width as f64 - 1.0;
+        let y = 1.0 - 2.0 * ((pixel / width) as f64 + 0.5) / height as f64;
+
+        if let Some((lon, lat)) = projection.inverse(x, y) {
+            *value = lookup(cell(&lon, &lat)?).unwrap_or(fill_value);
+        }
+
+        Ok(())
+    })?;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inverse() {
+        let equirectangular = Projection::Equirectangular {
+            central_longitude: 10.0,
+        };
+        assert_eq!(equirectangular.inverse(0.5, -0.5), Some((100.0, -45.0)));
+
+        let mollweide = Projection::Mollweide {
+            central_longitude: 0.0,
+        };
+        assert_eq!(mollweide.inverse(1.0, 0.0), Some((180.0, 0.0)));
+        let (lon, lat) = mollweide.inverse(0.0, 1.0).unwrap();
+        assert!(lon.abs() < 1e-9 && (lat - 90.0).abs() < 1e-9);
+        assert_eq!(mollweide.inverse(0.8, 0.8), None);
+
+        let orthographic = Projection::Orthographic {
+            central_longitude: 30.0,
+            central_latitude: 45.0,
+        };
+        let (lon, lat) = orthographic.inverse(0.0, 0.0).unwrap();
+        assert!((lon - 30.0).abs() < 1e-9 && (lat - 45.0).abs() < 1e-9);
+        let (lon, lat) = orthographic.inverse(0.0, 0.5).unwrap();
+        assert!((lon - 30.0).abs() < 1e-9 && (lat - 75.0).abs() < 1e-9);
+        let (lon, lat) = orthographic.inverse(1.0, 0.0).unwrap();
+        assert!((lon - 120.0).abs() < 1e-9 && lat.abs() < 1e-9);
+        assert_eq!(orthographic.inverse(0.8, 0.8), None);
+    }
+
+    #[test]
+    fn test_rasterize() {
+        let projection = Projection::Equirectangular {
+            central_longitude: 0.0,
+        };
+        // the cell is the index of the hemisphere
+        let cell = |lon: &f64, _: &f64| -> Result<u64> { Ok(if *lon < 0.0 { 0 } else { 1 }) };
+
+        let actual = rasterize(&[2.0], &[1], &projection, 4, 2, -1.0, cell, 1).unwrap();
+
+        assert_eq!(actual, vec![-1.0, -1.0, 2.0, 2.0, -1.0, -1.0, 2.0, 2.0]);
+    }
+}
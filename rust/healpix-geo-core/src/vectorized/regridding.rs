@@ -0,0 +1,126 @@
+use crate::error::{Error, Result};
+
+/// The direction of a regridding between source cells and HEALPix cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// From the source cells to the HEALPix cells.
+    ToHealpix,
+    /// From the HEALPix cells to the source cells.
+    FromHealpix,
+}
+
+/// A sparse regridding weight matrix in compressed sparse row (CSR) format.
+///
+/// The rows are the target cells and the columns the input cells of the regridding.
+/// HEALPix cells are referred to by their position in `cell_ids`, and source cells by
+/// their position in the input. The weights of each row sum to 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseWeights {
+    /// The HEALPix cells overlapping any source cell, sorted.
+    pub cell_ids: Vec<u64>,
+    /// The start of each row in `indices` and `weights`, followed by their length.
+    pub indptr: Vec<usize>,
+    /// The column of each weight.
+    pub indices: Vec<usize>,
+    pub weights: Vec<f64>,
+}
+
+impl SparseWeights {
+    /// Build the weights from the overlap areas of each source cell.
+    ///
+    /// The weights are the overlap areas normalized by the area covered in each row,
+    /// so target cells only partially covered by the input are averages over the
+    /// covered part.
+    pub(crate) fn from_overlaps(overlaps: Vec<Vec<(u64, f64)>>, direction: Direction) -> Self {
+        let mut cell_ids: Vec<u64> = overlaps.iter().flatten().map(|(hash, _)| *hash).collect();
+        cell_ids.sort_unstable();
+        cell_ids.dedup();
+
+        let position = |hash: &u64| cell_ids.binary_search(hash).unwrap_or_default();
+
+        let mut entries: Vec<(usize, usize, f64)> = overlaps
+            .iter()
+            .enumerate()
+            .flat_map(|(source, row)| row.iter().map(move |(hash, area)| (source, hash, *area)))
+            .map(|(source, hash, area)| match direction {
+                Direction::ToHealpix => (position(hash), source, area),
+                Direction::FromHealpix => (source, position(hash), area),
+            })
+            .collect();
+        entries.sort_unstable_by_key(|(row, column, _)| (*row, *column));
+
+        let rows = match direction {
+            Direction::ToHealpix => cell_ids.len(),
+            Direction::FromHealpix => overlaps.len(),
+        };
+
+        let mut indptr = vec![0; rows + 1];
+        for (row, _, _) in &entries {
+            indptr[row + 1] += 1;
+        }
+        for row in 0..rows {
+            indptr[row + 1] += indptr[row];
+        }
+
+        let mut weights: Vec<f64> = entries.iter().map(|(_, _, area)| *area).collect();
+        for row in indptr.windows(2) {
+            let covered: f64 = weights[row[0]..row[1]].iter().sum();
+            weights[row[0]..row[1]]
+                .iter_mut()
+                .for_each(|weight| *weight /= covered);
+        }
+
+        Self {
+            cell_ids,
+            indptr,
+            indices: entries.into_iter().map(|(_, column, _)| column).collect(),
+            weights,
+        }
+    }
+}
+
+/// Split the vertices into polygons of `vertices_per_cell` vertices each.
+pub(crate) fn polygons(
+    vertices: &[(f64, f64)],
+    vertices_per_cell: usize,
+) -> Result<Vec<&[(f64, f64)]>> {
+    if vertices_per_cell == 0 || !vertices.len().is_multiple_of(vertices_per_cell) {
+        return Err(Error::InvalidGeometry(format!(
+            "cannot split {} vertices into cells of {vertices_per_cell} vertices",
+            vertices.len()
+        )));
+    }
+
+    Ok(vertices.chunks(vertices_per_cell).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_overlaps() {
+        let overlaps = vec![vec![(3, 1.0), (7, 3.0)], vec![(7, 1.0)], vec![]];
+
+        let actual = SparseWeights::from_overlaps(overlaps.clone(), Direction::ToHealpix);
+        assert_eq!(actual.cell_ids, vec![3, 7]);
+        assert_eq!(actual.indptr, vec![0, 1, 3]);
+        assert_eq!(actual.indices, vec![0, 0, 1]);
+        assert_eq!(actual.weights, vec![1.0, 0.75, 0.25]);
+
+        let actual = SparseWeights::from_overlaps(overlaps, Direction::FromHealpix);
+        assert_eq!(actual.cell_ids, vec![3, 7]);
+        assert_eq!(actual.indptr, vec![0, 2, 3, 3]);
+        assert_eq!(actual.indices, vec![0, 1, 1]);
+        assert_eq!(actual.weights, vec![0.25, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn test_polygons() {
+        let vertices = [(0.0, 0.0); 8];
+
+        assert_eq!(polygons(&vertices, 4).unwrap().len(), 2);
+        assert!(polygons(&vertices, 3).is_err());
+        assert!(polygons(&vertices, 0).is_err());
+    }
+}
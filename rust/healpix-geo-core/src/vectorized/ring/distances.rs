@@ -1,13 +1,19 @@
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_chunks, check_hash};
+use crate::geodesic::Geodesic;
 use crate::maybe_parallelize;
+use crate::vectorized::distances::geodesic_inverse;
 use cdshealpix::sph_geom::coo3d::{UnitVec3, UnitVect3, vec3_of};
 
 #[cfg(not(target_arch = "wasm32"))]
 use rayon::prelude::*;
 
-fn to_vec3(nside: &u32, hash: &u64) -> UnitVect3 {
+fn to_vec3(nside: &u32, hash: &u64) -> Result<UnitVect3> {
+    check_hash(*hash, cdshealpix::depth(*nside))?;
+
     let (lon, lat) = cdshealpix::ring::center(*nside, *hash);
 
-    vec3_of(lon, lat)
+    Ok(vec3_of(lon, lat))
 }
 
 pub fn angular_distances(
@@ -16,17 +22,119 @@ pub fn angular_distances(
     chunks: usize,
     nside: &u32,
     nthreads: usize,
-) -> Vec<Vec<f64>> {
+) -> Result<Vec<Vec<f64>>> {
+    check_chunks(from.len(), chunks, to.len())?;
+
     let data: Vec<(&u64, &[u64])> = from.iter().zip(to.chunks(chunks)).collect();
 
-    let mut result = Vec::<Vec<f64>>::with_capacity(from.len());
+    let mut result = Vec::<Result<Vec<f64>>>::with_capacity(from.len());
     maybe_parallelize!(nthreads, data, result, |(from_, to_)| {
-        let first = to_vec3(nside, from_);
+        let first = to_vec3(nside, from_)?;
         to_.iter()
-            .map(|h| to_vec3(nside, h))
-            .map(|vec| first.ang_dist(&vec))
-            .collect::<Vec<f64>>()
+            .map(|h| to_vec3(nside, h).map(|vec| first.ang_dist(&vec)))
+            .collect::<Result<Vec<f64>>>()
+    });
+
+    result.into_iter().collect()
+}
+
+fn to_lonlat<R: ReferenceBody>(nside: &u32, ellipsoid: &R, hash: &u64) -> Result<(f64, f64)> {
+    crate::scalar::ring::coordinates::healpix_to_lonlat(hash, nside, ellipsoid)
+}
+
+fn distances_from_cell<R: ReferenceBody>(
+    geodesic: &Geodesic,
+    nside: &u32,
+    ellipsoid: &R,
+    from: &u64,
+    to: impl Iterator<Item = Result<(f64, f64)>>,
+) -> Result<Vec<(f64, f64, f64)>> {
+    let first = to_lonlat(nside, ellipsoid, from)?;
+
+    to.map(|point| point.map(|point| geodesic_inverse(geodesic, first, point)))
+        .collect()
+}
+
+/// Geodesic distances between cell centers.
+///
+/// Returns the distance, the forward azimuth and the back azimuth of each pair, with
+/// the same layout as `angular_distances`.
+pub fn geodesic_distances<R: ReferenceBody>(
+    from: &[u64],
+    to: &[u64],
+    chunks: usize,
+    nside: &u32,
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<Vec<Vec<(f64, f64, f64)>>> {
+    check_chunks(from.len(), chunks, to.len())?;
+
+    let geodesic = ellipsoid.geodesic();
+    let data: Vec<(&u64, &[u64])> = from.iter().zip(to.chunks(chunks)).collect();
+
+    let mut result = Vec::<Result<Vec<(f64, f64, f64)>>>::with_capacity(from.len());
+    maybe_parallelize!(nthreads, data, result, |(from_, to_)| {
+        distances_from_cell(
+            &geodesic,
+            nside,
+            ellipsoid,
+            from_,
+            to_.iter().map(|h| to_lonlat(nside, ellipsoid, h)),
+        )
+    });
+
+    result.into_iter().collect()
+}
+
+/// Geodesic distances between cell centers and points.
+///
+/// Every cell of `from` is paired with a chunk of `chunks` points of `to`.
+pub fn geodesic_distances_to_points<R: ReferenceBody>(
+    from: &[u64],
+    to: &[(f64, f64)],
+    chunks: usize,
+    nside: &u32,
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<Vec<Vec<(f64, f64, f64)>>> {
+    check_chunks(from.len(), chunks, to.len())?;
+
+    let geodesic = ellipsoid.geodesic();
+    let data: Vec<(&u64, &[(f64, f64)])> = from.iter().zip(to.chunks(chunks)).collect();
+
+    let mut result = Vec::<Result<Vec<(f64, f64, f64)>>>::with_capacity(from.len());
+    maybe_parallelize!(nthreads, data, result, |(from_, to_)| {
+        distances_from_cell(
+            &geodesic,
+            nside,
+            ellipsoid,
+            from_,
+            to_.iter().map(|&point| Ok(point)),
+        )
+    });
+
+    result.into_iter().collect()
+}
+
+/// The cells whose center is within `radius` of the center of each cell.
+///
+/// Returns the neighbours of each cell, including itself, with their geodesic
+/// distance in the unit of the ellipsoid axes, sorted by distance.
+pub fn neighbours_within_distance<R: ReferenceBody>(
+    ipix: &[u64],
+    nside: &u32,
+    radius: f64,
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<Vec<Vec<(u64, f64)>>> {
+    let geodesic = ellipsoid.geodesic();
+
+    let mut result = Vec::<Result<Vec<(u64, f64)>>>::with_capacity(ipix.len());
+    maybe_parallelize!(nthreads, ipix, result, |hash| {
+        crate::scalar::ring::coverage::neighbours_within_distance(
+            hash, nside, radius, ellipsoid, &geodesic,
+        )
     });
 
-    result
+    result.into_iter().collect()
 }
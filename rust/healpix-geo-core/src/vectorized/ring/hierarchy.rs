@@ -1,15 +1,141 @@
 #[cfg(not(target_arch = "wasm32"))]
 use rayon::prelude::*;
 
+use cdshealpix as healpix;
+
+use crate::error::{Error, Result, check_delta_depth, check_hash, check_length};
 use crate::maybe_parallelize;
 use crate::scalar::ring::hierarchy as scalar;
+use crate::vectorized::aggregation::{Reduction, broadcast, reduce_by_parent};
 
-pub fn kth_neighbourhood(ipix: &[u64], nside: &u32, ring: &u32, nthreads: usize) -> Vec<Vec<i64>> {
-    let mut result = Vec::<Vec<i64>>::with_capacity(ipix.len());
+pub fn kth_neighbourhood(
+    ipix: &[u64],
+    nside: &u32,
+    ring: &u32,
+    nthreads: usize,
+) -> Result<Vec<Vec<i64>>> {
+    let mut result = Vec::<Result<Vec<i64>>>::with_capacity(ipix.len());
 
     maybe_parallelize!(nthreads, ipix, result, |hash| scalar::kth_neighbourhood(
         hash, nside, ring
     ));
 
-    result
+    result.into_iter().collect()
+}
+
+/// The 8 neighbours of each cell, see [`scalar::neighbours`].
+pub fn neighbours(ipix: &[u64], nside: &u32, nthreads: usize) -> Result<Vec<[i64; 8]>> {
+    let mut result = Vec::<Result<[i64; 8]>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, |hash| scalar::neighbours(
+        hash, nside
+    ));
+
+    result.into_iter().collect()
+}
+
+pub fn parents(ipix: &[u64], nside: &u32, delta_depth: u8, nthreads: usize) -> Result<Vec<u64>> {
+    let depth = healpix::depth(*nside);
+    if delta_depth > depth {
+        return Err(Error::DeltaDepthOutOfRange { delta_depth, depth });
+    }
+
+    let layer = healpix::nested::get(depth);
+    let parent_layer = healpix::nested::get(depth - delta_depth);
+
+    let mut result = Vec::<Result<u64>>::with_capacity(ipix.len());
+    maybe_parallelize!(nthreads, ipix, result, |hash| -> Result<_> {
+        check_hash(*hash, depth)?;
+
+        let parent = healpix::nested::parent(layer.from_ring(*hash), delta_depth);
+
+        Ok(parent_layer.to_ring(parent))
+    });
+
+    result.into_iter().collect()
+}
+
+pub fn children(
+    ipix: &[u64],
+    nside: &u32,
+    delta_depth: u8,
+    nthreads: usize,
+) -> Result<Vec<Vec<u64>>> {
+    let depth = healpix::depth(*nside);
+    check_delta_depth(depth, delta_depth)?;
+
+    let layer = healpix::nested::get(depth);
+    let children_layer = healpix::nested::get(depth + delta_depth);
+
+    let mut result = Vec::<Result<Vec<u64>>>::with_capacity(ipix.len());
+    maybe_parallelize!(nthreads, ipix, result, |hash| -> Result<_> {
+        check_hash(*hash, depth)?;
+
+        Ok(
+            healpix::nested::children(layer.from_ring(*hash), delta_depth)
+                .map(|child| children_layer.to_ring(child))
+                .collect::<Vec<u64>>(),
+        )
+    });
+
+    result.into_iter().collect()
+}
+
+pub fn siblings(ipix: &[u64], nside: &u32, nthreads: usize) -> Result<Vec<Vec<u64>>> {
+    let depth = healpix::depth(*nside);
+    let layer = healpix::nested::get(depth);
+
+    let mut result = Vec::<Result<Vec<u64>>>::with_capacity(ipix.len());
+    maybe_parallelize!(nthreads, ipix, result, |hash| -> Result<_> {
+        check_hash(*hash, depth)?;
+
+        Ok(healpix::nested::siblings(depth, layer.from_ring(*hash))
+            .map(|sibling| layer.to_ring(sibling))
+            .collect::<Vec<u64>>())
+    });
+
+    result.into_iter().collect()
+}
+
+/// Reduce the values of the cells of `nside` sharing a parent at `new_depth`.
+///
+/// Returns the sorted parents with their reduced value.
+pub fn aggregate_to_parent(
+    ipix: &[u64],
+    values: &[f64],
+    nside: &u32,
+    new_depth: u8,
+    reduction: Reduction,
+    nthreads: usize,
+) -> Result<(Vec<u64>, Vec<f64>)> {
+    check_length(ipix.len(), values.len())?;
+    let depth = healpix::depth(*nside);
+    let delta_depth = depth.checked_sub(new_depth).ok_or(Error::DepthOutOfRange {
+        depth: new_depth,
+        max_depth: depth,
+    })?;
+
+    let parents = parents(ipix, nside, delta_depth, nthreads)?;
+
+    Ok(reduce_by_parent(&parents, values, None, reduction))
+}
+
+/// Repeat the values of the cells of `nside` for each of their children at `new_depth`.
+pub fn broadcast_to_children(
+    ipix: &[u64],
+    values: &[f64],
+    nside: &u32,
+    new_depth: u8,
+    nthreads: usize,
+) -> Result<(Vec<u64>, Vec<f64>)> {
+    check_length(ipix.len(), values.len())?;
+    let depth = healpix::depth(*nside);
+    let delta_depth = new_depth.checked_sub(depth).ok_or(Error::DepthOutOfRange {
+        depth,
+        max_depth: new_depth,
+    })?;
+
+    let children = children(ipix, nside, delta_depth, nthreads)?;
+
+    Ok(broadcast(children, values))
 }
@@ -0,0 +1,33 @@
+use crate::ellipsoid::ReferenceBody;
+use crate::error::Result;
+use crate::scalar::ring::coordinates::lonlat_to_healpix;
+use crate::vectorized::rasterization::Projection;
+
+/// Render the map `values` defined on `cell_ids` onto an image of `width` by
+/// `height` pixels.
+///
+/// The pixels are in row-major order, starting from the north-western corner. Pixels
+/// outside of the sphere or whose cell is missing from `cell_ids` are `fill_value`.
+#[allow(clippy::too_many_arguments)]
+pub fn rasterize<R: ReferenceBody>(
+    values: &[f64],
+    cell_ids: &[u64],
+    nside: &u32,
+    ellipsoid: &R,
+    projection: &Projection,
+    width: usize,
+    height: usize,
+    fill_value: f64,
+    nthreads: usize,
+) -> Result<Vec<f64>> {
+    crate::vectorized::rasterization::rasterize(
+        values,
+        cell_ids,
+        projection,
+        width,
+        height,
+        fill_value,
+        |lon, lat| lonlat_to_healpix(lon, lat, nside, ellipsoid),
+        nthreads,
+    )
+}
@@ -0,0 +1,33 @@
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::Result;
+use crate::maybe_parallelize;
+use crate::scalar::ring::regridding as scalar;
+use crate::vectorized::regridding::{Direction, SparseWeights, polygons};
+
+/// Area-weighted regridding weights between source cells and the cells of `nside`.
+///
+/// The source cells are given by `vertices_per_cell` consecutive vertices each. See
+/// [`scalar::overlap_areas`] for how the overlaps are computed.
+pub fn regridding_weights<R: ReferenceBody>(
+    vertices: &[(f64, f64)],
+    vertices_per_cell: usize,
+    nside: &u32,
+    ellipsoid: &R,
+    refinement: u8,
+    direction: Direction,
+    nthreads: usize,
+) -> Result<SparseWeights> {
+    let polygons = polygons(vertices, vertices_per_cell)?;
+
+    let mut result = Vec::<Result<Vec<(u64, f64)>>>::with_capacity(polygons.len());
+    maybe_parallelize!(nthreads, polygons, result, |polygon| {
+        scalar::overlap_areas(polygon, nside, ellipsoid, refinement)
+    });
+
+    let overlaps = result.into_iter().collect::<Result<Vec<_>>>()?;
+
+    Ok(SparseWeights::from_overlaps(overlaps, direction))
+}
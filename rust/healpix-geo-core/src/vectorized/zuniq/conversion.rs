@@ -1,3 +1,4 @@
+use crate::error::Result;
 use crate::maybe_parallelize;
 use crate::vectorized::depth::DepthLike;
 
@@ -6,8 +7,8 @@ use crate::scalar::zuniq::conversion as scalar;
 #[cfg(not(target_arch = "wasm32"))]
 use rayon::prelude::*;
 
-pub fn from_nested(ipix: &[u64], depth: DepthLike, nthreads: usize) -> Vec<u64> {
-    let mut result = Vec::<u64>::with_capacity(ipix.len());
+pub fn from_nested(ipix: &[u64], depth: DepthLike, nthreads: usize) -> Result<Vec<u64>> {
+    let mut result = Vec::<Result<u64>>::with_capacity(ipix.len());
 
     match depth {
         DepthLike::Scalar(depth) => {
@@ -23,11 +24,11 @@ pub fn from_nested(ipix: &[u64], depth: DepthLike, nthreads: usize) -> Vec<u64>
         }
     }
 
-    result
+    result.into_iter().collect()
 }
 
-pub fn from_ring(ipix: &[u64], depth: DepthLike, nthreads: usize) -> Vec<u64> {
-    let mut result = Vec::<u64>::with_capacity(ipix.len());
+pub fn from_ring(ipix: &[u64], depth: DepthLike, nthreads: usize) -> Result<Vec<u64>> {
+    let mut result = Vec::<Result<u64>>::with_capacity(ipix.len());
 
     match depth {
         DepthLike::Scalar(depth) => {
@@ -46,21 +47,21 @@ pub fn from_ring(ipix: &[u64], depth: DepthLike, nthreads: usize) -> Vec<u64> {
         }
     }
 
-    result
+    result.into_iter().collect()
 }
 
-pub fn to_nested(ipix: &[u64], nthreads: usize) -> (Vec<u64>, Vec<u8>) {
-    let mut result = Vec::<(u64, u8)>::with_capacity(ipix.len());
+pub fn to_nested(ipix: &[u64], nthreads: usize) -> Result<(Vec<u64>, Vec<u8>)> {
+    let mut result = Vec::<Result<(u64, u8)>>::with_capacity(ipix.len());
     maybe_parallelize!(nthreads, ipix, result, scalar::to_nested);
 
-    result.into_iter().unzip()
+    result.into_iter().collect()
 }
 
-pub fn to_ring(ipix: &[u64], nthreads: usize) -> (Vec<u64>, Vec<u8>) {
-    let mut result = Vec::<(u64, u8)>::with_capacity(ipix.len());
+pub fn to_ring(ipix: &[u64], nthreads: usize) -> Result<(Vec<u64>, Vec<u8>)> {
+    let mut result = Vec::<Result<(u64, u8)>>::with_capacity(ipix.len());
     maybe_parallelize!(nthreads, ipix, result, scalar::to_ring);
 
-    let (nested, depths): (Vec<u64>, Vec<u8>) = result.into_iter().unzip();
+    let (nested, depths): (Vec<u64>, Vec<u8>) = result.into_iter().collect::<Result<_>>()?;
 
-    (nested, depths)
+    Ok((nested, depths))
 }
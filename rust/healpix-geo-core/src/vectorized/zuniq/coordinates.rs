@@ -1,48 +1,288 @@
 #[cfg(not(target_arch = "wasm32"))]
 use rayon::prelude::*;
 
-use cdshealpix::nested::Layer;
+use cdshealpix as healpix;
 
-use crate::ellipsoid::Ellipsoid;
-use crate::maybe_parallelize;
+use crate::crs::Crs;
+use crate::ellipsoid::{LatitudeKind, ReferenceBody};
+use crate::error::{InvalidPolicy, Result, check_depth, check_length, check_lonlat};
 use crate::scalar::zuniq::coordinates as scalar;
+use crate::vectorized::cartesian::{
+    cartesian_to_geodetic, geodetic_to_cartesian, vertices_to_cartesian,
+};
+use crate::vectorized::depth::DepthLike;
+use crate::vectorized::latitudes::{geographic_to_kind, kind_to_geographic};
+use crate::{maybe_parallelize, maybe_parallelize_zip};
 
-pub fn healpix_to_lonlat(ipix: &[u64], ellipsoid: &Ellipsoid, nthreads: usize) -> Vec<(f64, f64)> {
-    let mut result = Vec::<(f64, f64)>::with_capacity(ipix.len());
+pub fn healpix_to_lonlat<R: ReferenceBody>(
+    ipix: &[u64],
+    ellipsoid: &R,
+    latitude_kind: LatitudeKind,
+    policy: InvalidPolicy,
+    nthreads: usize,
+) -> Result<(Vec<f64>, Vec<f64>)> {
+    let mut lon = vec![0.0; ipix.len()];
+    let mut lat = vec![0.0; ipix.len()];
 
-    maybe_parallelize!(nthreads, ipix, result, |hash| scalar::healpix_to_lonlat(
-        hash, ellipsoid
-    ));
+    healpix_to_lonlat_into(
+        ipix,
+        ellipsoid,
+        latitude_kind,
+        &mut lon,
+        &mut lat,
+        policy,
+        nthreads,
+    )?;
 
-    result
+    Ok((lon, lat))
+}
+
+/// Write the coordinates of the cell centers into `lon` and `lat`.
+///
+/// The output slices must have the same length as `ipix`. Invalid cell ids are
+/// handled according to `policy`, and the latitudes are of the given kind.
+pub fn healpix_to_lonlat_into<R: ReferenceBody>(
+    ipix: &[u64],
+    ellipsoid: &R,
+    latitude_kind: LatitudeKind,
+    lon: &mut [f64],
+    lat: &mut [f64],
+    policy: InvalidPolicy,
+    nthreads: usize,
+) -> Result<()> {
+    check_length(ipix.len(), lon.len())?;
+    check_length(ipix.len(), lat.len())?;
+
+    maybe_parallelize_zip!(nthreads, (ipix, lon, lat), |(hash, lon, lat)| {
+        (*lon, *lat) = policy.apply(
+            scalar::healpix_to_lonlat(hash, ellipsoid),
+            (f64::NAN, f64::NAN),
+        )?;
+        *lat = geographic_to_kind(*lat, ellipsoid, latitude_kind);
+
+        Ok(())
+    })
+}
+
+pub fn lonlat_to_healpix<R: ReferenceBody>(
+    lon: &[f64],
+    lat: &[f64],
+    depth: DepthLike,
+    ellipsoid: &R,
+    latitude_kind: LatitudeKind,
+    policy: InvalidPolicy,
+    nthreads: usize,
+) -> Result<Vec<u64>> {
+    let mut ipix = vec![0; lon.len()];
+
+    lonlat_to_healpix_into(
+        lon,
+        lat,
+        depth,
+        ellipsoid,
+        latitude_kind,
+        &mut ipix,
+        policy,
+        nthreads,
+    )?;
+
+    Ok(ipix)
 }
 
-pub fn lonlat_to_healpix(
-    coords: &[(f64, f64)],
-    layer: &Layer,
-    ellipsoid: &Ellipsoid,
+/// Write the cells containing the given coordinates into `ipix`.
+///
+/// All slices must have the same length, including the depths if given as an array.
+/// Invalid coordinates are handled according to `policy`, and the latitudes are of
+/// the given kind.
+#[allow(clippy::too_many_arguments)]
+pub fn lonlat_to_healpix_into<R: ReferenceBody>(
+    lon: &[f64],
+    lat: &[f64],
+    depth: DepthLike,
+    ellipsoid: &R,
+    latitude_kind: LatitudeKind,
+    ipix: &mut [u64],
+    policy: InvalidPolicy,
     nthreads: usize,
-) -> Vec<u64> {
-    let mut result = Vec::<u64>::with_capacity(coords.len());
+) -> Result<()> {
+    check_length(lon.len(), lat.len())?;
+    check_length(lon.len(), ipix.len())?;
+
+    match depth {
+        DepthLike::Scalar(depth) => {
+            check_depth(depth)?;
+            let layer = healpix::nested::get(depth);
+
+            maybe_parallelize_zip!(nthreads, (lon, lat, ipix), |(lon, lat, hash)| {
+                let result = check_lonlat(*lon, *lat).and_then(|()| {
+                    let lat = kind_to_geographic(*lat, ellipsoid, latitude_kind);
+                    scalar::lonlat_to_healpix(lon, &lat, layer, ellipsoid)
+                });
+                *hash = policy.apply(result, u64::MAX)?;
 
-    maybe_parallelize!(nthreads, coords, result, |(lon, lat)| {
-        scalar::lonlat_to_healpix(lon, lat, layer, ellipsoid)
-    });
+                Ok(())
+            })
+        }
+        DepthLike::Array(depths) => {
+            check_length(lon.len(), depths.len())?;
+
+            maybe_parallelize_zip!(nthreads, (lon, lat, depths, ipix), |(
+                lon,
+                lat,
+                depth,
+                hash,
+            )| {
+                check_depth(*depth)?;
+                let layer = healpix::nested::get(*depth);
+                let result = check_lonlat(*lon, *lat).and_then(|()| {
+                    let lat = kind_to_geographic(*lat, ellipsoid, latitude_kind);
+                    scalar::lonlat_to_healpix(lon, &lat, layer, ellipsoid)
+                });
+                *hash = policy.apply(result, u64::MAX)?;
+
+                Ok(())
+            })
+        }
+    }
+}
+
+/// Whether each cell id is a valid cell.
+pub fn is_valid_cell(ipix: &[u64], nthreads: usize) -> Vec<bool> {
+    let mut result = Vec::<bool>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, scalar::is_valid_cell);
 
     result
 }
 
-pub fn vertices(
+pub fn vertices<R: ReferenceBody>(
     ipix: &[u64],
-    ellipsoid: &Ellipsoid,
+    ellipsoid: &R,
     step: usize,
     nthreads: usize,
-) -> Vec<Vec<(f64, f64)>> {
-    let mut result = Vec::<Vec<(f64, f64)>>::with_capacity(ipix.len());
+) -> Result<Vec<Vec<(f64, f64)>>> {
+    let mut result = Vec::<Result<Vec<(f64, f64)>>>::with_capacity(ipix.len());
 
     maybe_parallelize!(nthreads, ipix, result, |hash| scalar::vertices(
         hash, ellipsoid, &step
     ));
 
-    result
+    result.into_iter().collect()
+}
+
+/// The cells containing points given in a coordinate reference system.
+///
+/// `z` is required by three-dimensional systems. Points that the system can't
+/// convert are invalid coordinates, handled according to `policy`.
+#[allow(clippy::too_many_arguments)]
+pub fn projected_to_healpix<R: ReferenceBody>(
+    x: &[f64],
+    y: &[f64],
+    z: Option<&[f64]>,
+    crs: &Crs,
+    depth: DepthLike,
+    ellipsoid: &R,
+    policy: InvalidPolicy,
+    nthreads: usize,
+) -> Result<Vec<u64>> {
+    let (lon, lat) = crs.to_geographic(x, y, z, ellipsoid)?;
+
+    lonlat_to_healpix(
+        &lon,
+        &lat,
+        depth,
+        ellipsoid,
+        LatitudeKind::Geographic,
+        policy,
+        nthreads,
+    )
+}
+
+/// The coordinates of the cell centers in a coordinate reference system.
+///
+/// The third coordinate is the height for two-dimensional systems. Invalid cell ids
+/// are handled according to `policy`.
+pub fn healpix_to_projected<R: ReferenceBody>(
+    ipix: &[u64],
+    crs: &Crs,
+    ellipsoid: &R,
+    policy: InvalidPolicy,
+    nthreads: usize,
+) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    let (lon, lat) =
+        healpix_to_lonlat(ipix, ellipsoid, LatitudeKind::Geographic, policy, nthreads)?;
+
+    crs.from_geographic(&lon, &lat, ellipsoid)
+}
+
+/// The vertices of the cells in a coordinate reference system.
+pub fn vertices_projected<R: ReferenceBody>(
+    ipix: &[u64],
+    crs: &Crs,
+    ellipsoid: &R,
+    step: usize,
+    nthreads: usize,
+) -> Result<Vec<Vec<(f64, f64, f64)>>> {
+    crs.vertices_from_geographic(vertices(ipix, ellipsoid, step, nthreads)?, ellipsoid)
+}
+
+/// The cells containing points given in body-centered cartesian coordinates.
+///
+/// The points are projected onto the reference body along its normal, so their
+/// height is ignored. The center of the body is an invalid coordinate, handled
+/// according to `policy`.
+#[allow(clippy::too_many_arguments)]
+pub fn xyz_to_healpix<R: ReferenceBody>(
+    x: &[f64],
+    y: &[f64],
+    z: &[f64],
+    depth: DepthLike,
+    ellipsoid: &R,
+    policy: InvalidPolicy,
+    nthreads: usize,
+) -> Result<Vec<u64>> {
+    let (lon, lat, _) = cartesian_to_geodetic(x, y, z, ellipsoid, nthreads)?;
+
+    lonlat_to_healpix(
+        &lon,
+        &lat,
+        depth,
+        ellipsoid,
+        LatitudeKind::Geographic,
+        policy,
+        nthreads,
+    )
+}
+
+/// The body-centered cartesian coordinates of the cell centers, at `height` above
+/// the reference body.
+///
+/// Invalid cell ids are handled according to `policy`.
+pub fn healpix_to_xyz<R: ReferenceBody>(
+    ipix: &[u64],
+    ellipsoid: &R,
+    height: f64,
+    policy: InvalidPolicy,
+    nthreads: usize,
+) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    let (lon, lat) =
+        healpix_to_lonlat(ipix, ellipsoid, LatitudeKind::Geographic, policy, nthreads)?;
+
+    geodetic_to_cartesian(&lon, &lat, height, ellipsoid, nthreads)
+}
+
+/// The body-centered cartesian coordinates of the cell vertices, at `height` above
+/// the reference body.
+pub fn vertices_xyz<R: ReferenceBody>(
+    ipix: &[u64],
+    ellipsoid: &R,
+    height: f64,
+    step: usize,
+    nthreads: usize,
+) -> Result<Vec<Vec<(f64, f64, f64)>>> {
+    Ok(vertices_to_cartesian(
+        vertices(ipix, ellipsoid, step, nthreads)?,
+        height,
+        ellipsoid,
+    ))
 }
@@ -0,0 +1,62 @@
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_chunks, check_zuniq};
+use crate::maybe_parallelize;
+use cdshealpix::sph_geom::coo3d::{UnitVec3, UnitVect3, vec3_of};
+
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+fn to_vec3(hash: &u64) -> Result<UnitVect3> {
+    let (depth, hash_nested) = check_zuniq(*hash)?;
+
+    let (lon, lat) = cdshealpix::nested::get(depth).center(hash_nested);
+
+    Ok(vec3_of(lon, lat))
+}
+
+/// Angular distances between cell centers.
+///
+/// The cells may have different depths: each distance is computed from the center
+/// of the cell at its own depth.
+pub fn angular_distances(
+    from: &[u64],
+    to: &[u64],
+    chunks: usize,
+    nthreads: usize,
+) -> Result<Vec<Vec<f64>>> {
+    check_chunks(from.len(), chunks, to.len())?;
+
+    let data: Vec<(&u64, &[u64])> = from.iter().zip(to.chunks(chunks)).collect();
+
+    let mut result = Vec::<Result<Vec<f64>>>::with_capacity(from.len());
+    maybe_parallelize!(nthreads, data, result, |(from_, to_)| {
+        let first = to_vec3(from_)?;
+        to_.iter()
+            .map(|h| to_vec3(h).map(|vec| first.ang_dist(&vec)))
+            .collect::<Result<Vec<f64>>>()
+    });
+
+    result.into_iter().collect()
+}
+
+/// The cells whose center is within `radius` of the center of each cell.
+///
+/// Returns the neighbours of each cell, including itself, with their geodesic
+/// distance in the unit of the ellipsoid axes, sorted by distance.
+pub fn neighbours_within_distance<R: ReferenceBody>(
+    ipix: &[u64],
+    radius: f64,
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<Vec<Vec<(u64, f64)>>> {
+    let geodesic = ellipsoid.geodesic();
+
+    let mut result = Vec::<Result<Vec<(u64, f64)>>>::with_capacity(ipix.len());
+    maybe_parallelize!(nthreads, ipix, result, |hash| {
+        crate::scalar::zuniq::coverage::neighbours_within_distance(
+            hash, radius, ellipsoid, &geodesic,
+        )
+    });
+
+    result.into_iter().collect()
+}
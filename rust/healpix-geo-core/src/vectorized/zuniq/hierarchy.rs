@@ -1,15 +1,103 @@
 #[cfg(not(target_arch = "wasm32"))]
 use rayon::prelude::*;
 
+use crate::error::{Result, check_length, check_zuniq};
 use crate::maybe_parallelize;
 use crate::scalar::zuniq::hierarchy as scalar;
+use crate::vectorized::aggregation::{Reduction, broadcast, reduce_by_parent};
 
-pub fn kth_neighbourhood(ipix: &[u64], ring: &u32, nthreads: usize) -> Vec<Vec<i64>> {
-    let mut result = Vec::<Vec<i64>>::with_capacity(ipix.len());
+pub fn kth_neighbourhood(ipix: &[u64], ring: &u32, nthreads: usize) -> Result<Vec<Vec<i64>>> {
+    let mut result = Vec::<Result<Vec<i64>>>::with_capacity(ipix.len());
 
     maybe_parallelize!(nthreads, ipix, result, |hash| scalar::kth_neighbourhood(
         hash, ring
     ));
 
-    result
+    result.into_iter().collect()
+}
+
+/// The 8 neighbours of each cell, see [`scalar::neighbours`].
+pub fn neighbours(ipix: &[u64], nthreads: usize) -> Result<Vec<[i64; 8]>> {
+    let mut result = Vec::<Result<[i64; 8]>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, scalar::neighbours);
+
+    result.into_iter().collect()
+}
+
+pub fn parents(ipix: &[u64], new_depth: &u8, nthreads: usize) -> Result<Vec<u64>> {
+    let mut result = Vec::<Result<u64>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, |hash| scalar::parent(
+        hash, new_depth
+    ));
+
+    result.into_iter().collect()
+}
+
+pub fn children(ipix: &[u64], new_depth: &u8, nthreads: usize) -> Result<Vec<Vec<u64>>> {
+    let mut result = Vec::<Result<Vec<u64>>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, |hash| scalar::children(
+        hash, new_depth
+    ));
+
+    result.into_iter().collect()
+}
+
+pub fn siblings(ipix: &[u64], nthreads: usize) -> Result<Vec<Vec<u64>>> {
+    let mut result = Vec::<Result<Vec<u64>>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, scalar::siblings);
+
+    result.into_iter().collect()
+}
+
+pub fn zoom_to(ipix: &[u64], new_depth: &u8, nthreads: usize) -> Result<Vec<Vec<u64>>> {
+    let mut result = Vec::<Result<Vec<u64>>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, |hash| scalar::zoom_to(
+        hash, new_depth
+    ));
+
+    result.into_iter().collect()
+}
+
+/// Reduce the values of the cells sharing a parent at `new_depth`.
+///
+/// The cells can be of different depths, which must not be coarser than `new_depth`.
+/// Returns the sorted parents with their reduced value.
+pub fn aggregate_to_parent(
+    ipix: &[u64],
+    values: &[f64],
+    new_depth: &u8,
+    reduction: Reduction,
+    nthreads: usize,
+) -> Result<(Vec<u64>, Vec<f64>)> {
+    check_length(ipix.len(), values.len())?;
+
+    let parents = parents(ipix, new_depth, nthreads)?;
+    // the cells of a depth cover a quarter of the area of the cells of the previous depth
+    let areas = ipix
+        .iter()
+        .map(|hash| check_zuniq(*hash).map(|(depth, _)| 0.25f64.powi(depth as i32)))
+        .collect::<Result<Vec<f64>>>()?;
+
+    Ok(reduce_by_parent(&parents, values, Some(&areas), reduction))
+}
+
+/// Repeat the values of the cells for each of their children at `new_depth`.
+///
+/// The cells can be of different depths, which must not be finer than `new_depth`.
+pub fn broadcast_to_children(
+    ipix: &[u64],
+    values: &[f64],
+    new_depth: &u8,
+    nthreads: usize,
+) -> Result<(Vec<u64>, Vec<f64>)> {
+    check_length(ipix.len(), values.len())?;
+
+    let children = children(ipix, new_depth, nthreads)?;
+
+    Ok(broadcast(children, values))
 }
@@ -0,0 +1,59 @@
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+use cdshealpix as healpix;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::{Result, check_depth, check_length};
+use crate::maybe_parallelize;
+use crate::scalar::zuniq::interpolation as scalar;
+
+/// The 4 cells of `depth` surrounding each point, with their bilinear interpolation
+/// weights.
+pub fn interpolation_weights<R: ReferenceBody>(
+    lon: &[f64],
+    lat: &[f64],
+    depth: u8,
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<Vec<[(u64, f64); 4]>> {
+    check_length(lon.len(), lat.len())?;
+    check_depth(depth)?;
+    let layer = healpix::nested::get(depth);
+
+    let data: Vec<(&f64, &f64)> = lon.iter().zip(lat).collect();
+
+    let mut result = Vec::<Result<[(u64, f64); 4]>>::with_capacity(lon.len());
+    maybe_parallelize!(nthreads, data, result, |(lon, lat)| {
+        scalar::interpolation_weights(lon, lat, layer, ellipsoid)
+    });
+
+    result.into_iter().collect()
+}
+
+/// Bilinear interpolation of a field known at the cells `cell_ids`.
+///
+/// The field is interpolated from the cells at `depth`. Cells missing from `cell_ids`
+/// are skipped and the weights of the remaining cells renormalized. Points without any
+/// known surrounding cell are NaN.
+pub fn interpolate<R: ReferenceBody>(
+    values: &[f64],
+    cell_ids: &[u64],
+    lon: &[f64],
+    lat: &[f64],
+    depth: u8,
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<Vec<f64>> {
+    check_depth(depth)?;
+    let layer = healpix::nested::get(depth);
+
+    crate::vectorized::interpolation::interpolate(
+        values,
+        cell_ids,
+        lon,
+        lat,
+        |lon, lat| scalar::interpolation_weights(lon, lat, layer, ellipsoid),
+        nthreads,
+    )
+}
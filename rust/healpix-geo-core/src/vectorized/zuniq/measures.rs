@@ -0,0 +1,37 @@
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+use crate::ellipsoid::ReferenceBody;
+use crate::error::Result;
+use crate::maybe_parallelize;
+use crate::scalar::zuniq::measures as scalar;
+
+pub fn cell_area<R: ReferenceBody>(
+    ipix: &[u64],
+    ellipsoid: &R,
+    nthreads: usize,
+) -> Result<Vec<f64>> {
+    let mut result = Vec::<Result<f64>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, |hash| scalar::cell_area(
+        hash, ellipsoid
+    ));
+
+    result.into_iter().collect()
+}
+
+pub fn cell_perimeter<R: ReferenceBody>(
+    ipix: &[u64],
+    ellipsoid: &R,
+    step: usize,
+    nthreads: usize,
+) -> Result<Vec<f64>> {
+    let geodesic = ellipsoid.geodesic();
+    let mut result = Vec::<Result<f64>>::with_capacity(ipix.len());
+
+    maybe_parallelize!(nthreads, ipix, result, |hash| scalar::cell_perimeter(
+        hash, ellipsoid, &geodesic, &step
+    ));
+
+    result.into_iter().collect()
+}
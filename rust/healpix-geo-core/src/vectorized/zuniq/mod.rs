@@ -1,4 +1,9 @@
 pub mod conversion;
 pub mod coordinates;
 pub mod coverage;
+pub mod distances;
 pub mod hierarchy;
+pub mod interpolation;
+pub mod measures;
+pub mod rasterization;
+pub mod regridding;